@@ -2,36 +2,73 @@
 
 use std::sync::Arc;
 
-#[derive(Debug, Clone, Copy)]
+#[allow(variant_size_differences)] // Allow in this case, the FEE ID ranges are already a pointer.
+#[derive(Debug, Clone, PartialEq, Eq)]
 /// The target of an optional filter on the input data
 pub enum FilterTarget {
-    /// Filter on the link ID
-    Link(u8),
-    /// Filter on the FEE ID
-    Fee(u16),
+    /// Filter on a set of link IDs, as a bitmask where bit N is set if link N is kept
+    ///
+    /// Only the CRU link IDs (0-15) can be filtered on.
+    Links(u16),
+    /// Filter on a set of FEE IDs, as inclusive ranges where a single FEE ID is a range of one
+    Fee(Box<[(u16, u16)]>),
     /// Filter on the ITS layer and stave
     ItsLayerStave(u16),
 }
 
+impl FilterTarget {
+    /// Converts link IDs to the bitmask used by [FilterTarget::Links], link IDs above 15 are ignored
+    pub fn link_ids_to_mask(link_ids: &[u8]) -> u16 {
+        link_ids
+            .iter()
+            .filter_map(|&id| 1u16.checked_shl(u32::from(id)))
+            .fold(0, |mask, bit| mask | bit)
+    }
+
+    /// Returns the link IDs in a bitmask used by [FilterTarget::Links], in ascending order
+    pub fn link_ids_from_mask(mask: u16) -> impl Iterator<Item = u8> {
+        (0..16).filter(move |id| mask & (1 << id) != 0)
+    }
+
+    /// Returns if a FEE ID is within any of the inclusive FEE ID ranges used by [FilterTarget::Fee]
+    pub fn fee_id_in_ranges(fee_id: u16, fee_id_ranges: &[(u16, u16)]) -> bool {
+        fee_id_ranges
+            .iter()
+            .any(|&(start, end)| (start..=end).contains(&fee_id))
+    }
+}
+
 /// Trait for all filter options set by the user
 pub trait FilterOpt {
     /// Determine from args if payload should be skipped at input
     fn skip_payload(&self) -> bool;
 
-    /// Link ID to filter by
-    fn filter_link(&self) -> Option<u8>;
-    /// FEE ID to filter by
-    fn filter_fee(&self) -> Option<u16>;
+    /// Link IDs to filter by
+    fn filter_links(&self) -> Option<&[u8]>;
+    /// Inclusive ranges of FEE IDs to filter by, a single FEE ID is a range of one (e.g. `(20522, 20522)`)
+    fn filter_fee(&self) -> Option<&[(u16, u16)]>;
     /// ITS layer & stave to filter by
     fn filter_its_stave(&self) -> Option<u16>;
+    /// Trigger type bit mask to filter by, an RDH is kept if any of the bits in the mask are set in its trigger type
+    ///
+    /// Applies in addition to the [FilterTarget]
+    fn filter_trigger_type(&self) -> Option<u32>;
+    /// Inclusive range of orbits to filter by, an RDH is kept if its orbit is within the range
+    ///
+    /// Applies in addition to the [FilterTarget]
+    fn filter_orbit_range(&self) -> Option<(u32, u32)>;
+    /// Upper bound on the payload size of an RDH, payloads exceeding it are reported as an error and skipped instead of read
+    ///
+    /// Guards against allocating memory for the payload of a corrupt RDH, the payload size of an RDH is at most 65471 bytes.
+    fn max_payload_size(&self) -> Option<u16>;
 
     /// Get the target of the filter
     fn filter_target(&self) -> Option<FilterTarget> {
         #[allow(clippy::manual_map)] // Clippy is wrong here
-        if let Some(link) = self.filter_link() {
-            Some(FilterTarget::Link(link))
-        } else if let Some(fee) = self.filter_fee() {
-            Some(FilterTarget::Fee(fee))
+        if let Some(links) = self.filter_links() {
+            Some(FilterTarget::Links(FilterTarget::link_ids_to_mask(links)))
+        } else if let Some(fee_id_ranges) = self.filter_fee() {
+            Some(FilterTarget::Fee(fee_id_ranges.into()))
         } else if let Some(its_layer_stave) = self.filter_its_stave() {
             Some(FilterTarget::ItsLayerStave(its_layer_stave))
         } else {
@@ -41,9 +78,11 @@ pub trait FilterOpt {
 
     /// Determine if the filter is enabled
     fn filter_enabled(&self) -> bool {
-        self.filter_link().is_some()
+        self.filter_links().is_some()
             || self.filter_fee().is_some()
             || self.filter_its_stave().is_some()
+            || self.filter_trigger_type().is_some()
+            || self.filter_orbit_range().is_some()
     }
 }
 
@@ -51,15 +90,24 @@ impl<T> FilterOpt for &T
 where
     T: FilterOpt,
 {
-    fn filter_link(&self) -> Option<u8> {
-        (*self).filter_link()
+    fn filter_links(&self) -> Option<&[u8]> {
+        (*self).filter_links()
     }
-    fn filter_fee(&self) -> Option<u16> {
+    fn filter_fee(&self) -> Option<&[(u16, u16)]> {
         (*self).filter_fee()
     }
     fn filter_its_stave(&self) -> Option<u16> {
         (*self).filter_its_stave()
     }
+    fn filter_trigger_type(&self) -> Option<u32> {
+        (*self).filter_trigger_type()
+    }
+    fn filter_orbit_range(&self) -> Option<(u32, u32)> {
+        (*self).filter_orbit_range()
+    }
+    fn max_payload_size(&self) -> Option<u16> {
+        (*self).max_payload_size()
+    }
 
     fn skip_payload(&self) -> bool {
         (*self).skip_payload()
@@ -69,15 +117,24 @@ impl<T> FilterOpt for Box<T>
 where
     T: FilterOpt,
 {
-    fn filter_link(&self) -> Option<u8> {
-        (**self).filter_link()
+    fn filter_links(&self) -> Option<&[u8]> {
+        (**self).filter_links()
     }
-    fn filter_fee(&self) -> Option<u16> {
+    fn filter_fee(&self) -> Option<&[(u16, u16)]> {
         (**self).filter_fee()
     }
     fn filter_its_stave(&self) -> Option<u16> {
         (**self).filter_its_stave()
     }
+    fn filter_trigger_type(&self) -> Option<u32> {
+        (**self).filter_trigger_type()
+    }
+    fn filter_orbit_range(&self) -> Option<(u32, u32)> {
+        (**self).filter_orbit_range()
+    }
+    fn max_payload_size(&self) -> Option<u16> {
+        (**self).max_payload_size()
+    }
     fn skip_payload(&self) -> bool {
         (**self).skip_payload()
     }
@@ -86,16 +143,50 @@ impl<T> FilterOpt for Arc<T>
 where
     T: FilterOpt,
 {
-    fn filter_link(&self) -> Option<u8> {
-        (**self).filter_link()
+    fn filter_links(&self) -> Option<&[u8]> {
+        (**self).filter_links()
     }
-    fn filter_fee(&self) -> Option<u16> {
+    fn filter_fee(&self) -> Option<&[(u16, u16)]> {
         (**self).filter_fee()
     }
     fn filter_its_stave(&self) -> Option<u16> {
         (**self).filter_its_stave()
     }
+    fn filter_trigger_type(&self) -> Option<u32> {
+        (**self).filter_trigger_type()
+    }
+    fn filter_orbit_range(&self) -> Option<(u32, u32)> {
+        (**self).filter_orbit_range()
+    }
+    fn max_payload_size(&self) -> Option<u16> {
+        (**self).max_payload_size()
+    }
     fn skip_payload(&self) -> bool {
         (**self).skip_payload()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_link_ids_mask_roundtrip() {
+        let mask = FilterTarget::link_ids_to_mask(&[0, 3, 7, 15, 16]);
+        assert_eq!(mask, 0b1000_0000_1000_1001);
+        assert_eq!(
+            FilterTarget::link_ids_from_mask(mask).collect::<Vec<u8>>(),
+            vec![0, 3, 7, 15]
+        );
+    }
+
+    #[test]
+    fn test_fee_id_in_ranges() {
+        let fee_id_ranges = [(20522, 20523), (20600, 20600)];
+        assert!(FilterTarget::fee_id_in_ranges(20522, &fee_id_ranges));
+        assert!(FilterTarget::fee_id_in_ranges(20523, &fee_id_ranges));
+        assert!(FilterTarget::fee_id_in_ranges(20600, &fee_id_ranges));
+        assert!(!FilterTarget::fee_id_in_ranges(20524, &fee_id_ranges));
+        assert!(!FilterTarget::fee_id_in_ranges(20521, &fee_id_ranges));
+    }
+}
@@ -2,9 +2,12 @@ use super::filter::FilterOpt;
 
 #[derive(Default, Debug)]
 pub struct MockConfig {
-    pub(crate) filter_link: Option<u8>,
-    pub(crate) filter_fee: Option<u16>,
+    pub(crate) filter_links: Vec<u8>,
+    pub(crate) filter_fee: Vec<(u16, u16)>,
     pub(crate) filter_its_stave: Option<u16>,
+    pub(crate) filter_trigger_type: Option<u32>,
+    pub(crate) filter_orbit_range: Option<(u32, u32)>,
+    pub(crate) max_payload_size: Option<u16>,
     pub(crate) skip_payload: bool,
 }
 
@@ -13,15 +16,35 @@ impl FilterOpt for MockConfig {
         self.skip_payload
     }
 
-    fn filter_link(&self) -> Option<u8> {
-        self.filter_link
+    fn filter_links(&self) -> Option<&[u8]> {
+        if self.filter_links.is_empty() {
+            None
+        } else {
+            Some(&self.filter_links)
+        }
     }
 
-    fn filter_fee(&self) -> Option<u16> {
-        self.filter_fee
+    fn filter_fee(&self) -> Option<&[(u16, u16)]> {
+        if self.filter_fee.is_empty() {
+            None
+        } else {
+            Some(&self.filter_fee)
+        }
     }
 
     fn filter_its_stave(&self) -> Option<u16> {
         self.filter_its_stave
     }
+
+    fn filter_trigger_type(&self) -> Option<u32> {
+        self.filter_trigger_type
+    }
+
+    fn filter_orbit_range(&self) -> Option<(u32, u32)> {
+        self.filter_orbit_range
+    }
+
+    fn max_payload_size(&self) -> Option<u16> {
+        self.max_payload_size
+    }
 }
@@ -0,0 +1,110 @@
+//! Contains the [Endianness] of the input data, and the functions that convert big-endian words to the little-endian layout the words are deserialized from.
+//!
+//! ALICE data is little-endian, but some systems store the data with the byte order of each word reversed,
+//! i.e. each 64 bit subword of the [RDH](crate::prelude::RDH) and each GBT word of the payload.
+//! The [InputScanner](crate::prelude::InputScanner) converts big-endian data as it is read, so everything after it sees the little-endian layout.
+
+use std::{fmt, io, str::FromStr};
+
+/// Size of an RDH subword in bytes
+const RDH_SUBWORD_SIZE_BYTES: usize = 8;
+/// Size of a GBT word in bytes in data format 2
+const GBT_WORD_SIZE_BYTES: usize = 10;
+/// Size of a GBT word padded to 128 bits in bytes in data format 0
+const GBT_WORD_PADDED_SIZE_BYTES: usize = 16;
+
+/// The byte order of the words in the input data.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default)]
+pub enum Endianness {
+    /// Little-endian, the byte order of ALICE data.
+    #[default]
+    Little,
+    /// Big-endian, the byte order of each word is reversed.
+    Big,
+}
+
+impl fmt::Display for Endianness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Endianness::Little => write!(f, "little"),
+            Endianness::Big => write!(f, "big"),
+        }
+    }
+}
+
+impl FromStr for Endianness {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "little" => Ok(Endianness::Little),
+            "big" => Ok(Endianness::Big),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Invalid endianness, expected one of: little, big",
+            )),
+        }
+    }
+}
+
+/// Converts the big-endian subwords of (part of) an RDH to little-endian in place.
+///
+/// The slice has to start at the start of a subword, any bytes after the last full subword are left as is.
+pub fn rdh_subwords_to_le(buf: &mut [u8]) {
+    buf.chunks_exact_mut(RDH_SUBWORD_SIZE_BYTES)
+        .for_each(<[u8]>::reverse);
+}
+
+/// Converts the big-endian GBT words of a payload to little-endian in place.
+///
+/// GBT words are padded to 128 bits in data format 0, any bytes after the last full GBT word (e.g. padding) are left as is.
+pub fn payload_words_to_le(payload: &mut [u8], data_format: u8) {
+    let word_size = if data_format == 0 {
+        GBT_WORD_PADDED_SIZE_BYTES
+    } else {
+        GBT_WORD_SIZE_BYTES
+    };
+    payload
+        .chunks_exact_mut(word_size)
+        .for_each(<[u8]>::reverse);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::test_data::CORRECT_RDH_CRU_V7;
+    use crate::prelude::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_endianness_from_str() {
+        assert_eq!(Endianness::from_str("big").unwrap(), Endianness::Big);
+        assert_eq!(Endianness::from_str("Little").unwrap(), Endianness::Little);
+        assert!(Endianness::from_str("middle").is_err());
+    }
+
+    #[test]
+    fn test_rdh_subwords_to_le() {
+        let mut be_rdh = CORRECT_RDH_CRU_V7.to_byte_slice().to_vec();
+        be_rdh
+            .chunks_exact_mut(RDH_SUBWORD_SIZE_BYTES)
+            .for_each(<[u8]>::reverse);
+        // The RDH version is the last byte of the first big-endian subword
+        assert_eq!(be_rdh[7], 7);
+
+        rdh_subwords_to_le(&mut be_rdh);
+        assert_eq!(RdhCru::from_buf(&be_rdh).unwrap(), CORRECT_RDH_CRU_V7);
+    }
+
+    #[test]
+    fn test_payload_words_to_le() {
+        let mut payload = [
+            0x1, 0x2, 0x3, 0x4, 0x5, 0x6, 0x7, 0x8, 0x9, 0xE0, 0xFF, 0xFF,
+        ];
+        payload_words_to_le(&mut payload, 2);
+        assert_eq!(
+            payload,
+            [0xE0, 0x9, 0x8, 0x7, 0x6, 0x5, 0x4, 0x3, 0x2, 0x1, 0xFF, 0xFF]
+        );
+    }
+}
@@ -0,0 +1,222 @@
+//! Contains the [ForwardReaderSeeker] that wraps a reader that can only be read forward, e.g. a gzip or Zstandard decoder, implements [BufferedReaderWrapper].
+//!
+//! The [InputCompression] of an input file is detected by its extension or magic bytes, and determines the decoder it is wrapped in.
+//!
+//! Reading the input is a forward-only scan, so seeking forward is done by reading and discarding the bytes, just like for stdin.
+use super::bufreader_wrapper::BufferedReaderWrapper;
+use flate2::read::MultiGzDecoder;
+use std::fs::File;
+use std::io::{self, BufReader, Read, SeekFrom};
+use std::path::Path;
+
+/// Magic bytes at the start of a gzip stream
+const GZIP_MAGIC: [u8; 2] = [0x1F, 0x8B];
+/// Magic bytes at the start of a Zstandard frame
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xB5, 0x2F, 0xFD];
+
+/// Compression format of an input, detected by the file extension or the magic bytes at the start of the file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputCompression {
+    /// Not compressed
+    None,
+    /// Gzip, `.gz` extension
+    Gzip,
+    /// Zstandard, `.zst` extension
+    Zstd,
+}
+
+impl InputCompression {
+    /// Detects the compression of the file at `path`, by the `.gz` or `.zst` extension, or else by the magic bytes at the start of the file
+    pub fn detect(path: &Path) -> io::Result<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("gz") => return Ok(Self::Gzip),
+            Some("zst") => return Ok(Self::Zstd),
+            _ => (),
+        }
+        // A file shorter than the magic bytes is not compressed
+        let mut magic = Vec::with_capacity(ZSTD_MAGIC.len());
+        _ = File::open(path)?
+            .take(ZSTD_MAGIC.len() as u64)
+            .read_to_end(&mut magic)?;
+        if magic.starts_with(&GZIP_MAGIC) {
+            Ok(Self::Gzip)
+        } else if magic.starts_with(&ZSTD_MAGIC) {
+            Ok(Self::Zstd)
+        } else {
+            Ok(Self::None)
+        }
+    }
+
+    /// Wraps the reader in a decompressor for the compression, an uncompressed reader is returned as is.
+    ///
+    /// Concatenated gzip members and Zstandard frames are decompressed as one stream.
+    pub fn decoder<R: Read + Send + 'static>(self, reader: R) -> io::Result<Box<dyn Read + Send>> {
+        Ok(match self {
+            Self::None => Box::new(reader),
+            Self::Gzip => Box::new(MultiGzDecoder::new(reader)),
+            Self::Zstd => Box::new(zstd::Decoder::new(reader)?),
+        })
+    }
+}
+
+/// Returns true if the file at `path` has a `.gz` extension or starts with the gzip magic bytes
+pub fn is_gzip_file(path: &Path) -> io::Result<bool> {
+    Ok(InputCompression::detect(path)? == InputCompression::Gzip)
+}
+
+/// Wrapper for a forward-only reader, seeking forward reads and discards the bytes, seeking backwards is not supported.
+#[derive(Debug)]
+pub struct ForwardReaderSeeker<R> {
+    reader: BufReader<R>,
+    /// Number of bytes read or skipped
+    pos: u64,
+}
+
+impl<R: Read> ForwardReaderSeeker<R> {
+    /// Wraps the reader in a [BufReader] with the given capacity.
+    pub fn new(reader: R, capacity: usize) -> Self {
+        Self {
+            reader: BufReader::with_capacity(capacity, reader),
+            pos: 0,
+        }
+    }
+
+    /// Reads and discards `count` bytes
+    fn discard(&mut self, count: u64) -> io::Result<()> {
+        let discarded = io::copy(&mut (&mut self.reader).take(count), &mut io::sink())?;
+        self.pos += discarded;
+        if discarded < count {
+            // Seeking past EOF means that the offset retrieved from the RDH is likely wrong
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Failed to read and discard a payload of size {count} (according to previously loaded RDH): reached end of input after {discarded} bytes"),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for ForwardReaderSeeker<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let bytes_read = self.reader.read(buf)?;
+        self.pos += bytes_read as u64;
+        Ok(bytes_read)
+    }
+}
+
+impl<R: Read> io::Seek for ForwardReaderSeeker<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let offset = match pos {
+            SeekFrom::Current(offset) if offset >= 0 => offset as u64,
+            SeekFrom::Start(target) if target >= self.pos => target - self.pos,
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "Cannot seek backwards or from the end in a forward-only input",
+                ))
+            }
+        };
+        self.discard(offset)?;
+        Ok(self.pos)
+    }
+}
+
+impl<R: Read + Send> BufferedReaderWrapper for ForwardReaderSeeker<R> {
+    #[inline]
+    fn seek_relative_offset(&mut self, offset: i64) -> io::Result<()> {
+        io::Seek::seek(self, SeekFrom::Current(offset)).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::GzEncoder, Compression};
+    use io::{Seek, Write};
+    use temp_dir::TempDir;
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_seek_forward_in_gzip_stream() {
+        let compressed = gzip(&[0, 1, 2, 3, 4, 5, 6, 7]);
+        let mut reader =
+            ForwardReaderSeeker::new(flate2::read::MultiGzDecoder::new(compressed.as_slice()), 4);
+
+        let mut buf = [0u8; 2];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [0, 1]);
+        reader.seek_relative_offset(3).unwrap();
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [5, 6]);
+        assert_eq!(reader.stream_position().unwrap(), 7);
+        assert_eq!(
+            reader.seek_relative_offset(-2).unwrap_err().kind(),
+            io::ErrorKind::Unsupported
+        );
+        assert_eq!(
+            reader.seek_relative_offset(2).unwrap_err().kind(),
+            io::ErrorKind::InvalidInput
+        );
+    }
+
+    #[test]
+    fn test_is_gzip_file() {
+        let tmp_d = TempDir::new().unwrap();
+        let gz_no_ext = tmp_d.child("compressed.raw");
+        std::fs::write(&gz_no_ext, gzip(&[0, 1])).unwrap();
+        let raw = tmp_d.child("uncompressed.raw");
+        std::fs::write(&raw, [0x1F]).unwrap();
+
+        assert!(is_gzip_file(&gz_no_ext).unwrap());
+        assert!(!is_gzip_file(&raw).unwrap());
+        assert!(is_gzip_file(&tmp_d.child("missing.raw.gz")).unwrap());
+    }
+
+    #[test]
+    fn test_detect_compression() {
+        let tmp_d = TempDir::new().unwrap();
+        let zst_no_ext = tmp_d.child("compressed.raw");
+        std::fs::write(
+            &zst_no_ext,
+            zstd::encode_all([0u8, 1].as_slice(), 0).unwrap(),
+        )
+        .unwrap();
+        let short = tmp_d.child("short.raw");
+        std::fs::write(&short, [0x28, 0xB5]).unwrap();
+
+        assert_eq!(
+            InputCompression::detect(&zst_no_ext).unwrap(),
+            InputCompression::Zstd
+        );
+        assert_eq!(
+            InputCompression::detect(&short).unwrap(),
+            InputCompression::None
+        );
+        assert_eq!(
+            InputCompression::detect(&tmp_d.child("missing.raw.zst")).unwrap(),
+            InputCompression::Zstd
+        );
+        assert!(InputCompression::detect(&tmp_d.child("missing.raw")).is_err());
+    }
+
+    #[test]
+    fn test_zstd_decoder_concatenated_frames() {
+        let mut compressed = zstd::encode_all([0u8, 1, 2].as_slice(), 0).unwrap();
+        compressed.extend(zstd::encode_all([3u8, 4].as_slice(), 0).unwrap());
+        let mut reader = ForwardReaderSeeker::new(
+            InputCompression::Zstd
+                .decoder(io::Cursor::new(compressed))
+                .unwrap(),
+            4,
+        );
+
+        let mut decompressed = Vec::new();
+        _ = reader.read_to_end(&mut decompressed).unwrap();
+        assert_eq!(decompressed, [0, 1, 2, 3, 4]);
+    }
+}
@@ -2,17 +2,24 @@
 //!
 //! The [InputScanner] implements the [ScanCDP] trait.
 
+pub mod builder;
+
 use crate::prelude::RdhCru;
 
 use super::bufreader_wrapper::BufferedReaderWrapper;
 use super::config::filter::{FilterOpt, FilterTarget};
+use super::endianness::{payload_words_to_le, rdh_subwords_to_le, Endianness};
 use super::mem_pos_tracker::MemPosTracker;
 use super::rdh::Rdh0;
 use super::rdh::{SerdeRdh, RDH};
 use super::scan_cdp::ScanCDP;
 use super::stats::InputStatType;
-use super::stats::Stats;
-use std::io::Read;
+use super::stats::{Stats, StatsCounters};
+use builder::InputScannerBuilder;
+use std::io::{Read, Seek, SeekFrom};
+use std::iter::FusedIterator;
+use std::marker::PhantomData;
+use std::time::Duration;
 
 type CdpTuple<T> = (T, Vec<u8>, u64);
 
@@ -26,9 +33,33 @@ pub struct InputScanner<R: ?Sized + BufferedReaderWrapper> {
     tracker: MemPosTracker,
     stats_sender_ch: Option<flume::Sender<InputStatType>>,
     filter_target: Option<FilterTarget>,
+    filter_trigger_type: Option<u32>,
+    filter_orbit_range: Option<(u32, u32)>,
+    max_payload_size: Option<u16>,
     skip_payload: bool,
     stats: Option<Stats>,
     initial_rdh0: Option<Rdh0>,
+    input_size: Option<u64>,
+    stall_threshold: Option<Duration>,
+    stalls: u64,
+    stall_time: Duration,
+    initial_stats_reported: bool,
+    follow_poll_interval: Option<Duration>,
+    endianness: Endianness,
+    start_offset: u64,
+    max_bytes: Option<u64>,
+    sample_every: Option<u64>,
+    cdps_loaded: u64,
+}
+
+/// The state of an [InputScanner] before loading a CDP, restored if the CDP is only partially written when following the input.
+#[derive(Debug, Clone)]
+struct Checkpoint {
+    stream_pos: u64,
+    tracker: MemPosTracker,
+    initial_rdh0: Option<Rdh0>,
+    initial_stats_reported: bool,
+    stats_counters: Option<StatsCounters>,
 }
 
 impl<R: ?Sized + BufferedReaderWrapper> InputScanner<R> {
@@ -38,15 +69,9 @@ impl<R: ?Sized + BufferedReaderWrapper> InputScanner<R> {
         reader: Box<R>,
         stats_sender_ch: Option<flume::Sender<InputStatType>>,
     ) -> Self {
-        InputScanner {
-            reader,
-            tracker: MemPosTracker::new(),
-            stats_sender_ch: stats_sender_ch.clone(),
-            filter_target: config.filter_target(),
-            skip_payload: config.skip_payload(),
-            stats: stats_sender_ch.map(Stats::new),
-            initial_rdh0: None,
-        }
+        Self::builder_with_stats_sender(stats_sender_ch)
+            .filter_config(config)
+            .build_with_reader(reader)
     }
     /// Creates a new [InputScanner] from a config that implemenents [FilterOpt], [BufferedReaderWrapper],  a producer channel for [InputStatType] and an initial [Rdh0].
     ///
@@ -57,29 +82,132 @@ impl<R: ?Sized + BufferedReaderWrapper> InputScanner<R> {
         stats_sender_ch: Option<flume::Sender<InputStatType>>,
         rdh0: Rdh0,
     ) -> Self {
-        InputScanner {
-            reader,
-            tracker: MemPosTracker::new(),
-            filter_target: config.filter_target(),
-            stats_sender_ch: stats_sender_ch.clone(),
-            skip_payload: config.skip_payload(),
-            stats: stats_sender_ch.map(Stats::new),
-            initial_rdh0: Some(rdh0),
-        }
+        Self::builder_with_stats_sender(stats_sender_ch)
+            .filter_config(config)
+            .initial_rdh0(rdh0)
+            .build_with_reader(reader)
     }
 
     /// Creates a new [InputScanner] with minimal functionality from a [BufferedReaderWrapper].
     ///
     /// Every feature is disabled but the [InputScanner] can still load `CDP`s.
     pub fn minimal(reader: Box<R>) -> Self {
-        Self {
-            reader,
-            tracker: Default::default(),
-            stats_sender_ch: Default::default(),
-            filter_target: Default::default(),
-            skip_payload: Default::default(),
-            stats: Default::default(),
-            initial_rdh0: Default::default(),
+        InputScannerBuilder::new().build_with_reader(reader)
+    }
+
+    /// Returns an [InputScannerBuilder] to configure an [InputScanner] with named methods instead of the positional arguments of the constructors.
+    pub fn builder() -> InputScannerBuilder<R> {
+        InputScannerBuilder::new()
+    }
+
+    fn builder_with_stats_sender(
+        stats_sender_ch: Option<flume::Sender<InputStatType>>,
+    ) -> InputScannerBuilder<R> {
+        match stats_sender_ch {
+            Some(stats_sender_ch) => InputScannerBuilder::new().stats_sender(stats_sender_ch),
+            None => InputScannerBuilder::new(),
+        }
+    }
+
+    /// Set the memory position of the first RDH, e.g. if bytes before it were skipped because they could not be parsed as an RDH.
+    ///
+    /// Memory positions are then still relative to the start of the input.
+    pub fn with_start_offset(mut self, start_offset: u64) -> Self {
+        self.tracker.update_mem_address(start_offset);
+        self.start_offset += start_offset;
+        self
+    }
+
+    /// Stop loading CDPs once the number of bytes consumed from the start offset reaches the limit, e.g. to sample the start of a large input.
+    ///
+    /// The CDP that crosses the limit is still loaded in full, after that [ScanCDP::load_cdp] returns an [UnexpectedEof](std::io::ErrorKind::UnexpectedEof) error as if the input was exhausted.
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Returns true if a byte limit is set with [InputScanner::with_max_bytes] and the bytes consumed so far reached it.
+    pub fn is_byte_limit_reached(&self) -> bool {
+        self.max_bytes.is_some_and(|max_bytes| {
+            self.tracker.current_mem_address() - self.start_offset >= max_bytes
+        })
+    }
+
+    /// Only forward every Nth CDP from the readers spawned with [spawn_reader](crate::spawn_reader) and [spawn_vec_reader](crate::spawn_vec_reader), starting with the first CDP.
+    ///
+    /// The CDPs in between are still loaded, so the RDHs seen and the payload size are counted in the stats for all CDPs.
+    pub fn with_sample_every(mut self, sample_every: u64) -> Self {
+        self.sample_every = Some(sample_every);
+        self
+    }
+
+    /// Counts a loaded CDP, returns true if it should be forwarded, i.e. if sampling is not set with [InputScanner::with_sample_every] or the CDP is sampled.
+    pub(crate) fn is_loaded_cdp_sampled(&mut self) -> bool {
+        let is_sampled = match self.sample_every {
+            Some(sample_every) => self.cdps_loaded.checked_rem(sample_every) == Some(0),
+            None => true,
+        };
+        self.cdps_loaded += 1;
+        is_sampled
+    }
+
+    /// Set the total size of the input in bytes, e.g. the length of the input file.
+    ///
+    /// If the input size is known, the number of trailing bytes that could not be parsed as an RDH at EOF is reported as a warning.
+    pub fn with_input_size(mut self, input_size: u64) -> Self {
+        self.input_size = Some(input_size);
+        self
+    }
+
+    /// Set the threshold for how long sending CDPs from the reader thread can block before it counts as a stall.
+    ///
+    /// The number of stalls and the total time spent in them is reported as [InputStatType::ReaderStalls] when the [InputScanner] is dropped.
+    pub fn with_stall_threshold(mut self, stall_threshold: Duration) -> Self {
+        self.stall_threshold = Some(stall_threshold);
+        self
+    }
+
+    /// Follow an input file that is still being written to, like `tail -f`, waiting for the poll interval each time the end of the file is reached.
+    ///
+    /// A CDP that is only partially written when the end of the file is reached is not counted, and the [InputScanner] is rewound to the start of it,
+    /// so it can be loaded again when more data is written. The readers spawned with [spawn_reader](crate::spawn_reader) and [spawn_vec_reader](crate::spawn_vec_reader) then keep reading until stopped.
+    pub fn with_follow(mut self, poll_interval: Duration) -> Self {
+        self.follow_poll_interval = Some(poll_interval);
+        self
+    }
+
+    /// Set the byte order of the words in the input, big-endian RDHs and payloads are converted to little-endian as they are loaded.
+    ///
+    /// If the [InputScanner] is created from an initial [Rdh0], the [Rdh0] has to be loaded with the same endianness, see [Rdh0::load_with_endianness].
+    pub fn with_endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    /// Returns the interval to wait before reading again at the end of the input if the input is followed, see [InputScanner::with_follow].
+    pub fn follow_poll_interval(&self) -> Option<Duration> {
+        self.follow_poll_interval
+    }
+
+    /// Record how long sending CDPs from the reader thread blocked, counting it as a stall if it exceeds the stall threshold (if set).
+    pub fn record_send_time(&mut self, send_time: Duration) {
+        if self
+            .stall_threshold
+            .is_some_and(|stall_threshold| send_time > stall_threshold)
+        {
+            self.stalls += 1;
+            self.stall_time += send_time;
+        }
+    }
+
+    /// Returns an iterator that lazily loads CDPs as ([RDH], payload, memory position) tuples until the input is exhausted.
+    ///
+    /// See [CdpIter] for how errors are handled.
+    pub fn cdp_iter<T: RDH>(&mut self) -> CdpIter<'_, T, R> {
+        CdpIter {
+            scanner: self,
+            done: false,
+            _rdh: PhantomData,
         }
     }
 
@@ -114,6 +242,18 @@ impl<R: ?Sized + BufferedReaderWrapper> InputScanner<R> {
         if let Some(stat_tracker) = self.stats.as_mut() {
             stat_tracker.try_add_fee_id(rdh.fee_id());
         }
+
+        // If the CRU ID or DW has not been seen before, report it and add it to the list of unique CRU IDs or DWs
+        if let Some(stat_tracker) = self.stats.as_mut() {
+            stat_tracker.try_add_cru_id(rdh.cru_id());
+            stat_tracker.try_add_dw(rdh.dw());
+        }
+
+        // If the data format differs from the previous RDH, report the change
+        let mem_pos = self.tracker.current_mem_address();
+        if let Some(stat_tracker) = self.stats.as_mut() {
+            stat_tracker.record_data_format(rdh.data_format(), mem_pos);
+        }
     }
     #[inline]
     fn initial_collect_stats(&mut self, rdh: &impl RDH) {
@@ -123,6 +263,26 @@ impl<R: ?Sized + BufferedReaderWrapper> InputScanner<R> {
         self.report(InputStatType::SystemId(rdh.rdh0().system_id));
     }
 
+    // If EOF is reached while loading an RDH, reports any bytes left between the current position and the end of the input.
+    fn on_load_rdh_error(&self, e: std::io::Error) -> std::io::Error {
+        if e.kind() != std::io::ErrorKind::UnexpectedEof {
+            return e;
+        }
+        if let Some(input_size) = self.input_size {
+            let trailing_bytes = input_size.saturating_sub(self.current_mem_pos());
+            if trailing_bytes > 0 {
+                self.report(InputStatType::Warning(
+                    format!(
+                        "{mem_pos:#X}: {trailing_bytes} trailing bytes could not be parsed as an RDH",
+                        mem_pos = self.current_mem_pos()
+                    )
+                    .into(),
+                ));
+            }
+        }
+        e
+    }
+
     fn seek_to_next_rdh(&mut self, offset_to_next: u16) -> Result<(), std::io::Error> {
         self.reader
             .seek_relative_offset(self.tracker.next(offset_to_next as u64))
@@ -141,18 +301,14 @@ where
     fn load_rdh_cru<T: RDH>(&mut self) -> Result<T, std::io::Error> {
         // If it is the first time we get an RDH, we would already have loaded the initial RDH0
         //  from the input. If so, we use it to create the first RDH.
-        let rdh: T = if self.initial_rdh0.is_some() {
-            let rdh: T =
-                SerdeRdh::load_from_rdh0(&mut self.reader, self.initial_rdh0.take().unwrap())?;
-
-            rdh
-        } else {
-            SerdeRdh::load(&mut self.reader)?
-        };
+        let rdh: T = self
+            .load_rdh_with_endianness()
+            .map_err(|e| self.on_load_rdh_error(e))?;
 
-        if self.current_mem_pos() == 0 {
+        if !self.initial_stats_reported {
             // Report general initial stats assumed to be the same for the rest of the data
             self.initial_collect_stats(&rdh);
+            self.initial_stats_reported = true;
         }
 
         // Collect stats
@@ -164,8 +320,11 @@ where
         )?;
 
         // If a filter is set, check if the RDH matches the filter
-        let rdh = if let Some(target) = self.filter_target {
-            if is_rdh_filter_target(&rdh, target) {
+        let rdh = if self.filter_target.is_some()
+            || self.filter_trigger_type.is_some()
+            || self.filter_orbit_range.is_some()
+        {
+            if self.is_rdh_filter_match(&rdh) {
                 if let Some(stat_tracker) = self.stats.as_mut() {
                     stat_tracker.rdh_filtered();
                 }
@@ -173,7 +332,7 @@ where
                 Ok(rdh)
             } else {
                 // If it doesn't match: Set tracker to jump to next RDH and try until we find a matching link or EOF
-                self.load_next_rdh_to_filter(rdh.offset_to_next(), target)
+                self.load_next_filter_match(rdh.offset_to_next())
             }
         } else {
             // No filter set, return the RDH (nop)
@@ -183,6 +342,11 @@ where
         if let Ok(rdh) = &rdh {
             if let Some(stat_tracker) = self.stats.as_mut() {
                 stat_tracker.add_payload_size(rdh.payload_size());
+                // Counted for every loaded CDP, as only the sampled CDPs are forwarded if sampling is set
+                stat_tracker.add_link_payload(rdh.link_id(), rdh.payload_size());
+                if rdh.stop_bit() == 1 {
+                    stat_tracker.hbf_seen();
+                }
             }
         }
         rdh
@@ -209,9 +373,56 @@ where
     /// Reads the next CDP from file
     #[inline]
     fn load_cdp<T: RDH>(&mut self) -> Result<CdpTuple<T>, std::io::Error> {
-        let loading_at_memory_offset = self.tracker.current_mem_address();
+        if self.is_byte_limit_reached() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::UnexpectedEof,
+                "Reached the limit of bytes to read",
+            ));
+        }
+        if self.follow_poll_interval.is_none() {
+            return self.load_complete_cdp();
+        }
+        // The end of the input might be in the middle of a CDP that is still being written
+        let checkpoint = self.checkpoint()?;
+        match self.load_complete_cdp() {
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                self.rewind(checkpoint)?;
+                Err(e)
+            }
+            result => result,
+        }
+    }
+
+    #[inline]
+    fn load_next_rdh_to_filter<T: RDH>(
+        &mut self,
+        offset_to_next: u16,
+        filter_target: FilterTarget,
+    ) -> Result<T, std::io::Error> {
+        // Filter by the given target instead of the configured one while loading the next RDH
+        let configured_filter_target = self.filter_target.replace(filter_target);
+        let rdh = self.load_next_filter_match(offset_to_next);
+        self.filter_target = configured_filter_target;
+        rdh
+    }
+
+    fn current_mem_pos(&self) -> u64 {
+        self.tracker.current_mem_address()
+    }
+}
+
+impl<R> InputScanner<R>
+where
+    R: ?Sized + BufferedReaderWrapper,
+{
+    /// Loads the next CDP, the [RDH] and payload are counted in the stats as they are loaded.
+    #[inline]
+    fn load_complete_cdp<T: RDH>(&mut self) -> Result<CdpTuple<T>, std::io::Error> {
         let rdh: T = self.load_rdh_cru()?;
 
+        // RDHs that don't match a filter are skipped, so the position is only known after loading the RDH
+        let loading_at_memory_offset = self.tracker.current_mem_address();
+
         if self.skip_payload {
             // Only interested in RDHs, seek to next RDH
             if let Err(e) = self.seek_to_next_rdh(rdh.offset_to_next()) {
@@ -237,9 +448,37 @@ where
         // If we want the payload, read it, otherwise return a vector that cannot allocate
         let payload = if self.skip_payload {
             Vec::with_capacity(0)
+        } else if let Some(max_size) = self
+            .max_payload_size
+            .filter(|&max_size| rdh.payload_size() > max_size)
+        {
+            // Report the error and skip the payload instead of allocating memory for it. We still want to process the RDH.
+            self.report(InputStatType::Error(
+                format!(
+                    "{rdh_mem_pos:#X}: [E102] Payload size {sz} exceeds the maximum payload size {max_size}, skipping payload",
+                    rdh_mem_pos = self.current_mem_pos() - u64::from(rdh.offset_to_next()),
+                    sz = rdh.payload_size(),
+                )
+                .into(),
+            ));
+            self.reader
+                .seek_relative_offset(i64::from(rdh.payload_size()))?;
+            Vec::with_capacity(0)
         } else {
             match self.load_payload_raw(rdh.payload_size() as usize) {
-                Ok(payload) => payload,
+                Ok(mut payload) => {
+                    if self.endianness == Endianness::Big {
+                        payload_words_to_le(&mut payload, rdh.data_format());
+                    }
+                    payload
+                }
+                // The rest of the payload might not be written yet to the followed input
+                Err(e)
+                    if e.kind() == std::io::ErrorKind::UnexpectedEof
+                        && self.follow_poll_interval.is_some() =>
+                {
+                    return Err(e)
+                }
                 Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
                     // Report the error and continue. We still want to process the RDH.
                     self.report(InputStatType::Error(
@@ -256,18 +495,82 @@ where
             }
         };
 
+        if let Some(stat_tracker) = self.stats.as_mut() {
+            stat_tracker.try_report_bytes_read(self.tracker.current_mem_address());
+        }
+
         Ok((rdh, payload, loading_at_memory_offset))
     }
 
+    /// Loads the next [RDH], or the rest of it if the initial [Rdh0] is already loaded, converting it to little-endian if the input is big-endian.
     #[inline]
-    fn load_next_rdh_to_filter<T: RDH>(
-        &mut self,
-        offset_to_next: u16,
-        filter_target: FilterTarget,
-    ) -> Result<T, std::io::Error> {
+    fn load_rdh_with_endianness<T: RDH>(&mut self) -> Result<T, std::io::Error> {
+        match (self.initial_rdh0.take(), self.endianness) {
+            (Some(rdh0), Endianness::Little) => SerdeRdh::load_from_rdh0(&mut self.reader, rdh0),
+            (None, Endianness::Little) => SerdeRdh::load(&mut self.reader),
+            (Some(rdh0), Endianness::Big) => {
+                let mut buf = [0u8; 56];
+                self.reader.read_exact(&mut buf)?;
+                rdh_subwords_to_le(&mut buf);
+                T::from_rdh0_and_buf(rdh0, &buf)
+            }
+            (None, Endianness::Big) => {
+                let mut buf = [0u8; 64];
+                self.reader.read_exact(&mut buf)?;
+                rdh_subwords_to_le(&mut buf);
+                T::from_buf(&buf)
+            }
+        }
+    }
+
+    fn checkpoint(&mut self) -> Result<Checkpoint, std::io::Error> {
+        Ok(Checkpoint {
+            stream_pos: self.reader.stream_position()?,
+            tracker: self.tracker,
+            initial_rdh0: self.initial_rdh0,
+            initial_stats_reported: self.initial_stats_reported,
+            stats_counters: self.stats.as_ref().map(Stats::counters),
+        })
+    }
+
+    /// Restores the state from before loading a CDP that is only partially written, so that it can be loaded again.
+    fn rewind(&mut self, checkpoint: Checkpoint) -> Result<(), std::io::Error> {
+        let _ = self.reader.seek(SeekFrom::Start(checkpoint.stream_pos))?;
+        self.tracker = checkpoint.tracker;
+        self.initial_rdh0 = checkpoint.initial_rdh0;
+        self.initial_stats_reported = checkpoint.initial_stats_reported;
+        if let Some((stats, counters)) = self.stats.as_mut().zip(checkpoint.stats_counters) {
+            stats.restore_counters(counters);
+        }
+        Ok(())
+    }
+
+    /// Check if the RDH matches the filter target (if any), the trigger type filter (if any), and the orbit range filter (if any)
+    #[inline]
+    fn is_rdh_filter_match(&self, rdh: &impl RDH) -> bool {
+        let target_match = match &self.filter_target {
+            Some(target) => is_rdh_filter_target(rdh, target),
+            None => true,
+        };
+        let trigger_type_match = match self.filter_trigger_type {
+            Some(mask) => rdh.trigger_type() & mask != 0,
+            None => true,
+        };
+        let orbit_match = match self.filter_orbit_range {
+            Some((start, end)) => (start..=end).contains(&{ rdh.rdh1().orbit }),
+            None => true,
+        };
+        target_match && trigger_type_match && orbit_match
+    }
+
+    /// Loads the next [RDH] that matches the filter target (if any), the trigger type filter (if any), and the orbit range filter (if any)
+    #[inline]
+    fn load_next_filter_match<T: RDH>(&mut self, offset_to_next: u16) -> Result<T, std::io::Error> {
         self.seek_to_next_rdh(offset_to_next)?;
         loop {
-            let rdh: T = SerdeRdh::load(&mut self.reader)?;
+            let rdh: T = self
+                .load_rdh_with_endianness()
+                .map_err(|e| self.on_load_rdh_error(e))?;
             sanity_check_offset_next(
                 &rdh,
                 self.tracker.current_mem_address(),
@@ -275,7 +578,7 @@ where
             )?;
             self.collect_rdh_seen_stats(&rdh);
 
-            if is_rdh_filter_target(&rdh, filter_target) {
+            if self.is_rdh_filter_match(&rdh) {
                 if let Some(stat_tracker) = self.stats.as_mut() {
                     stat_tracker.rdh_filtered();
                 }
@@ -284,18 +587,63 @@ where
             self.seek_to_next_rdh(rdh.offset_to_next())?;
         }
     }
+}
 
-    fn current_mem_pos(&self) -> u64 {
-        self.tracker.current_mem_address()
+/// Iterator over the CDPs of an [InputScanner], created by [InputScanner::cdp_iter].
+///
+/// Iteration stops when the end of the input is reached, any other error is returned once and also ends the iteration.
+#[derive(Debug)]
+pub struct CdpIter<'a, T: RDH, R: ?Sized + BufferedReaderWrapper> {
+    scanner: &'a mut InputScanner<R>,
+    done: bool,
+    _rdh: PhantomData<T>,
+}
+
+impl<T, R> Iterator for CdpIter<'_, T, R>
+where
+    T: RDH,
+    R: ?Sized + BufferedReaderWrapper,
+{
+    type Item = Result<CdpTuple<T>, std::io::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.scanner.load_cdp() {
+            Ok(cdp) => Some(Ok(cdp)),
+            Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                self.done = true;
+                None
+            }
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            }
+        }
     }
 }
 
+impl<T, R> FusedIterator for CdpIter<'_, T, R>
+where
+    T: RDH,
+    R: ?Sized + BufferedReaderWrapper,
+{
+}
+
 impl<R> Drop for InputScanner<R>
 where
     R: ?Sized + BufferedReaderWrapper,
 {
     fn drop(&mut self) {
+        if self.stall_threshold.is_some() {
+            self.report(InputStatType::ReaderStalls {
+                stalls: self.stalls,
+                stall_time: self.stall_time,
+            });
+        }
         if let Some(mut stat_tracker) = self.stats.take() {
+            self.report(InputStatType::BytesRead(self.current_mem_pos()));
             stat_tracker.flush_stats();
         }
     }
@@ -303,11 +651,15 @@ where
 
 // Check if the RDH matches the filter target
 #[inline]
-fn is_rdh_filter_target(rdh: &impl RDH, target: FilterTarget) -> bool {
+fn is_rdh_filter_target(rdh: &impl RDH, target: &FilterTarget) -> bool {
     match target {
-        FilterTarget::Link(id) => rdh.link_id() == id,
-        FilterTarget::Fee(id) => rdh.fee_id() == id,
-        FilterTarget::ItsLayerStave(fee_id) => is_match_feeid_layer_stave(rdh.fee_id(), fee_id),
+        FilterTarget::Links(mask) => 1u16
+            .checked_shl(u32::from(rdh.link_id()))
+            .is_some_and(|bit| mask & bit != 0),
+        FilterTarget::Fee(fee_id_ranges) => {
+            FilterTarget::fee_id_in_ranges(rdh.fee_id(), fee_id_ranges)
+        }
+        FilterTarget::ItsLayerStave(fee_id) => is_match_feeid_layer_stave(rdh.fee_id(), *fee_id),
     }
 }
 
@@ -370,7 +722,7 @@ mod tests {
     ) {
         use super::*;
         let config = MockConfig {
-            filter_link: Some(0),
+            filter_links: vec![0],
             ..Default::default()
         };
         let (controller_send, controller_recv): (
@@ -409,6 +761,327 @@ mod tests {
         assert!(!stats_recv.is_empty(), "stats_recv was empty!");
     }
 
+    #[test]
+    fn test_load_rdhcruv7_filter_trigger_type() {
+        let soc_rdh = CORRECT_RDH_CRU_V7;
+        let mut pht_rdh = CORRECT_RDH_CRU_V7;
+        pht_rdh.rdh2.trigger_type = 0x10;
+        pht_rdh.rdh1.orbit += 1;
+
+        let tmp_d = TempDir::new().unwrap();
+        let test_file = tmp_d.child("test.raw");
+        let mut test_data = Vec::new();
+        for rdh in [&soc_rdh, &pht_rdh] {
+            test_data.extend_from_slice(rdh.to_byte_slice());
+            test_data.resize(test_data.len() + rdh.payload_size() as usize, 0);
+        }
+        std::fs::write(&test_file, test_data).unwrap();
+
+        let config = MockConfig {
+            filter_trigger_type: Some(0x10),
+            ..Default::default()
+        };
+        let reader = std::fs::File::open(&test_file).unwrap();
+        let mut scanner = InputScanner::new(&config, Box::new(BufReader::new(reader)), None);
+
+        let rdh = scanner.load_rdh_cru::<RdhCru>().unwrap();
+        assert_eq!(rdh, pht_rdh);
+    }
+
+    #[test]
+    fn test_follow_partially_written_cdp() {
+        let rdh = CORRECT_RDH_CRU_V7;
+        let mut cdp = rdh.to_byte_slice().to_vec();
+        cdp.resize(cdp.len() + rdh.payload_size() as usize, 0);
+        let tmp_d = TempDir::new().unwrap();
+        let test_file = tmp_d.child("test.raw");
+        // The payload of the second CDP is only partially written
+        let partial_len = 100;
+        std::fs::write(&test_file, [cdp.as_slice(), &cdp[..partial_len]].concat()).unwrap();
+
+        let (send, recv) = flume::unbounded();
+        let reader = std::fs::File::open(&test_file).unwrap();
+        {
+            let mut scanner = InputScanner::new(
+                &MockConfig::default(),
+                Box::new(BufReader::new(reader)),
+                Some(send),
+            )
+            .with_follow(Duration::from_millis(10));
+            let (_, _, mem_pos) = scanner.load_cdp::<RdhCru>().unwrap();
+            assert_eq!(mem_pos, 0);
+            assert_eq!(
+                scanner.load_cdp::<RdhCru>().unwrap_err().kind(),
+                std::io::ErrorKind::UnexpectedEof
+            );
+
+            // The rest of the CDP is written
+            let mut file = std::fs::OpenOptions::new()
+                .append(true)
+                .open(&test_file)
+                .unwrap();
+            std::io::Write::write_all(&mut file, &cdp[partial_len..]).unwrap();
+
+            let (loaded_rdh, payload, mem_pos) = scanner.load_cdp::<RdhCru>().unwrap();
+            assert_eq!(loaded_rdh, rdh);
+            assert_eq!(payload.len(), rdh.payload_size() as usize);
+            assert_eq!(mem_pos, cdp.len() as u64);
+        }
+
+        // The partially written CDP is not counted
+        let stats: Vec<InputStatType> = recv.drain().collect();
+        let rdhs_seen: u32 = stats
+            .iter()
+            .filter_map(|stat| match stat {
+                InputStatType::RDHSeen(rdhs) => Some(rdhs),
+                _ => None,
+            })
+            .sum();
+        assert_eq!(rdhs_seen, 2);
+        assert_eq!(
+            stats
+                .iter()
+                .filter(|stat| matches!(stat, InputStatType::DataFormat(_)))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_bytes_read_reported_on_drop() {
+        let rdh = CORRECT_RDH_CRU_V7;
+        let tmp_d = TempDir::new().unwrap();
+        let test_file = tmp_d.child("test.raw");
+        let mut test_data = rdh.to_byte_slice().to_vec();
+        test_data.resize(test_data.len() + rdh.payload_size() as usize, 0);
+        std::fs::write(&test_file, test_data).unwrap();
+
+        let config = MockConfig::default();
+        let (send, recv) = flume::unbounded();
+        let reader = std::fs::File::open(&test_file).unwrap();
+        {
+            let mut scanner =
+                InputScanner::new(&config, Box::new(BufReader::new(reader)), Some(send));
+            let _ = scanner.load_cdp::<RdhCru>().unwrap();
+        }
+        let bytes_read = recv
+            .drain()
+            .filter_map(|stat| match stat {
+                InputStatType::BytesRead(bytes_read) => Some(bytes_read),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(bytes_read, vec![rdh.offset_to_next() as u64]);
+    }
+
+    #[test]
+    fn test_data_format_change_reported() {
+        let rdh = CORRECT_RDH_CRU_V7;
+        let tmp_d = TempDir::new().unwrap();
+        let test_file = tmp_d.child("test.raw");
+        let mut cdp = rdh.to_byte_slice().to_vec();
+        cdp.resize(cdp.len() + rdh.payload_size() as usize, 0);
+        let cdp_len = cdp.len() as u64;
+        // The data format is the first byte of the 4th 64 bit word of the RDH
+        let mut changed_cdp = cdp.clone();
+        changed_cdp[24] = rdh.data_format() ^ 2;
+        std::fs::write(&test_file, [cdp.clone(), changed_cdp, cdp].concat()).unwrap();
+
+        let config = MockConfig::default();
+        let (send, recv) = flume::unbounded();
+        let reader = std::fs::File::open(&test_file).unwrap();
+        {
+            let mut scanner =
+                InputScanner::new(&config, Box::new(BufReader::new(reader)), Some(send));
+            while scanner.load_cdp::<RdhCru>().is_ok() {}
+        }
+        let changes = recv
+            .drain()
+            .filter(|stat| matches!(stat, InputStatType::DataFormatChanged { .. }))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changes,
+            [
+                InputStatType::DataFormatChanged {
+                    mem_pos: cdp_len,
+                    from: rdh.data_format(),
+                    to: rdh.data_format() ^ 2,
+                },
+                InputStatType::DataFormatChanged {
+                    mem_pos: 2 * cdp_len,
+                    from: rdh.data_format() ^ 2,
+                    to: rdh.data_format(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reader_stalls_reported_on_drop() {
+        let tmp_d = TempDir::new().unwrap();
+        let test_file = tmp_d.child("test.raw");
+        std::fs::write(&test_file, CORRECT_RDH_CRU_V7.to_byte_slice()).unwrap();
+
+        let config = MockConfig::default();
+        let (send, recv) = flume::unbounded();
+        let reader = std::fs::File::open(&test_file).unwrap();
+        {
+            let mut scanner =
+                InputScanner::new(&config, Box::new(BufReader::new(reader)), Some(send))
+                    .with_stall_threshold(Duration::from_millis(10));
+            scanner.record_send_time(Duration::from_millis(5));
+            scanner.record_send_time(Duration::from_millis(20));
+            scanner.record_send_time(Duration::from_millis(30));
+        }
+        let stalls = recv
+            .drain()
+            .filter(|stat| matches!(stat, InputStatType::ReaderStalls { .. }))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            stalls,
+            vec![InputStatType::ReaderStalls {
+                stalls: 2,
+                stall_time: Duration::from_millis(50)
+            }]
+        );
+    }
+
+    #[test]
+    fn test_load_rdhcruv7_filter_orbit_range() {
+        let mut rdhs = [CORRECT_RDH_CRU_V7; 3];
+        for (rdh, orbit_offset) in rdhs.iter_mut().zip(0..) {
+            rdh.rdh1.orbit += orbit_offset;
+        }
+
+        let tmp_d = TempDir::new().unwrap();
+        let test_file = tmp_d.child("test.raw");
+        let mut test_data = Vec::new();
+        for rdh in &rdhs {
+            test_data.extend_from_slice(rdh.to_byte_slice());
+            test_data.resize(test_data.len() + rdh.payload_size() as usize, 0);
+        }
+        std::fs::write(&test_file, test_data).unwrap();
+
+        let orbit = CORRECT_RDH_CRU_V7.rdh1.orbit;
+        let config = MockConfig {
+            filter_orbit_range: Some((orbit + 1, orbit + 1)),
+            ..Default::default()
+        };
+        let reader = std::fs::File::open(&test_file).unwrap();
+        let mut scanner = InputScanner::new(&config, Box::new(BufReader::new(reader)), None);
+
+        let (rdh, _, mem_pos) = scanner.load_cdp::<RdhCru>().unwrap();
+        assert_eq!(rdh, rdhs[1]);
+        assert_eq!(mem_pos, u64::from(rdhs[0].offset_to_next()));
+        // The last RDH is outside the orbit range
+        assert_eq!(
+            scanner.load_cdp::<RdhCru>().unwrap_err().kind(),
+            std::io::ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn test_load_cdp_max_payload_size_exceeded() {
+        let mut rdhs = [CORRECT_RDH_CRU_V7; 2];
+        rdhs[0].memory_size = 0x40 + 0x100;
+        rdhs[0].offset_new_packet = 0x40 + 0x100;
+        rdhs[1].memory_size = 0x40 + 0x20;
+        rdhs[1].offset_new_packet = 0x40 + 0x20;
+
+        let tmp_d = TempDir::new().unwrap();
+        let test_file = tmp_d.child("test.raw");
+        let mut test_data = Vec::new();
+        for rdh in &rdhs {
+            test_data.extend_from_slice(rdh.to_byte_slice());
+            test_data.resize(test_data.len() + rdh.payload_size() as usize, 0);
+        }
+        std::fs::write(&test_file, test_data).unwrap();
+
+        let config = MockConfig {
+            max_payload_size: Some(0x80),
+            ..Default::default()
+        };
+        let (send, recv) = flume::unbounded();
+        let reader = std::fs::File::open(&test_file).unwrap();
+        let mut scanner = InputScanner::new(&config, Box::new(BufReader::new(reader)), Some(send));
+
+        // The payload of the first RDH is skipped
+        let (rdh, payload, _) = scanner.load_cdp::<RdhCru>().unwrap();
+        assert_eq!(rdh, rdhs[0]);
+        assert!(payload.is_empty());
+        // The second RDH is still found and its payload read
+        let (rdh, payload, _) = scanner.load_cdp::<RdhCru>().unwrap();
+        assert_eq!(rdh, rdhs[1]);
+        assert_eq!(payload.len(), 0x20);
+        drop(scanner);
+
+        let errors = recv
+            .drain()
+            .filter_map(|stat| match stat {
+                InputStatType::Error(msg) => Some(msg),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            &*errors[0],
+            "0x0: [E102] Payload size 256 exceeds the maximum payload size 128, skipping payload"
+        );
+    }
+
+    #[test]
+    fn test_cdp_iter() {
+        let mut rdhs = [CORRECT_RDH_CRU_V7; 3];
+        for (rdh, packet_counter) in rdhs.iter_mut().zip(0..) {
+            rdh.packet_counter = packet_counter;
+        }
+
+        let tmp_d = TempDir::new().unwrap();
+        let test_file = tmp_d.child("test.raw");
+        let mut test_data = Vec::new();
+        for rdh in &rdhs {
+            test_data.extend_from_slice(rdh.to_byte_slice());
+            test_data.resize(test_data.len() + rdh.payload_size() as usize, 0);
+        }
+        std::fs::write(&test_file, test_data).unwrap();
+
+        let reader = std::fs::File::open(&test_file).unwrap();
+        let mut scanner = InputScanner::minimal(Box::new(BufReader::new(reader)));
+
+        let cdps = scanner
+            .cdp_iter::<RdhCru>()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(cdps.len(), 3);
+        for ((rdh, payload, mem_pos), (expect_rdh, idx)) in cdps.iter().zip(rdhs.iter().zip(0..)) {
+            assert_eq!(rdh, expect_rdh);
+            assert_eq!(payload.len(), expect_rdh.payload_size() as usize);
+            assert_eq!(*mem_pos, idx * u64::from(expect_rdh.offset_to_next()));
+        }
+        // The iterator is exhausted at EOF
+        assert!(scanner.cdp_iter::<RdhCru>().next().is_none());
+    }
+
+    #[test]
+    fn test_cdp_iter_stops_after_invalid_data() {
+        let mut rdh = CORRECT_RDH_CRU_V7;
+        rdh.offset_new_packet = 0x20; // Invalid offset to the next RDH
+
+        let tmp_d = TempDir::new().unwrap();
+        let test_file = tmp_d.child("test.raw");
+        std::fs::write(&test_file, rdh.to_byte_slice()).unwrap();
+
+        let reader = std::fs::File::open(&test_file).unwrap();
+        let mut scanner = InputScanner::minimal(Box::new(BufReader::new(reader)));
+
+        let mut cdp_iter = scanner.cdp_iter::<RdhCru>();
+        assert_eq!(
+            cdp_iter.next().unwrap().unwrap_err().kind(),
+            std::io::ErrorKind::InvalidData
+        );
+        assert!(cdp_iter.next().is_none());
+    }
+
     #[test]
     fn test_load_rdhcruv7_test_unexp_eof() {
         let mut test_data = CORRECT_RDH_CRU_V7;
@@ -520,4 +1193,136 @@ mod tests {
 
         assert!(payload.is_err());
     }
+
+    #[test]
+    fn test_load_big_endian_cdp() {
+        let test_data = CORRECT_RDH_CRU_V7;
+        // Payload of data format 2 GBT words with increasing byte values
+        let payload: Vec<u8> = (0..test_data.payload_size())
+            .map(|i| (i % 251) as u8)
+            .collect();
+        let mut be_rdh = test_data.to_byte_slice().to_vec();
+        be_rdh.chunks_exact_mut(8).for_each(<[u8]>::reverse);
+        let mut be_payload = payload.clone();
+        be_payload.chunks_exact_mut(10).for_each(<[u8]>::reverse);
+
+        let tmp_dir = TempDir::new().unwrap();
+        let test_file = tmp_dir.child("test.raw");
+        std::fs::write(&test_file, [be_rdh, be_payload].concat()).unwrap();
+
+        // The initial RDH0 is loaded first, as when determining the RDH version
+        let mut reader = BufReader::new(std::fs::File::open(test_file).unwrap());
+        let rdh0 = Rdh0::load_with_endianness(&mut reader, Endianness::Big).unwrap();
+        assert_eq!(rdh0, *test_data.rdh0());
+        let mut input_scanner =
+            InputScanner::new_from_rdh0(&MockConfig::default(), Box::new(reader), None, rdh0)
+                .with_endianness(Endianness::Big);
+
+        let (rdh, cdp_payload, mem_pos) = input_scanner.load_cdp::<RdhCru>().unwrap();
+        assert_eq!(rdh, test_data);
+        assert_eq!(cdp_payload, payload);
+        assert_eq!(mem_pos, 0);
+    }
+
+    #[test]
+    fn test_max_bytes_stops_after_crossing_cdp() {
+        let test_data = CORRECT_RDH_CRU_V7;
+        let cdp = [
+            test_data.to_byte_slice(),
+            &vec![0; test_data.payload_size() as usize],
+        ]
+        .concat();
+
+        let tmp_dir = TempDir::new().unwrap();
+        let test_file = tmp_dir.child("test.raw");
+        std::fs::write(&test_file, cdp.repeat(3)).unwrap();
+
+        let reader = std::fs::File::open(test_file).expect("File not found");
+        // The limit is reached within the second CDP
+        let mut input_scanner = InputScanner::minimal(Box::new(BufReader::new(reader)))
+            .with_max_bytes(cdp.len() as u64 + 1);
+
+        assert!(input_scanner.load_cdp::<RdhCru>().is_ok());
+        assert!(!input_scanner.is_byte_limit_reached());
+        let (_, _, mem_pos) = input_scanner.load_cdp::<RdhCru>().unwrap();
+        assert_eq!(mem_pos, cdp.len() as u64);
+        assert!(input_scanner.is_byte_limit_reached());
+        let err = input_scanner.load_cdp::<RdhCru>().unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_trailing_bytes_at_eof_reported() {
+        let test_data = CORRECT_RDH_CRU_V7;
+        let payload = vec![0; test_data.payload_size() as usize];
+        let trailing_bytes = [0xAB; 17];
+
+        let tmp_dir = TempDir::new().unwrap();
+        let test_file = tmp_dir.child("test.raw");
+        std::fs::write(
+            &test_file,
+            [test_data.to_byte_slice(), &payload, &trailing_bytes].concat(),
+        )
+        .unwrap();
+        let input_size = std::fs::metadata(&test_file).unwrap().len();
+
+        let (send, recv) = flume::unbounded();
+        let reader = std::fs::File::open(test_file).expect("File not found");
+        let mut input_scanner = InputScanner::new(
+            &MockConfig::default(),
+            Box::new(BufReader::new(reader)),
+            Some(send),
+        )
+        .with_input_size(input_size);
+
+        let (rdh, cdp_payload, _) = input_scanner.load_cdp::<RdhCru>().unwrap();
+        assert_eq!(rdh, test_data);
+        assert_eq!(cdp_payload, payload);
+        let err = input_scanner.load_cdp::<RdhCru>().unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+        drop(input_scanner);
+
+        let warnings: Vec<InputStatType> = recv
+            .try_iter()
+            .filter(|stat| matches!(stat, InputStatType::Warning(_)))
+            .collect();
+        assert_eq!(
+            warnings,
+            vec![InputStatType::Warning(
+                format!(
+                    "{:#X}: 17 trailing bytes could not be parsed as an RDH",
+                    test_data.offset_to_next()
+                )
+                .into()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_no_trailing_bytes_no_warning() {
+        let test_data = CORRECT_RDH_CRU_V7;
+        let payload = vec![0; test_data.payload_size() as usize];
+
+        let tmp_dir = TempDir::new().unwrap();
+        let test_file = tmp_dir.child("test.raw");
+        std::fs::write(&test_file, [test_data.to_byte_slice(), &payload].concat()).unwrap();
+        let input_size = std::fs::metadata(&test_file).unwrap().len();
+
+        let (send, recv) = flume::unbounded();
+        let reader = std::fs::File::open(test_file).expect("File not found");
+        let mut input_scanner = InputScanner::new(
+            &MockConfig::default(),
+            Box::new(BufReader::new(reader)),
+            Some(send),
+        )
+        .with_input_size(input_size);
+
+        assert!(input_scanner.load_cdp::<RdhCru>().is_ok());
+        assert!(input_scanner.load_cdp::<RdhCru>().is_err());
+        drop(input_scanner);
+
+        assert!(!recv
+            .try_iter()
+            .any(|stat| matches!(stat, InputStatType::Warning(_))));
+    }
 }
@@ -0,0 +1,208 @@
+//! Contains the [InputScannerBuilder] that configures and builds an [InputScanner] with named methods instead of positional arguments.
+//!
+//! # Example
+//! ```
+//! use alice_protocol_reader::prelude::*;
+//! use std::fs::File;
+//! use std::io::BufReader;
+//!
+//! # let tmp_dir = temp_dir::TempDir::new().unwrap();
+//! # let path = tmp_dir.child("input.raw");
+//! # std::fs::write(&path, test_data::CORRECT_RDH_CRU_V7.to_byte_slice()).unwrap();
+//! let reader = BufReader::new(File::open(path).unwrap());
+//! let (stats_send, _stats_recv) = flume::unbounded();
+//! let mut input_scanner = InputScannerBuilder::new()
+//!     .reader(Box::new(reader))
+//!     .stats_sender(stats_send)
+//!     .build()
+//!     .unwrap();
+//!
+//! let rdh = input_scanner.load_rdh_cru::<RdhCru>().unwrap();
+//! assert_eq!(rdh, test_data::CORRECT_RDH_CRU_V7);
+//! ```
+
+use super::InputScanner;
+use crate::bufreader_wrapper::BufferedReaderWrapper;
+use crate::config::filter::{FilterOpt, FilterTarget};
+use crate::endianness::Endianness;
+use crate::mem_pos_tracker::MemPosTracker;
+use crate::rdh::Rdh0;
+use crate::stats::{InputStatType, Stats};
+use std::time::Duration;
+
+/// Builds an [InputScanner] from a reader, and optionally a filter config, a stats sender and an initial [Rdh0].
+///
+/// Everything that is not set is disabled, as with [InputScanner::minimal].
+#[derive(Debug)]
+pub struct InputScannerBuilder<R: ?Sized + BufferedReaderWrapper> {
+    reader: Option<Box<R>>,
+    stats_sender_ch: Option<flume::Sender<InputStatType>>,
+    filter_target: Option<FilterTarget>,
+    filter_trigger_type: Option<u32>,
+    filter_orbit_range: Option<(u32, u32)>,
+    max_payload_size: Option<u16>,
+    skip_payload: bool,
+    initial_rdh0: Option<Rdh0>,
+}
+
+impl<R: ?Sized + BufferedReaderWrapper> Default for InputScannerBuilder<R> {
+    fn default() -> Self {
+        Self {
+            reader: None,
+            stats_sender_ch: None,
+            filter_target: None,
+            filter_trigger_type: None,
+            filter_orbit_range: None,
+            max_payload_size: None,
+            skip_payload: false,
+            initial_rdh0: None,
+        }
+    }
+}
+
+impl<R: ?Sized + BufferedReaderWrapper> InputScannerBuilder<R> {
+    /// Creates a builder with nothing set, a reader has to be set before building.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the reader to load the CDPs from.
+    pub fn reader(mut self, reader: Box<R>) -> Self {
+        self.reader = Some(reader);
+        self
+    }
+
+    /// Set the filters and whether to skip payloads from a config that implements [FilterOpt].
+    pub fn filter_config(mut self, config: &impl FilterOpt) -> Self {
+        self.filter_target = config.filter_target();
+        self.filter_trigger_type = config.filter_trigger_type();
+        self.filter_orbit_range = config.filter_orbit_range();
+        self.max_payload_size = config.max_payload_size();
+        self.skip_payload = config.skip_payload();
+        self
+    }
+
+    /// Set the producer channel that the [InputStatType]s are sent through as the input is loaded.
+    pub fn stats_sender(mut self, stats_sender_ch: flume::Sender<InputStatType>) -> Self {
+        self.stats_sender_ch = Some(stats_sender_ch);
+        self
+    }
+
+    /// Set an [Rdh0] that is already loaded from the reader, e.g. to determine the RDH version, the first RDH is then loaded from the rest of it.
+    pub fn initial_rdh0(mut self, rdh0: Rdh0) -> Self {
+        self.initial_rdh0 = Some(rdh0);
+        self
+    }
+
+    /// Builds the [InputScanner], returns an [InvalidInput](std::io::ErrorKind::InvalidInput) error if no reader is set.
+    pub fn build(mut self) -> Result<InputScanner<R>, std::io::Error> {
+        let reader = self.reader.take().ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "No reader set to build the InputScanner from",
+            )
+        })?;
+        Ok(self.build_with_reader(reader))
+    }
+
+    /// Builds the [InputScanner] with the reader, used by the constructors of [InputScanner] that always have a reader.
+    pub(super) fn build_with_reader(self, reader: Box<R>) -> InputScanner<R> {
+        InputScanner {
+            reader,
+            tracker: MemPosTracker::new(),
+            stats: self.stats_sender_ch.clone().map(Stats::new),
+            stats_sender_ch: self.stats_sender_ch,
+            filter_target: self.filter_target,
+            filter_trigger_type: self.filter_trigger_type,
+            filter_orbit_range: self.filter_orbit_range,
+            max_payload_size: self.max_payload_size,
+            skip_payload: self.skip_payload,
+            initial_rdh0: self.initial_rdh0,
+            input_size: None,
+            stall_threshold: None,
+            stalls: 0,
+            stall_time: Duration::ZERO,
+            initial_stats_reported: false,
+            follow_poll_interval: None,
+            endianness: Endianness::Little,
+            start_offset: 0,
+            max_bytes: None,
+            sample_every: None,
+            cdps_loaded: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rdh::test_data::CORRECT_RDH_CRU_V7;
+    use crate::rdh::ByteSlice;
+    use crate::rdh::RdhCru;
+    use crate::scan_cdp::ScanCDP;
+    use std::io::BufReader;
+    use temp_dir::TempDir;
+
+    struct LinkFilter;
+
+    impl FilterOpt for LinkFilter {
+        fn skip_payload(&self) -> bool {
+            true
+        }
+        fn filter_links(&self) -> Option<&[u8]> {
+            Some(&[2])
+        }
+        fn filter_fee(&self) -> Option<&[(u16, u16)]> {
+            None
+        }
+        fn filter_its_stave(&self) -> Option<u16> {
+            None
+        }
+        fn filter_trigger_type(&self) -> Option<u32> {
+            None
+        }
+        fn filter_orbit_range(&self) -> Option<(u32, u32)> {
+            None
+        }
+        fn max_payload_size(&self) -> Option<u16> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_build_without_reader_fails() {
+        let err = InputScannerBuilder::<BufReader<std::fs::File>>::new()
+            .filter_config(&LinkFilter)
+            .build()
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_build_with_filter_config() {
+        let tmp_dir = TempDir::new().unwrap();
+        let test_file = tmp_dir.child("test.raw");
+        std::fs::write(&test_file, CORRECT_RDH_CRU_V7.to_byte_slice()).unwrap();
+        let reader = BufReader::new(std::fs::File::open(test_file).unwrap());
+        let (stats_send, stats_recv) = flume::unbounded();
+
+        let mut input_scanner = InputScannerBuilder::new()
+            .reader(Box::new(reader))
+            .filter_config(&LinkFilter)
+            .stats_sender(stats_send)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            input_scanner.filter_target,
+            Some(FilterTarget::Links(1 << 2))
+        );
+        assert!(input_scanner.skip_payload);
+        // The only RDH is on link 0, so it is filtered out but still counted as seen
+        assert!(input_scanner.load_cdp::<RdhCru>().is_err());
+        drop(input_scanner);
+        assert!(stats_recv
+            .drain()
+            .any(|stat| matches!(stat, InputStatType::RDHSeen(1))));
+    }
+}
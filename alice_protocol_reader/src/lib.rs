@@ -41,7 +41,7 @@
 //! use alice_protocol_reader::init_reader;
 //! use alice_protocol_reader::rdh::RdhCru;
 //!
-//! let reader = init_reader(&Some(test_file_path)).unwrap();
+//! let reader = init_reader(&[test_file_path]).unwrap();
 //!
 //! let mut input_scanner = InputScanner::minimal(reader);
 //!
@@ -81,36 +81,56 @@
 //!         // Implement your config rules for determining if you're skipping the payload (only reading `RDH`s)
 //!     }
 //!
-//!     fn filter_link(&self) -> Option<u8> {
-//!         // Implement your config rules for setting a link to filter by
+//!     fn filter_links(&self) -> Option<&[u8]> {
+//!         // Implement your config rules for setting the links to filter by
 //!     }
 //!
-//!     fn filter_fee(&self) -> Option<u16> {
-//!         // Implement your config rules for setting a FEE ID to filter by
+//!     fn filter_fee(&self) -> Option<&[(u16, u16)]> {
+//!         // Implement your config rules for setting the FEE ID ranges to filter by
 //!     }
 //!
 //!     fn filter_its_stave(&self) -> Option<u16> {
 //!         // Implement your config rules for setting an ITS Stave to filter by
 //!     }
+//!
+//!     fn filter_trigger_type(&self) -> Option<u32> {
+//!         // Implement your config rules for setting a trigger type bit mask to filter by
+//!     }
+//!
+//!     fn filter_orbit_range(&self) -> Option<(u32, u32)> {
+//!         // Implement your config rules for setting an inclusive orbit range to filter by
+//!     }
+//!
+//!     fn max_payload_size(&self) -> Option<u16> {
+//!         // Implement your config rules for setting an upper bound on the payload size
+//!     }
 //! }
 //!
 //! use alice_protocol_reader::input_scanner::InputScanner;
 //! use alice_protocol_reader::init_reader;
 //! use alice_protocol_reader::rdh::RdhCru;
 //! pub fn main() {}
-//!     let reader = init_reader(&Some(test_file_path)).unwrap();
+//!     let reader = init_reader(&[test_file_path]).unwrap();
 //!
 //!     let mut input_scanner = input_scanner::InputScanner::new(&MyCfg, reader, None); // None: Option<flume::Sender<InputStatType>>
 //!
 //!     let rdh = input_scanner.load_cdp::<RdhCru<u8>>();
+//!
+//!     // Or iterate over the remaining CDPs until the end of the input
+//!     for cdp in input_scanner.cdp_iter::<RdhCru<u8>>() {
+//!         let (rdh, payload, mem_pos) = cdp.unwrap();
+//!     }
 //! }
 //! ```
 
 pub mod bufreader_wrapper;
 pub mod cdp_wrapper;
 pub mod config;
+pub mod endianness;
+pub mod forward_reader_seeker;
 pub mod input_scanner;
 pub mod mem_pos_tracker;
+pub mod multi_file_reader;
 pub mod prelude;
 pub mod rdh;
 pub mod scan_cdp;
@@ -119,13 +139,16 @@ pub mod stdin_reader;
 
 use cdp_wrapper::cdp_array::CdpArray;
 use crossbeam_channel::Receiver;
+use forward_reader_seeker::{ForwardReaderSeeker, InputCompression};
+use multi_file_reader::MultiFileReader;
 use prelude::{BufferedReaderWrapper, CdpVec, InputScanner, ScanCDP, RDH};
 use std::sync::Arc;
-use std::thread::{Builder, JoinHandle};
+use std::thread::{self, Builder, JoinHandle};
+use std::time::Instant;
 use std::{fs, io};
 use std::{
     io::IsTerminal,
-    path::Path,
+    path::PathBuf,
     sync::atomic::{AtomicBool, Ordering},
 };
 use stdin_reader::StdInReaderSeeker;
@@ -134,17 +157,34 @@ use stdin_reader::StdInReaderSeeker;
 const CHANNEL_CDP_BATCH_CAPACITY: usize = 100;
 const READER_BUFFER_SIZE: usize = 1024 * 50; // 50KB
 
-/// Initializes the reader based on the input mode (file or stdin) and returns it
+/// Initializes the reader based on the input mode (file(s) or stdin) and returns it
+///
+/// The input mode is determined by the input file paths, if there are none, the input is read from stdin.
+/// Multiple files are read in order as one continuous stream with a [MultiFileReader].
 ///
-/// The input mode is determined by the presence of the input file path in the config
+/// Compressed files (see [InputCompression::detect]) are decompressed while reading with a [ForwardReaderSeeker].
 #[inline]
-pub fn init_reader(input_file: Option<&Path>) -> Result<Box<dyn BufferedReaderWrapper>, io::Error> {
-    if let Some(path) = input_file {
+pub fn init_reader(input_files: &[PathBuf]) -> Result<Box<dyn BufferedReaderWrapper>, io::Error> {
+    let compressions = input_files
+        .iter()
+        .map(|path| InputCompression::detect(path))
+        .collect::<io::Result<Vec<InputCompression>>>()?;
+    if compressions
+        .iter()
+        .any(|&compression| compression != InputCompression::None)
+    {
+        init_compressed_files_reader(input_files, &compressions)
+    } else if let [path] = input_files {
         let f = fs::OpenOptions::new().read(true).open(path)?;
         Ok(Box::new(io::BufReader::with_capacity(
             READER_BUFFER_SIZE,
             f,
         )))
+    } else if !input_files.is_empty() {
+        Ok(Box::new(MultiFileReader::new(
+            input_files,
+            READER_BUFFER_SIZE,
+        )?))
     } else if !io::stdin().is_terminal() {
         Ok(Box::new(StdInReaderSeeker {
             reader: io::stdin(),
@@ -157,6 +197,41 @@ pub fn init_reader(input_file: Option<&Path>) -> Result<Box<dyn BufferedReaderWr
     }
 }
 
+/// Initializes a reader that decompresses gzip-compressed input from stdin
+#[inline]
+pub fn init_gzip_stdin_reader() -> Result<Box<dyn BufferedReaderWrapper>, io::Error> {
+    init_compressed_stdin_reader(InputCompression::Gzip)
+}
+
+/// Initializes a reader that decompresses input from stdin with the given compression
+pub fn init_compressed_stdin_reader(
+    compression: InputCompression,
+) -> Result<Box<dyn BufferedReaderWrapper>, io::Error> {
+    if io::stdin().is_terminal() {
+        return Err(io::Error::other("stdin not redirected!"));
+    }
+    Ok(Box::new(ForwardReaderSeeker::new(
+        compression.decoder(io::stdin())?,
+        READER_BUFFER_SIZE,
+    )))
+}
+
+/// Chains the files into one forward-only stream, where each compressed file is decompressed
+fn init_compressed_files_reader(
+    input_files: &[PathBuf],
+    compressions: &[InputCompression],
+) -> Result<Box<dyn BufferedReaderWrapper>, io::Error> {
+    let mut chained: Box<dyn io::Read + Send> = Box::new(io::empty());
+    for (path, &compression) in input_files.iter().zip(compressions) {
+        let f = fs::OpenOptions::new().read(true).open(path)?;
+        chained = Box::new(io::Read::chain(chained, compression.decoder(f)?));
+    }
+    Ok(Box::new(ForwardReaderSeeker::new(
+        chained,
+        READER_BUFFER_SIZE,
+    )))
+}
+
 /// Spawns a reader thread that reads CDPs from the input and sends them to a producer channel
 ///
 /// Returns the thread handle and the receiver channel
@@ -175,25 +250,44 @@ pub fn spawn_reader<T: RDH + 'static, const CAP: usize>(
         .spawn({
             move || {
                 let mut input_scanner = input_scanner;
+                let follow_poll_interval = input_scanner.follow_poll_interval();
 
                 // Automatically extracts link to filter if one is supplied
                 while !stop_flag.load(Ordering::SeqCst) && !local_stop_on_non_full_batch {
                     let cdps = match get_array_batch::<T, CAP>(&mut input_scanner) {
-                        Ok(cdp) => {
-                            if cdp.len() < CAP {
-                                local_stop_on_non_full_batch = true; // Stop on non-full batch, could be InvalidData
-                            }
+                        Ok((cdp, stopped_early_by)) => {
+                            // Stop on non-full batch, could be InvalidData, unless more data can be written to the followed input
+                            local_stop_on_non_full_batch = match stopped_early_by {
+                                Some(io::ErrorKind::UnexpectedEof) => {
+                                    follow_poll_interval.is_none()
+                                        || input_scanner.is_byte_limit_reached()
+                                }
+                                Some(_) => true,
+                                None => false,
+                            };
                             cdp
                         }
+                        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                            if let Some(poll_interval) = follow_poll_interval
+                                .filter(|_| !input_scanner.is_byte_limit_reached())
+                            {
+                                // Wait for more data to be written to the followed input
+                                thread::sleep(poll_interval);
+                                continue;
+                            }
+                            break;
+                        }
                         Err(_) => {
                             break;
                         }
                     };
 
-                    // Send a batch to the checker
+                    // Send a batch to the checker, blocks if the channel is full
+                    let send_start = Instant::now();
                     if send_chan.send(cdps).is_err() {
                         break;
                     }
+                    input_scanner.record_send_time(send_start.elapsed());
                 }
             }
         })
@@ -203,36 +297,38 @@ pub fn spawn_reader<T: RDH + 'static, const CAP: usize>(
 
 /// Attempts to fill a CDP batch with as many CDPs as possible (up to the batch capacity) and returns it
 ///
-/// If an error occurs after one or more CDPs have been read, the CDP batch is returned with the CDPs read so far
+/// If an error occurs after one or more CDPs have been read, the CDP batch is returned with the CDPs read so far,
+/// along with the kind of the error if it was invalid data or EOF.
 /// If the error occurs before any CDPs have been read, the error is returned
 #[inline]
 fn get_array_batch<T: RDH, const CAP: usize>(
     file_scanner: &mut InputScanner<impl BufferedReaderWrapper + ?Sized>,
-) -> Result<CdpArray<T, CAP>, io::Error> {
+) -> Result<(CdpArray<T, CAP>, Option<io::ErrorKind>), io::Error> {
     let mut cdp_arr = CdpArray::<T, CAP>::new_const();
 
-    for _ in 0..CAP {
+    while cdp_arr.len() < CAP {
         let (rdh, payload, mem_pos) = match file_scanner.load_cdp() {
             Ok(cdp) => cdp,
-            Err(e) if e.kind() == io::ErrorKind::InvalidData => {
-                break;
-            }
-            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => {
-                break;
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::InvalidData | io::ErrorKind::UnexpectedEof
+                ) =>
+            {
+                if cdp_arr.is_empty() {
+                    return Err(e);
+                }
+                return Ok((cdp_arr, Some(e.kind())));
             }
             Err(e) => return Err(e),
         };
-        cdp_arr.push(rdh, payload, mem_pos);
-    }
-
-    if cdp_arr.is_empty() {
-        return Err(io::Error::new(
-            io::ErrorKind::UnexpectedEof,
-            "No CDPs found",
-        ));
+        // Only the sampled CDPs are forwarded, if sampling is set
+        if file_scanner.is_loaded_cdp_sampled() {
+            cdp_arr.push(rdh, payload, mem_pos);
+        }
     }
 
-    Ok(cdp_arr)
+    Ok((cdp_arr, None))
 }
 
 /// Spawns a reader thread that reads CDPs from the input and sends them to a producer channel
@@ -251,25 +347,44 @@ pub fn spawn_vec_reader<T: RDH + 'static>(
         .spawn({
             move || {
                 let mut input_scanner = input_scanner;
+                let follow_poll_interval = input_scanner.follow_poll_interval();
 
                 // Automatically extracts link to filter if one is supplied
                 while !stop_flag.load(Ordering::SeqCst) && !local_stop_on_non_full_batch {
                     let cdps = match get_vec_batch::<T>(&mut input_scanner, CDP_BATCH_SIZE) {
-                        Ok(cdp) => {
-                            if cdp.len() < CDP_BATCH_SIZE {
-                                local_stop_on_non_full_batch = true; // Stop on non-full batch, could be InvalidData
-                            }
+                        Ok((cdp, stopped_early_by)) => {
+                            // Stop on non-full batch, could be InvalidData, unless more data can be written to the followed input
+                            local_stop_on_non_full_batch = match stopped_early_by {
+                                Some(io::ErrorKind::UnexpectedEof) => {
+                                    follow_poll_interval.is_none()
+                                        || input_scanner.is_byte_limit_reached()
+                                }
+                                Some(_) => true,
+                                None => false,
+                            };
                             cdp
                         }
+                        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => {
+                            if let Some(poll_interval) = follow_poll_interval
+                                .filter(|_| !input_scanner.is_byte_limit_reached())
+                            {
+                                // Wait for more data to be written to the followed input
+                                thread::sleep(poll_interval);
+                                continue;
+                            }
+                            break;
+                        }
                         Err(_) => {
                             break;
                         }
                     };
 
-                    // Send a batch to the checker
+                    // Send a batch to the checker, blocks if the channel is full
+                    let send_start = Instant::now();
                     if send_chan.send(cdps).is_err() {
                         break;
                     }
+                    input_scanner.record_send_time(send_start.elapsed());
                 }
             }
         })
@@ -279,39 +394,41 @@ pub fn spawn_vec_reader<T: RDH + 'static>(
 
 /// Attempts to fill a CDP batch with as many CDPs as possible (up to the batch size) and returns it.
 ///
-/// If an error occurs after one or more CDPs have been read, the CDP batch is returned with the CDPs read so far
+/// If an error occurs after one or more CDPs have been read, the CDP batch is returned with the CDPs read so far,
+/// along with the kind of the error if it was invalid data or EOF.
 /// If the error occurs before any CDPs have been read, the error is returned
 #[inline]
 fn get_vec_batch<T: RDH>(
     file_scanner: &mut InputScanner<impl BufferedReaderWrapper + ?Sized>,
     batch_size_cdps: usize,
-) -> Result<CdpVec<T>, io::Error> {
+) -> Result<(CdpVec<T>, Option<io::ErrorKind>), io::Error> {
     let mut cdp_batch = CdpVec::with_capacity(batch_size_cdps);
 
-    for _ in 0..batch_size_cdps {
+    while cdp_batch.len() < batch_size_cdps {
         let cdp_tuple = match file_scanner.load_cdp() {
             Ok(cdp) => cdp,
-            Err(e) if e.kind() == io::ErrorKind::InvalidData => {
-                break;
-            }
-            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => {
-                // This error will always be returned when the input is exhausted
-                //  as we try to read a CDP from the input without knowing if there is one
-                break;
+            // EOF will always be returned when the input is exhausted
+            //  as we try to read a CDP from the input without knowing if there is one
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    io::ErrorKind::InvalidData | io::ErrorKind::UnexpectedEof
+                ) =>
+            {
+                if cdp_batch.is_empty() {
+                    return Err(e);
+                }
+                return Ok((cdp_batch, Some(e.kind())));
             }
             Err(e) => return Err(e),
         };
-        cdp_batch.push(cdp_tuple.0, cdp_tuple.1, cdp_tuple.2);
-    }
-
-    if cdp_batch.is_empty() {
-        return Err(io::Error::new(
-            io::ErrorKind::UnexpectedEof,
-            "No CDPs found",
-        ));
+        // Only the sampled CDPs are forwarded, if sampling is set
+        if file_scanner.is_loaded_cdp_sampled() {
+            cdp_batch.push(cdp_tuple.0, cdp_tuple.1, cdp_tuple.2);
+        }
     }
 
-    Ok(cdp_batch)
+    Ok((cdp_batch, None))
 }
 
 #[cfg(test)]
@@ -322,6 +439,7 @@ mod tests {
     use rdh::test_data::CORRECT_RDH_CRU_V7_NEXT;
     use rdh::test_data::CORRECT_RDH_CRU_V7_NEXT_NEXT_STOP;
     use rdh::ByteSlice;
+    use rdh::RDH_CRU;
     use temp_dir::TempDir;
 
     #[test]
@@ -335,7 +453,7 @@ mod tests {
 
         use crate::input_scanner::InputScanner;
         use rdh::RdhCru;
-        let reader = init_reader(Some(&test_file_path)).unwrap();
+        let reader = init_reader(&[test_file_path]).unwrap();
 
         let mut input_scanner = InputScanner::minimal(reader);
 
@@ -353,17 +471,29 @@ mod tests {
             false
         }
 
-        fn filter_link(&self) -> Option<u8> {
+        fn filter_links(&self) -> Option<&[u8]> {
             None
         }
 
-        fn filter_fee(&self) -> Option<u16> {
+        fn filter_fee(&self) -> Option<&[(u16, u16)]> {
             None
         }
 
         fn filter_its_stave(&self) -> Option<u16> {
             None
         }
+
+        fn filter_trigger_type(&self) -> Option<u32> {
+            None
+        }
+
+        fn filter_orbit_range(&self) -> Option<(u32, u32)> {
+            None
+        }
+
+        fn max_payload_size(&self) -> Option<u16> {
+            None
+        }
     }
 
     #[test]
@@ -377,7 +507,7 @@ mod tests {
 
         use rdh::RdhCru;
 
-        let reader = init_reader(Some(&test_file_path)).unwrap();
+        let reader = init_reader(&[test_file_path]).unwrap();
 
         let mut input_scanner = input_scanner::InputScanner::new(&MyCfg, reader, None);
 
@@ -388,4 +518,32 @@ mod tests {
             Err(e) => eprintln!("{e}"),
         }
     }
+
+    #[test]
+    fn test_vec_batch_sample_every() {
+        let tmp_d = TempDir::new().unwrap();
+        let test_file_path = tmp_d.child("test.raw");
+        let cdp = [
+            CORRECT_RDH_CRU_V7.to_byte_slice(),
+            &vec![0; CORRECT_RDH_CRU_V7.payload_size() as usize],
+        ]
+        .concat();
+        std::fs::write(&test_file_path, cdp.repeat(5)).unwrap();
+
+        let reader = init_reader(&[test_file_path]).unwrap();
+        let mut input_scanner = InputScanner::minimal(reader).with_sample_every(2);
+
+        let (cdp_batch, stopped_early_by) =
+            get_vec_batch::<rdh::RdhCru>(&mut input_scanner, 10).unwrap();
+
+        assert_eq!(stopped_early_by, Some(io::ErrorKind::UnexpectedEof));
+        let cdp_len = cdp.len() as u64;
+        assert_eq!(
+            cdp_batch
+                .into_iter()
+                .map(|(_, _, mem_pos)| mem_pos)
+                .collect::<Vec<_>>(),
+            [0, 2 * cdp_len, 4 * cdp_len]
+        );
+    }
 }
@@ -0,0 +1,190 @@
+//! Contains the [MultiFileReader] that reads several files in order as one continuous stream, implements [BufferedReaderWrapper].
+//!
+//! Useful when data from a single run is split across several files, the files are read as if they were concatenated.
+//! Memory positions are therefore relative to the start of the first file, e.g. the first byte of the second file is at the position equal to the size of the first file.
+use super::bufreader_wrapper::BufferedReaderWrapper;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Reads several files in order as one continuous stream
+#[derive(Debug)]
+pub struct MultiFileReader {
+    readers: Vec<BufReader<File>>,
+    /// Position of the first byte of each file in the stream
+    file_starts: Vec<u64>,
+    total_size: u64,
+    /// Index of the file that is currently read from
+    current: usize,
+    /// Position in the stream
+    pos: u64,
+}
+
+impl MultiFileReader {
+    /// Opens the files at the given paths, each file is read through a [BufReader] with the given capacity.
+    pub fn new(paths: &[impl AsRef<Path>], capacity: usize) -> io::Result<Self> {
+        let mut readers = Vec::with_capacity(paths.len());
+        let mut file_starts = Vec::with_capacity(paths.len());
+        let mut total_size = 0;
+        for path in paths {
+            let f = File::open(path.as_ref())?;
+            file_starts.push(total_size);
+            total_size += f.metadata()?.len();
+            readers.push(BufReader::with_capacity(capacity, f));
+        }
+        Ok(Self {
+            readers,
+            file_starts,
+            total_size,
+            current: 0,
+            pos: 0,
+        })
+    }
+
+    /// Returns the position of the first byte of each file in the stream, in the order the files are read.
+    pub fn file_start_positions(&self) -> &[u64] {
+        &self.file_starts
+    }
+
+    /// Returns the combined size of all the files in bytes.
+    pub fn total_size(&self) -> u64 {
+        self.total_size
+    }
+
+    /// Moves to the absolute position `target` in the stream and returns it.
+    ///
+    /// Positions past the end of the stream are placed in the last file, like seeking past the end of a single file.
+    fn seek_to(&mut self, target: u64) -> io::Result<u64> {
+        if self.readers.is_empty() {
+            self.pos = target;
+            return Ok(target);
+        }
+        let file_idx = self
+            .file_starts
+            .partition_point(|&start| start <= target)
+            .saturating_sub(1);
+        if file_idx == self.current {
+            // Keeps the buffered data if the target is within the buffer
+            self.readers[file_idx].seek_relative(target as i64 - self.pos as i64)?;
+        } else {
+            let _ = self.readers[file_idx]
+                .seek(SeekFrom::Start(target - self.file_starts[file_idx]))?;
+            self.current = file_idx;
+        }
+        self.pos = target;
+        Ok(target)
+    }
+}
+
+impl Read for MultiFileReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while let Some(reader) = self.readers.get_mut(self.current) {
+            let bytes_read = reader.read(buf)?;
+            if bytes_read > 0 || buf.is_empty() {
+                self.pos += bytes_read as u64;
+                return Ok(bytes_read);
+            }
+            // End of the current file, continue from the start of the next file
+            let Some(&next_start) = self.file_starts.get(self.current + 1) else {
+                break;
+            };
+            let _ = self.readers[self.current + 1].seek(SeekFrom::Start(0))?;
+            self.current += 1;
+            self.pos = next_start;
+        }
+        Ok(0)
+    }
+}
+
+impl Seek for MultiFileReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+            SeekFrom::End(offset) => self.total_size.checked_add_signed(offset),
+        };
+        match target {
+            Some(target) => self.seek_to(target),
+            None => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+}
+
+impl BufferedReaderWrapper for MultiFileReader {
+    #[inline]
+    fn seek_relative_offset(&mut self, offset: i64) -> io::Result<()> {
+        self.seek(SeekFrom::Current(offset)).map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use temp_dir::TempDir;
+
+    fn make_files(tmp_d: &TempDir, contents: &[&[u8]]) -> Vec<PathBuf> {
+        contents
+            .iter()
+            .enumerate()
+            .map(|(idx, content)| {
+                let path = tmp_d.child(format!("{idx}.raw"));
+                std::fs::write(&path, content).unwrap();
+                path
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_read_across_files() {
+        let tmp_d = TempDir::new().unwrap();
+        let paths = make_files(&tmp_d, &[&[0, 1, 2], &[], &[3, 4], &[5]]);
+        let mut reader = MultiFileReader::new(&paths, 2).unwrap();
+        assert_eq!(reader.file_start_positions(), &[0, 3, 3, 5]);
+        assert_eq!(reader.total_size(), 6);
+
+        let mut buf = Vec::new();
+        let _ = reader.read_to_end(&mut buf).unwrap();
+        assert_eq!(buf, vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_seek_relative_across_files() {
+        let tmp_d = TempDir::new().unwrap();
+        let paths = make_files(&tmp_d, &[&[0, 1, 2], &[3, 4, 5, 6], &[7, 8]]);
+        let mut reader = MultiFileReader::new(&paths, 2).unwrap();
+
+        let mut buf = [0u8; 2];
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [0, 1]);
+        // Skip from the first file into the second file
+        reader.seek_relative_offset(2).unwrap();
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [4, 5]);
+        // Read across the boundary of the second and third file
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [6, 7]);
+        // Seek back into the first file
+        reader.seek_relative_offset(-6).unwrap();
+        reader.read_exact(&mut buf).unwrap();
+        assert_eq!(buf, [2, 3]);
+        assert_eq!(reader.stream_position().unwrap(), 4);
+    }
+
+    #[test]
+    fn test_seek_past_end() {
+        let tmp_d = TempDir::new().unwrap();
+        let paths = make_files(&tmp_d, &[&[0, 1], &[2]]);
+        let mut reader = MultiFileReader::new(&paths, 2).unwrap();
+        reader.seek_relative_offset(10).unwrap();
+        let mut buf = [0u8; 1];
+        assert_eq!(
+            reader.read_exact(&mut buf).unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+        assert!(reader.seek_relative_offset(-20).is_err());
+    }
+}
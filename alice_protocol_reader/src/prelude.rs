@@ -2,12 +2,18 @@
 
 pub use super::bufreader_wrapper::BufferedReaderWrapper;
 pub use super::cdp_wrapper::cdp_vec::CdpVec;
+pub use super::endianness::Endianness;
+pub use super::forward_reader_seeker::ForwardReaderSeeker;
+pub use super::input_scanner::builder::InputScannerBuilder;
+pub use super::input_scanner::CdpIter;
 pub use super::input_scanner::InputScanner;
+pub use super::multi_file_reader::MultiFileReader;
 pub use super::scan_cdp::ScanCDP;
 pub use super::stats::InputStatType;
 pub use super::stdin_reader::StdInReaderSeeker;
 // RDH related
 pub use super::rdh::macros;
+pub use super::rdh::parse_rdh;
 pub use super::rdh::rdh0::FeeId;
 pub use super::rdh::rdh0::Rdh0;
 pub use super::rdh::rdh1::BcReserved;
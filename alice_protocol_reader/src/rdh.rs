@@ -85,6 +85,8 @@ pub trait RDH_CRU {
     fn trigger_type(&self) -> u32;
     /// Returns the value of the FEE ID.
     fn fee_id(&self) -> u16;
+    /// Returns the value of the priority bit, set for priority readout.
+    fn priority_bit(&self) -> u8;
     /// Returns the value of the CRU ID.
     fn cru_id(&self) -> u16;
     /// Returns the value of the DW.
@@ -162,6 +164,11 @@ where
         (*self).fee_id()
     }
 
+    #[inline]
+    fn priority_bit(&self) -> u8 {
+        (*self).priority_bit()
+    }
+
     #[inline]
     fn cru_id(&self) -> u16 {
         (*self).cru_id()
@@ -214,6 +221,43 @@ where
     fn from_rdh0_and_buf(rdh0: Rdh0, buf: &[u8]) -> Result<Self, std::io::Error>;
 }
 
+/// Parses a single [RDH] from a byte slice that starts with a full [RDH].
+///
+/// The [RDH0][Rdh0] is parsed first to check that the RDH version is supported, before parsing the rest of the [RDH].
+///
+/// # Errors
+/// Returns an error if the slice is shorter than [RDH_CRU_SIZE_BYTES] or if the RDH version is not supported (3-100).
+///
+/// # Example
+/// ```
+/// use alice_protocol_reader::prelude::*;
+/// use alice_protocol_reader::prelude::test_data::CORRECT_RDH_CRU_V7;
+///
+/// let rdh = parse_rdh(CORRECT_RDH_CRU_V7.to_byte_slice()).unwrap();
+/// assert_eq!(rdh, CORRECT_RDH_CRU_V7);
+/// assert_eq!(rdh.version(), 7);
+/// ```
+pub fn parse_rdh(bytes: &[u8]) -> Result<RdhCru, std::io::Error> {
+    if bytes.len() < RDH_CRU_SIZE_BYTES as usize {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::UnexpectedEof,
+            format!(
+                "Expected at least {RDH_CRU_SIZE_BYTES} bytes to parse an RDH, got {}",
+                bytes.len()
+            ),
+        ));
+    }
+    let rdh0 = Rdh0::from_buf(&bytes[0..=7])?;
+    // Same range of versions as the ones attempted to be parsed when reading input data
+    if !(3..=100).contains(&rdh0.header_id) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Unsupported RDH version: {}", rdh0.header_id),
+        ));
+    }
+    RdhCru::from_rdh0_and_buf(rdh0, &bytes[8..=63])
+}
+
 /// Trait used to convert a struct to a byte slice.
 /// All structs that are used to represent a full GBT word (not sub RDH words) must implement this trait.
 pub trait ByteSlice: Sized {
@@ -255,3 +299,30 @@ pub mod macros {
     }
     pub use load_bytes;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::test_data::{CORRECT_RDH_CRU_V6, CORRECT_RDH_CRU_V7};
+    use super::*;
+
+    #[test]
+    fn test_parse_rdh_v6() {
+        let rdh = parse_rdh(CORRECT_RDH_CRU_V6.to_byte_slice()).unwrap();
+        assert_eq!(rdh, CORRECT_RDH_CRU_V6);
+    }
+
+    #[test]
+    fn test_parse_rdh_too_short() {
+        let err = parse_rdh(&CORRECT_RDH_CRU_V7.to_byte_slice()[..63]).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_parse_rdh_unsupported_version() {
+        let mut bytes = CORRECT_RDH_CRU_V7.to_byte_slice().to_vec();
+        bytes[0] = 0xFF;
+        let err = parse_rdh(&bytes).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "Unsupported RDH version: 255");
+    }
+}
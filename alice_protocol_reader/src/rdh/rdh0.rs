@@ -2,6 +2,7 @@
 use crate::prelude::{BLUE, GREEN};
 
 use super::RdhSubword;
+use crate::endianness::{rdh_subwords_to_le, Endianness};
 use byteorder::{ByteOrder, LittleEndian};
 use owo_colors::OwoColorize;
 use std::fmt::{self, Debug, Display};
@@ -68,6 +69,58 @@ impl Rdh0 {
     pub fn fee_id(&self) -> u16 {
         self.fee_id.0
     }
+
+    /// Scans forward from an [Rdh0] that is not valid, one byte at a time, until `is_valid` accepts the [Rdh0] at the current position.
+    ///
+    /// Only reads forward, so it also works with input that cannot seek, e.g. stdin.
+    /// Returns the valid [Rdh0] and the number of bytes skipped before it, or [None] if no valid [Rdh0] starts within `max_skip_bytes` bytes, or the input ends before one is found.
+    pub fn scan_for_valid<R: io::Read>(
+        invalid_rdh0: Rdh0,
+        reader: &mut R,
+        max_skip_bytes: u64,
+        is_valid: impl Fn(&Rdh0) -> bool,
+    ) -> Result<Option<(Rdh0, u64)>, io::Error> {
+        let mut window = invalid_rdh0.to_le_bytes();
+        let mut next_byte = [0u8; 1];
+        for skipped_bytes in 1..=max_skip_bytes {
+            match reader.read_exact(&mut next_byte) {
+                Ok(()) => (),
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+                Err(e) => return Err(e),
+            }
+            window.rotate_left(1);
+            window[7] = next_byte[0];
+            let rdh0 = Self::from_buf(&window)?;
+            if is_valid(&rdh0) {
+                return Ok(Some((rdh0, skipped_bytes)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Deserializes a [Rdh0] from a reader, where the subword is stored with the byte order of the given [Endianness].
+    pub fn load_with_endianness<R: io::Read>(
+        reader: &mut R,
+        endianness: Endianness,
+    ) -> Result<Self, io::Error> {
+        let mut buf = [0u8; 8];
+        reader.read_exact(&mut buf)?;
+        if endianness == Endianness::Big {
+            rdh_subwords_to_le(&mut buf);
+        }
+        Self::from_buf(&buf)
+    }
+
+    fn to_le_bytes(self) -> [u8; 8] {
+        let mut bytes = [0u8; 8];
+        bytes[0] = self.header_id;
+        bytes[1] = self.header_size;
+        LittleEndian::write_u16(&mut bytes[2..=3], self.fee_id.0);
+        bytes[4] = self.priority_bit;
+        bytes[5] = self.system_id;
+        LittleEndian::write_u16(&mut bytes[6..=7], self.reserved0);
+        bytes
+    }
 }
 
 impl Display for Rdh0 {
@@ -131,4 +184,42 @@ mod tests {
         };
         assert_eq!(rdh0, rdh0_2);
     }
+
+    #[test]
+    fn test_scan_for_valid() {
+        let is_valid = |rdh0: &Rdh0| rdh0.header_id == 7 && rdh0.header_size == Rdh0::HEADER_SIZE;
+        // 3 bytes of junk before a valid RDH0
+        let data = [0xFF, 0xFF, 0xFF, 7, 0x40, 0x0C, 0x20, 0, 0x20, 0, 0];
+        let mut reader = io::Cursor::new(&data[..]);
+        let junk_rdh0 = Rdh0::load(&mut reader).unwrap();
+        assert!(!is_valid(&junk_rdh0));
+
+        let (rdh0, skipped_bytes) = Rdh0::scan_for_valid(junk_rdh0, &mut reader, 100, is_valid)
+            .unwrap()
+            .unwrap();
+        assert_eq!(skipped_bytes, 3);
+        assert_eq!(rdh0.header_id, 7);
+        assert_eq!(rdh0.fee_id(), 0x200C);
+        assert_eq!({ rdh0.system_id }, 0x20);
+    }
+
+    #[test]
+    fn test_scan_for_valid_not_found() {
+        let is_valid = |rdh0: &Rdh0| rdh0.header_id == 7 && rdh0.header_size == Rdh0::HEADER_SIZE;
+        let data = [0xFF, 0xFF, 0xFF, 7, 0x40, 0x0C, 0x20, 0, 0x20, 0, 0];
+        // The valid RDH0 starts after the maximum number of bytes to skip
+        let mut reader = io::Cursor::new(&data[..]);
+        let junk_rdh0 = Rdh0::load(&mut reader).unwrap();
+        assert_eq!(
+            Rdh0::scan_for_valid(junk_rdh0, &mut reader, 2, is_valid).unwrap(),
+            None
+        );
+        // The input ends before a valid RDH0 is found
+        let mut reader = io::Cursor::new(&data[..9]);
+        let junk_rdh0 = Rdh0::load(&mut reader).unwrap();
+        assert_eq!(
+            Rdh0::scan_for_valid(junk_rdh0, &mut reader, 100, is_valid).unwrap(),
+            None
+        );
+    }
 }
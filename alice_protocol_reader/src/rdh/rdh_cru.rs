@@ -13,7 +13,7 @@ use std::fmt::{self, Display};
 
 // Contains the header text for printing a column view of the [RDH CRU][RdhCru].
 // each tuple contains the top and bottom text for a column.
-const HEADER_TEXT_TOP_BOT: [(&str, &str); 14] = [
+const HEADER_TEXT_TOP_BOT: [(&str, &str); 15] = [
     ("RDH   ", "ver   "),
     ("Header ", "size   "),
     ("FEE    ", "ID     "),
@@ -27,10 +27,11 @@ const HEADER_TEXT_TOP_BOT: [(&str, &str); 14] = [
     ("Trigger   ", "type      "),
     ("Pages    ", "counter  "),
     ("Stop  ", "bit   "),
-    ("Detector", "field   "),
+    ("Detector  ", "field     "),
+    ("Prio", "bit "),
 ];
 // The width of the header text column (characters)
-const HEADER_TEXT_COLUMN_WIDTH: usize = 111;
+const HEADER_TEXT_COLUMN_WIDTH: usize = 117;
 
 /// Represents the `Data format` and `reserved` fields. Using a newtype because the fields are packed in 64 bits, and extracting the values requires some work.
 #[repr(packed)]
@@ -71,12 +72,13 @@ impl Display for RdhCru {
         let detector_field = self.rdh3.detector_field;
         write!(
             f,
-            "{rdh0}{rdhcru_fields0}{rdh1}{data_format:<11}{rdh2} {det_field:#x}",
+            "{rdh0}{rdhcru_fields0}{rdh1}{data_format:<11}{rdh2} {det_field:<#10x}{priority_bit}",
             rdh0 = self.rdh0,
             rdh1 = self.rdh1,
             data_format = self.data_format(),
             rdh2 = self.rdh2,
-            det_field = detector_field
+            det_field = detector_field,
+            priority_bit = self.rdh0.priority_bit
         )
     }
 }
@@ -157,8 +159,8 @@ impl RdhCru {
     /// Takes an [usize] as an argument, which is the number of spaces to indent the 2 lines by.
     #[inline]
     pub fn rdh_header_text_with_indent_to_string(indent: usize) -> String {
-        let header_text_top = "RDH   Header  FEE   Sys   Offset  Link  Packet    BC   Orbit       Data       Trigger   Pages    Stop  Detector";
-        let header_text_bot = "ver   size    ID    ID    next    ID    counter        counter     format     type      counter  bit   field";
+        let header_text_top = "RDH   Header  FEE   Sys   Offset  Link  Packet    BC   Orbit       Data       Trigger   Pages    Stop  Detector  Prio";
+        let header_text_bot = "ver   size    ID    ID    next    ID    counter        counter     format     type      counter  bit   field     bit";
         format!(
             "{:indent$}{header_text_top}\n{:indent2$}{header_text_bot}\n",
             "",
@@ -237,7 +239,7 @@ impl RDH for RdhCru {
         let tmp_packet_cnt = self.packet_counter;
         let detector_field = self.rdh3.detector_field;
         format!(
-            "{rdh0}{tmp_offset:<8}{tmp_link:<6}{tmp_packet_cnt:<10}{rdh1}{data_format:<11}{rdh2}{det_field:#x}",
+            "{rdh0}{tmp_offset:<8}{tmp_link:<6}{tmp_packet_cnt:<10}{rdh1}{data_format:<11}{rdh2}{det_field}{priority_bit}",
             rdh0 = self.rdh0.to_styled_row_view(),
             tmp_offset = tmp_offset.white().bg_rgb::<0, GREEN, 0>(),
             tmp_link = tmp_link.white().bg_rgb::<0, 0, BLUE>(),
@@ -245,7 +247,10 @@ impl RDH for RdhCru {
             rdh1 = self.rdh1.to_styled_row_view(),
             data_format = self.data_format().white().bg_rgb::<0, 0, BLUE>(),
             rdh2 = self.rdh2.to_styled_row_view(),
-            det_field = detector_field.white().bg_rgb::<0, 0, BLUE>()
+            det_field = format_args!("{detector_field:<#10x}")
+                .white()
+                .bg_rgb::<0, 0, BLUE>(),
+            priority_bit = self.rdh0.priority_bit.white().bg_rgb::<0, GREEN, 0>()
         )
     }
 }
@@ -285,6 +290,10 @@ impl RDH_CRU for RdhCru {
         self.rdh0.fee_id.0
     }
     #[inline]
+    fn priority_bit(&self) -> u8 {
+        self.rdh0.priority_bit
+    }
+    #[inline]
     fn version(&self) -> u8 {
         self.rdh0.header_id
     }
@@ -355,6 +364,7 @@ mod tests {
         assert_eq!(rdh.rdh0.header_size, 0x40);
         assert_eq!(rdh.rdh0.fee_id, FeeId(0x502A));
         assert_eq!(rdh.rdh0.priority_bit, 0x0);
+        assert_eq!(rdh.priority_bit(), 0x0);
         assert_eq!(rdh.rdh0.system_id, 0x20);
         let pages_counter = rdh.rdh2.pages_counter;
         assert_eq!(pages_counter, 0x0);
@@ -183,3 +183,98 @@ pub const CORRECT_RDH_CRU_V7_NEXT_NEXT_STOP: RdhCru = RdhCru {
     },
     reserved2: 0x0,
 };
+
+/// Builds an [RDH CRU][RdhCru] for tests, starting from [CORRECT_RDH_CRU_V7] and changing only the fields that are set.
+///
+/// # Example
+/// ```
+/// use alice_protocol_reader::prelude::*;
+///
+/// let rdh = test_data::RdhCruBuilder::new()
+///     .link_id(3)
+///     .orbit(0x200)
+///     .stop_bit(1)
+///     .build();
+/// assert_eq!(rdh.link_id(), 3);
+/// assert_eq!({ rdh.rdh1().orbit }, 0x200);
+/// assert_eq!(rdh.stop_bit(), 1);
+/// assert_eq!(rdh.fee_id(), test_data::CORRECT_RDH_CRU_V7.fee_id());
+/// ```
+#[derive(Debug)]
+pub struct RdhCruBuilder {
+    rdh: RdhCru,
+}
+
+impl Default for RdhCruBuilder {
+    fn default() -> Self {
+        Self {
+            rdh: CORRECT_RDH_CRU_V7,
+        }
+    }
+}
+
+impl RdhCruBuilder {
+    /// Creates a builder of a copy of [CORRECT_RDH_CRU_V7].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the header size of the [RDH0][Rdh0].
+    pub fn header_size(mut self, header_size: u8) -> Self {
+        self.rdh.rdh0.header_size = header_size;
+        self
+    }
+
+    /// Set the offset to the next RDH.
+    pub fn offset_to_next(mut self, offset_to_next: u16) -> Self {
+        self.rdh.offset_new_packet = offset_to_next;
+        self
+    }
+
+    /// Set the memory size, i.e. the size of the RDH and its payload.
+    pub fn memory_size(mut self, memory_size: u16) -> Self {
+        self.rdh.memory_size = memory_size;
+        self
+    }
+
+    /// Set the link ID.
+    pub fn link_id(mut self, link_id: u8) -> Self {
+        self.rdh.link_id = link_id;
+        self
+    }
+
+    /// Set the packet counter.
+    pub fn packet_counter(mut self, packet_counter: u8) -> Self {
+        self.rdh.packet_counter = packet_counter;
+        self
+    }
+
+    /// Set the raw 16 bit word of the CRU ID (12 bits) and the DW (4 bits).
+    pub fn cruid_dw(mut self, cruid_dw: u16) -> Self {
+        self.rdh.cruid_dw = CruidDw(cruid_dw);
+        self
+    }
+
+    /// Set the orbit of the [RDH1][Rdh1].
+    pub fn orbit(mut self, orbit: u32) -> Self {
+        self.rdh.rdh1.orbit = orbit;
+        self
+    }
+
+    /// Set the bunch crossing of the [RDH1][Rdh1], the reserved bits are set to 0.
+    pub fn bc(mut self, bc: u16) -> Self {
+        self.rdh.rdh1.bc_reserved0 = BcReserved(u32::from(bc));
+        self
+    }
+
+    /// Set the stop bit of the [RDH2][Rdh2].
+    pub fn stop_bit(mut self, stop_bit: u8) -> Self {
+        self.rdh.rdh2.stop_bit = stop_bit;
+        self
+    }
+
+    /// Builds the [RDH CRU][RdhCru].
+    pub fn build(self) -> RdhCru {
+        self.rdh
+    }
+}
@@ -1,5 +1,7 @@
 //! Contains the [InputStatType] enum for which kind of statistics are gathered, and the [Stats] struct for tracking and reporting statistics about the input data.
 
+use std::collections::BTreeMap;
+
 #[allow(variant_size_differences)] // Allow in this case, the string is already a pointer.
 #[derive(Debug, Clone, PartialEq)]
 /// Possible stats that can be sent to the Controller.
@@ -8,26 +10,76 @@ pub enum InputStatType {
     Fatal(Box<str>),
     /// Error that can be recovered from, continue processing, reading might stop but the sent data is still valid.
     Error(Box<str>),
+    /// Something unexpected in the input that does not invalidate the data read so far, e.g. trailing bytes at EOF.
+    Warning(Box<str>),
     /// The first trigger type observed is the type of run the data comes from
     ///
     /// Contains the raw value and the string description summarizing the trigger type
     RunTriggerType(u32),
     /// Record the data format detected.
     DataFormat(u8),
+    /// The data format of an RDH differs from the data format of the previous RDH, the first data format is reported as [InputStatType::DataFormat].
+    DataFormatChanged {
+        /// Memory position of the RDH with the new data format
+        mem_pos: u64,
+        /// Data format of the previous RDH
+        from: u8,
+        /// Data format of the RDH at the memory position
+        to: u8,
+    },
     /// Add a link to the list of links observed.
     LinksObserved(u8),
     /// Record the generic FEE ID
     FeeId(u16),
+    /// Record the CRU ID
+    CruId(u16),
+    /// Record the DW (data wrapper ID) of the CRU
+    Dw(u8),
     /// Increment the total RDHs seen.
     RDHSeen(u32),
     /// Increment the total RDHs filtered.
     RDHFiltered(u32),
     /// Increment the total payload size.
     PayloadSize(u32),
+    /// Increment the total HBFs seen, i.e. the RDHs with the stop bit set.
+    HBFsSeen(u32),
+    /// Increment the RDHs seen and the payload size of a link.
+    LinkPayload {
+        /// Link ID
+        link_id: u8,
+        /// Number of RDHs from the link
+        rdh_count: u64,
+        /// Total payload size of the RDHs from the link in bytes
+        payload_size: u64,
+    },
     /// The first system ID observed is the basis for the rest of processing
     SystemId(u8),
+    /// The number of bytes of the input that has been read so far, reported periodically to track progress.
+    BytesRead(u64),
+    /// Summary of how often sending CDPs from the reader thread blocked for longer than the stall threshold, reported when reading is done.
+    ///
+    /// Frequent stalls mean that processing the CDPs is the bottleneck rather than reading them.
+    ReaderStalls {
+        /// Number of sends that blocked for longer than the stall threshold
+        stalls: u64,
+        /// Total time spent blocked in those sends
+        stall_time: std::time::Duration,
+    },
 }
 
+/// Snapshot of the counters of [Stats], used to undo counting the RDHs of a CDP that could not be loaded completely.
+#[derive(Debug, Clone)]
+pub(crate) struct StatsCounters {
+    rdhs_seen: u32,
+    rdhs_filtered: u32,
+    payload_size_seen: u32,
+    hbfs_seen: u32,
+    link_payloads: BTreeMap<u8, (u64, u64)>,
+}
+
+/// Minimum number of bytes read between two [InputStatType::BytesRead] reports.
+const BYTES_READ_REPORT_INTERVAL: u64 = 1024 * 1024;
+
 /// Struct for tracking and reporting statistics about the input data.
 #[derive(Debug)]
 pub struct Stats {
@@ -35,8 +87,15 @@ pub struct Stats {
     rdhs_seen: u32,
     rdhs_filtered: u32,
     payload_size_seen: u32,
+    hbfs_seen: u32,
+    /// RDHs seen and payload size per link since the last report
+    link_payloads: BTreeMap<u8, (u64, u64)>,
     unique_links_observed: Vec<u8>,
     unique_feeids_observed: Vec<u16>,
+    unique_cru_ids_observed: Vec<u16>,
+    unique_dws_observed: Vec<u8>,
+    last_data_format: Option<u8>,
+    bytes_read_reported: u64,
 }
 
 impl Stats {
@@ -47,8 +106,14 @@ impl Stats {
             rdhs_seen: 0,
             rdhs_filtered: 0,
             payload_size_seen: 0,
+            hbfs_seen: 0,
+            link_payloads: BTreeMap::new(),
             unique_links_observed: Vec::new(),
             unique_feeids_observed: Vec::new(),
+            unique_cru_ids_observed: Vec::new(),
+            unique_dws_observed: Vec::new(),
+            last_data_format: None,
+            bytes_read_reported: 0,
         }
     }
 
@@ -70,6 +135,39 @@ impl Stats {
         }
     }
 
+    /// Attempt to add a CRU ID to the observed CRU IDs (is only added if not already present in the list).
+    pub fn try_add_cru_id(&mut self, cru_id: u16) {
+        if !self.unique_cru_ids_observed.contains(&cru_id) {
+            self.unique_cru_ids_observed.push(cru_id);
+            self.reporter.send(InputStatType::CruId(cru_id)).unwrap();
+        }
+    }
+
+    /// Attempt to add a DW to the observed DWs (is only added if not already present in the list).
+    pub fn try_add_dw(&mut self, dw: u8) {
+        if !self.unique_dws_observed.contains(&dw) {
+            self.unique_dws_observed.push(dw);
+            self.reporter.send(InputStatType::Dw(dw)).unwrap();
+        }
+    }
+
+    /// Record the data format of an RDH, reporting a change if it differs from the data format of the previous RDH.
+    pub fn record_data_format(&mut self, data_format: u8, mem_pos: u64) {
+        if let Some(last_data_format) = self
+            .last_data_format
+            .filter(|&last_data_format| last_data_format != data_format)
+        {
+            self.reporter
+                .send(InputStatType::DataFormatChanged {
+                    mem_pos,
+                    from: last_data_format,
+                    to: data_format,
+                })
+                .unwrap();
+        }
+        self.last_data_format = Some(data_format);
+    }
+
     /// Increment the RDH seen counter..
     pub fn rdh_seen(&mut self) {
         self.rdhs_seen += 1;
@@ -103,6 +201,60 @@ impl Stats {
         }
     }
 
+    /// Increment the HBF seen counter.
+    pub fn hbf_seen(&mut self) {
+        self.hbfs_seen += 1;
+        if self.hbfs_seen == u32::MAX {
+            self.reporter
+                .send(InputStatType::HBFsSeen(u32::MAX))
+                .unwrap();
+            self.hbfs_seen = 0;
+        }
+    }
+
+    /// Add an RDH and its payload size to the RDHs seen and payload size of its link.
+    pub fn add_link_payload(&mut self, link_id: u8, payload_size: u16) {
+        let (rdh_count, link_payload_size) = self.link_payloads.entry(link_id).or_default();
+        *rdh_count += 1;
+        *link_payload_size += payload_size as u64;
+    }
+
+    /// Returns a snapshot of the counters that can be restored with [Stats::restore_counters].
+    pub(crate) fn counters(&self) -> StatsCounters {
+        StatsCounters {
+            rdhs_seen: self.rdhs_seen,
+            rdhs_filtered: self.rdhs_filtered,
+            payload_size_seen: self.payload_size_seen,
+            hbfs_seen: self.hbfs_seen,
+            link_payloads: self.link_payloads.clone(),
+        }
+    }
+
+    /// Restores the counters from a snapshot, the observed links, FEE IDs, CRU IDs, DWs and data format are kept as they are already reported.
+    pub(crate) fn restore_counters(&mut self, counters: StatsCounters) {
+        self.rdhs_seen = counters.rdhs_seen;
+        self.rdhs_filtered = counters.rdhs_filtered;
+        self.payload_size_seen = counters.payload_size_seen;
+        self.hbfs_seen = counters.hbfs_seen;
+        self.link_payloads = counters.link_payloads;
+    }
+
+    /// Report the number of bytes read if at least [BYTES_READ_REPORT_INTERVAL] bytes were read since the last report.
+    ///
+    /// The HBFs seen since the last report are reported along with it, to track the progress in HBFs.
+    pub fn try_report_bytes_read(&mut self, bytes_read: u64) {
+        if bytes_read >= self.bytes_read_reported + BYTES_READ_REPORT_INTERVAL {
+            self.bytes_read_reported = bytes_read;
+            self.reporter
+                .send(InputStatType::BytesRead(bytes_read))
+                .unwrap();
+            self.reporter
+                .send(InputStatType::HBFsSeen(self.hbfs_seen))
+                .unwrap();
+            self.hbfs_seen = 0;
+        }
+    }
+
     /// Flush the stats to the reporter channel (sends all the current stats).
     pub fn flush_stats(&mut self) {
         self.reporter
@@ -114,5 +266,17 @@ impl Stats {
         self.reporter
             .send(InputStatType::PayloadSize(self.payload_size_seen))
             .unwrap();
+        self.reporter
+            .send(InputStatType::HBFsSeen(self.hbfs_seen))
+            .unwrap();
+        for (link_id, (rdh_count, payload_size)) in std::mem::take(&mut self.link_payloads) {
+            self.reporter
+                .send(InputStatType::LinkPayload {
+                    link_id,
+                    rdh_count,
+                    payload_size,
+                })
+                .unwrap();
+        }
     }
 }
@@ -1,5 +1,7 @@
 //! Contains modules for analyzing the data. Displaying the data in a human readable format or performing data validation.
 
+pub mod compare_raw;
+pub mod error_codes;
 pub mod lib;
 pub mod validators;
 pub mod view;
@@ -0,0 +1,217 @@
+//! Contains the [compare_raw_files] function that compares a candidate raw data file against a reference "golden" file.
+//!
+//! The files are scanned CDP by CDP with an [InputScanner] each, the RDHs and payloads are then compared byte by byte.
+//! Comparison stops at the first divergence, which is reported as a [Divergence] with the memory position and the differing word.
+
+use crate::util::*;
+use alice_protocol_reader::init_reader;
+
+/// Size of the words an RDH is compared in (4x 128-bit words)
+const RDH_WORD_SIZE: usize = 16;
+
+/// The outcome of comparing a candidate file against a golden file
+#[derive(Debug, PartialEq)]
+pub enum CompareOutcome {
+    /// All CDPs in the files are identical
+    Identical {
+        /// Number of CDPs compared
+        cdps: u64,
+    },
+    /// The files diverge
+    Diverged(Divergence),
+}
+
+/// The first position where the candidate file diverges from the golden file
+#[derive(Debug, PartialEq)]
+pub struct Divergence {
+    /// Memory position of the first differing word
+    pub mem_pos: u64,
+    /// Description of the divergence
+    pub description: String,
+    /// The differing word in the golden file (empty if the golden file has no data at this position)
+    pub golden_word: Vec<u8>,
+    /// The differing word in the candidate file (empty if the candidate file has no data at this position)
+    pub candidate_word: Vec<u8>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:#X}: {}", self.mem_pos, self.description)?;
+        writeln!(f, "  golden:    {}", hex_word(&self.golden_word))?;
+        write!(f, "  candidate: {}", hex_word(&self.candidate_word))
+    }
+}
+
+/// Compare the `candidate` raw data file against the `golden` raw data file.
+pub fn compare_raw_files(golden: &Path, candidate: &Path) -> io::Result<CompareOutcome> {
+    let mut golden_scanner = InputScanner::minimal(init_reader(&[golden.to_path_buf()])?);
+    let mut candidate_scanner = InputScanner::minimal(init_reader(&[candidate.to_path_buf()])?);
+    compare_cdps::<RdhCru>(&mut golden_scanner, &mut candidate_scanner)
+}
+
+/// Loads CDPs from both scanners until EOF or the first divergence.
+pub fn compare_cdps<T: RDH>(
+    golden: &mut impl ScanCDP,
+    candidate: &mut impl ScanCDP,
+) -> io::Result<CompareOutcome> {
+    let mut cdps: u64 = 0;
+    loop {
+        match (load_next_cdp::<T>(golden)?, load_next_cdp::<T>(candidate)?) {
+            (None, None) => return Ok(CompareOutcome::Identical { cdps }),
+            (Some((rdh, _, mem_pos)), None) => {
+                return Ok(CompareOutcome::Diverged(Divergence {
+                    mem_pos,
+                    description: "Candidate file ends before the golden file".to_string(),
+                    golden_word: rdh.to_byte_slice()[..RDH_WORD_SIZE].to_vec(),
+                    candidate_word: Vec::new(),
+                }))
+            }
+            (None, Some((rdh, _, mem_pos))) => {
+                return Ok(CompareOutcome::Diverged(Divergence {
+                    mem_pos,
+                    description: "Candidate file has more data than the golden file".to_string(),
+                    golden_word: Vec::new(),
+                    candidate_word: rdh.to_byte_slice()[..RDH_WORD_SIZE].to_vec(),
+                }))
+            }
+            (Some(golden_cdp), Some(candidate_cdp)) => {
+                if let Some(divergence) = compare_cdp(golden_cdp, candidate_cdp) {
+                    return Ok(CompareOutcome::Diverged(divergence));
+                }
+                cdps += 1;
+            }
+        }
+    }
+}
+
+/// Load the next CDP, returns [None] at EOF
+fn load_next_cdp<T: RDH>(scanner: &mut impl ScanCDP) -> io::Result<Option<(T, Vec<u8>, u64)>> {
+    match scanner.load_cdp::<T>() {
+        Ok(cdp) => Ok(Some(cdp)),
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+fn compare_cdp<T: RDH>(
+    golden: (T, Vec<u8>, u64),
+    candidate: (T, Vec<u8>, u64),
+) -> Option<Divergence> {
+    let (golden_rdh, golden_payload, rdh_mem_pos) = golden;
+    let (candidate_rdh, candidate_payload, _) = candidate;
+
+    if let Some((word_idx, golden_word, candidate_word)) = first_diverging_word(
+        golden_rdh.to_byte_slice(),
+        candidate_rdh.to_byte_slice(),
+        RDH_WORD_SIZE,
+    ) {
+        return Some(Divergence {
+            mem_pos: rdh_mem_pos + (word_idx * RDH_WORD_SIZE) as u64,
+            description: format!("RDH differs in word {word_idx}"),
+            golden_word: golden_word.to_vec(),
+            candidate_word: candidate_word.to_vec(),
+        });
+    }
+
+    // Data format 0 has GBT words padded to 16 bytes
+    let word_size = if golden_rdh.data_format() == 0 {
+        16
+    } else {
+        10
+    };
+    if let Some((word_idx, golden_word, candidate_word)) =
+        first_diverging_word(&golden_payload, &candidate_payload, word_size)
+    {
+        let description = if golden_payload.len() == candidate_payload.len() {
+            format!("Payload differs in GBT word {word_idx}")
+        } else {
+            format!(
+                "Payload size differs (golden: {golden_sz}, candidate: {candidate_sz}), first difference in GBT word {word_idx}",
+                golden_sz = golden_payload.len(),
+                candidate_sz = candidate_payload.len()
+            )
+        };
+        return Some(Divergence {
+            mem_pos: rdh_mem_pos + RDH_CRU_SIZE_BYTES as u64 + (word_idx * word_size) as u64,
+            description,
+            golden_word: golden_word.to_vec(),
+            candidate_word: candidate_word.to_vec(),
+        });
+    }
+    None
+}
+
+/// Splits both slices into words of `word_size` and returns the index of the first differing word, along with the words.
+fn first_diverging_word<'a>(
+    golden: &'a [u8],
+    candidate: &'a [u8],
+    word_size: usize,
+) -> Option<(usize, &'a [u8], &'a [u8])> {
+    let word_at = |slice: &'a [u8], idx: usize| slice.chunks(word_size).nth(idx).unwrap_or(&[]);
+
+    if let Some(idx) = golden
+        .chunks(word_size)
+        .zip(candidate.chunks(word_size))
+        .position(|(golden_word, candidate_word)| golden_word != candidate_word)
+    {
+        Some((idx, word_at(golden, idx), word_at(candidate, idx)))
+    } else if golden.len() != candidate.len() {
+        // All words up to the end of the shortest slice match
+        let idx = golden.len().min(candidate.len()) / word_size;
+        Some((idx, word_at(golden, idx), word_at(candidate, idx)))
+    } else {
+        None
+    }
+}
+
+fn hex_word(word: &[u8]) -> String {
+    format!("[{}]", word.iter().map(|b| format!("{b:02X}")).join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_first_diverging_word_identical() {
+        let golden = [0xE0; 30];
+        assert_eq!(first_diverging_word(&golden, &golden, 10), None);
+    }
+
+    #[test]
+    fn test_first_diverging_word_flipped_byte() {
+        let golden = [0xE0; 30];
+        let mut candidate = golden;
+        candidate[13] = 0xE1;
+        let (idx, golden_word, candidate_word) =
+            first_diverging_word(&golden, &candidate, 10).unwrap();
+        assert_eq!(idx, 1);
+        assert_eq!(golden_word, &golden[10..20]);
+        assert_eq!(candidate_word, &candidate[10..20]);
+    }
+
+    #[test]
+    fn test_first_diverging_word_truncated() {
+        let golden = [0xE0; 30];
+        let (idx, golden_word, candidate_word) =
+            first_diverging_word(&golden, &golden[..20], 10).unwrap();
+        assert_eq!(idx, 2);
+        assert_eq!(golden_word, &golden[20..30]);
+        assert!(candidate_word.is_empty());
+    }
+
+    #[test]
+    fn test_divergence_display() {
+        let divergence = Divergence {
+            mem_pos: 0x4A,
+            description: "Payload differs in GBT word 1".to_string(),
+            golden_word: vec![0x00, 0xE8],
+            candidate_word: vec![0x01, 0xE8],
+        };
+        assert_eq!(
+            divergence.to_string(),
+            "0x4A: Payload differs in GBT word 1\n  golden:    [00 E8]\n  candidate: [01 E8]"
+        );
+    }
+}
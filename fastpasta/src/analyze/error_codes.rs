@@ -0,0 +1,693 @@
+//! Contains the [ErrorCode] type and the [possible_error_codes] function that lists the error codes a check configuration can emit.
+//!
+//! This is pure metadata, e.g. for pre-populating error code filters or legends in a UI, no data is read.
+
+use crate::util::*;
+
+/// Describes which checks need to be enabled for an error code to be emitted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Requires {
+    /// `check sanity` or `check all`
+    Sanity,
+    /// `check all`
+    Running,
+    /// Any check without `--allow-mixed-data-format`
+    SingleDataFormat,
+    /// `check sanity its` or `check all its`
+    ItsSanity,
+    /// `check all its`
+    ItsRunning,
+    /// `check all its-stave`
+    ItsStave,
+    /// `check sanity its` or `check all its` with `--strict-padding`
+    ItsStrictPadding,
+    /// `check sanity its` or `check all its` with `--check-reserved`
+    ItsCheckReserved,
+    /// `check all its-stave` with a trigger period specified
+    ItsTriggerPeriod,
+    /// `check sanity ft0` or `check all ft0`
+    Ft0Sanity,
+    /// `check all ft0`
+    Ft0Running,
+    /// `check ordering`
+    Ordering,
+    /// Any check with `--max-payload-size` and payloads that are read
+    MaxPayloadSize,
+    /// Any check where payloads are read
+    PayloadRead,
+    /// Any check where payloads are skipped, e.g. `check sanity` without a target
+    PayloadSkipped,
+    /// Custom check on the number of CDPs
+    CustomCdps,
+    /// Custom check on the number of PhT triggers
+    CustomTriggersPht,
+    /// Custom check on the number of HBFs
+    CustomHbfs,
+    /// Custom check on the links observed
+    CustomExpectedLinks,
+    /// Custom check on the number of staves observed per ITS layer
+    CustomLayerStaveCounts,
+}
+
+/// An error code as it appears in error messages, e.g. `[E10]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ErrorCode {
+    code: u16,
+    description: &'static str,
+}
+
+impl ErrorCode {
+    const fn new(code: u16, description: &'static str) -> Self {
+        Self { code, description }
+    }
+
+    /// The numeric value of the error code, e.g. `10` for `[E10]`
+    pub fn code(&self) -> u16 {
+        self.code
+    }
+
+    /// Short description of the check that emits the error code
+    pub fn description(&self) -> &'static str {
+        self.description
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "E{}", self.code)
+    }
+}
+
+const ERROR_CODES: [(ErrorCode, Requires); 65] = [
+    (
+        ErrorCode::new(10, "RDH sanity check failed"),
+        Requires::Sanity,
+    ),
+    (
+        ErrorCode::new(11, "RDH running check failed"),
+        Requires::Running,
+    ),
+    (
+        ErrorCode::new(12, "IHW observed but RDH stop bit is not 0"),
+        Requires::ItsRunning,
+    ),
+    (
+        ErrorCode::new(13, "CDP is out of order"),
+        Requires::Ordering,
+    ),
+    (
+        ErrorCode::new(14, "RDH is not at the offset to next of the previous RDH"),
+        Requires::Running,
+    ),
+    (
+        ErrorCode::new(15, "RDH packet counter did not increase within the HBF"),
+        Requires::Running,
+    ),
+    (
+        ErrorCode::new(16, "RDH is identical to the previous RDH of the link"),
+        Requires::Running,
+    ),
+    (
+        ErrorCode::new(17, "Data format changed within the input"),
+        Requires::SingleDataFormat,
+    ),
+    (
+        ErrorCode::new(18, "RDH memory size exceeds the offset to next"),
+        Requires::Sanity,
+    ),
+    (
+        ErrorCode::new(30, "IHW sanity check failed"),
+        Requires::ItsSanity,
+    ),
+    (
+        ErrorCode::new(31, "IHW reserved bits are not 0"),
+        Requires::ItsCheckReserved,
+    ),
+    (
+        ErrorCode::new(35, "RDH CRU ID out of range"),
+        Requires::Sanity,
+    ),
+    (
+        ErrorCode::new(36, "RDH link ID out of range"),
+        Requires::Sanity,
+    ),
+    (
+        ErrorCode::new(40, "TDH sanity check failed"),
+        Requires::ItsSanity,
+    ),
+    (
+        ErrorCode::new(41, "TDH continuation is not 1"),
+        Requires::ItsRunning,
+    ),
+    (
+        ErrorCode::new(42, "TDH continuation is not 0"),
+        Requires::ItsRunning,
+    ),
+    (
+        ErrorCode::new(43, "TDH reserved bits are not 0"),
+        Requires::ItsCheckReserved,
+    ),
+    (
+        ErrorCode::new(44, "TDH trigger_type does not match RDH trigger_type"),
+        Requires::ItsRunning,
+    ),
+    (
+        ErrorCode::new(45, "TDH trigger period mismatch with user specified"),
+        Requires::ItsTriggerPeriod,
+    ),
+    (
+        ErrorCode::new(48, "TDH orbit advance inconsistent with trigger period"),
+        Requires::ItsTriggerPeriod,
+    ),
+    (
+        ErrorCode::new(49, "Repeated orbit with inconsistent bc"),
+        Requires::Running,
+    ),
+    (
+        ErrorCode::new(50, "TDT sanity check failed"),
+        Requires::ItsSanity,
+    ),
+    (
+        ErrorCode::new(51, "TDT reserved bits are not 0"),
+        Requires::ItsCheckReserved,
+    ),
+    (
+        ErrorCode::new(
+            58,
+            "HBF ended without a TDT with packet_done closing the readout frame",
+        ),
+        Requires::ItsRunning,
+    ),
+    (
+        ErrorCode::new(59, "End of readout frame without a start of readout frame"),
+        Requires::ItsStave,
+    ),
+    (
+        ErrorCode::new(60, "DDW0 sanity check failed"),
+        Requires::ItsSanity,
+    ),
+    (
+        ErrorCode::new(61, "DDW0 reserved bits are not 0"),
+        Requires::ItsCheckReserved,
+    ),
+    (
+        ErrorCode::new(66, "TDT lane error not reflected in DDW0"),
+        Requires::ItsRunning,
+    ),
+    (
+        ErrorCode::new(70, "Data word ID is invalid"),
+        Requires::ItsSanity,
+    ),
+    (
+        ErrorCode::new(71, "OB lane is not active according to IHW"),
+        Requires::ItsRunning,
+    ),
+    (
+        ErrorCode::new(
+            72,
+            "IB lane is not active according to IHW or IB ALPIDE data frame lanes are invalid",
+        ),
+        Requires::ItsRunning,
+    ),
+    (
+        ErrorCode::new(
+            73,
+            "OB data word input connector out of range or OB ALPIDE data frame lanes are invalid",
+        ),
+        Requires::ItsRunning,
+    ),
+    (
+        ErrorCode::new(74, "IB ALPIDE data frame has lane errors"),
+        Requires::ItsStave,
+    ),
+    (
+        ErrorCode::new(75, "OB ALPIDE data frame has lane errors"),
+        Requires::ItsStave,
+    ),
+    (
+        ErrorCode::new(76, "Data word observed but the IHW has no active lanes"),
+        Requires::ItsRunning,
+    ),
+    (
+        ErrorCode::new(81, "CDW index is not 0"),
+        Requires::ItsRunning,
+    ),
+    (
+        ErrorCode::new(82, "CDW index is not contiguous"),
+        Requires::ItsRunning,
+    ),
+    (
+        ErrorCode::new(83, "CDW reserved bits are not 0"),
+        Requires::ItsCheckReserved,
+    ),
+    (
+        ErrorCode::new(90, "End of payload 0xFF padding is malformed"),
+        Requires::ItsStrictPadding,
+    ),
+    (
+        ErrorCode::new(100, "Failed to read the payload, the input ends before it"),
+        Requires::PayloadRead,
+    ),
+    (
+        ErrorCode::new(
+            101,
+            "Failed to skip the payload, the offset to the next RDH is invalid",
+        ),
+        Requires::PayloadSkipped,
+    ),
+    (
+        ErrorCode::new(102, "Payload size exceeds the maximum payload size"),
+        Requires::MaxPayloadSize,
+    ),
+    (
+        ErrorCode::new(110, "DDW0 observed but RDH stop bit is not 1"),
+        Requires::ItsRunning,
+    ),
+    (
+        ErrorCode::new(111, "DDW0 observed but RDH page counter is 0"),
+        Requires::ItsRunning,
+    ),
+    (
+        ErrorCode::new(
+            200,
+            "FT0 payload size is not a multiple of the GBT word size",
+        ),
+        Requires::Ft0Sanity,
+    ),
+    (
+        ErrorCode::new(201, "FT0 data format is not the same as in the first CDP"),
+        Requires::Ft0Running,
+    ),
+    (
+        ErrorCode::new(440, "TDH trigger_bc is not increasing"),
+        Requires::ItsRunning,
+    ),
+    (
+        ErrorCode::new(441, "TDH trigger_bc is not the same as previous TDH"),
+        Requires::ItsRunning,
+    ),
+    (
+        ErrorCode::new(442, "TDH trigger_orbit is not the same as previous TDH"),
+        Requires::ItsRunning,
+    ),
+    (
+        ErrorCode::new(443, "TDH trigger_type is not the same as previous TDH"),
+        Requires::ItsRunning,
+    ),
+    (
+        ErrorCode::new(444, "TDH trigger_orbit is not equal to RDH orbit"),
+        Requires::ItsRunning,
+    ),
+    (
+        ErrorCode::new(445, "TDH trigger_bc is not equal to RDH bc"),
+        Requires::ItsRunning,
+    ),
+    (
+        ErrorCode::new(446, "TDH trigger_orbit is decreasing"),
+        Requires::ItsRunning,
+    ),
+    (
+        ErrorCode::new(701, "ALPIDE data frame has no data words"),
+        Requires::ItsStave,
+    ),
+    (
+        ErrorCode::new(990, "Unrecognized ID in ITS payload, parsed as TDH"),
+        Requires::ItsSanity,
+    ),
+    (
+        ErrorCode::new(991, "Unrecognized ID in ITS payload, parsed as data word"),
+        Requires::ItsSanity,
+    ),
+    (
+        ErrorCode::new(992, "Unrecognized ID in ITS payload, parsed as DDW0"),
+        Requires::ItsSanity,
+    ),
+    (
+        ErrorCode::new(9001, "Number of CDPs does not match the custom check"),
+        Requires::CustomCdps,
+    ),
+    (
+        ErrorCode::new(
+            9002,
+            "Number of PhT triggers does not match the custom check",
+        ),
+        Requires::CustomTriggersPht,
+    ),
+    (
+        ErrorCode::new(9003, "ALPIDE chip bunch counter mismatch"),
+        Requires::ItsStave,
+    ),
+    (
+        ErrorCode::new(9004, "ALPIDE chip ID count mismatch"),
+        Requires::ItsStave,
+    ),
+    (
+        ErrorCode::new(9005, "ALPIDE chip ID order mismatch"),
+        Requires::ItsStave,
+    ),
+    (
+        ErrorCode::new(9006, "Number of HBFs does not match the custom check"),
+        Requires::CustomHbfs,
+    ),
+    (
+        ErrorCode::new(9007, "Observed links do not match the custom check"),
+        Requires::CustomExpectedLinks,
+    ),
+    (
+        ErrorCode::new(
+            9008,
+            "Fewer staves observed in ITS layers than the custom check",
+        ),
+        Requires::CustomLayerStaveCounts,
+    ),
+];
+
+/// Returns the error codes that can be emitted under the check configuration of `config`, sorted by code.
+///
+/// Returns an empty list if checks are not enabled.
+pub fn possible_error_codes<C: ChecksOpt + CustomChecksOpt + FilterOpt>(
+    config: &'static C,
+) -> Vec<ErrorCode> {
+    let Some(check) = config.check() else {
+        return Vec::new();
+    };
+    let running = matches!(check, CheckCommands::All(_));
+    let its = matches!(check.target(), Some(System::ITS | System::ITS_Stave));
+    let ft0 = check.target() == Some(System::FT0);
+    let its_stave = running && check.target() == Some(System::ITS_Stave);
+
+    let enabled = |requires: Requires| match requires {
+        Requires::Sanity => !matches!(check, CheckCommands::Ordering(_)),
+        Requires::Running => running,
+        Requires::SingleDataFormat => !config.allow_mixed_data_format(),
+        Requires::ItsSanity => its,
+        Requires::ItsRunning => its && running,
+        Requires::ItsStave => its_stave,
+        Requires::ItsStrictPadding => its && config.strict_padding(),
+        Requires::ItsCheckReserved => its && config.check_reserved(),
+        Requires::ItsTriggerPeriod => its_stave && config.check_its_trigger_period().is_some(),
+        Requires::Ft0Sanity => ft0,
+        Requires::Ft0Running => ft0 && running,
+        Requires::Ordering => matches!(check, CheckCommands::Ordering(_)),
+        Requires::MaxPayloadSize => !config.skip_payload() && config.max_payload_size().is_some(),
+        Requires::PayloadRead => !config.skip_payload(),
+        Requires::PayloadSkipped => config.skip_payload(),
+        Requires::CustomCdps => config.cdps().is_some(),
+        Requires::CustomTriggersPht => config.triggers_pht().is_some(),
+        Requires::CustomHbfs => config.hbfs().is_some(),
+        Requires::CustomExpectedLinks => config.expected_links().is_some(),
+        Requires::CustomLayerStaveCounts => config.layer_stave_counts().is_some(),
+    };
+
+    let mut error_codes: Vec<ErrorCode> = ERROR_CODES
+        .iter()
+        .filter(|(_, requires)| enabled(*requires))
+        .map(|(error_code, _)| *error_code)
+        .collect();
+    error_codes.sort();
+    error_codes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn codes(error_codes: &[ErrorCode]) -> Vec<u16> {
+        error_codes.iter().map(ErrorCode::code).collect()
+    }
+
+    #[test]
+    fn test_possible_error_codes_no_checks() {
+        static CFG: OnceLock<MockConfig> = OnceLock::new();
+        let cfg = CFG.get_or_init(MockConfig::new);
+        assert!(possible_error_codes(cfg).is_empty());
+    }
+
+    #[test]
+    fn test_possible_error_codes_sanity_vs_all_its_stave() {
+        static CFG_SANITY: OnceLock<MockConfig> = OnceLock::new();
+        let cfg_sanity = CFG_SANITY.get_or_init(|| {
+            let mut cfg = MockConfig::new();
+            cfg.check = Some(CheckCommands::Sanity(CheckModeArgs::default()));
+            cfg
+        });
+        static CFG_ITS_STAVE: OnceLock<MockConfig> = OnceLock::new();
+        let cfg_its_stave = CFG_ITS_STAVE.get_or_init(|| {
+            let mut cfg = MockConfig::new();
+            cfg.check = Some(CheckCommands::All(CheckModeArgs {
+                target: Some(System::ITS_Stave),
+                ..Default::default()
+            }));
+            cfg
+        });
+
+        let sanity_codes = possible_error_codes(cfg_sanity);
+        let its_stave_codes = possible_error_codes(cfg_its_stave);
+
+        assert_eq!(codes(&sanity_codes), vec![10, 17, 18, 35, 36, 100]);
+        assert!(sanity_codes.iter().all(|ec| its_stave_codes.contains(ec)));
+        assert!(its_stave_codes.len() > sanity_codes.len());
+        let its_stave_codes = codes(&its_stave_codes);
+        for code in [11, 16, 30, 66, 74, 701, 9003] {
+            assert!(its_stave_codes.contains(&code), "E{code} missing");
+        }
+        // Requires a trigger period, `--check-reserved` or custom checks
+        for code in [13, 31, 43, 45, 48, 51, 61, 83, 9001, 9002, 9006, 9007, 9008] {
+            assert!(!its_stave_codes.contains(&code), "E{code} unexpected");
+        }
+    }
+
+    #[test]
+    fn test_possible_error_codes_its_running() {
+        static CFG_SANITY: OnceLock<MockConfig> = OnceLock::new();
+        let cfg_sanity = CFG_SANITY.get_or_init(|| {
+            let mut cfg = MockConfig::new();
+            cfg.check = Some(CheckCommands::Sanity(CheckModeArgs {
+                target: Some(System::ITS),
+                ..Default::default()
+            }));
+            cfg
+        });
+        static CFG_ALL: OnceLock<MockConfig> = OnceLock::new();
+        let cfg_all = CFG_ALL.get_or_init(|| {
+            let mut cfg = MockConfig::new();
+            cfg.check = Some(CheckCommands::All(CheckModeArgs {
+                target: Some(System::ITS),
+                ..Default::default()
+            }));
+            cfg
+        });
+
+        // A readout frame not closed at the end of the HBF is only detected by the running checks
+        assert!(!codes(&possible_error_codes(cfg_sanity)).contains(&58));
+        assert!(codes(&possible_error_codes(cfg_all)).contains(&58));
+    }
+
+    #[test]
+    fn test_possible_error_codes_ordering() {
+        static CFG: OnceLock<MockConfig> = OnceLock::new();
+        let cfg = CFG.get_or_init(|| {
+            let mut cfg = MockConfig::new();
+            cfg.check = Some(CheckCommands::Ordering(Default::default()));
+            cfg
+        });
+        let error_codes = possible_error_codes(cfg);
+        assert_eq!(codes(&error_codes), vec![13, 17, 100]);
+        assert_eq!(error_codes[0].to_string(), "E13");
+    }
+
+    #[test]
+    fn test_possible_error_codes_ft0() {
+        static CFG: OnceLock<MockConfig> = OnceLock::new();
+        let cfg = CFG.get_or_init(|| {
+            let mut cfg = MockConfig::new();
+            cfg.check = Some(CheckCommands::All(CheckModeArgs {
+                target: Some(System::FT0),
+                ..Default::default()
+            }));
+            cfg
+        });
+        let ft0_codes = codes(&possible_error_codes(cfg));
+        for code in [10, 11, 200, 201] {
+            assert!(ft0_codes.contains(&code), "E{code} missing");
+        }
+        // ITS specific
+        for code in [12, 30, 70] {
+            assert!(!ft0_codes.contains(&code), "E{code} unexpected");
+        }
+    }
+
+    #[test]
+    fn test_possible_error_codes_ft0_sanity() {
+        static CFG: OnceLock<MockConfig> = OnceLock::new();
+        let cfg = CFG.get_or_init(|| {
+            let mut cfg = MockConfig::new();
+            cfg.check = Some(CheckCommands::Sanity(CheckModeArgs {
+                target: Some(System::FT0),
+                ..Default::default()
+            }));
+            cfg
+        });
+        let ft0_codes = codes(&possible_error_codes(cfg));
+        // The payload size is checked per CDP, the data format is compared across CDPs by the running checks
+        assert!(ft0_codes.contains(&200));
+        assert!(!ft0_codes.contains(&201));
+    }
+
+    #[test]
+    fn test_possible_error_codes_check_reserved() {
+        static CFG: OnceLock<MockConfig> = OnceLock::new();
+        let cfg = CFG.get_or_init(|| {
+            let mut cfg = MockConfig::new();
+            cfg.check = Some(CheckCommands::Sanity(CheckModeArgs {
+                target: Some(System::ITS),
+                ..Default::default()
+            }));
+            cfg.check_reserved = true;
+            cfg
+        });
+        let its_codes = codes(&possible_error_codes(cfg));
+        for code in [31, 43, 51, 61, 83] {
+            assert!(its_codes.contains(&code), "E{code} missing");
+        }
+    }
+
+    #[test]
+    fn test_possible_error_codes_custom_layer_stave_counts() {
+        static CFG: OnceLock<MockConfig> = OnceLock::new();
+        let cfg = CFG.get_or_init(|| {
+            let mut cfg = MockConfig::new();
+            cfg.check = Some(CheckCommands::Sanity(CheckModeArgs {
+                target: Some(System::ITS),
+                ..Default::default()
+            }));
+            cfg.custom_checks = Some(toml::from_str("layer_stave_counts = [[0, 12]]").unwrap());
+            cfg
+        });
+        let its_codes = codes(&possible_error_codes(cfg));
+        assert!(its_codes.contains(&9008));
+        // Only the custom checks that are set are listed
+        for code in [9001, 9002, 9006, 9007] {
+            assert!(!its_codes.contains(&code), "E{code} unexpected");
+        }
+    }
+
+    #[test]
+    fn test_possible_error_codes_allow_mixed_data_format() {
+        static CFG: OnceLock<MockConfig> = OnceLock::new();
+        let cfg = CFG.get_or_init(|| {
+            let mut cfg = MockConfig::new();
+            cfg.check = Some(CheckCommands::Sanity(CheckModeArgs::default()));
+            cfg.allow_mixed_data_format = true;
+            cfg
+        });
+        assert_eq!(codes(&possible_error_codes(cfg)), vec![10, 18, 35, 36, 100]);
+    }
+
+    #[test]
+    fn test_possible_error_codes_max_payload_size() {
+        static CFG: OnceLock<MockConfig> = OnceLock::new();
+        let cfg = CFG.get_or_init(|| {
+            let mut cfg = MockConfig::new();
+            cfg.check = Some(CheckCommands::Sanity(CheckModeArgs {
+                target: Some(System::ITS),
+                ..Default::default()
+            }));
+            cfg.max_payload_size = Some(0x100);
+            cfg
+        });
+        assert!(codes(&possible_error_codes(cfg)).contains(&102));
+    }
+
+    #[test]
+    fn test_possible_error_codes_skip_payload() {
+        static CFG: OnceLock<MockConfig> = OnceLock::new();
+        let cfg = CFG.get_or_init(|| {
+            let mut cfg = MockConfig::new();
+            cfg.check = Some(CheckCommands::Sanity(CheckModeArgs::default()));
+            cfg.skip_payload = true;
+            cfg
+        });
+        assert_eq!(
+            codes(&possible_error_codes(cfg)),
+            vec![10, 17, 18, 35, 36, 101]
+        );
+    }
+
+    /// Sources of all the error messages, the error codes they emit should all be listed in [ERROR_CODES]
+    const ERROR_CODE_SOURCES: [(&str, &str); 18] = [
+        (
+            "input_scanner.rs",
+            include_str!("../../../alice_protocol_reader/src/input_scanner.rs"),
+        ),
+        ("controller.rs", include_str!("../controller.rs")),
+        (
+            "stats_validation.rs",
+            include_str!("../stats/stats_validation.rs"),
+        ),
+        ("validators/lib.rs", include_str!("validators/lib.rs")),
+        (
+            "link_validator.rs",
+            include_str!("validators/link_validator.rs"),
+        ),
+        ("ordering.rs", include_str!("validators/ordering.rs")),
+        ("rdh.rs", include_str!("validators/rdh.rs")),
+        ("rdh_offset.rs", include_str!("validators/rdh_offset.rs")),
+        ("rdh_running.rs", include_str!("validators/rdh_running.rs")),
+        (
+            "ft0_running.rs",
+            include_str!("validators/ft0/ft0_running.rs"),
+        ),
+        (
+            "alpide_lane_error.rs",
+            include_str!("validators/its/alpide/alpide_lane_error.rs"),
+        ),
+        (
+            "cdp_running.rs",
+            include_str!("validators/its/cdp_running.rs"),
+        ),
+        (
+            "rdh_validator.rs",
+            include_str!("validators/its/cdp_running/rdh_validator.rs"),
+        ),
+        (
+            "readout_frame.rs",
+            include_str!("validators/its/cdp_running/readout_frame.rs"),
+        ),
+        ("ib.rs", include_str!("validators/its/data_words/ib.rs")),
+        ("ob.rs", include_str!("validators/its/data_words/ob.rs")),
+        ("ddw.rs", include_str!("validators/its/status_word/ddw.rs")),
+        ("tdh.rs", include_str!("validators/its/status_word/tdh.rs")),
+    ];
+
+    #[test]
+    fn test_error_codes_complete() {
+        // Matches e.g. `[E10]` in error messages and `"E74"` where the code is passed separately
+        let re = regex::Regex::new(r#"\[E(\d+)\]|"E(\d+)""#).unwrap();
+        let listed: Vec<u16> = ERROR_CODES.iter().map(|(ec, _)| ec.code()).collect();
+
+        let mut emitted = Vec::new();
+        for (file, src) in ERROR_CODE_SOURCES {
+            // Codes in the tests and comments are only examples
+            let src = src.split("#[cfg(test)]").next().unwrap();
+            for line in src.lines().filter(|l| !l.trim_start().starts_with("//")) {
+                for cap in re.captures_iter(line) {
+                    let code: u16 = cap
+                        .get(1)
+                        .or_else(|| cap.get(2))
+                        .unwrap()
+                        .as_str()
+                        .parse()
+                        .unwrap();
+                    let count = listed.iter().filter(|&&c| c == code).count();
+                    assert_eq!(count, 1, "E{code} from {file} is listed {count} times");
+                    emitted.push(code);
+                }
+            }
+        }
+        for code in listed {
+            assert!(emitted.contains(&code), "E{code} is listed but not emitted");
+        }
+    }
+}
@@ -1,6 +1,21 @@
 //! Contains the [spawn_analysis] function that spawns the analysis thread for either data validation or view generation.
-use super::validators::validator_dispatcher::ValidatorDispatcher;
+use super::{
+    validators::{
+        ordering::OrderingValidator, rdh_offset::RdhOffsetValidator,
+        validator_dispatcher::ValidatorDispatcher,
+    },
+    view::{
+        alpide_view::AlpideView, det_field_status_view::DetFieldStatusView, hbf_view::HbfView,
+        rdh_html_view::RdhHtmlView,
+    },
+};
 use crate::util::*;
+use crossbeam_channel::RecvTimeoutError;
+
+/// A hook called with each RDH and its memory position in the analysis thread, before the CDP is dispatched to the checks or views.
+///
+/// Takes the RDH as [RDH_CRU] as the [RDH] trait is not object safe.
+pub type RdhHook = Box<dyn FnMut(&dyn RDH_CRU, u64) + Send>;
 
 /// Analysis thread that performs checks with the [super::validators] module or generate views with the [super::view::lib::generate_view] function.
 pub fn spawn_analysis<T: RDH + 'static, const CAP: usize>(
@@ -8,30 +23,119 @@ pub fn spawn_analysis<T: RDH + 'static, const CAP: usize>(
     stop_flag: Arc<AtomicBool>,
     stats_send: flume::Sender<StatType>,
     data_recv: crossbeam_channel::Receiver<CdpArray<T, CAP>>,
+    mut rdh_hook: Option<RdhHook>,
 ) -> Result<JoinHandle<()>, io::Error> {
     let analysis_thread = thread::Builder::new().name("Analysis".to_string());
     let mut system_id: Option<SystemId> = None; // System ID is only set once
+    let mut word_id_histogram = config.word_id_histogram().then(WordIdHistogram::default);
     analysis_thread.spawn({
         move || {
             // Setup for check case
             let mut validator_dispatcher = ValidatorDispatcher::new(config, stats_send.clone());
+            // Setup for ordering check case
+            let mut ordering_validator = match config.check() {
+                Some(CheckCommands::Ordering(args)) => Some(OrderingValidator::new(args.key)),
+                _ => None,
+            };
+            // Setup for RDH offset check case, consecutive RDHs are only adjacent in the stream if no RDHs are filtered out
+            let no_rdhs_filtered = config.filter_target().is_none()
+                && config.filter_trigger_type().is_none()
+                && config.filter_orbit_range().is_none()
+                && config.extract_rdhs().is_none();
+            let mut rdh_offset_validator = (matches!(config.check(), Some(CheckCommands::All(_)))
+                && no_rdhs_filtered)
+                .then(|| RdhOffsetValidator::new(config.skip_payload()));
+            // Setup for HTML view case
+            let mut html_view = match config.view_html_output() {
+                Some(path) => match RdhHtmlView::<T>::new(path, !config.disable_styled_views()) {
+                    Ok(html_view) => Some(html_view),
+                    Err(e) => {
+                        stats_send
+                            .send(StatType::Fatal(
+                                format!("Failed to create HTML file {}: {e}", path.display())
+                                    .into(),
+                            ))
+                            .expect("Couldn't send to Controller");
+                        return;
+                    }
+                },
+                None => None,
+            };
+            // Setup for ALPIDE view case, readout frames can span several batches
+            let mut alpide_view = if config.view() == Some(ViewCommands::Alpide) {
+                match AlpideView::new(config.disable_styled_views()) {
+                    Ok(alpide_view) => Some(alpide_view),
+                    Err(e) => {
+                        stats_send
+                            .send(StatType::Fatal(e.to_string().into()))
+                            .expect("Couldn't send to Controller");
+                        return;
+                    }
+                }
+            } else {
+                None
+            };
+            // Setup for detector field status view case, the last status of each FEE ID is kept between batches
+            let mut det_field_status_view = if config.view() == Some(ViewCommands::DetFieldStatus) {
+                match DetFieldStatusView::new(config.disable_styled_views()) {
+                    Ok(det_field_status_view) => Some(det_field_status_view),
+                    Err(e) => {
+                        stats_send
+                            .send(StatType::Fatal(e.to_string().into()))
+                            .expect("Couldn't send to Controller");
+                        return;
+                    }
+                }
+            } else {
+                None
+            };
+            // Setup for HBF view case, an HBF can span several batches
+            let mut hbf_view = if config.view() == Some(ViewCommands::Hbf) {
+                match HbfView::new(config.disable_styled_views()) {
+                    Ok(hbf_view) => Some(hbf_view),
+                    Err(e) => {
+                        stats_send
+                            .send(StatType::Fatal(e.to_string().into()))
+                            .expect("Couldn't send to Controller");
+                        return;
+                    }
+                }
+            } else {
+                None
+            };
             // Start analysis
             while !stop_flag.load(Ordering::SeqCst) {
-                // Receive batch from reader
-                let cdp_batch = match data_recv.recv() {
+                // Receive batch from reader, waiting at most the receive timeout if set
+                let received = match config.recv_timeout() {
+                    Some(timeout) => data_recv.recv_timeout(timeout),
+                    None => data_recv.recv().map_err(RecvTimeoutError::from),
+                };
+                let cdp_batch = match received {
                     Ok(cdp) => cdp,
-                    Err(e) => {
-                        debug_assert_eq!(e, crossbeam_channel::RecvError);
+                    Err(RecvTimeoutError::Timeout) => {
+                        log::warn!(
+                            "No data received for {}s, stopping gracefully",
+                            config.recv_timeout().unwrap_or_default().as_secs()
+                        );
+                        stop_flag.store(true, Ordering::SeqCst);
                         break;
                     }
+                    Err(RecvTimeoutError::Disconnected) => break,
                 };
 
+                if let Some(rdh_hook) = rdh_hook.as_mut() {
+                    for (rdh, &rdh_mem_pos) in cdp_batch
+                        .rdh_slice()
+                        .iter()
+                        .zip(cdp_batch.rdh_mem_pos_slice())
+                    {
+                        rdh_hook(rdh, rdh_mem_pos);
+                    }
+                }
+
                 // Collect global stats
-                // Send HBF seen if stop bit is 1
-                let mut hbfs_seen: u32 = 0;
+                // HBFs and payload sizes are counted by the input scanner, as only the sampled CDPs are received if sampling is set
                 for rdh in cdp_batch.rdh_slice().iter() {
-                    hbfs_seen += (rdh.stop_bit() == 1) as u32;
-
                     stats_send
                         .send(StatType::TriggerType(rdh.trigger_type()))
                         .unwrap();
@@ -43,11 +147,80 @@ pub fn spawn_analysis<T: RDH + 'static, const CAP: usize>(
                         break; // Fatal error
                     }
                 }
-                stats_send.send(StatType::HBFsSeen(hbfs_seen)).unwrap();
+
+                if !cdp_batch.is_empty() {
+                    let rdh_position = |idx: usize| RdhPosition {
+                        mem_pos: cdp_batch.rdh_mem_pos_slice()[idx],
+                        orbit: cdp_batch.rdh_slice()[idx].rdh1().orbit,
+                    };
+                    stats_send
+                        .send(StatType::RdhPositions {
+                            first: rdh_position(0),
+                            last: rdh_position(cdp_batch.len() - 1),
+                        })
+                        .unwrap();
+                }
+
+                if config.live_status() {
+                    stats_send
+                        .send(StatType::CdpsSeen(
+                            cdp_batch
+                                .rdh_slice()
+                                .iter()
+                                .zip(cdp_batch.rdh_mem_pos_slice())
+                                .map(|(rdh, &mem_pos)| (mem_pos, rdh.link_id(), rdh.fee_id()))
+                                .collect(),
+                        ))
+                        .unwrap();
+                }
+
+                if let Some(histogram) = word_id_histogram.as_mut() {
+                    for (_, payload, _) in &cdp_batch {
+                        histogram.count_payload(payload);
+                    }
+                }
+
+                if let Some(rdh_offset_validator) = rdh_offset_validator.as_mut() {
+                    for e in rdh_offset_validator.check_cdp_array(&cdp_batch) {
+                        stats_send
+                            .send(StatType::Error(e.into()))
+                            .expect("Couldn't send to Controller");
+                    }
+                }
 
                 // Do checks or view
-                if config.check().is_some() {
+                if let Some(ordering_validator) = ordering_validator.as_mut() {
+                    if let Err(e) = ordering_validator.check_cdp_array(&cdp_batch) {
+                        stats_send
+                            .send(StatType::Error(e.into()))
+                            .expect("Couldn't send to Controller");
+                    }
+                } else if config.check().is_some() {
                     validator_dispatcher.dispatch_cdp_batch(cdp_batch);
+                } else if let Some(html_view) = html_view.as_mut() {
+                    if let Err(e) = html_view.write_rows(&cdp_batch) {
+                        stats_send
+                            .send(StatType::Fatal(e.to_string().into()))
+                            .expect("Couldn't send to Controller");
+                    }
+                } else if let Some(alpide_view) = alpide_view.as_mut() {
+                    if let Err(e) = alpide_view.view_cdps(&cdp_batch) {
+                        stats_send
+                            .send(StatType::Fatal(e.to_string().into()))
+                            .expect("Couldn't send to Controller");
+                    }
+                } else if let Some(det_field_status_view) = det_field_status_view.as_mut() {
+                    if let Err(e) = det_field_status_view.view_cdps(&cdp_batch) {
+                        stats_send
+                            .send(StatType::Fatal(e.to_string().into()))
+                            .expect("Couldn't send to Controller");
+                    }
+                } else if let Some(hbf_view) = hbf_view.as_mut() {
+                    if let Err(e) = hbf_view.view_cdps(&cdp_batch) {
+                        stats_send
+                            .send(StatType::Fatal(e.to_string().into()))
+                            .expect("Couldn't send to Controller");
+                    }
                 } else if let Some(view) = config.view() {
                     if let Err(e) = view::lib::generate_view(view, &cdp_batch) {
                         stats_send
@@ -58,6 +231,28 @@ pub fn spawn_analysis<T: RDH + 'static, const CAP: usize>(
             }
             // Join all threads the dispatcher spawned
             validator_dispatcher.join();
+
+            if let Some(html_view) = html_view {
+                if let Err(e) = html_view.finish() {
+                    stats_send
+                        .send(StatType::Fatal(e.to_string().into()))
+                        .expect("Couldn't send to Controller");
+                }
+            }
+
+            if let Some(hbf_view) = hbf_view {
+                if let Err(e) = hbf_view.finish() {
+                    stats_send
+                        .send(StatType::Fatal(e.to_string().into()))
+                        .expect("Couldn't send to Controller");
+                }
+            }
+
+            if let Some(histogram) = word_id_histogram {
+                stats_send
+                    .send(StatType::WordIdHistogram(Box::new(histogram)))
+                    .expect("Couldn't send to Controller");
+            }
         }
     })
 }
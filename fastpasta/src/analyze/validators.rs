@@ -1,8 +1,11 @@
 //! Contains the [LinkValidator](link_validator::LinkValidator) struct that is the entry point for all data validation, also contains all the subvalidators.
 
+pub mod ft0;
 pub mod its;
 pub mod lib;
 pub mod link_validator;
+pub mod ordering;
 pub mod rdh;
+pub mod rdh_offset;
 pub mod rdh_running;
 pub mod validator_dispatcher;
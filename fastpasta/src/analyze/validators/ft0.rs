@@ -0,0 +1,7 @@
+//! # FT0 specific validation
+//!
+//! FT0 support is currently limited to basic checks, the payload words are counted but not decoded.
+//!
+//! The [Ft0RunningValidator](crate::analyze::validators::ft0::ft0_running::Ft0RunningValidator) is called from the [LinkValidator](crate::analyze::validators::link_validator::LinkValidator) when the system target is FT0.
+
+pub mod ft0_running;
@@ -0,0 +1,178 @@
+//! Contains the [Ft0RunningValidator] that performs the FT0 specific checks on the CDPs of a link.
+//!
+//! The checks are:
+//! - The payload consists of whole GBT words, 16 bytes per word in data format 0 and 10 bytes per word in data format 2.
+//! - The data format is the same in all CDPs of the link (only with running checks).
+
+use crate::util::*;
+
+/// Size of a GBT word in bytes
+const GBT_WORD_SIZE: usize = 10;
+/// Size of a GBT word padded to 128 bits in data format 0
+const GBT_WORD_SIZE_PADDED: usize = 16;
+
+/// Validates the CDPs of an FT0 link and counts the payload words.
+///
+/// The number of payload words is sent to the stats collector when the validator is dropped.
+pub struct Ft0RunningValidator {
+    running_checks_enabled: bool,
+    stats_send_ch: flume::Sender<StatType>,
+    /// Data format of the first CDP, the data format of the following CDPs should be the same.
+    data_format: Option<u8>,
+    payload_words_seen: u64,
+}
+
+impl Ft0RunningValidator {
+    /// Creates a new [Ft0RunningValidator] from a config that implements [ChecksOpt] and a [StatType] producer channel.
+    pub fn new(config: &impl ChecksOpt, stats_send_ch: flume::Sender<StatType>) -> Self {
+        Self {
+            running_checks_enabled: matches!(config.check(), Some(CheckCommands::All(_))),
+            stats_send_ch,
+            data_format: None,
+            payload_words_seen: 0,
+        }
+    }
+
+    /// Checks a CDP and counts its payload words.
+    pub fn check<T: RDH>(&mut self, rdh: &T, payload: &[u8], rdh_mem_pos: u64) {
+        let data_format = rdh.data_format();
+        if self.running_checks_enabled {
+            match self.data_format {
+                Some(first_data_format) if first_data_format != data_format => self.report_error(
+                    rdh,
+                    rdh_mem_pos,
+                    &format!("[E201] Data format is {data_format}, but the data format of the first CDP is {first_data_format}"),
+                ),
+                Some(_) => (),
+                None => self.data_format = Some(data_format),
+            }
+        }
+
+        let (payload_len, word_size) = if data_format == 0 {
+            (payload.len(), GBT_WORD_SIZE_PADDED)
+        } else {
+            // Data format 2 pads the end of the payload with 0xFF to a multiple of 16 bytes
+            (
+                payload.len() - data_format_0_ff_padding_len(payload),
+                GBT_WORD_SIZE,
+            )
+        };
+        if payload_len % word_size != 0 {
+            self.report_error(
+                rdh,
+                rdh_mem_pos,
+                &format!("[E200] Payload size {payload_len} is not a multiple of the GBT word size in data format {data_format} ({word_size} bytes)"),
+            );
+        }
+        self.payload_words_seen += (payload_len / word_size) as u64;
+    }
+
+    /// Returns the number of payload words seen.
+    pub fn payload_words_seen(&self) -> u64 {
+        self.payload_words_seen
+    }
+
+    fn report_error<T: RDH>(&self, rdh: &T, rdh_mem_pos: u64, error_msg: &str) {
+        self.stats_send_ch
+            .send(StatType::CheckError(Box::new(CheckError::new(
+                rdh_mem_pos,
+                error_msg,
+                rdh.fee_id(),
+                rdh.link_id(),
+            ))))
+            .expect("Failed to send error to stats channel");
+    }
+}
+
+impl Drop for Ft0RunningValidator {
+    fn drop(&mut self) {
+        if self.payload_words_seen > 0 {
+            // The receiver might already be gone if processing stopped early
+            let _ = self
+                .stats_send_ch
+                .send(StatType::PayloadWordsSeen(self.payload_words_seen));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alice_protocol_reader::prelude::test_data::CORRECT_RDH_CRU_V7;
+    use pretty_assertions::assert_eq;
+
+    fn ft0_config(check: CheckCommands) -> MockConfig {
+        let mut cfg = MockConfig::new();
+        cfg.check = Some(check);
+        cfg
+    }
+
+    #[test]
+    fn test_count_payload_words_data_format_2() {
+        let cfg = ft0_config(CheckCommands::Sanity(CheckModeArgs {
+            target: Some(System::FT0),
+            ..Default::default()
+        }));
+        let (send, recv) = flume::unbounded();
+        let mut validator = Ft0RunningValidator::new(&cfg, send);
+        assert_eq!(CORRECT_RDH_CRU_V7.data_format(), 2);
+
+        // 3 GBT words padded with 0xFF to 32 bytes
+        let mut payload = vec![0; 3 * GBT_WORD_SIZE];
+        payload.extend([0xFF; 2]);
+        validator.check(&CORRECT_RDH_CRU_V7, &payload, 0);
+        assert_eq!(validator.payload_words_seen(), 3);
+        assert!(recv.try_recv().is_err());
+
+        drop(validator);
+        assert_eq!(recv.recv().unwrap(), StatType::PayloadWordsSeen(3));
+    }
+
+    #[test]
+    fn test_payload_not_whole_gbt_words() {
+        let cfg = ft0_config(CheckCommands::Sanity(CheckModeArgs {
+            target: Some(System::FT0),
+            ..Default::default()
+        }));
+        let (send, recv) = flume::unbounded();
+        let mut validator = Ft0RunningValidator::new(&cfg, send);
+
+        validator.check(&CORRECT_RDH_CRU_V7, &[0; GBT_WORD_SIZE + 3], 0x40);
+        match recv.recv().unwrap() {
+            StatType::CheckError(err) => {
+                assert_eq!(err.code, Some(200));
+                assert_eq!(err.mem_pos, Some(0x40));
+            }
+            stat => panic!("Expected an error, got {stat}"),
+        }
+    }
+
+    #[test]
+    fn test_data_format_changed() {
+        let cfg = ft0_config(CheckCommands::All(CheckModeArgs {
+            target: Some(System::FT0),
+            ..Default::default()
+        }));
+        let (send, recv) = flume::unbounded();
+        let mut validator = Ft0RunningValidator::new(&cfg, send);
+
+        let mut rdh_bytes = CORRECT_RDH_CRU_V7.to_byte_slice().to_vec();
+        rdh_bytes[24] = 0; // Data format
+        let rdh_data_format_0 = RdhCru::load(&mut rdh_bytes.as_slice()).unwrap();
+        assert_eq!(rdh_data_format_0.data_format(), 0);
+
+        validator.check(&CORRECT_RDH_CRU_V7, &[], 0);
+        validator.check(&rdh_data_format_0, &[0; GBT_WORD_SIZE_PADDED], 0x40);
+        match recv.recv().unwrap() {
+            StatType::CheckError(err) => {
+                assert_eq!(err.code, Some(201));
+                assert_eq!(
+                    err.message,
+                    "Data format is 0, but the data format of the first CDP is 2"
+                );
+            }
+            stat => panic!("Expected an error, got {stat}"),
+        }
+        assert_eq!(validator.payload_words_seen(), 1);
+    }
+}
@@ -2,10 +2,12 @@
 //!
 //! It contains some utility functions, and then it publishes modules with more specific ALPIDE related functionality.
 
+pub mod alpide_lane_error;
 pub mod alpide_readout_frame;
 pub mod lane_alpide_frame_analyzer;
 
 use crate::util::*;
+use alpide_lane_error::{AlpideLaneError, LaneErrors};
 
 // Helper struct to group lanes and bunch counters, used for comparing bunch counters between lanes
 struct ValidatedLane {
@@ -15,13 +17,13 @@ struct ValidatedLane {
 
 /// Process ALPIDE data for a readout frame, per lane.
 ///
-/// Returns a tuple of a vector of lane ids with errors, and a vector of error messages.
+/// Returns a tuple of the errors of each lane as (lane number, error), a vector of error messages, the ALPIDE stats, and the lanes in FATAL state if any.
 pub fn check_alpide_data_frame(
     alpide_readout_frame: &AlpideReadoutFrame,
     custom_checks: &'static impl CustomChecksOpt,
-) -> (Vec<u8>, Vec<String>, AlpideStats, Option<Vec<u8>>) {
+) -> (LaneErrors, Vec<String>, AlpideStats, Option<Vec<u8>>) {
     let mut lane_error_msgs: Vec<String> = Vec::new();
-    let mut lane_error_ids: Vec<u8> = Vec::new();
+    let mut lane_errors: LaneErrors = Vec::new();
     let mut validated_lanes: Vec<ValidatedLane> = Vec::new();
     let mut fatal_lanes: Option<Vec<u8>> = None;
 
@@ -44,10 +46,13 @@ pub fn check_alpide_data_frame(
             let lane_number = lane_data_frame.lane_number(frame_from_layer);
             log::trace!("Processing lane #{lane_number}");
 
-            if let Err(mut error_msgs) = analyzer.analyze_alpide_frame(lane_data_frame) {
-                error_msgs.insert_str(0, &format!("\n\tLane {lane_number} errors: "));
+            if let Err(errors) = analyzer.analyze_alpide_frame(lane_data_frame) {
+                let mut error_msgs = format!("\n\tLane {lane_number} errors: ");
+                errors
+                    .iter()
+                    .for_each(|err| error_msgs.push_str(&err.to_string()));
                 lane_error_msgs.push(error_msgs);
-                lane_error_ids.push(lane_number);
+                lane_errors.extend(errors.into_iter().map(|err| (lane_number, err)));
             } else if analyzer.is_fatal_lane() {
                 log::warn!("Lane {lane_number} is in FATAL state, now expecting 1 fewer lane in data frames");
                 if fatal_lanes.is_none() {
@@ -67,21 +72,36 @@ pub fn check_alpide_data_frame(
         });
 
     // Compare all validated bunch counters to each other across lanes
-    validate_lane_bcs(&validated_lanes, &mut lane_error_msgs, &mut lane_error_ids);
+    validate_lane_bcs(&validated_lanes, &mut lane_error_msgs, &mut lane_errors);
 
     (
-        lane_error_ids,
+        lane_errors,
         lane_error_msgs,
         total_alpide_stats,
         fatal_lanes,
     )
 }
 
+/// Decode the ALPIDE data of a readout frame, per lane, and return the ALPIDE stats without checking the data.
+pub fn collect_alpide_stats(alpide_readout_frame: &AlpideReadoutFrame) -> AlpideStats {
+    let frame_from_layer = alpide_readout_frame.from_layer();
+    let mut total_alpide_stats = AlpideStats::default();
+    alpide_readout_frame
+        .lane_data_frames_as_slice()
+        .iter()
+        .for_each(|lane_data_frame| {
+            let mut analyzer = LaneAlpideFrameAnalyzer::new(frame_from_layer, None, None);
+            analyzer.decode_alpide_frame(lane_data_frame);
+            total_alpide_stats.sum(*analyzer.alpide_stats());
+        });
+    total_alpide_stats
+}
+
 /// Compare all validated bunch counters to each other across lanes
 fn validate_lane_bcs(
     validated_lanes: &[ValidatedLane],
     lane_error_msgs: &mut Vec<String>, // Just to reduce the amount of copying...
-    lane_error_ids: &mut Vec<u8>,      // Just to reduce the amount of copying...
+    lane_errors: &mut LaneErrors,      // Just to reduce the amount of copying...
 ) {
     let unique_bunch_counters: Vec<u8> = validated_lanes
         .iter()
@@ -117,7 +137,20 @@ fn validate_lane_bcs(
         }
 
         lane_error_msgs.push(error_string);
-        lane_error_ids.extend(lanes_to_bunch_counter.iter().flat_map(|(_, lanes)| lanes));
+        lane_errors.extend(
+            lanes_to_bunch_counter
+                .iter()
+                .flat_map(|(bunch_counter, lanes)| {
+                    lanes.iter().map(|&lane| {
+                        (
+                            lane,
+                            AlpideLaneError::LaneBunchCounterMismatch {
+                                bunch_counter: *bunch_counter,
+                            },
+                        )
+                    })
+                }),
+        );
     }
 }
 
@@ -0,0 +1,159 @@
+//! Contains the [AlpideLaneError] enum of the errors that can be found in the ALPIDE data of a lane in a readout frame.
+
+use crate::util::*;
+
+/// The errors found in the lanes of a readout frame, as (lane number, error)
+pub type LaneErrors = Vec<(u8, AlpideLaneError)>;
+
+/// An error found in the ALPIDE data of a single lane in a readout frame
+#[derive(Debug, Clone, PartialEq)]
+pub enum AlpideLaneError {
+    /// A chip was seen more than once in the readout frame, with a bunch counter each time
+    BunchCounterAlreadySet {
+        /// Chip ID
+        chip_id: u8,
+        /// The bunch counter stored for the chip
+        current_bc: u8,
+        /// The bunch counter that was attempted to be stored
+        new_bc: u8,
+    },
+    /// The chips of the lane do not have identical bunch counters `[E9003]`
+    ChipBunchCounterMismatch {
+        /// Each bunch counter seen, with the IDs of the chips that have it
+        bc_to_chip_ids: Vec<(u8, Vec<u8>)>,
+    },
+    /// The number of chips in the lane does not match the expected chip count `[E9004]`
+    ChipCountMismatch {
+        /// The layer the data is from
+        layer: Layer,
+        /// Expected number of chips
+        expected: usize,
+        /// IDs of the chips seen
+        chip_ids: Vec<u8>,
+    },
+    /// The ID of the chip of an inner barrel lane does not match the lane number `[E9005]`
+    ChipIdMismatch {
+        /// Expected chip ID (the lane number)
+        expected: u8,
+        /// The chip ID seen
+        found: u8,
+    },
+    /// The IDs of the chips of an outer barrel lane are not in any of the valid orders `[E9005]`
+    ChipIdOrderMismatch {
+        /// The layer the data is from
+        layer: Layer,
+        /// The valid orders of the chip IDs
+        valid_orders: Vec<Vec<u8>>,
+        /// IDs of the chips seen, in the order they were seen
+        chip_ids: Vec<u8>,
+    },
+    /// The bunch counter of the lane does not match the bunch counter of other lanes in the same readout frame
+    LaneBunchCounterMismatch {
+        /// The bunch counter of the lane
+        bunch_counter: u8,
+    },
+}
+
+impl AlpideLaneError {
+    const ERR_MSG_PREFIX: &'static str = "\n\t\t\t   "; // Newline + indentation for error messages
+
+    /// Name of the kind of error, used as key when counting the errors of each kind
+    pub fn name(&self) -> &'static str {
+        match self {
+            AlpideLaneError::BunchCounterAlreadySet { .. } => "bunch_counter_already_set",
+            AlpideLaneError::ChipBunchCounterMismatch { .. } => "chip_bunch_counter_mismatch",
+            AlpideLaneError::ChipCountMismatch { .. } => "chip_count_mismatch",
+            AlpideLaneError::ChipIdMismatch { .. }
+            | AlpideLaneError::ChipIdOrderMismatch { .. } => "chip_id_order_mismatch",
+            AlpideLaneError::LaneBunchCounterMismatch { .. } => "lane_bunch_counter_mismatch",
+        }
+    }
+}
+
+impl fmt::Display for AlpideLaneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let newline_indent = Self::ERR_MSG_PREFIX;
+        match self {
+            AlpideLaneError::BunchCounterAlreadySet {
+                chip_id,
+                current_bc,
+                new_bc,
+            } => write!(
+                f,
+                "Bunch counter already set for chip {chip_id}, is {current_bc}, tried to set to {new_bc}"
+            ),
+            AlpideLaneError::ChipBunchCounterMismatch { bc_to_chip_ids } => {
+                write!(f, "\n\t\t [E9003] Chip bunch counter mismatch:")?;
+                bc_to_chip_ids.iter().try_for_each(|(bc, chip_ids)| {
+                    write!(
+                        f,
+                        "{newline_indent}Bunch counter: {bc:>3?} | Chip IDs: {chip_ids:?}"
+                    )
+                })
+            }
+            AlpideLaneError::ChipCountMismatch {
+                layer,
+                expected,
+                chip_ids,
+            } => {
+                let (chip_id_str, barrel) = if *layer == Layer::Inner {
+                    ("Chip ID", "IB")
+                } else {
+                    ("Chip ID(s)", "OB")
+                };
+                write!(
+                    f,
+                    "\n\t\t [E9004] Chip ID count mismatch:{newline_indent}Expected {expected} {chip_id_str} in {barrel} but found {id_cnt}: {chip_ids:?}",
+                    id_cnt = chip_ids.len()
+                )
+            }
+            AlpideLaneError::ChipIdMismatch { expected, found } => write!(
+                f,
+                "\n\t\t [E9005] Chip ID order mismatch:{newline_indent}Expected Chip ID {expected} in IB but found {found}"
+            ),
+            AlpideLaneError::ChipIdOrderMismatch {
+                layer,
+                valid_orders,
+                chip_ids,
+            } => write!(
+                f,
+                "\n\t\t [E9005] Chip ID order mismatch:{newline_indent}Expected any order={valid_orders:?} in {layer} but found {chip_ids:?}"
+            ),
+            AlpideLaneError::LaneBunchCounterMismatch { bunch_counter } => write!(
+                f,
+                "Bunch counter {bunch_counter} mismatches the bunch counters of other lanes in the same readout frame"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_display_matches_lane_error_messages() {
+        let chip_count_mismatch = AlpideLaneError::ChipCountMismatch {
+            layer: Layer::Outer,
+            expected: 7,
+            chip_ids: vec![1, 2],
+        };
+        assert_eq!(
+            chip_count_mismatch.to_string(),
+            "\n\t\t [E9004] Chip ID count mismatch:\n\t\t\t   Expected 7 Chip ID(s) in OB but found 2: [1, 2]"
+        );
+        assert_eq!(chip_count_mismatch.name(), "chip_count_mismatch");
+
+        let bc_mismatch = AlpideLaneError::ChipBunchCounterMismatch {
+            bc_to_chip_ids: vec![(1, vec![0, 2]), (12, vec![3])],
+        };
+        assert_eq!(
+            bc_mismatch.to_string(),
+            "\n\t\t [E9003] Chip bunch counter mismatch:\
+            \n\t\t\t   Bunch counter:   1 | Chip IDs: [0, 2]\
+            \n\t\t\t   Bunch counter:  12 | Chip IDs: [3]"
+        );
+        assert_eq!(bc_mismatch.name(), "chip_bunch_counter_mismatch");
+    }
+}
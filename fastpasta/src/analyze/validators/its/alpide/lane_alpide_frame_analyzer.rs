@@ -2,6 +2,7 @@
 //!
 //! Analysis consists of decoding the ALPIDE data and then performing checks on the decoded data.
 
+use super::alpide_lane_error::AlpideLaneError;
 use crate::util::*;
 use crate::words::its::{
     alpide::{
@@ -17,6 +18,7 @@ pub struct LaneAlpideFrameAnalyzer<'a> {
     lane_number: u8,
     is_header_seen: bool, // Set when a Chip Header is seen, reset when a Chip Trailer is seen
     last_chip_id: u8,     // 4 bits
+    last_chip_empty_frame: bool, // Set when the last chip was seen in a Chip Empty Frame instead of a Chip Header
     skip_n_bytes: u8, // Used when an irrelevant word larger than 1 byte is seen, to skip the next n bytes
     chip_data: Vec<AlpideFrameChipData>,
     // Indicate that the next byte should be saved as bunch counter for frame
@@ -24,7 +26,7 @@ pub struct LaneAlpideFrameAnalyzer<'a> {
     // Indicates that the lane status SHOULD be fatal. In this case only padding bytes should be observed which would have no effect the rest of analysis.
     // Meaning that decoding will continue until the end of the frame, but no checks will be performed.
     lane_status_fatal: bool,
-    errors: Option<String>,
+    errors: Vec<AlpideLaneError>,
     from_layer: Option<Layer>,
     validated_bc: Option<u8>, // Bunch counter for the frame if the bunch counters match
     valid_chip_order_ob: Option<&'a [Vec<u8>]>, // Valid chip orders for Outer Barrel
@@ -35,7 +37,6 @@ pub struct LaneAlpideFrameAnalyzer<'a> {
 
 // impl for core utility
 impl<'a> LaneAlpideFrameAnalyzer<'a> {
-    const ERR_MSG_PREFIX: &'static str = "\n\t\t\t   "; // Newline + indentation for error messages
     const IL_CHIP_COUNT: usize = 1; // Number of chips in an inner layer readout frame
     const ML_OL_CHIP_COUNT: usize = 7; // Number of chips in a middle/outer layer readout frame
 
@@ -49,6 +50,7 @@ impl<'a> LaneAlpideFrameAnalyzer<'a> {
             lane_number: 0,
             is_header_seen: false,
             last_chip_id: 0,
+            last_chip_empty_frame: false,
             skip_n_bytes: 0,
             chip_data: match data_origin {
                 // ALPIDE data from IB should have 9 chips per frame, OB should have 7
@@ -57,7 +59,7 @@ impl<'a> LaneAlpideFrameAnalyzer<'a> {
             },
             next_is_bc: false,
             lane_status_fatal: false,
-            errors: Some(String::new()),
+            errors: Vec::new(),
             from_layer: Some(data_origin),
             validated_bc: None,
             valid_chip_order_ob,
@@ -66,11 +68,27 @@ impl<'a> LaneAlpideFrameAnalyzer<'a> {
         }
     }
 
-    /// Decodes the readout frame for a lane byte by byte, then performs checks on the data and stores error messages
+    /// Decodes the readout frame for a lane byte by byte, then performs checks on the data and stores the errors
     ///
     /// First data is decoded, then it is validated.
-    /// If the validation fails, the error messages are stored in the errors vector that is returned.
-    pub fn analyze_alpide_frame(&mut self, lane_data_frame: &LaneDataFrame) -> Result<(), String> {
+    /// If the validation fails, the errors are stored in the errors vector that is returned.
+    pub fn analyze_alpide_frame(
+        &mut self,
+        lane_data_frame: &LaneDataFrame,
+    ) -> Result<(), Vec<AlpideLaneError>> {
+        self.decode_alpide_frame(lane_data_frame);
+        if self.lane_status_fatal {
+            // If the lane status is fatal, skip the rest of the analysis
+            Ok(())
+        } else {
+            self.do_lane_alpide_checks()
+        }
+    }
+
+    /// Decodes the readout frame for a lane byte by byte without performing any checks.
+    ///
+    /// The decoded data of each chip is then available from [LaneAlpideFrameAnalyzer::chip_data].
+    pub fn decode_alpide_frame(&mut self, lane_data_frame: &LaneDataFrame) {
         self.lane_number = lane_data_frame.lane_number(self.from_layer.unwrap());
         log::debug!(
             "Processing ALPIDE frame for lane {lane_id}",
@@ -79,12 +97,6 @@ impl<'a> LaneAlpideFrameAnalyzer<'a> {
         lane_data_frame.data().iter().for_each(|alpide_byte| {
             self.decode(*alpide_byte);
         });
-        if self.lane_status_fatal {
-            // If the lane status is fatal, skip the rest of the analysis
-            Ok(())
-        } else {
-            self.do_lane_alpide_checks()
-        }
     }
 
     /// Takes one ALPIDE byte at a time and decodes information from it.
@@ -96,8 +108,8 @@ impl<'a> LaneAlpideFrameAnalyzer<'a> {
             return;
         }
         if self.next_is_bc {
-            if let Err(msg) = self.store_bunch_counter(alpide_byte) {
-                self.errors.as_mut().unwrap().push_str(&msg);
+            if let Err(err) = self.store_bunch_counter(alpide_byte) {
+                self.errors.push(err);
             }
 
             // Done with the byte containing the bunch counter
@@ -128,12 +140,14 @@ impl<'a> LaneAlpideFrameAnalyzer<'a> {
                     } // Do nothing at the moment
                     AlpideWord::ChipHeader => {
                         self.is_header_seen = true;
+                        self.last_chip_empty_frame = false;
                         self.last_chip_id = alpide_byte & 0b1111;
                         self.next_is_bc = true;
                         log::trace!("{alpide_byte:#02X}: ChipHeader");
                     }
                     AlpideWord::ChipEmptyFrame => {
                         self.is_header_seen = false;
+                        self.last_chip_empty_frame = true;
                         self.last_chip_id = alpide_byte & 0b1111;
                         self.next_is_bc = true;
                         log::trace!("{alpide_byte:#02X}: ChipEmptyFrame");
@@ -141,6 +155,7 @@ impl<'a> LaneAlpideFrameAnalyzer<'a> {
                     AlpideWord::ChipTrailer => {
                         self.is_header_seen = false;
                         self.alpide_stats.log_readout_flags(alpide_byte);
+                        self.store_readout_flags(alpide_byte);
                         log::trace!("{alpide_byte:#02X}: ChipTrailer");
                     } // Reset the header seen flag
 
@@ -177,34 +192,24 @@ impl<'a> LaneAlpideFrameAnalyzer<'a> {
     }
 
     // All checks performed after decoding starts here
-    fn do_lane_alpide_checks(&mut self) -> Result<(), String> {
+    fn do_lane_alpide_checks(&mut self) -> Result<(), Vec<AlpideLaneError>> {
         // Check all bunch counters match
-        if let Err(msg) = self.check_bunch_counters() {
-            // if it is already in the errors_per_lane, add it to the list
-            self.errors
-                .as_mut()
-                .unwrap()
-                .push_str(&format!("\n\t\t [E9003] Chip bunch counter mismatch:{msg}"));
+        if let Err(err) = self.check_bunch_counters() {
+            self.errors.push(err);
         }
 
-        if let Err(msg) = self.check_chip_count() {
-            self.errors
-                .as_mut()
-                .unwrap()
-                .push_str(&format!("\n\t\t [E9004] Chip ID count mismatch:{msg}"));
+        if let Err(err) = self.check_chip_count() {
+            self.errors.push(err);
         } else {
             // Only check if the chip count is valid.
             // Check chip ID order
-            if let Err(msg) = self.check_chip_id_order() {
-                self.errors
-                    .as_mut()
-                    .unwrap()
-                    .push_str(&format!("\n\t\t [E9005] Chip ID order mismatch:{msg}"))
+            if let Err(err) = self.check_chip_id_order() {
+                self.errors.push(err);
             }
         }
 
         if self.has_errors() {
-            Err(self.errors.take().unwrap())
+            Err(mem::take(&mut self.errors))
         } else {
             Ok(())
         }
@@ -213,7 +218,7 @@ impl<'a> LaneAlpideFrameAnalyzer<'a> {
     /// Check that all bunch counters are identical
     ///
     /// If the check passes, the bunch counter value is stored as the validated bunch counter (bc).
-    fn check_bunch_counters(&mut self) -> Result<(), String> {
+    fn check_bunch_counters(&mut self) -> Result<(), AlpideLaneError> {
         // Return all unique bunch counters
         let unique_bcs: Vec<&AlpideFrameChipData> = self
             .chip_data
@@ -240,16 +245,7 @@ impl<'a> LaneAlpideFrameAnalyzer<'a> {
                     bc_to_chip_ids.push(bc_to_chip_id);
                 }
             });
-            // Print the bunch counters and the chip IDs that have the same bunch counter
-            let error_str = bc_to_chip_ids
-                .iter()
-                .fold(String::from(""), |acc, (bc, chip_ids)| {
-                    format!(
-                        "{acc}{newline_indent}Bunch counter: {bc:>3?} | Chip IDs: {chip_ids:?}",
-                        newline_indent = Self::ERR_MSG_PREFIX
-                    )
-                });
-            Err(error_str)
+            Err(AlpideLaneError::ChipBunchCounterMismatch { bc_to_chip_ids })
         } else {
             self.validated_bc = unique_bcs.first().unwrap().bunch_counter;
             Ok(())
@@ -257,47 +253,37 @@ impl<'a> LaneAlpideFrameAnalyzer<'a> {
     }
 
     /// Check if the number of chip data matches the expected number of chips
-    fn check_chip_count(&self) -> Result<(), String> {
-        if matches!(self.from_layer, Some(Layer::Inner)) {
-            if self.chip_data.len() != Self::IL_CHIP_COUNT {
-                return Err(format!(
-                    "{newline_indent}Expected {expected_chip_count} Chip ID in IB but found {id_cnt}: {chip_ids:?}",
-                    expected_chip_count = Self::IL_CHIP_COUNT,
-                    newline_indent = Self::ERR_MSG_PREFIX,
-                    id_cnt = self.chip_data.len(),
-                    chip_ids = self.chip_data.iter().map(|cd| cd.chip_id).collect_vec()
-                ));
-            }
-        }
-        // Middle or Outer layer (Outer barrel)
-        else if let Some(custom_chip_count_check) = self.valid_chip_count_ob {
-            if self.chip_data.len() != custom_chip_count_check as usize {
-                return Err(format!(
-                    "{newline_indent}Expected {expected_chip_count} Chip ID(s) in OB but found {id_cnt}: {chip_ids:?}",
-                    expected_chip_count = custom_chip_count_check,
-                    newline_indent = Self::ERR_MSG_PREFIX,
-                    id_cnt = self.chip_data.len(),
-                    chip_ids = self.chip_data.iter().map(|cd| cd.chip_id).collect_vec()
-                ));
+    fn check_chip_count(&self) -> Result<(), AlpideLaneError> {
+        let expected_chip_count = if matches!(self.from_layer, Some(Layer::Inner)) {
+            Some(Self::IL_CHIP_COUNT)
+        } else {
+            // Middle or Outer layer (Outer barrel)
+            self.valid_chip_count_ob.map(usize::from)
+        };
+        if let Some(expected) = expected_chip_count {
+            if self.chip_data.len() != expected {
+                return Err(AlpideLaneError::ChipCountMismatch {
+                    layer: self.from_layer.unwrap(),
+                    expected,
+                    chip_ids: self.chip_data.iter().map(|cd| cd.chip_id).collect_vec(),
+                });
             }
         }
         Ok(())
     }
 
     /// Get the chip IDs from the chip data vector
-    fn check_chip_id_order(&self) -> Result<(), String> {
+    fn check_chip_id_order(&self) -> Result<(), AlpideLaneError> {
         let chip_ids: Vec<u8> = self.chip_data.iter().map(|cd| cd.chip_id).collect();
         if let Some(data_from) = &self.from_layer {
             match data_from {
                 Layer::Inner => {
                     // IB only has one chip but it should match the lane number
                     if chip_ids[0] != self.lane_number {
-                        return Err(format!(
-                            "{newline_indent}Expected Chip ID {lane} in IB but found {chip_id}",
-                            newline_indent = Self::ERR_MSG_PREFIX,
-                            lane = self.lane_number,
-                            chip_id = chip_ids[0]
-                        ));
+                        return Err(AlpideLaneError::ChipIdMismatch {
+                            expected: self.lane_number,
+                            found: chip_ids[0],
+                        });
                     }
                 }
                 Layer::Middle | Layer::Outer => {
@@ -305,12 +291,11 @@ impl<'a> LaneAlpideFrameAnalyzer<'a> {
                     if let Some(valid_orderings) = self.valid_chip_order_ob {
                         if !valid_orderings.contains(&chip_ids) {
                             // If the chip IDs do not match any of the valid orders, return an error
-                            return Err(format!(
-                                    "{newline_indent}Expected any order={valid_orderings:?} in {layer} but found {chip_ids:?}",
-                                    newline_indent = Self::ERR_MSG_PREFIX,
-                                    layer = data_from,
-                                    chip_ids = chip_ids
-                                ));
+                            return Err(AlpideLaneError::ChipIdOrderMismatch {
+                                layer: *data_from,
+                                valid_orders: valid_orderings.to_vec(),
+                                chip_ids,
+                            });
                         }
                     }
                 }
@@ -319,7 +304,7 @@ impl<'a> LaneAlpideFrameAnalyzer<'a> {
         Ok(())
     }
 
-    fn store_bunch_counter(&mut self, bc: u8) -> Result<(), String> {
+    fn store_bunch_counter(&mut self, bc: u8) -> Result<(), AlpideLaneError> {
         // Search for the chip data matching the last chip id
         if let Some(cd) = self
             .chip_data
@@ -327,18 +312,37 @@ impl<'a> LaneAlpideFrameAnalyzer<'a> {
             .find(|cd| cd.chip_id == self.last_chip_id)
         {
             // Store the bunch counter for the chip data
-            cd.store_bc(bc)?;
+            if let Some(current_bc) = cd.bunch_counter {
+                return Err(AlpideLaneError::BunchCounterAlreadySet {
+                    chip_id: cd.chip_id,
+                    current_bc,
+                    new_bc: bc,
+                });
+            }
+            cd.bunch_counter = Some(bc);
         } else {
             // ID not found, create a instance of AlpideFrameChipData with the ID
             let mut cd = AlpideFrameChipData::from_id_no_data(self.last_chip_id);
+            cd.empty_frame = self.last_chip_empty_frame;
             // Add the bunch counter to the bunch counter vector
-            cd.store_bc(bc)?;
+            cd.bunch_counter = Some(bc);
             // Add the chip data to the chip data vector
             self.chip_data.push(cd);
         }
 
         Ok(())
     }
+
+    /// Store the readout flags of a Chip Trailer for the chip of the last Chip Header
+    fn store_readout_flags(&mut self, chip_trailer: u8) {
+        if let Some(cd) = self
+            .chip_data
+            .iter_mut()
+            .find(|cd| cd.chip_id == self.last_chip_id)
+        {
+            cd.readout_flags = Some(chip_trailer & 0b1111);
+        }
+    }
 }
 
 // impl for utility member functions
@@ -358,11 +362,7 @@ impl<'a> LaneAlpideFrameAnalyzer<'a> {
     }
 
     fn has_errors(&self) -> bool {
-        if let Some(error_msg) = self.errors.as_ref() {
-            !error_msg.is_empty()
-        } else {
-            false
-        }
+        !self.errors.is_empty()
     }
 
     /// Get if the lane status is fatal (To avoid checking the data against other lanes that were validated in the same readout frame)
@@ -375,6 +375,11 @@ impl<'a> LaneAlpideFrameAnalyzer<'a> {
         self.validated_bc
     }
 
+    /// Get the decoded data of each chip, in the order the chips were seen
+    pub fn chip_data(&self) -> &[AlpideFrameChipData] {
+        &self.chip_data
+    }
+
     /// Get the collected ALPIDE stats
     pub fn alpide_stats(&mut self) -> &AlpideStats {
         &self.alpide_stats
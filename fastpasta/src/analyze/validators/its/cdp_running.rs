@@ -2,15 +2,18 @@
 //!
 //! [CdpRunningValidator] delegates sanity checks to word specific sanity checkers.
 
+pub mod ambiguous_word_dump;
 mod cdp_tracker;
+mod error_context;
 mod rdh_validator;
 mod readout_frame;
 use self::{
-    cdp_tracker::CdpTracker, rdh_validator::ItsRdhValidator,
-    readout_frame::ItsReadoutFrameValidator,
+    ambiguous_word_dump::AmbiguousWordDump, cdp_tracker::CdpTracker, error_context::ErrorContext,
+    rdh_validator::ItsRdhValidator, readout_frame::ItsReadoutFrameValidator,
 };
-use super::status_word::tdh::TdhValidator;
+use super::status_word::{ddw::Ddw0Validator, tdh::TdhValidator};
 use crate::util::*;
+use std::sync::Mutex;
 
 #[derive(Debug, Clone, Copy)]
 enum StatusWordKind<'a> {
@@ -28,21 +31,58 @@ pub struct CdpRunningValidator<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOp
     tracker: CdpTracker,
     rdh_validator: ItsRdhValidator<T>,
     status_words: StatusWordContainer,
+    // The most severe status of each lane reported by the TDTs in the current HBF
+    hbf_tdt_lane_status: u64,
+    // Set when a data word is seen in the current readout frame while the IHW has no active lanes, to only report it once per frame
+    no_active_lanes_reported: bool,
+    // Set when a TDH with data starts a readout frame, cleared when a TDT with packet_done closes it or the HBF ends
+    is_readout_frame: bool,
+    // Set when a calibration block ends, the next CDW then starts a new calibration block at index 0
+    calibration_block_ended: bool,
+    // Set when the current CDP is from a stave of the ITS barrel(s) selected with `--its-barrel`, the data words are skipped if not
+    is_barrel_selected: bool,
     stats_send_ch: flume::Sender<StatType>,
     // Stores the ALPIDE data from an ITS readout frame, if the config is set to check ALPIDE data, and a filter for a stave is set.
     readout_frame_validator: Option<ItsReadoutFrameValidator<C>>,
+    // Trigger periods detected between TDHs with internal trigger set, if the config is set to collect them.
+    trigger_period_histogram: Option<TriggerPeriodHistogram>,
+    // Holds back errors until the GBT words surrounding them are seen, if the config is set to add error context.
+    error_context: Option<ErrorContext>,
+    // Words with an unrecognized ID are written to it, if the config is set to dump them.
+    ambiguous_word_dump: Option<&'static Mutex<AmbiguousWordDump>>,
 }
 
 impl<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt> CdpRunningValidator<T, C> {
     /// Creates a new [CdpRunningValidator] from a config that implements [ChecksOpt] + [FilterOpt] and a [StatType] producer channel.
     pub fn new(config: &'static C, stats_send_ch: flume::Sender<StatType>) -> Self {
+        let ambiguous_word_dump =
+            config
+                .dump_ambiguous()
+                .and_then(|path| match AmbiguousWordDump::shared(path) {
+                    Ok(dump) => Some(dump),
+                    Err(e) => {
+                        // The receiver might already be gone if processing stopped early
+                        _ = stats_send_ch.send(StatType::Fatal(e.into()));
+                        None
+                    }
+                });
         Self {
             config,
             tracker: CdpTracker::default(),
             rdh_validator: ItsRdhValidator::default(),
             running_checks_enabled: matches!(config.check(), Some(CheckCommands::All(_))),
             its_state_machine: ItsPayloadFsmContinuous::default(),
-            status_words: StatusWordContainer::new_const(),
+            status_words: if config.check_reserved() {
+                // The reserved bits are checked separately with a dedicated error code per status word
+                StatusWordContainer::new_const().without_reserved_sanity_checks()
+            } else {
+                StatusWordContainer::new_const()
+            },
+            hbf_tdt_lane_status: 0,
+            no_active_lanes_reported: false,
+            is_readout_frame: false,
+            calibration_block_ended: false,
+            is_barrel_selected: true,
             stats_send_ch,
             readout_frame_validator: if config.check().is_some_and(|check| {
                 check
@@ -50,33 +90,67 @@ impl<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt> CdpRunningValidator<T,
                     .is_some_and(|target| target == System::ITS_Stave)
             }) {
                 Some(ItsReadoutFrameValidator::new(config))
+            } else if config.alpide_stats_only() {
+                Some(ItsReadoutFrameValidator::new_stats_only(config))
             } else {
                 None
             },
+            trigger_period_histogram: config
+                .trigger_period_histogram()
+                .then(TriggerPeriodHistogram::default),
+            error_context: config.error_context().map(ErrorContext::new),
+            ambiguous_word_dump,
         }
     }
 
     /// Helper function to format and report an error
     ///
     /// Takes in the error string slice and the word slice
-    /// Adds the current memory position to the error string
-    /// Sends the error to the stats channel
+    /// Adds the current memory position, FEE ID and link ID of the current CDP
+    /// Sends the error to the stats channel as a [CheckError], or if error context is enabled, sends it when the words following it are seen
     #[inline]
-    fn report_error(&self, error: &str, word_slice: &[u8]) {
-        super::util::report_error(
-            self.tracker.current_word_mem_pos(),
-            error,
-            word_slice,
-            &self.stats_send_ch,
-        );
+    fn report_error(&mut self, error: &str, word_slice: &[u8]) {
+        let mem_pos = self.tracker.current_word_mem_pos();
+        if let Some(error_context) = self.error_context.as_mut() {
+            error_context.add_error(
+                super::util::check_error(mem_pos, error, word_slice, self.rdh_validator.rdh()),
+                mem_pos,
+                word_slice,
+            );
+        } else {
+            super::util::report_error(
+                mem_pos,
+                error,
+                word_slice,
+                self.rdh_validator.rdh(),
+                &self.stats_send_ch,
+            );
+        }
+    }
+
+    /// Returns true if the end of payload 0xFF padding should be checked strictly, set with `--strict-padding`
+    pub fn strict_padding(&self) -> bool {
+        self.config.strict_padding()
     }
 
-    /// Resets the state machine to the initial state and logs a warning
+    /// Resets the state machine to the initial state and reports a warning
     ///
     /// Use this if a payload format is invalid and the next payload can be processed from the initial state
     pub fn reset_fsm(&mut self) {
-        log::warn!("Resetting CDP Payload FSM");
+        self.stats_send_ch
+            .send(StatType::Warning(
+                format!(
+                    "{mem_pos:#X}: Resetting CDP Payload FSM",
+                    mem_pos = self.tracker.payload_mem_pos()
+                )
+                .into(),
+            ))
+            .expect("Failed to send warning to stats channel");
         self.its_state_machine.reset_fsm();
+        self.is_readout_frame = false;
+        // Continuity of the calibration block is unknown after the skipped payload
+        self.status_words.clear_cdw();
+        self.calibration_block_ended = false;
     }
 
     /// This function has to be called for every RDH
@@ -85,9 +159,26 @@ impl<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt> CdpRunningValidator<T,
     /// It uses the RDH to determine size of padding
     #[inline]
     pub fn set_current_rdh(&mut self, rdh: &T, rdh_mem_pos: u64) {
+        // The error context does not extend across CDPs
+        if let Some(error_context) = self.error_context.as_mut() {
+            error_context.flush(&self.stats_send_ch);
+        }
+        // A new HBF starting while a readout frame is open means the previous HBF ended without closing it
+        if self.running_checks_enabled && rdh.pages_counter() == 0 && self.is_readout_frame {
+            self.is_readout_frame = false;
+            self.stats_send_ch
+                .send(StatType::CheckError(Box::new(CheckError::new(
+                    rdh_mem_pos,
+                    "[E58] HBF ended without a TDT with packet_done closing the readout frame",
+                    rdh.fee_id(),
+                    rdh.link_id(),
+                ))))
+                .expect("Failed to send error to stats channel");
+        }
         // Initialize a new tracker and RDH validator for the current CDP
         self.tracker = CdpTracker::new(rdh, rdh_mem_pos);
         self.rdh_validator = ItsRdhValidator::new(rdh);
+        self.is_barrel_selected = self.config.its_barrel().includes_fee_id(rdh.fee_id());
 
         // If the ItsReadoutFrameValidator is present (meaning ALPIDE checks are enabled)
         // and the stave the data is from is not known yet, then set the stave.
@@ -122,12 +213,16 @@ impl<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt> CdpRunningValidator<T,
                     self.preprocess_status_word(StatusWordKind::Tdh(gbt_word));
                     if self.running_checks_enabled {
                         self.check_tdh_no_continuation(gbt_word);
+                        self.check_tdh_trigger_orbit_not_decreasing(gbt_word);
                         self.check_tdh_trigger_interval(gbt_word);
+                        self.record_tdh_trigger_period();
                     }
                 }
                 ItsPayloadWord::TDT => self.preprocess_status_word(StatusWordKind::Tdt(gbt_word)),
                 ItsPayloadWord::IHW => {
                     self.preprocess_status_word(StatusWordKind::Ihw(gbt_word));
+                    // Start of a new HBF
+                    self.hbf_tdt_lane_status = 0;
                     if self.running_checks_enabled {
                         self.check_rdh_at_initial_ihw(gbt_word);
                     }
@@ -137,7 +232,9 @@ impl<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt> CdpRunningValidator<T,
                     self.preprocess_status_word(StatusWordKind::Tdh(gbt_word));
                     if self.running_checks_enabled {
                         self.check_tdh_by_was_tdt_packet_done_true(gbt_word);
+                        self.check_tdh_trigger_orbit_not_decreasing(gbt_word);
                         self.check_tdh_trigger_interval(gbt_word);
+                        self.record_tdh_trigger_period();
                     }
                 }
 
@@ -160,18 +257,54 @@ impl<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt> CdpRunningValidator<T,
                     "[E990] Unrecognized ID in ITS payload, could be TDH/DDW0 based on current state, attempting to parse as TDH",
                     gbt_word,
                 );
+                    self.dump_ambiguous_word(gbt_word, 990);
                     self.preprocess_status_word(StatusWordKind::Tdh(gbt_word));
                 }
                 its_payload_fsm_cont::AmbigiousError::DW_or_TDT_CDW => {
                     self.report_error("[E991] Unrecognized ID in ITS payload, could be Data Word/TDT/CDW based on current state, attempting to parse as Data Word", gbt_word);
+                    self.dump_ambiguous_word(gbt_word, 991);
                     self.preprocess_data_word(gbt_word);
                 }
                 its_payload_fsm_cont::AmbigiousError::DDW0_or_TDH_IHW => {
                     self.report_error("[E992] Unrecognized ID in ITS payload, could be DDW0/TDH/IHW based on current state, attempting to parse as DDW0", gbt_word);
+                    self.dump_ambiguous_word(gbt_word, 992);
                     self.preprocess_status_word(StatusWordKind::Ddw0(gbt_word));
                 }
             },
         }
+
+        if let Some(error_context) = self.error_context.as_mut() {
+            error_context.word_checked(
+                self.tracker.current_word_mem_pos(),
+                gbt_word,
+                &self.stats_send_ch,
+            );
+        }
+    }
+
+    /// Writes a word with an unrecognized ID to the dump along with the error code it was reported with, if the config is set to dump them
+    fn dump_ambiguous_word(&self, gbt_word: &[u8], error_code: u16) {
+        let Some(dump) = self.ambiguous_word_dump else {
+            return;
+        };
+        let rdh = self.rdh_validator.rdh();
+        if let Err(e) = dump
+            .lock()
+            .expect("Ambiguous word dump lock poisoned")
+            .write(
+                gbt_word,
+                self.tracker.current_word_mem_pos(),
+                rdh.fee_id(),
+                rdh.link_id(),
+                error_code,
+            )
+        {
+            self.stats_send_ch
+                .send(StatType::Fatal(
+                    format!("Failed to dump ambiguous word: {e}").into(),
+                ))
+                .expect("Failed to send error to stats channel");
+        }
     }
 
     /// Takes a slice of bytes wrapped in an enum of the expected status word then:
@@ -196,8 +329,14 @@ impl<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt> CdpRunningValidator<T,
         if let Err(e) = self.status_words.sanity_check_tdh(&tdh) {
             self.report_error(&format!("[E40] {e}"), tdh_slice);
         }
+        self.check_reserved_bits(&tdh, "[E43] TDH reserved bits are not 0", tdh_slice);
 
         self.status_words.replace_tdh(tdh);
+        if self.status_words.tdh().unwrap().continuation() == 0 {
+            self.no_active_lanes_reported = false;
+            // A TDH with no_data set is not followed by a TDT
+            self.is_readout_frame = self.status_words.tdh().unwrap().no_data() == 0;
+        }
 
         // If the current TDH does not have continuation set, then it is the start of a new readout frame
         if self
@@ -219,6 +358,14 @@ impl<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt> CdpRunningValidator<T,
         if let Err(e) = self.status_words.sanity_check_tdt(&tdt) {
             self.report_error(&format!("[E50] {e}"), tdh_slice);
         }
+        self.check_reserved_bits(&tdt, "[E51] TDT reserved bits are not 0", tdh_slice);
+        if self.running_checks_enabled {
+            self.hbf_tdt_lane_status =
+                Ddw0Validator::max_lane_status(self.hbf_tdt_lane_status, tdt.lane_status());
+        }
+        if tdt.packet_done() {
+            self.is_readout_frame = false;
+        }
         // Replace TDT before processing ALPIDE readout frame
         self.status_words.replace_tdt(tdt);
 
@@ -233,6 +380,7 @@ impl<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt> CdpRunningValidator<T,
         if let Err(e) = self.status_words.sanity_check_ihw(&ihw) {
             self.report_error(&format!("[E30] {e}"), ihw_slice);
         }
+        self.check_reserved_bits(&ihw, "[E31] IHW reserved bits are not 0", ihw_slice);
         self.status_words.replace_ihw(ihw);
     }
 
@@ -241,14 +389,33 @@ impl<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt> CdpRunningValidator<T,
         if let Err(e) = self.status_words.sanity_check_ddw0(&ddw0) {
             self.report_error(&format!("[E60] {e}"), ddw0_slice);
         }
+        self.check_reserved_bits(&ddw0, "[E61] DDW0 reserved bits are not 0", ddw0_slice);
 
         // Additional state dependent checks on RDH
         if self.running_checks_enabled {
             self.check_rdh_at_ddw0(ddw0_slice);
+            self.check_ddw0_lane_status(&ddw0, ddw0_slice);
+            self.check_readout_frame_closed_at_ddw0(ddw0_slice);
         }
+        // End of the HBF
+        self.hbf_tdt_lane_status = 0;
+        self.is_readout_frame = false;
         self.status_words.replace_ddw(ddw0);
     }
 
+    /// Reports the error if the reserved bits of the status word are not 0, only if enabled with `--check-reserved`
+    #[inline]
+    fn check_reserved_bits(
+        &mut self,
+        status_word: &impl StatusWord,
+        error: &str,
+        word_slice: &[u8],
+    ) {
+        if self.config.check_reserved() && !status_word.is_reserved_0() {
+            self.report_error(error, word_slice);
+        }
+    }
+
     /// Takes a slice of bytes expected to be a data word, and checks if it has a valid identifier.
     #[inline]
     fn preprocess_data_word(&mut self, data_word_slice: &[u8]) {
@@ -257,6 +424,9 @@ impl<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt> CdpRunningValidator<T,
             self.process_cdw(data_word_slice);
         } else {
             // Regular data word
+            if self.tracker.start_of_data() {
+                self.end_calibration_block();
+            }
             if let Err(e) = DataWordSanityChecker::check_any(data_word_slice) {
                 self.report_error(&format!("[E70] {e}"), data_word_slice);
             }
@@ -276,9 +446,10 @@ impl<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt> CdpRunningValidator<T,
 
     #[inline]
     fn process_ib_data_word(&mut self, ib_slice: &[u8]) {
-        if !self.running_checks_enabled {
+        if !self.running_checks_enabled || !self.is_barrel_selected {
             return;
         }
+        self.check_ihw_has_active_lanes(ib_slice);
 
         if let Err(err_msg) =
             IbDataWordValidator::check(ib_slice, self.status_words.ihw().unwrap().active_lanes())
@@ -295,9 +466,10 @@ impl<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt> CdpRunningValidator<T,
 
     #[inline]
     fn process_ob_data_word(&mut self, ob_slice: &[u8]) {
-        if !self.running_checks_enabled {
+        if !self.running_checks_enabled || !self.is_barrel_selected {
             return;
         }
+        self.check_ihw_has_active_lanes(ob_slice);
 
         if let Err(err_msgs) =
             ObDataWordValidator::check(ob_slice, self.status_words.ihw().unwrap().active_lanes())
@@ -315,22 +487,49 @@ impl<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt> CdpRunningValidator<T,
 
     #[inline]
     fn process_cdw(&mut self, cdw_slice: &[u8]) {
+        let cdw = Cdw::load(&mut <&[u8]>::clone(&cdw_slice)).unwrap();
+        self.check_reserved_bits(&cdw, "[E83] CDW reserved bits are not 0", cdw_slice);
         if !self.running_checks_enabled {
             return;
         }
-        let cdw = Cdw::load(&mut <&[u8]>::clone(&cdw_slice)).unwrap();
 
-        // If this is not the first CDW, check that the user fields matches the previous CDW
-        if self.status_words.cdw().is_some_and(|prv_cdw| {
-            prv_cdw.calibration_user_fields() != cdw.calibration_user_fields()
-                && cdw.calibration_word_index() != 0
-        }) {
+        // If this is not the first CDW, check the index against the previous CDW
+        if let Some(&prv_cdw) = self.status_words.cdw() {
+            if prv_cdw.calibration_user_fields() != cdw.calibration_user_fields() {
+                // User fields changed, a new calibration block starts at index 0
+                if cdw.calibration_word_index() != 0 {
+                    self.report_error("[E81] CDW index is not 0", cdw_slice);
+                }
+            } else if cdw.calibration_word_index() != prv_cdw.calibration_word_index() + 1 {
+                // Within a calibration block the indices are contiguous
+                self.report_error(
+                    &format!(
+                        "[E82] CDW index is not contiguous, expected: {expected}, found: {found}",
+                        expected = prv_cdw.calibration_word_index() + 1,
+                        found = cdw.calibration_word_index()
+                    ),
+                    cdw_slice,
+                );
+            }
+        } else if self.calibration_block_ended && cdw.calibration_word_index() != 0 {
+            // The previous calibration block ended, so a new one starts at index 0
             self.report_error("[E81] CDW index is not 0", cdw_slice);
         }
 
         self.status_words.replace_cdw(cdw);
     }
 
+    /// Ends the current calibration block, if any, when the data of a CDP does not start with a CDW
+    ///
+    /// A calibration block can span multiple CDPs of a link, as long as the data of each CDP starts with a CDW
+    #[inline]
+    fn end_calibration_block(&mut self) {
+        if self.running_checks_enabled && self.status_words.cdw().is_some() {
+            self.status_words.clear_cdw();
+            self.calibration_block_ended = true;
+        }
+    }
+
     // Minor checks done in certain states
 
     /// Checks TDH trigger and continuation following a TDT packet_done = 1
@@ -348,6 +547,38 @@ impl<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt> CdpRunningValidator<T,
         }
     }
 
+    /// Checks that the trigger_orbit of a TDH starting a new readout frame did not decrease from the previous TDH of the link
+    #[inline]
+    fn check_tdh_trigger_orbit_not_decreasing(&mut self, tdh_slice: &[u8]) {
+        if let Some(prev_tdh) = self.status_words.prv_tdh() {
+            if let Err(err_msg) = TdhValidator::check_trigger_orbit_not_decreasing(
+                self.status_words.tdh().unwrap(),
+                prev_tdh,
+            ) {
+                self.report_error(&err_msg, tdh_slice);
+            }
+        }
+    }
+
+    /// Checks that the IHW declares any active lanes when an IB or OB data word is observed, reported once per readout frame
+    #[inline]
+    fn check_ihw_has_active_lanes(&mut self, data_word_slice: &[u8]) {
+        if self.no_active_lanes_reported {
+            return;
+        }
+        if self
+            .status_words
+            .ihw()
+            .is_some_and(|ihw| ihw.active_lanes() == 0)
+        {
+            self.no_active_lanes_reported = true;
+            self.report_error(
+                "[E76] Data word in a readout frame where the IHW has no active lanes",
+                data_word_slice,
+            );
+        }
+    }
+
     /// Checks RDH stop_bit and pages_counter when a DDW0 is observed
     #[inline]
     fn check_rdh_at_ddw0(&mut self, ddw0_slice: &[u8]) {
@@ -358,6 +589,27 @@ impl<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt> CdpRunningValidator<T,
         }
     }
 
+    /// Checks that the lane status reported by TDTs in the HBF is reflected in the DDW0
+    #[inline]
+    fn check_ddw0_lane_status(&mut self, ddw0: &Ddw0, ddw0_slice: &[u8]) {
+        if let Err(err_msg) =
+            Ddw0Validator::check_lane_status_reflects_tdts(ddw0, self.hbf_tdt_lane_status)
+        {
+            self.report_error(&err_msg, ddw0_slice);
+        }
+    }
+
+    /// Checks that the last readout frame of the HBF was closed by a TDT with packet_done when a DDW0 is observed
+    #[inline]
+    fn check_readout_frame_closed_at_ddw0(&mut self, ddw0_slice: &[u8]) {
+        if self.is_readout_frame {
+            self.report_error(
+                "[E58] HBF ended without a TDT with packet_done closing the readout frame",
+                ddw0_slice,
+            );
+        }
+    }
+
     /// Checks RDH stop_bit and pages_counter when an initial IHW is observed (not IHW during continuation)
     #[inline]
     fn check_rdh_at_initial_ihw(&mut self, ihw_slice: &[u8]) {
@@ -426,6 +678,23 @@ impl<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt> CdpRunningValidator<T,
         }
     }
 
+    /// Records the trigger period between the current and previous TDH with internal_trigger set, if the trigger period histogram is enabled
+    fn record_tdh_trigger_period(&mut self) {
+        if let Some(histogram) = self.trigger_period_histogram.as_mut() {
+            if let (Some(current_tdh), Some(prev_int_tdh)) = (
+                self.status_words.tdh(),
+                self.status_words.tdh_previous_with_internal_trg(),
+            ) {
+                if current_tdh.internal_trigger() == 1 {
+                    histogram.add(TdhValidator::detected_trigger_period(
+                        current_tdh.trigger_bc(),
+                        prev_int_tdh.trigger_bc(),
+                    ));
+                }
+            }
+        }
+    }
+
     /// Close a readout frame by supplying the current memory position
     ///
     /// And start the processing by the [ItsReadoutFrameValidator]
@@ -456,11 +725,37 @@ impl<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt> CdpRunningValidator<T,
     }
 }
 
+impl<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt> Drop for CdpRunningValidator<T, C> {
+    fn drop(&mut self) {
+        if let Some(error_context) = self.error_context.as_mut() {
+            error_context.flush(&self.stats_send_ch);
+        }
+        if let Some(dump) = self.ambiguous_word_dump {
+            if let Err(e) = dump
+                .lock()
+                .expect("Ambiguous word dump lock poisoned")
+                .flush()
+            {
+                log::error!("Failed to flush the dumped ambiguous words: {e}");
+            }
+        }
+        if let Some(histogram) = self.trigger_period_histogram.take() {
+            // The receiver might already be gone if processing stopped early
+            let _ = self
+                .stats_send_ch
+                .send(StatType::TriggerPeriodHistogram(Box::new(histogram)));
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use alice_protocol_reader::{
-        prelude::test_data::CORRECT_RDH_CRU_V7, rdh::test_data::CORRECT_RDH_CRU_V7_SOT,
+        prelude::test_data::CORRECT_RDH_CRU_V7,
+        rdh::test_data::{
+            CORRECT_RDH_CRU_V7_NEXT, CORRECT_RDH_CRU_V7_NEXT_NEXT_STOP, CORRECT_RDH_CRU_V7_SOT,
+        },
     };
     use pretty_assertions::{assert_eq, assert_ne, assert_str_eq};
 
@@ -517,9 +812,9 @@ mod tests {
         validator.check(&raw_data_ihw);
 
         match stats_recv_ch.recv() {
-            Ok(StatType::Error(msg)) => {
+            Ok(StatType::CheckError(msg)) => {
                 assert_eq!(
-                    &*msg,
+                    &*msg.to_string(),
                     "0x40: [E30] ID is not 0xE0: 0xE1  [FF 3F 00 00 00 00 00 00 00 E1]"
                 );
                 println!("{msg}");
@@ -544,9 +839,9 @@ mod tests {
         validator.check(&raw_data_tdt);
 
         match stats_recv_ch.recv() {
-            Ok(StatType::Error(msg)) => {
+            Ok(StatType::CheckError(msg)) => {
                 assert_eq!(
-                    &*msg,
+                    &*msg.to_string(),
                     "0x40: [E30] ID is not 0xE0: 0xF1  [00 00 00 00 00 00 00 00 01 F1]"
                 );
                 println!("{msg}");
@@ -617,12 +912,12 @@ mod tests {
         // ASSERT (receive message and assert it is expected)
         // First we get an error that the first TDH trigger_bc doesn't match the RDH bc
         match stats_recv_ch.recv() {
-            Ok(StatType::Error(msg)) => assert_str_eq!("0x4A: [E445] TDH trigger_bc is not equal to RDH bc, TDH: 0x1, RDH: 0x0. [03 3A 01 00 75 D5 7D 0B 00 E8]", &*msg),
+            Ok(StatType::CheckError(msg)) => assert_str_eq!("0x4A: [E445] TDH trigger_bc is not equal to RDH bc, TDH: 0x1, RDH: 0x0. [03 3A 01 00 75 D5 7D 0B 00 E8]", &*msg.to_string()),
             _ => unreachable!(),
         }
         // Then we get the TDH trigger_bc mismatch
         match stats_recv_ch.recv() {
-            Ok(StatType::Error(msg)) => assert_str_eq!("0x54: [E440] TDH trigger_bc is not increasing, previous: 0x1, current: 0x0. [03 1A 00 00 75 D5 7D 0B 00 E8]", &*msg),
+            Ok(StatType::CheckError(msg)) => assert_str_eq!("0x54: [E440] TDH trigger_bc is not increasing, previous: 0x1, current: 0x0. [03 1A 00 00 75 D5 7D 0B 00 E8]", &*msg.to_string()),
             _ => unreachable!(),
         }
         // No more errors
@@ -678,8 +973,8 @@ mod tests {
 
         // ASSERT (receive message and assert it is expected)
         match stats_recv_ch.recv() {
-            Ok(StatType::Error(msg)) => {
-                assert_str_eq!(&*msg, "0x4A: [E44] TDH trigger_type 0xA03 != 0x893 RDH trigger_type[11:0]. [03 1A 00 00 75 D5 7D 0B 00 E8]");
+            Ok(StatType::CheckError(msg)) => {
+                assert_str_eq!(&*msg.to_string(), "0x4A: [E44] TDH trigger_type 0xA03 != 0x893 RDH trigger_type[11:0]. [03 1A 00 00 75 D5 7D 0B 00 E8]");
             }
             _ => unreachable!(),
         }
@@ -703,9 +998,9 @@ mod tests {
         validator.check(&raw_data_tdt_next);
 
         match stats_recv_ch.recv() {
-            Ok(StatType::Error(msg)) => {
+            Ok(StatType::CheckError(msg)) => {
                 assert_eq!(
-                    &*msg,
+                    &*msg.to_string(),
                     "0x40: [E30] ID is not 0xE0: 0xF1  [00 00 00 00 00 00 00 00 01 F1]"
                 );
                 println!("{msg}");
@@ -713,9 +1008,9 @@ mod tests {
             _ => unreachable!(),
         }
         match stats_recv_ch.recv() {
-            Ok(StatType::Error(msg)) => {
+            Ok(StatType::CheckError(msg)) => {
                 assert_eq!(
-                    &*msg,
+                    &*msg.to_string(),
                     "0x4A: [E40] ID is not 0xE8: 0xF2  [00 00 00 00 00 00 00 00 01 F2]"
                 );
                 println!("{msg}");
@@ -745,9 +1040,9 @@ mod tests {
         validator.check(&raw_data_tdt_next_next);
 
         match stats_recv_ch.recv() {
-            Ok(StatType::Error(msg)) => {
+            Ok(StatType::CheckError(msg)) => {
                 assert_eq!(
-                    &*msg,
+                    &*msg.to_string(),
                     "0x40: [E30] ID is not 0xE0: 0xF1  [00 00 00 00 00 00 00 00 01 F1]"
                 );
                 println!("{msg}");
@@ -755,9 +1050,9 @@ mod tests {
             _ => unreachable!(),
         }
         match stats_recv_ch.recv() {
-            Ok(StatType::Error(msg)) => {
+            Ok(StatType::CheckError(msg)) => {
                 assert_eq!(
-                    &*msg,
+                    &*msg.to_string(),
                     "0x4A: [E40] ID is not 0xE8: 0xF2  [00 00 00 00 00 00 00 00 01 F2]"
                 );
                 println!("{msg}");
@@ -765,9 +1060,9 @@ mod tests {
             _ => unreachable!(),
         }
         match stats_recv_ch.recv() {
-            Ok(StatType::Error(msg)) => {
+            Ok(StatType::CheckError(msg)) => {
                 assert_eq!(
-                    &*msg,
+                    &*msg.to_string(),
                     "0x4A: [E444] TDH trigger_orbit is not equal to RDH orbit [00 00 00 00 00 00 00 00 01 F2]"
                 );
                 println!("{msg}");
@@ -775,10 +1070,10 @@ mod tests {
             _ => unreachable!(),
         }
         match stats_recv_ch.recv() {
-            Ok(StatType::Error(msg)) => {
+            Ok(StatType::CheckError(msg)) => {
                 // Amibiguous error, could be several different data words
                 assert_eq!(
-                    &*msg,
+                    &*msg.to_string(),
                     "0x54: [E991] Unrecognized ID in ITS payload, could be Data Word/TDT/CDW based on current state, attempting to parse as Data Word [00 00 00 00 00 00 00 00 01 F3]"
                 );
                 println!("{msg}");
@@ -786,10 +1081,10 @@ mod tests {
             _ => unreachable!(),
         }
         match stats_recv_ch.recv() {
-            Ok(StatType::Error(msg)) => {
+            Ok(StatType::CheckError(msg)) => {
                 // Amibiguous error, could be several different data words
                 assert_eq!(
-                    &*msg,
+                    &*msg.to_string(),
                     "0x54: [E70] ID is invalid: 0xF3 [00 00 00 00 00 00 00 00 01 F3]"
                 );
                 println!("{msg}");
@@ -799,4 +1094,615 @@ mod tests {
         // No more errors
         assert!(stats_recv_ch.try_recv().is_err());
     }
+
+    /// Feeds an HBF with a TDT and the given DDW0 to the validator and returns the [E66] errors
+    fn e66_errors_from_hbf(raw_data_tdt: [u8; 10], raw_data_ddw0: [u8; 10]) -> Vec<String> {
+        let raw_data_ihw = [
+            0xFF,
+            0x3F,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            Ihw::ID,
+        ];
+        let raw_data_tdh = [
+            0x03,
+            0x1A,
+            0x00,
+            0x00,
+            0x75,
+            0xD5,
+            0x7D,
+            0x0B,
+            0x00,
+            Tdh::ID,
+        ];
+
+        let (send, stats_recv_ch) = flume::unbounded();
+        let mut validator: CdpRunningValidator<RdhCru, MockConfig> =
+            CdpRunningValidator::new(get_running_checks_config(), send);
+
+        validator.set_current_rdh(&CORRECT_RDH_CRU_V7_SOT, 0);
+        validator.check(&raw_data_ihw);
+        validator.check(&raw_data_tdh);
+        validator.check(&raw_data_tdt);
+        validator.set_current_rdh(&CORRECT_RDH_CRU_V7_NEXT_NEXT_STOP, 0x100);
+        validator.check(&raw_data_ddw0);
+
+        stats_recv_ch
+            .try_iter()
+            .filter_map(|stat| match stat {
+                StatType::CheckError(msg) if msg.code == Some(66) => Some(msg.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tdt_lane_error_not_reflected_in_ddw0() {
+        // TDT with lane 1 in error and packet_done
+        let raw_data_tdt = [
+            0b1000,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x01,
+            Tdt::ID,
+        ];
+        let raw_data_ddw0_clean = [
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            Ddw0::ID,
+        ];
+
+        let errors = e66_errors_from_hbf(raw_data_tdt, raw_data_ddw0_clean);
+        assert_eq!(
+            errors,
+            vec!["0x140: [E66] TDT lane error not reflected in DDW0: lane 1 (TDT: Error, DDW0: OK) [00 00 00 00 00 00 00 00 00 E4]"]
+        );
+    }
+
+    #[test]
+    fn test_tdt_lane_error_reflected_in_ddw0() {
+        // TDT with lane 1 in error and packet_done
+        let raw_data_tdt = [
+            0b1000,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x01,
+            Tdt::ID,
+        ];
+        // DDW0 with lane 1 in fatal
+        let raw_data_ddw0 = [
+            0b1100,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            Ddw0::ID,
+        ];
+
+        assert!(e66_errors_from_hbf(raw_data_tdt, raw_data_ddw0).is_empty());
+    }
+
+    #[test]
+    fn test_data_word_with_no_active_lanes_in_ihw() {
+        let raw_data_ihw = [
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            Ihw::ID,
+        ];
+        // TDH with continuation = 0, i.e. the start of a readout frame
+        let raw_data_tdh = [
+            0x03,
+            0x1A,
+            0x00,
+            0x00,
+            0x75,
+            0xD5,
+            0x7D,
+            0x0B,
+            0x00,
+            Tdh::ID,
+        ];
+        // IB data word from lane 2
+        let raw_data_ib = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x22];
+
+        let (send, stats_recv_ch) = flume::unbounded();
+        let mut validator: CdpRunningValidator<RdhCru, MockConfig> =
+            CdpRunningValidator::new(get_running_checks_config(), send);
+        validator.set_current_rdh(&CORRECT_RDH_CRU_V7, 0);
+        validator.tracker.incr_word_count();
+        validator.preprocess_ihw(&raw_data_ihw);
+        for _ in 0..2 {
+            validator.tracker.incr_word_count();
+            validator.preprocess_tdh(&raw_data_tdh);
+            // Only reported once per readout frame
+            for _ in 0..2 {
+                validator.tracker.incr_word_count();
+                validator.preprocess_data_word(&raw_data_ib);
+            }
+        }
+
+        let no_active_lanes_errors = stats_recv_ch
+            .drain()
+            .filter(|stat| matches!(stat, StatType::CheckError(check_error) if check_error.code == Some(76)))
+            .count();
+        assert_eq!(no_active_lanes_errors, 2);
+    }
+
+    #[test]
+    fn test_data_words_skipped_from_unselected_barrel() {
+        let raw_data_ihw = [0, 0, 0, 0, 0, 0, 0, 0, 0, Ihw::ID];
+        let raw_data_tdh = [
+            0x03,
+            0x1A,
+            0x00,
+            0x00,
+            0x75,
+            0xD5,
+            0x7D,
+            0x0B,
+            0x00,
+            Tdh::ID,
+        ];
+        // OB data word from lane 0x40, with no active lanes in the IHW
+        let raw_data_ob = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0x40];
+        // The FEE ID of the RDH (0x502A) is from layer 5, i.e. the outer barrel
+        let no_active_lanes_errors = |config: &'static MockConfig| {
+            let (send, stats_recv_ch) = flume::unbounded();
+            let mut validator: CdpRunningValidator<RdhCru, MockConfig> =
+                CdpRunningValidator::new(config, send);
+            validator.set_current_rdh(&CORRECT_RDH_CRU_V7, 0);
+            validator.tracker.incr_word_count();
+            validator.preprocess_ihw(&raw_data_ihw);
+            validator.tracker.incr_word_count();
+            validator.preprocess_tdh(&raw_data_tdh);
+            validator.tracker.incr_word_count();
+            validator.preprocess_data_word(&raw_data_ob);
+            stats_recv_ch
+                .drain()
+                .filter(|stat| matches!(stat, StatType::CheckError(check_error) if check_error.code == Some(76)))
+                .count()
+        };
+        let config_with_barrel = |its_barrel| -> &'static MockConfig {
+            Box::leak(Box::new(MockConfig {
+                its_barrel,
+                ..MockConfig::new_check_all_its()
+            }))
+        };
+
+        assert_eq!(
+            no_active_lanes_errors(config_with_barrel(ItsBarrel::All)),
+            1
+        );
+        assert_eq!(
+            no_active_lanes_errors(config_with_barrel(ItsBarrel::Outer)),
+            1
+        );
+        assert_eq!(
+            no_active_lanes_errors(config_with_barrel(ItsBarrel::Inner)),
+            0
+        );
+        assert_eq!(
+            no_active_lanes_errors(config_with_barrel(ItsBarrel::Middle)),
+            0
+        );
+    }
+
+    #[test]
+    fn test_check_reserved() {
+        // IHW, TDH, TDT & DDW0 with reserved bits set
+        let raw_data_ihw_bad_reserved = [
+            0xFF,
+            0x3F,
+            0x00,
+            0x00,
+            0x00,
+            0x20,
+            0x00,
+            0x00,
+            0x00,
+            Ihw::ID,
+        ];
+        let raw_data_tdh_bad_reserved = [
+            0x03,
+            0x1A,
+            0x00,
+            0x00,
+            0x75,
+            0xD5,
+            0x7D,
+            0x0B,
+            0x0F,
+            Tdh::ID,
+        ];
+        let raw_data_tdt_bad_reserved = [0, 0, 0, 0, 0, 0, 0, 0, 0xF1, Tdt::ID];
+        let raw_data_ddw0_bad_reserved = [0, 0, 0, 0, 0, 0, 0, 0xFF, 0, Ddw0::ID];
+        let reserved_error_codes = |config: &'static MockConfig| {
+            let (send, stats_recv_ch) = flume::unbounded();
+            let mut validator: CdpRunningValidator<RdhCru, MockConfig> =
+                CdpRunningValidator::new(config, send);
+            validator.set_current_rdh(&CORRECT_RDH_CRU_V7, 0);
+            validator.tracker.incr_word_count();
+            validator.preprocess_ihw(&raw_data_ihw_bad_reserved);
+            validator.tracker.incr_word_count();
+            validator.preprocess_tdh(&raw_data_tdh_bad_reserved);
+            validator.tracker.incr_word_count();
+            validator.preprocess_tdt(&raw_data_tdt_bad_reserved);
+            validator.tracker.incr_word_count();
+            validator.preprocess_ddw0(&raw_data_ddw0_bad_reserved);
+            stats_recv_ch
+                .drain()
+                .filter_map(|stat| match stat {
+                    StatType::CheckError(check_error)
+                        if matches!(check_error.code, Some(31 | 43 | 51 | 61)) =>
+                    {
+                        check_error.code
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let config = Box::leak(Box::new(MockConfig::new_check_all_its()));
+        assert!(reserved_error_codes(config).is_empty());
+        let config_check_reserved = Box::leak(Box::new(MockConfig {
+            check_reserved: true,
+            ..MockConfig::new_check_all_its()
+        }));
+        assert_eq!(
+            reserved_error_codes(config_check_reserved),
+            [31, 43, 51, 61]
+        );
+    }
+
+    #[test]
+    fn test_check_reserved_not_reported_twice() {
+        let raw_data_ihw_bad_reserved = [
+            0xFF,
+            0x3F,
+            0x00,
+            0x00,
+            0x00,
+            0x20,
+            0x00,
+            0x00,
+            0x00,
+            Ihw::ID,
+        ];
+        let ihw_error_codes = |config: &'static MockConfig| {
+            let (send, stats_recv_ch) = flume::unbounded();
+            let mut validator: CdpRunningValidator<RdhCru, MockConfig> =
+                CdpRunningValidator::new(config, send);
+            validator.set_current_rdh(&CORRECT_RDH_CRU_V7, 0);
+            validator.tracker.incr_word_count();
+            validator.preprocess_ihw(&raw_data_ihw_bad_reserved);
+            stats_recv_ch
+                .drain()
+                .filter_map(|stat| match stat {
+                    StatType::CheckError(check_error) => check_error.code,
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // The reserved bits are part of the IHW sanity check
+        let config = Box::leak(Box::new(MockConfig::new_check_all_its()));
+        assert_eq!(ihw_error_codes(config), [30]);
+        // The reserved bits are only reported with the dedicated error code
+        let config_check_reserved = Box::leak(Box::new(MockConfig {
+            check_reserved: true,
+            ..MockConfig::new_check_all_its()
+        }));
+        assert_eq!(ihw_error_codes(config_check_reserved), [31]);
+    }
+
+    /// Makes a CDW with the given calibration word index and user fields
+    fn raw_cdw(index: u32, user_fields: u64) -> [u8; 10] {
+        let mut raw_data_cdw = [0; 10];
+        raw_data_cdw[..8]
+            .copy_from_slice(&(user_fields | (u64::from(index & 0xFFFF) << 48)).to_le_bytes());
+        raw_data_cdw[8] = (index >> 16) as u8;
+        raw_data_cdw[9] = Cdw::ID;
+        raw_data_cdw
+    }
+
+    #[test]
+    fn test_cdw_index_contiguous() {
+        let (send, stats_recv_ch) = flume::unbounded();
+        let mut validator: CdpRunningValidator<RdhCru, MockConfig> =
+            CdpRunningValidator::new(get_running_checks_config(), send);
+        validator.set_current_rdh(&CORRECT_RDH_CRU_V7, 0);
+
+        // Index 2 is missing in the first calibration block, the second block starts at index 0
+        for raw_data_cdw in [
+            raw_cdw(0, 0xA0000),
+            raw_cdw(1, 0xA0000),
+            raw_cdw(3, 0xA0000),
+            raw_cdw(0, 0xA0001),
+            raw_cdw(1, 0xA0001),
+        ] {
+            // Each CDW is the first word of the data of a trigger
+            validator.tracker.incr_word_count();
+            validator.process_cdw(&raw_data_cdw);
+        }
+
+        let errors: Vec<String> = stats_recv_ch
+            .drain()
+            .filter_map(|stat| match stat {
+                StatType::CheckError(check_error) => Some(check_error.message),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            errors,
+            vec!["CDW index is not contiguous, expected: 2, found: 3 [00 00 0A 00 00 00 03 00 00 F8]"]
+        );
+    }
+
+    #[test]
+    fn test_cdw_index_not_0_in_new_calibration_block() {
+        let (send, stats_recv_ch) = flume::unbounded();
+        let mut validator: CdpRunningValidator<RdhCru, MockConfig> =
+            CdpRunningValidator::new(get_running_checks_config(), send);
+        validator.set_current_rdh(&CORRECT_RDH_CRU_V7, 0);
+
+        for raw_data_cdw in [raw_cdw(0x1_0000, 0xA0000), raw_cdw(5, 0xA0001)] {
+            validator.tracker.incr_word_count();
+            validator.process_cdw(&raw_data_cdw);
+        }
+
+        let error_codes: Vec<Option<u16>> = stats_recv_ch
+            .drain()
+            .filter_map(|stat| match stat {
+                StatType::CheckError(check_error) => Some(check_error.code),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(error_codes, vec![Some(81)]);
+    }
+
+    /// Feeds the data words of 3 consecutive CDPs of an HBF to the validator, each CDP as a readout frame continuing in the next CDP, and returns the CDW index errors
+    fn cdw_errors_from_cdps(cdps_data_words: [&[[u8; 10]]; 3]) -> Vec<String> {
+        let raw_data_ihw = [
+            0xFF,
+            0x3F,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            Ihw::ID,
+        ];
+        let raw_data_tdh = [
+            0x03,
+            0x1A,
+            0x00,
+            0x00,
+            0x75,
+            0xD5,
+            0x7D,
+            0x0B,
+            0x00,
+            Tdh::ID,
+        ];
+        // TDH with continuation = 1
+        let raw_data_tdh_continuation = [
+            0x03,
+            0x5A,
+            0x00,
+            0x00,
+            0x75,
+            0xD5,
+            0x7D,
+            0x0B,
+            0x00,
+            Tdh::ID,
+        ];
+        let raw_data_tdt = [
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            Tdt::ID,
+        ];
+        let raw_data_tdt_packet_done = [
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x01,
+            Tdt::ID,
+        ];
+
+        let (send, stats_recv_ch) = flume::unbounded();
+        let mut validator: CdpRunningValidator<RdhCru, MockConfig> =
+            CdpRunningValidator::new(get_running_checks_config(), send);
+
+        for (i, (rdh, data_words)) in [
+            CORRECT_RDH_CRU_V7_SOT,
+            CORRECT_RDH_CRU_V7_NEXT,
+            CORRECT_RDH_CRU_V7_NEXT_NEXT_STOP,
+        ]
+        .iter()
+        .zip(cdps_data_words)
+        .enumerate()
+        {
+            validator.set_current_rdh(rdh, i as u64 * 0x100);
+            validator.check(&raw_data_ihw);
+            validator.check(if i == 0 {
+                &raw_data_tdh
+            } else {
+                &raw_data_tdh_continuation
+            });
+            data_words
+                .iter()
+                .for_each(|data_word| validator.check(data_word));
+            validator.check(if i == 2 {
+                &raw_data_tdt_packet_done
+            } else {
+                &raw_data_tdt
+            });
+        }
+
+        stats_recv_ch
+            .try_iter()
+            .filter_map(|stat| match stat {
+                StatType::CheckError(msg) if matches!(msg.code, Some(81 | 82)) => {
+                    Some(msg.to_string())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_cdw_index_contiguous_across_cdps() {
+        let errors = cdw_errors_from_cdps([
+            &[raw_cdw(0, 0xA0000)],
+            &[raw_cdw(1, 0xA0000)],
+            &[raw_cdw(3, 0xA0000)],
+        ]);
+        assert_eq!(
+            errors,
+            vec!["0x254: [E82] CDW index is not contiguous, expected: 2, found: 3 [00 00 0A 00 00 00 03 00 00 F8]"]
+        );
+    }
+
+    #[test]
+    fn test_calibration_block_ends_at_cdp_without_cdw() {
+        // IB data word of lane 0
+        let raw_data_word = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x20];
+        // The calibration block of the first CDP ends as the second CDP has no CDW, so the third CDP starts a new block at index 0
+        assert!(cdw_errors_from_cdps([
+            &[raw_cdw(0, 0xA0000)],
+            &[raw_data_word],
+            &[raw_cdw(0, 0xA0000)],
+        ])
+        .is_empty());
+        assert_eq!(
+            cdw_errors_from_cdps([
+                &[raw_cdw(0, 0xA0000)],
+                &[raw_data_word],
+                &[raw_cdw(5, 0xA0000)],
+            ]),
+            vec!["0x254: [E81] CDW index is not 0 [00 00 0A 00 00 00 05 00 00 F8]"]
+        );
+    }
+
+    /// Feeds an HBF ending with a TDT with the given packet_done to the validator, then starts a new HBF, and returns the [E58] errors
+    fn e58_errors_from_hbf(tdt_packet_done: bool) -> Vec<String> {
+        let raw_data_ihw = [
+            0xFF,
+            0x3F,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            Ihw::ID,
+        ];
+        // TDH with continuation = 0 and no_data = 0, i.e. the start of a readout frame
+        let raw_data_tdh = [
+            0x03,
+            0x1A,
+            0x00,
+            0x00,
+            0x75,
+            0xD5,
+            0x7D,
+            0x0B,
+            0x00,
+            Tdh::ID,
+        ];
+        let raw_data_tdt = [
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            u8::from(tdt_packet_done),
+            Tdt::ID,
+        ];
+
+        let (send, stats_recv_ch) = flume::unbounded();
+        let mut validator: CdpRunningValidator<RdhCru, MockConfig> =
+            CdpRunningValidator::new(get_running_checks_config(), send);
+
+        validator.set_current_rdh(&CORRECT_RDH_CRU_V7_SOT, 0);
+        validator.check(&raw_data_ihw);
+        validator.check(&raw_data_tdh);
+        validator.check(&raw_data_tdt);
+        // The next HBF starts
+        validator.set_current_rdh(&CORRECT_RDH_CRU_V7_SOT, 0x100);
+
+        stats_recv_ch
+            .try_iter()
+            .filter_map(|stat| match stat {
+                StatType::CheckError(msg) if msg.code == Some(58) => Some(msg.to_string()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_hbf_ends_with_open_readout_frame() {
+        assert_eq!(
+            e58_errors_from_hbf(false),
+            vec!["0x100: [E58] HBF ended without a TDT with packet_done closing the readout frame"]
+        );
+    }
+
+    #[test]
+    fn test_hbf_ends_with_closed_readout_frame() {
+        assert!(e58_errors_from_hbf(true).is_empty());
+    }
 }
@@ -0,0 +1,109 @@
+//! Contains the [AmbiguousWordDump] that writes the ITS payload words with an unrecognized ID to a file for offline study
+//!
+//! The words are written as is, 10 bytes each, and for each word a line with its memory position, FEE ID, link ID, and error code is written to a CSV index next to the file.
+//! The validators of all links write to the same files, so the dump is shared between them.
+
+use crate::util::*;
+use std::{io::Write, sync::Mutex};
+
+/// The dump shared by the validators of all links, or the error from creating its files
+static SHARED_DUMP: OnceLock<Result<Mutex<AmbiguousWordDump>, String>> = OnceLock::new();
+
+/// Writes GBT words to a file, and their memory position, FEE ID, link ID, and error code to a CSV index.
+///
+/// Line `N` of the index (excluding the header) describes the `N`th word of the file.
+#[derive(Debug)]
+pub struct AmbiguousWordDump {
+    words: io::BufWriter<fs::File>,
+    index: io::BufWriter<fs::File>,
+}
+
+impl AmbiguousWordDump {
+    const INDEX_HEADER: &'static str = "mem_pos,fee_id,link_id,error_code";
+
+    /// Creates the file to write the words to, and the CSV index next to it, see [index_path].
+    pub fn create(path: &Path) -> io::Result<Self> {
+        let words = io::BufWriter::new(fs::File::create(path)?);
+        let mut index = io::BufWriter::new(fs::File::create(index_path(path))?);
+        writeln!(index, "{}", Self::INDEX_HEADER)?;
+        Ok(Self { words, index })
+    }
+
+    /// Returns the dump shared by the validators of all links, the files are created at the first call.
+    pub fn shared(path: &Path) -> Result<&'static Mutex<Self>, String> {
+        SHARED_DUMP
+            .get_or_init(|| {
+                Self::create(path).map(Mutex::new).map_err(|e| {
+                    format!(
+                        "Failed to create the file to dump ambiguous words to {}: {e}",
+                        path.display()
+                    )
+                })
+            })
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+
+    /// Writes a word to the file and its line to the index.
+    pub fn write(
+        &mut self,
+        word: &[u8],
+        mem_pos: u64,
+        fee_id: u16,
+        link_id: u8,
+        error_code: u16,
+    ) -> io::Result<()> {
+        self.words.write_all(word)?;
+        writeln!(self.index, "{mem_pos:#X},{fee_id},{link_id},E{error_code}")
+    }
+
+    /// Flushes the buffered words and index lines to the files.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.words.flush()?;
+        self.index.flush()
+    }
+}
+
+/// Derives the path of the CSV index from the path of the dumped words, e.g. `ambiguous.raw` -> `ambiguous_index.csv`.
+pub fn index_path(path: &Path) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    path.with_file_name(format!("{stem}_index.csv"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use temp_dir::TempDir;
+
+    #[test]
+    fn test_index_path() {
+        assert_eq!(
+            index_path(Path::new("out/ambiguous.raw")),
+            PathBuf::from("out/ambiguous_index.csv")
+        );
+        assert_eq!(
+            index_path(Path::new("ambiguous")),
+            PathBuf::from("ambiguous_index.csv")
+        );
+    }
+
+    #[test]
+    fn test_write_words_and_index() {
+        let tmp_dir = TempDir::new().unwrap();
+        let path = tmp_dir.child("ambiguous.raw");
+        let word_990 = [0, 0, 0, 0, 0, 0, 0, 0, 0, 0xF2];
+        let word_991 = [1, 2, 3, 4, 5, 6, 7, 8, 9, 0xF3];
+
+        let mut dump = AmbiguousWordDump::create(&path).unwrap();
+        dump.write(&word_990, 0x4A, 524, 8, 990).unwrap();
+        dump.write(&word_991, 0x54, 524, 8, 991).unwrap();
+        dump.flush().unwrap();
+
+        assert_eq!(fs::read(&path).unwrap(), [word_990, word_991].concat());
+        assert_eq!(
+            fs::read_to_string(index_path(&path)).unwrap(),
+            "mem_pos,fee_id,link_id,error_code\n0x4A,524,8,E990\n0x54,524,8,E991\n"
+        );
+    }
+}
@@ -41,6 +41,11 @@ impl CdpTracker {
         self.is_start_of_data = false;
     }
 
+    /// Returns the position in the memory of the first byte of the current payload.
+    pub fn payload_mem_pos(&self) -> u64 {
+        self.payload_mem_pos
+    }
+
     /// Returns the current position in the memory of the current word.
     ///
     /// It is calculated as follows:
@@ -0,0 +1,202 @@
+//! Contains the [ErrorContext] that adds the GBT words surrounding an error to the error message
+//!
+//! Words are checked one at a time, so the most recent words are kept in a ring buffer,
+//! and an error is held back until the words following it are checked (or the CDP ends).
+
+use crate::util::*;
+use std::collections::VecDeque;
+
+/// A GBT word and its memory position
+type ContextWord = (u64, [u8; 10]);
+
+/// An error waiting for the words following it
+#[derive(Debug)]
+struct PendingError {
+    check_error: CheckError,
+    words: Vec<ContextWord>,
+    // Index of the word the error was found in
+    error_word_idx: usize,
+    words_after: u8,
+}
+
+impl PendingError {
+    fn into_stat(self) -> StatType {
+        let context = self
+            .words
+            .iter()
+            .enumerate()
+            .map(|(idx, (mem_pos, word))| {
+                format!(
+                    "\n\t{mem_pos:#X}: {word}{marker}",
+                    word = word.iter().map(|byte| format!("{byte:02X}")).join(" "),
+                    marker = if idx == self.error_word_idx {
+                        " <--"
+                    } else {
+                        ""
+                    }
+                )
+            })
+            .join("");
+        let mut check_error = self.check_error;
+        check_error.message.push_str(&context);
+        StatType::CheckError(Box::new(check_error))
+    }
+}
+
+/// Keeps track of the most recent GBT words and the errors waiting for the words following them.
+#[derive(Debug)]
+pub struct ErrorContext {
+    // Number of words to include before and after the word an error was found in
+    context_words: u8,
+    recent_words: VecDeque<ContextWord>,
+    // Errors in the word that is currently being checked
+    current_word_errors: Vec<PendingError>,
+    pending_errors: Vec<PendingError>,
+}
+
+impl ErrorContext {
+    pub fn new(context_words: u8) -> Self {
+        Self {
+            context_words,
+            recent_words: VecDeque::with_capacity(context_words as usize),
+            current_word_errors: Vec::new(),
+            pending_errors: Vec::new(),
+        }
+    }
+
+    /// Add an error found in the word that is currently being checked, it is sent when the words following it are checked.
+    pub fn add_error(&mut self, check_error: CheckError, mem_pos: u64, word: &[u8]) {
+        let mut words: Vec<ContextWord> = self.recent_words.iter().copied().collect();
+        let error_word_idx = words.len();
+        words.push((mem_pos, word_array(word)));
+        self.current_word_errors.push(PendingError {
+            check_error,
+            words,
+            error_word_idx,
+            words_after: 0,
+        });
+    }
+
+    /// Record a word that is done being checked, sending the errors that now have all the words following them.
+    pub fn word_checked(&mut self, mem_pos: u64, word: &[u8], sender: &flume::Sender<StatType>) {
+        let context_word = (mem_pos, word_array(word));
+        for pending_error in &mut self.pending_errors {
+            pending_error.words.push(context_word);
+            pending_error.words_after += 1;
+        }
+        let (complete, pending): (Vec<_>, Vec<_>) = self
+            .pending_errors
+            .drain(..)
+            .partition(|pending_error| pending_error.words_after >= self.context_words);
+        self.pending_errors = pending;
+        complete
+            .into_iter()
+            .for_each(|pending_error| send(sender, pending_error));
+        self.pending_errors.append(&mut self.current_word_errors);
+
+        if self.recent_words.len() == self.context_words as usize {
+            let _ = self.recent_words.pop_front();
+        }
+        self.recent_words.push_back(context_word);
+    }
+
+    /// Send all pending errors with the words seen so far, and forget the recent words, e.g. when a new CDP starts.
+    pub fn flush(&mut self, sender: &flume::Sender<StatType>) {
+        self.pending_errors
+            .drain(..)
+            .chain(self.current_word_errors.drain(..))
+            .for_each(|pending_error| send(sender, pending_error));
+        self.recent_words.clear();
+    }
+}
+
+fn send(sender: &flume::Sender<StatType>, pending_error: PendingError) {
+    // The receiver might already be gone if processing stopped early
+    let _ = sender.send(pending_error.into_stat());
+}
+
+fn word_array(word: &[u8]) -> [u8; 10] {
+    word[..10].try_into().expect("GBT word is 10 bytes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn word(id: u8) -> [u8; 10] {
+        [0, 0, 0, 0, 0, 0, 0, 0, 0, id]
+    }
+
+    fn received_messages(recv: &flume::Receiver<StatType>) -> Vec<String> {
+        recv.drain()
+            .map(|stat| match stat {
+                StatType::CheckError(check_error) => check_error.to_string(),
+                _ => panic!("Unexpected stat: {stat}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_error_with_words_before_and_after() {
+        let (send, recv) = flume::unbounded();
+        let mut error_context = ErrorContext::new(1);
+        error_context.word_checked(0x40, &word(0xE0), &send);
+        error_context.add_error(
+            CheckError::new(0x50, "[E40] ID is not 0xE8", 524, 8),
+            0x50,
+            &word(0xE4),
+        );
+        error_context.word_checked(0x50, &word(0xE4), &send);
+        assert!(recv.is_empty(), "Error is sent before the word after it");
+        error_context.word_checked(0x60, &word(0x20), &send);
+
+        assert_eq!(
+            received_messages(&recv),
+            vec![
+                "0x50: [E40] ID is not 0xE8\
+                \n\t0x40: 00 00 00 00 00 00 00 00 00 E0\
+                \n\t0x50: 00 00 00 00 00 00 00 00 00 E4 <--\
+                \n\t0x60: 00 00 00 00 00 00 00 00 00 20"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flush_sends_pending_errors() {
+        let (send, recv) = flume::unbounded();
+        let mut error_context = ErrorContext::new(2);
+        error_context.word_checked(0x40, &word(0xE0), &send);
+        error_context.word_checked(0x50, &word(0xE8), &send);
+        error_context.word_checked(0x60, &word(0x20), &send);
+        error_context.add_error(
+            CheckError::new(0x70, "[E70] ID is invalid", 524, 8),
+            0x70,
+            &word(0x01),
+        );
+        error_context.word_checked(0x70, &word(0x01), &send);
+        // The CDP ends after the offending word
+        error_context.flush(&send);
+
+        assert_eq!(
+            received_messages(&recv),
+            vec![
+                "0x70: [E70] ID is invalid\
+                \n\t0x50: 00 00 00 00 00 00 00 00 00 E8\
+                \n\t0x60: 00 00 00 00 00 00 00 00 00 20\
+                \n\t0x70: 00 00 00 00 00 00 00 00 00 01 <--"
+            ]
+        );
+        // Recent words are forgotten after a flush
+        error_context.add_error(
+            CheckError::new(0x80, "[E70] ID is invalid", 524, 8),
+            0x80,
+            &word(0x02),
+        );
+        error_context.flush(&send);
+        assert_eq!(
+            received_messages(&recv),
+            vec!["0x80: [E70] ID is invalid\n\t0x80: 00 00 00 00 00 00 00 00 00 02 <--"]
+        );
+    }
+}
@@ -15,6 +15,8 @@ pub struct ItsReadoutFrameValidator<C: CustomChecksOpt + 'static> {
     from_stave: Option<Stave>,
     // If any lane is in FATAL state (and correctly reported it through the ITS protocol), then store the lane ids here.
     fatal_lanes: Option<Vec<u8>>,
+    // Only collect ALPIDE stats from the readout frames, without checking them.
+    stats_only: bool,
 }
 
 impl<C: CustomChecksOpt> ItsReadoutFrameValidator<C> {
@@ -25,6 +27,15 @@ impl<C: CustomChecksOpt> ItsReadoutFrameValidator<C> {
             is_readout_frame: false,
             from_stave: None,
             fatal_lanes: None,
+            stats_only: false,
+        }
+    }
+
+    /// Creates a validator that only collects ALPIDE stats from the readout frames, skipping the checks on the ALPIDE data and the lanes of the frames.
+    pub fn new_stats_only(custom_checks: &'static C) -> Self {
+        Self {
+            stats_only: true,
+            ..Self::new(custom_checks)
         }
     }
 
@@ -90,12 +101,31 @@ impl<C: CustomChecksOpt> ItsReadoutFrameValidator<C> {
         debug_assert!(!self.is_readout_frame);
         debug_assert!(frame.start_mem_pos() != 0, "Frame start mem pos not set");
 
+        if self.stats_only {
+            let mut alpide_stats = if frame.is_empty() {
+                AlpideStats::default()
+            } else {
+                alpide::collect_alpide_stats(&frame)
+            };
+            log_lane_occupancy(&mut alpide_stats, &frame, status_words);
+            err_chan
+                .send(StatType::AlpideStats(alpide_stats))
+                .expect("Failed to send error to stats channel");
+            return;
+        }
+
         let mem_pos_start = frame.start_mem_pos();
         let mem_pos_end = frame.end_mem_pos();
 
         if frame.is_empty() {
             // No data in a full readout frame is a protocol error unless lanes in error has been reported by the TDT/DDW.
             self.report_empty_alpide_frame_error(&frame, err_chan, status_words, current_rdh);
+            // The frame still counts towards the lane occupancy, with no lanes present
+            let mut alpide_stats = AlpideStats::default();
+            log_lane_occupancy(&mut alpide_stats, &frame, status_words);
+            err_chan
+                .send(StatType::AlpideStats(alpide_stats))
+                .expect("Failed to send error to stats channel");
             // No data, nothing to process (and erroneous to do so) early return
             return;
         }
@@ -103,8 +133,9 @@ impl<C: CustomChecksOpt> ItsReadoutFrameValidator<C> {
         let is_ib = frame.from_layer() == Layer::Inner;
 
         // Process the data frame
-        let (lanes_in_error_ids, lane_error_msgs, alpide_stats, fatal_lanes) =
+        let (lane_errors, lane_error_msgs, mut alpide_stats, fatal_lanes) =
             alpide::check_alpide_data_frame(&frame, self.custom_checks_config);
+        log_lane_occupancy(&mut alpide_stats, &frame, status_words);
 
         // Add the fatal lanes to the running list of fatal lanes
         if let Some(new_fatal_lanes) = fatal_lanes {
@@ -133,9 +164,11 @@ impl<C: CustomChecksOpt> ItsReadoutFrameValidator<C> {
         // Format and send all errors
         if !lane_error_msgs.is_empty() {
             let err_code = if is_ib { "E74" } else { "E75" };
-            let lane_error_numbers = lanes_in_error_ids
+            let lane_error_numbers = lane_errors
                 .iter()
-                .map(|lane_id| lane_id_to_lane_number(*lane_id, is_ib))
+                .map(|(lane_id, _)| *lane_id)
+                .unique()
+                .map(|lane_id| lane_id_to_lane_number(lane_id, is_ib))
                 .collect_vec();
             let mut error_string = format!(
                 "{mem_pos_start:#X}: [{err_code}] FEE ID:{feeid} ALPIDE data frame ending at {mem_pos_end:#X} has errors in lane {lane_error_numbers:?}:", feeid=current_rdh.fee_id()
@@ -151,6 +184,14 @@ impl<C: CustomChecksOpt> ItsReadoutFrameValidator<C> {
                 .send(StatType::Error(error_string.into()))
                 .expect("Failed to send error to stats channel");
         }
+        if !lane_errors.is_empty() {
+            err_chan
+                .send(StatType::AlpideLaneErrors {
+                    fee_id: current_rdh.fee_id(),
+                    lane_errors: lane_errors.into_boxed_slice(),
+                })
+                .expect("Failed to send error to stats channel");
+        }
     }
 
     fn report_empty_alpide_frame_error(
@@ -197,3 +238,17 @@ impl<C: CustomChecksOpt> ItsReadoutFrameValidator<C> {
             .expect("Failed to send error to stats channel");
     }
 }
+
+/// Logs the lanes with data in the readout frame out of the active lanes in the last IHW
+fn log_lane_occupancy(
+    alpide_stats: &mut AlpideStats,
+    frame: &AlpideReadoutFrame,
+    status_words: &StatusWordContainer,
+) {
+    if let Some(ihw) = status_words.ihw() {
+        alpide_stats.log_lane_occupancy(
+            frame.lane_data_frames_as_slice().len() as u8,
+            ihw.active_lanes().count_ones() as u8,
+        );
+    }
+}
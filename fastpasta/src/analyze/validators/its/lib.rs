@@ -1,4 +1,6 @@
 //! Contains the [do_payload_checks] which is the entry point for the ITS specific CDP validator
+//!
+//! [validate_cdp] runs the same checks on a single CDP without any threads or channels, e.g. to test generated data.
 use crate::util::*;
 
 /// # Arguments
@@ -12,6 +14,30 @@ pub fn do_payload_checks<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt>(
 ) -> Result<(), flume::SendError<StatType>> {
     let (rdh, payload, rdh_mem_pos) = cdp;
     cdp_validator.set_current_rdh(rdh, rdh_mem_pos);
+    if rdh.data_format() == 0 {
+        let ff_padding_len = data_format_0_ff_padding_len(payload);
+        if ff_padding_len > 0 {
+            stats_send_chan.send(StatType::Warning(
+                format!(
+                    "{mem_pos:#X}: [W02] unexpected 0xFF padding in data format 0 payload ({ff_padding_len} bytes)",
+                    mem_pos = rdh_mem_pos + RDH_CRU_SIZE_BYTES as u64 + (payload.len() - ff_padding_len) as u64
+                )
+                .into(),
+            ))?;
+        }
+    }
+    if cdp_validator.strict_padding() {
+        if let Err(e) = check_payload_padding(payload) {
+            let padding_mem_pos = rdh_mem_pos
+                + RDH_CRU_SIZE_BYTES as u64
+                + (payload.len() - data_format_0_ff_padding_len(payload)) as u64;
+            stats_send_chan.send(StatType::Error(
+                format!("{padding_mem_pos:#X}: {e}: Skipping current payload").into(),
+            ))?;
+            cdp_validator.reset_fsm();
+            return Ok(());
+        }
+    }
     match preprocess_payload(payload) {
         Ok(gbt_word_chunks) => gbt_word_chunks.for_each(|gbt_word| {
             cdp_validator.check(&gbt_word[..10]); // Take 10 bytes as flavor 0 would have additional 6 bytes of padding
@@ -27,6 +53,52 @@ pub fn do_payload_checks<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt>(
     Ok(())
 }
 
+/// Validates a single CDP with a new [CdpRunningValidator] and returns the errors found in it, in the order they are found.
+///
+/// The checks are the same as [do_payload_checks], but instead of being sent through a channel the errors are collected synchronously.
+/// The validator only sees this CDP, so checks that span multiple CDPs are checked as if it were the first CDP of the data.
+///
+/// # Example
+/// ```
+/// use fastpasta::analyze::validators::its::lib::validate_cdp;
+/// # use fastpasta::config::test_util::MockConfig;
+/// # use fastpasta::config::check::{CheckCommands, CheckModeArgs, System};
+/// # use alice_protocol_reader::prelude::test_data::CORRECT_RDH_CRU_V7;
+/// # let mut config = MockConfig::new();
+/// # config.check = Some(CheckCommands::Sanity(CheckModeArgs { target: Some(System::ITS), ..Default::default() }));
+/// # let config: &'static MockConfig = Box::leak(Box::new(config));
+/// let payload = vec![0x3D; 100];
+/// let errors = validate_cdp(&CORRECT_RDH_CRU_V7, &payload, 0, config);
+/// assert!(!errors.is_empty());
+/// ```
+pub fn validate_cdp<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt>(
+    rdh: &T,
+    payload: &[u8],
+    mem_pos: u64,
+    config: &'static C,
+) -> Vec<CheckError> {
+    let (stats_send_chan, stats_recv_chan) = flume::unbounded();
+    let mut cdp_validator = CdpRunningValidator::new(config, stats_send_chan.clone());
+    // The receiver is held until the end of the function, so sending cannot fail
+    do_payload_checks(
+        (rdh, payload, mem_pos),
+        &stats_send_chan,
+        &mut cdp_validator,
+    )
+    .expect("Failed to send stats");
+    // Dropping the validator sends any errors that are held back, e.g. for error context
+    drop(cdp_validator);
+    drop(stats_send_chan);
+    stats_recv_chan
+        .drain()
+        .filter_map(|stat| match stat {
+            StatType::CheckError(check_error) => Some(*check_error),
+            StatType::Error(error_msg) => Some(CheckError::from_error_msg(&error_msg)),
+            _ => None,
+        })
+        .collect()
+}
+
 #[allow(non_camel_case_types)] // An exception to the Rust naming convention, for these words that are already acronyms
 /// ITS Payload word types
 #[derive(Debug, Clone, Copy)]
@@ -106,4 +178,27 @@ mod tests {
             println!("Stats: {stats:?}")
         }
     }
+
+    static CFG_TEST_VALIDATE_CDP: OnceLock<MockConfig> = OnceLock::new();
+
+    #[test]
+    fn test_validate_cdp() {
+        let mut mock_config = MockConfig::new();
+        mock_config.check = Some(CheckCommands::Sanity(CheckModeArgs {
+            target: Some(System::ITS),
+            ..Default::default()
+        }));
+        CFG_TEST_VALIDATE_CDP.set(mock_config).unwrap();
+        let config = CFG_TEST_VALIDATE_CDP.get().unwrap();
+        let rdh = CORRECT_RDH_CRU_V7;
+
+        let errors = validate_cdp(&rdh, &[0x3D; 100], 0x40, config);
+        assert!(!errors.is_empty());
+        for error in &errors {
+            assert!(error.message.contains("3D"), "{error}");
+            assert_eq!(error.fee_id, Some(rdh.fee_id()));
+            assert_eq!(error.link_id, Some(rdh.link_id()));
+            assert!(error.mem_pos.is_some_and(|mem_pos| mem_pos > 0x40));
+        }
+    }
 }
@@ -8,7 +8,7 @@ use ihw::IhwValidator;
 use tdh::TdhValidator;
 use tdt::TdtValidator;
 
-mod ddw;
+pub(super) mod ddw;
 mod ihw;
 pub(super) mod tdh;
 pub(super) mod tdt;
@@ -19,21 +19,21 @@ pub mod util;
 pub struct StatusWordSanityChecker;
 
 impl StatusWordSanityChecker {
-    /// Checks if argument is a valid [IHW][Ihw] status word.
-    pub fn check_ihw(ihw: &Ihw) -> Result<(), String> {
-        IhwValidator::sanity_check(ihw)
+    /// Checks if argument is a valid [IHW][Ihw] status word, the reserved bits are only checked if `include_reserved` is true.
+    pub fn check_ihw(ihw: &Ihw, include_reserved: bool) -> Result<(), String> {
+        IhwValidator::sanity_check_fields(ihw, include_reserved)
     }
-    /// Checks if argument is a valid [TDH][Tdh] status word.
-    pub fn check_tdh(tdh: &Tdh) -> Result<(), String> {
-        TdhValidator::sanity_check(tdh)
+    /// Checks if argument is a valid [TDH][Tdh] status word, the reserved bits are only checked if `include_reserved` is true.
+    pub fn check_tdh(tdh: &Tdh, include_reserved: bool) -> Result<(), String> {
+        TdhValidator::sanity_check_fields(tdh, include_reserved)
     }
-    /// Checks if argument is a valid [TDT][Tdt] status word.
-    pub fn check_tdt(tdt: &Tdt) -> Result<(), String> {
-        TdtValidator::sanity_check(tdt)
+    /// Checks if argument is a valid [TDT][Tdt] status word, the reserved bits are only checked if `include_reserved` is true.
+    pub fn check_tdt(tdt: &Tdt, include_reserved: bool) -> Result<(), String> {
+        TdtValidator::sanity_check_fields(tdt, include_reserved)
     }
-    /// Checks if argument is a valid [DDW0][Ddw0] status word.
-    pub fn check_ddw0(ddw0: &Ddw0) -> Result<(), String> {
-        Ddw0Validator::sanity_check(ddw0)
+    /// Checks if argument is a valid [DDW0][Ddw0] status word, the reserved bits are only checked if `include_reserved` is true.
+    pub fn check_ddw0(ddw0: &Ddw0, include_reserved: bool) -> Result<(), String> {
+        Ddw0Validator::sanity_check_fields(ddw0, include_reserved)
     }
 }
 
@@ -46,7 +46,15 @@ pub trait StatusWordValidator<T: StatusWord> {
     ///
     /// # Errors
     /// If a check fails, returns [Err(String)][Err] where the string describes the sanity check that failed
-    fn sanity_check(status_word: &T) -> Result<(), String>;
+    fn sanity_check(status_word: &T) -> Result<(), String> {
+        Self::sanity_check_fields(status_word, true)
+    }
+
+    /// Perform a sanity check on a [Status Word][StatusWord], the reserved bits are only checked if `include_reserved` is true.
+    ///
+    /// # Errors
+    /// If a check fails, returns [Err(String)][Err] where the string describes the sanity check that failed
+    fn sanity_check_fields(status_word: &T, include_reserved: bool) -> Result<(), String>;
 }
 
 #[cfg(test)]
@@ -70,7 +78,7 @@ mod tests {
         ];
 
         let ddw0_bad = Ddw0::load(&mut raw_ddw0_bad_index.as_slice()).unwrap();
-        StatusWordSanityChecker::check_ddw0(&ddw0_bad).unwrap();
+        StatusWordSanityChecker::check_ddw0(&ddw0_bad, true).unwrap();
     }
 
     #[test]
@@ -89,7 +97,7 @@ mod tests {
         ];
 
         let ddw0_bad = Ddw0::load(&mut raw_ddw0_bad_index.as_slice()).unwrap();
-        assert!(StatusWordSanityChecker::check_ddw0(&ddw0_bad).is_err());
+        assert!(StatusWordSanityChecker::check_ddw0(&ddw0_bad, true).is_err());
     }
 
     #[test]
@@ -108,7 +116,7 @@ mod tests {
         ];
 
         let ddw0_bad = Ddw0::load(&mut raw_ddw0_bad_index.as_slice()).unwrap();
-        let err = StatusWordSanityChecker::check_ddw0(&ddw0_bad).err();
+        let err = StatusWordSanityChecker::check_ddw0(&ddw0_bad, true).err();
         eprintln!("{:?}", err);
         assert!(err.unwrap().contains("index is not 0"));
     }
@@ -117,7 +125,7 @@ mod tests {
         let raw_data_ddw0_bad_id = [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x14];
 
         let ddw0_bad = Ddw0::load(&mut raw_data_ddw0_bad_id.as_slice()).unwrap();
-        let err = StatusWordSanityChecker::check_ddw0(&ddw0_bad).err();
+        let err = StatusWordSanityChecker::check_ddw0(&ddw0_bad, true).err();
         eprintln!("{:?}", err);
         assert!(err.unwrap().contains("ID is not 0xE4: 0x"));
     }
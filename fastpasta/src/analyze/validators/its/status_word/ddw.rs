@@ -4,12 +4,15 @@ use std::fmt::Write;
 use super::StatusWordValidator;
 use crate::words::its::status_words::{ddw::Ddw0, StatusWord};
 
+/// Number of lanes in the lane_status field of [Ddw0] and [TDT][crate::words::its::status_words::tdt::Tdt]
+const LANE_STATUS_LANES: u8 = 28;
+
 /// Validator for [Ddw0]
 #[derive(Debug, Copy, Clone)]
 pub struct Ddw0Validator;
 
 impl StatusWordValidator<Ddw0> for Ddw0Validator {
-    fn sanity_check(ddw0: &Ddw0) -> Result<(), String> {
+    fn sanity_check_fields(ddw0: &Ddw0, include_reserved: bool) -> Result<(), String> {
         let mut err_str = String::new();
 
         if ddw0.id() != Ddw0::ID {
@@ -18,7 +21,7 @@ impl StatusWordValidator<Ddw0> for Ddw0Validator {
             return Err(err_str);
         }
 
-        if !ddw0.is_reserved_0() {
+        if include_reserved && !ddw0.is_reserved_0() {
             write!(
                 err_str,
                 "reserved bits are not 0:  {:b} {:b} ",
@@ -39,6 +42,64 @@ impl StatusWordValidator<Ddw0> for Ddw0Validator {
     }
 }
 
+impl Ddw0Validator {
+    /// Checks that the lane status reported in TDTs during an HBF is reflected in the [Ddw0] closing the HBF.
+    ///
+    /// Each lane has a 2 bit status (0: OK, 1: Warning, 2: Error, 3: Fatal).
+    /// The status of a lane in the DDW0 should be at least as severe as the most severe status reported for that lane in the TDTs of the HBF.
+    ///
+    /// `tdt_lane_status` is the accumulated lane status of the TDTs, see [Ddw0Validator::max_lane_status].
+    pub fn check_lane_status_reflects_tdts(
+        ddw0: &Ddw0,
+        tdt_lane_status: u64,
+    ) -> Result<(), String> {
+        let ddw0_lane_status = ddw0.lane_status();
+        let unreflected_lanes: Vec<String> = (0..LANE_STATUS_LANES)
+            .filter_map(|lane| {
+                let tdt_severity = lane_severity(tdt_lane_status, lane);
+                let ddw0_severity = lane_severity(ddw0_lane_status, lane);
+                (tdt_severity > ddw0_severity).then(|| {
+                    format!(
+                        "lane {lane} (TDT: {tdt}, DDW0: {ddw0})",
+                        tdt = severity_as_str(tdt_severity),
+                        ddw0 = severity_as_str(ddw0_severity)
+                    )
+                })
+            })
+            .collect();
+        if unreflected_lanes.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "[E66] TDT lane error not reflected in DDW0: {}",
+                unreflected_lanes.join(", ")
+            ))
+        }
+    }
+
+    /// Returns the lane status where each lane has the most severe status of the two lane status fields.
+    pub fn max_lane_status(lane_status_a: u64, lane_status_b: u64) -> u64 {
+        (0..LANE_STATUS_LANES).fold(0, |max_lane_status, lane| {
+            let severity =
+                lane_severity(lane_status_a, lane).max(lane_severity(lane_status_b, lane));
+            max_lane_status | (severity as u64) << (lane * 2)
+        })
+    }
+}
+
+fn lane_severity(lane_status: u64, lane: u8) -> u8 {
+    ((lane_status >> (lane * 2)) & 0b11) as u8
+}
+
+fn severity_as_str(severity: u8) -> &'static str {
+    match severity {
+        0 => "OK",
+        1 => "Warning",
+        2 => "Error",
+        _ => "Fatal",
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,4 +150,46 @@ mod tests {
         println!("{ddw0:#?}");
         assert!(Ddw0Validator::sanity_check(&ddw0_new).is_ok());
     }
+
+    #[test]
+    fn test_max_lane_status() {
+        // Lane 0 warning, lane 1 error
+        let lane_status_a = 0b1001;
+        // Lane 0 fatal, lane 27 warning
+        let lane_status_b = 0b11 | 0b01 << 54;
+        assert_eq!(
+            Ddw0Validator::max_lane_status(lane_status_a, lane_status_b),
+            0b1011 | 0b01 << 54
+        );
+    }
+
+    #[test]
+    fn test_lane_status_reflects_tdts() {
+        // DDW0 with lane 0 in error and lane 1 in warning
+        let raw_data_ddw0 = [
+            0b0110,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            0x00,
+            Ddw0::ID,
+        ];
+        let ddw0 = Ddw0::load(&mut raw_data_ddw0.as_slice()).unwrap();
+
+        // Same or lower severity in the TDTs
+        assert!(Ddw0Validator::check_lane_status_reflects_tdts(&ddw0, 0b0110).is_ok());
+        assert!(Ddw0Validator::check_lane_status_reflects_tdts(&ddw0, 0b0101).is_ok());
+        assert!(Ddw0Validator::check_lane_status_reflects_tdts(&ddw0, 0).is_ok());
+
+        // Lane 1 in error and lane 3 in fatal in the TDTs
+        let err = Ddw0Validator::check_lane_status_reflects_tdts(&ddw0, 0b1100_1010).unwrap_err();
+        assert_eq!(
+            err,
+            "[E66] TDT lane error not reflected in DDW0: lane 1 (TDT: Error, DDW0: Warning), lane 3 (TDT: Fatal, DDW0: OK)"
+        );
+    }
 }
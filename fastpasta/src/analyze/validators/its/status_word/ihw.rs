@@ -9,7 +9,7 @@ use crate::words::its::status_words::{ihw::Ihw, StatusWord};
 pub struct IhwValidator;
 
 impl StatusWordValidator<Ihw> for IhwValidator {
-    fn sanity_check(ihw: &Ihw) -> Result<(), String> {
+    fn sanity_check_fields(ihw: &Ihw, include_reserved: bool) -> Result<(), String> {
         let mut err_str = String::new();
 
         if ihw.id() != Ihw::ID {
@@ -18,7 +18,7 @@ impl StatusWordValidator<Ihw> for IhwValidator {
             return Err(err_str);
         }
 
-        if !ihw.is_reserved_0() {
+        if include_reserved && !ihw.is_reserved_0() {
             write!(err_str, "reserved bits are not 0: {:2X} ", ihw.reserved()).unwrap();
         }
         if err_str.is_empty() {
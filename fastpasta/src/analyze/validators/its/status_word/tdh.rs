@@ -10,7 +10,7 @@ use crate::words::its::status_words::{tdh::Tdh, StatusWord};
 pub struct TdhValidator;
 
 impl StatusWordValidator<Tdh> for TdhValidator {
-    fn sanity_check(tdh: &Tdh) -> Result<(), String> {
+    fn sanity_check_fields(tdh: &Tdh, include_reserved: bool) -> Result<(), String> {
         let mut err_str = String::new();
 
         if tdh.id() != Tdh::ID {
@@ -19,7 +19,7 @@ impl StatusWordValidator<Tdh> for TdhValidator {
             return Err(err_str);
         }
 
-        if !tdh.is_reserved_0() {
+        if include_reserved && !tdh.is_reserved_0() {
             write!(
                 err_str,
                 "reserved bits are not 0:  {:X} {:X} {:X} ",
@@ -52,7 +52,7 @@ impl StatusWordValidator<Tdh> for TdhValidator {
 }
 
 impl TdhValidator {
-    /// Checks if the TDH trigger_bc period matches the specified value
+    /// Checks if the TDH trigger_bc period matches the specified value, and that the trigger_orbit advanced accordingly
     ///
     /// reports an error with the detected erroneous period if the check fails
     ///
@@ -77,30 +77,85 @@ impl TdhValidator {
                                     current_trigger_orbit = tdh.trigger_orbit(),
                                     current_trigger_bc = tdh.trigger_bc()
             ))
+        } else if let Err(expect_orbit) = Self::matches_trigger_orbit_advance(
+            tdh.trigger_orbit(),
+            prev_int_tdh.trigger_orbit(),
+            prev_int_tdh.trigger_bc(),
+            expect_period,
+        ) {
+            Err(format!(
+                "[E48] TDH orbit advance inconsistent with trigger period: expected orbit {expect_orbit:#X} != {current_trigger_orbit:#X}\
+                                    \n\tPrevious TDH Orbit_BC: {prev_trigger_orbit}_{prev_trigger_bc:>4}\
+                                    \n\tCurrent  TDH Orbit_BC: {current_trigger_orbit}_{current_trigger_bc:>4}",
+                                    prev_trigger_orbit = prev_int_tdh.trigger_orbit(),
+                                    prev_trigger_bc = prev_int_tdh.trigger_bc(),
+                                    current_trigger_orbit = tdh.trigger_orbit(),
+                                    current_trigger_bc = tdh.trigger_bc()
+            ))
         } else {
             Ok(())
         }
     }
 
-    /// Checks if the period between two TDH trigger_bc values matches a specified value
+    /// Checks if the trigger_orbit advanced as expected from the previous TDH and the specified trigger period
     ///
-    /// returns an error with the detected erroneous period if the check fails
+    /// returns an error with the expected orbit if the check fails
     ///
-    /// The check is only applicable to consecutive TDHs with internal_trigger set.
+    /// A trigger is positioned at the combined bunch crossing `orbit * (MAX_BC + 1) + bc`.
+    /// Advancing by the period `P` from the previous trigger, the orbit therefor has to advance by
+    /// `(prev_bc + P) / (MAX_BC + 1)` (integer division), meaning the orbit is the same unless the bc wraps around.
+    ///
+    /// A period of 0 passes the trigger_bc check when triggers are at the same bc in consecutive orbits, it is therefor treated as a full orbit.
     #[inline]
-    fn matches_trigger_interval(
-        current_trg_bc: u16,
+    fn matches_trigger_orbit_advance(
+        current_trg_orbit: u32,
+        previous_trg_orbit: u32,
         previous_trg_bc: u16,
         specified_period: u16,
-    ) -> Result<(), u16> {
-        let detected_period = if current_trg_bc < previous_trg_bc {
+    ) -> Result<(), u32> {
+        let bcs_per_orbit = Tdh::MAX_BC as u32 + 1;
+        let period = if specified_period == 0 {
+            bcs_per_orbit
+        } else {
+            specified_period as u32
+        };
+        let orbit_advance = (previous_trg_bc as u32 + period) / bcs_per_orbit;
+        // The orbit counter wraps around at u32::MAX
+        let expect_orbit = previous_trg_orbit.wrapping_add(orbit_advance);
+        if current_trg_orbit == expect_orbit {
+            Ok(())
+        } else {
+            Err(expect_orbit)
+        }
+    }
+
+    /// Calculates the trigger period between two TDH trigger_bc values, accounting for the trigger_bc wrapping around
+    ///
+    /// Only meaningful for consecutive TDHs with internal_trigger set.
+    #[inline]
+    pub fn detected_trigger_period(current_trg_bc: u16, previous_trg_bc: u16) -> u16 {
+        if current_trg_bc < previous_trg_bc {
             // Bunch Crossing ID wrapped around
             // +1 cause of incrementing the Orbit counter for the rollover
             let distance_to_max = Tdh::MAX_BC - previous_trg_bc + 1;
             distance_to_max + current_trg_bc
         } else {
             current_trg_bc - previous_trg_bc
-        };
+        }
+    }
+
+    /// Checks if the period between two TDH trigger_bc values matches a specified value
+    ///
+    /// returns an error with the detected erroneous period if the check fails
+    ///
+    /// The check is only applicable to consecutive TDHs with internal_trigger set.
+    #[inline]
+    fn matches_trigger_interval(
+        current_trg_bc: u16,
+        previous_trg_bc: u16,
+        specified_period: u16,
+    ) -> Result<(), u16> {
+        let detected_period = Self::detected_trigger_period(current_trg_bc, previous_trg_bc);
         if detected_period == specified_period {
             Ok(())
         } else {
@@ -121,6 +176,22 @@ impl TdhValidator {
         Ok(())
     }
 
+    /// Checks that the TDH trigger_orbit did not decrease from the previous TDH, when the TDH starts a new readout frame
+    ///
+    /// Valid if current TDH trigger_orbit >= previous TDH trigger_orbit, the orbit counter wrapping around at [u32::MAX] is not a decrease.
+    #[inline]
+    pub fn check_trigger_orbit_not_decreasing(tdh: &Tdh, prev_tdh: &Tdh) -> Result<(), String> {
+        // The distance as a signed value is negative if the orbit went back, while wrapping around gives a small positive distance
+        if (tdh.trigger_orbit().wrapping_sub(prev_tdh.trigger_orbit()) as i32) < 0 {
+            return Err(format!(
+                "[E446] TDH trigger_orbit is decreasing, previous: {:#X}, current: {:#X}.",
+                prev_tdh.trigger_orbit(),
+                tdh.trigger_orbit()
+            ));
+        }
+        Ok(())
+    }
+
     /// Checks TDH fields: continuation, orbit, when the TDH immediately follows an IHW.
     ///
     /// If any checks fail, returns `Err(Vec<ErrMsgs>)`
@@ -259,4 +330,94 @@ mod tests {
         println!("{err:?}");
         assert!(err.unwrap().contains("ID is not 0xE8: 0x"));
     }
+
+    /// Make a TDH with internal_trigger set and the specified orbit and bc
+    fn internal_trigger_tdh(orbit: u32, bc: u16) -> Tdh {
+        let [bc_lsb, bc_msb] = bc.to_le_bytes();
+        let [o0, o1, o2, o3] = orbit.to_le_bytes();
+        let raw_tdh = [0x00, 0x10, bc_lsb, bc_msb, o0, o1, o2, o3, 0x00, Tdh::ID];
+        Tdh::load(&mut raw_tdh.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_detected_trigger_period() {
+        assert_eq!(TdhValidator::detected_trigger_period(298, 100), 198);
+        // bc wraps around
+        assert_eq!(
+            TdhValidator::detected_trigger_period(98, Tdh::MAX_BC - 99),
+            198
+        );
+        assert_eq!(TdhValidator::detected_trigger_period(100, 100), 0);
+    }
+
+    #[test]
+    fn test_trigger_interval_orbit_advance_across_bc_wrap() {
+        let period = 200;
+        let prev_tdh = internal_trigger_tdh(0x10, Tdh::MAX_BC - 99);
+        // bc wraps around, so the orbit has to increment
+        let tdh = internal_trigger_tdh(0x11, 100);
+        assert!(TdhValidator::check_trigger_interval(&tdh, &prev_tdh, period).is_ok());
+        // No bc wrap, orbit should be the same
+        let next_tdh = internal_trigger_tdh(0x11, 300);
+        assert!(TdhValidator::check_trigger_interval(&next_tdh, &tdh, period).is_ok());
+    }
+
+    #[test]
+    fn test_trigger_interval_orbit_not_advanced_across_bc_wrap() {
+        let period = 200;
+        let prev_tdh = internal_trigger_tdh(0x10, Tdh::MAX_BC - 99);
+        // The bc period matches but the orbit didn't increment when bc wrapped around
+        let tdh = internal_trigger_tdh(0x10, 100);
+        let err = TdhValidator::check_trigger_interval(&tdh, &prev_tdh, period).unwrap_err();
+        println!("{err}");
+        assert!(err.starts_with(
+            "[E48] TDH orbit advance inconsistent with trigger period: expected orbit 0x11 != 0x10"
+        ));
+    }
+
+    #[test]
+    fn test_trigger_interval_orbit_advanced_without_bc_wrap() {
+        let period = 200;
+        let prev_tdh = internal_trigger_tdh(0x10, 100);
+        let tdh = internal_trigger_tdh(0x11, 300);
+        let err = TdhValidator::check_trigger_interval(&tdh, &prev_tdh, period).unwrap_err();
+        assert!(err.starts_with("[E48]"));
+    }
+
+    #[test]
+    fn test_trigger_interval_period_0_is_one_orbit() {
+        let prev_tdh = internal_trigger_tdh(0x200, 0);
+        let tdh = internal_trigger_tdh(0x201, 0);
+        assert!(TdhValidator::check_trigger_interval(&tdh, &prev_tdh, 0).is_ok());
+        let same_orbit_tdh = internal_trigger_tdh(0x201, 0);
+        assert!(TdhValidator::check_trigger_interval(&same_orbit_tdh, &tdh, 0).is_err());
+    }
+
+    #[test]
+    fn test_trigger_interval_orbit_counter_wraps() {
+        let period = 200;
+        let prev_tdh = internal_trigger_tdh(u32::MAX, Tdh::MAX_BC);
+        let tdh = internal_trigger_tdh(0, 199);
+        assert!(TdhValidator::check_trigger_interval(&tdh, &prev_tdh, period).is_ok());
+    }
+
+    #[test]
+    fn test_trigger_orbit_not_decreasing() {
+        let prev_tdh = internal_trigger_tdh(0x10, 100);
+        assert!(TdhValidator::check_trigger_orbit_not_decreasing(&prev_tdh, &prev_tdh).is_ok());
+        let tdh = internal_trigger_tdh(0x11, 0);
+        assert!(TdhValidator::check_trigger_orbit_not_decreasing(&tdh, &prev_tdh).is_ok());
+        assert_eq!(
+            TdhValidator::check_trigger_orbit_not_decreasing(&prev_tdh, &tdh).unwrap_err(),
+            "[E446] TDH trigger_orbit is decreasing, previous: 0x11, current: 0x10."
+        );
+    }
+
+    #[test]
+    fn test_trigger_orbit_wraps_around() {
+        let prev_tdh = internal_trigger_tdh(u32::MAX, 100);
+        let tdh = internal_trigger_tdh(0x2, 100);
+        assert!(TdhValidator::check_trigger_orbit_not_decreasing(&tdh, &prev_tdh).is_ok());
+        assert!(TdhValidator::check_trigger_orbit_not_decreasing(&prev_tdh, &tdh).is_err());
+    }
 }
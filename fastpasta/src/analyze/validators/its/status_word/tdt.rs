@@ -8,7 +8,7 @@ use std::fmt::Write;
 pub(super) struct TdtValidator;
 
 impl StatusWordValidator<Tdt> for TdtValidator {
-    fn sanity_check(tdt: &Tdt) -> Result<(), String> {
+    fn sanity_check_fields(tdt: &Tdt, include_reserved: bool) -> Result<(), String> {
         let mut err_str = String::new();
         if tdt.id() != Tdt::ID {
             write!(err_str, "ID is not 0xF0: {:#2X} ", tdt.id()).unwrap();
@@ -16,7 +16,7 @@ impl StatusWordValidator<Tdt> for TdtValidator {
             return Err(err_str);
         }
 
-        if !tdt.is_reserved_0() {
+        if include_reserved && !tdt.is_reserved_0() {
             write!(err_str, "reserved bits are not 0").unwrap();
         }
 
@@ -40,6 +40,8 @@ pub struct StatusWordContainer {
     tdt: Option<Tdt>,
     ddw0: Option<Ddw0>,
     cdw: Option<Cdw>,
+    // If the reserved bits are part of the sanity checks, i.e. not checked separately
+    sanity_check_reserved: bool,
 }
 
 impl StatusWordContainer {
@@ -55,12 +57,19 @@ impl StatusWordContainer {
             tdt: None,
             ddw0: None,
             cdw: None,
+            sanity_check_reserved: true,
         }
     }
 
+    /// Exclude the reserved bits from the sanity checks, e.g. if they are checked separately
+    pub const fn without_reserved_sanity_checks(mut self) -> Self {
+        self.sanity_check_reserved = false;
+        self
+    }
+
     /// Perform sanity check on a [TDH][Tdh]
     pub fn sanity_check_tdh(&self, tdh: &Tdh) -> Result<(), String> {
-        StatusWordSanityChecker::check_tdh(tdh)
+        StatusWordSanityChecker::check_tdh(tdh, self.sanity_check_reserved)
     }
 
     /// Replace the stored [TDH][Tdh] with a new [TDH][Tdh]
@@ -88,7 +97,7 @@ impl StatusWordContainer {
 
     /// Checks if argument is a valid [TDT][Tdt] status word.
     pub fn sanity_check_tdt(&self, tdt: &Tdt) -> Result<(), String> {
-        StatusWordSanityChecker::check_tdt(tdt)
+        StatusWordSanityChecker::check_tdt(tdt, self.sanity_check_reserved)
     }
 
     /// Replace the stored [TDT][Tdt] with a new [TDT][Tdt]
@@ -104,7 +113,7 @@ impl StatusWordContainer {
 
     /// Checks if argument is a valid [IHW][Ihw] status word.
     pub fn sanity_check_ihw(&self, ihw: &Ihw) -> Result<(), String> {
-        StatusWordSanityChecker::check_ihw(ihw)
+        StatusWordSanityChecker::check_ihw(ihw, self.sanity_check_reserved)
     }
 
     /// Replace the stored [IHW][Ihw] with a new [IHW][Ihw]
@@ -120,7 +129,7 @@ impl StatusWordContainer {
 
     /// Checks if argument is a valid [DDW0][Ddw0] status word.
     pub fn sanity_check_ddw0(&self, ddw0: &Ddw0) -> Result<(), String> {
-        StatusWordSanityChecker::check_ddw0(ddw0)
+        StatusWordSanityChecker::check_ddw0(ddw0, self.sanity_check_reserved)
     }
 
     /// Replace the stored [DDW][Ddw0] with a new [DDW][Ddw0]
@@ -139,6 +148,11 @@ impl StatusWordContainer {
         self.cdw = Some(cdw);
     }
 
+    /// Clears the stored [CDW][Cdw], e.g. at the end of a calibration block
+    pub fn clear_cdw(&mut self) {
+        self.cdw = None;
+    }
+
     /// Get a reference to the stored [CDW][Cdw].
     /// Returns [None] if no [CDW][Cdw] was set (seen in the data) yet.
     pub fn cdw(&self) -> Option<&Cdw> {
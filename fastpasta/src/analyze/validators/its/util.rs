@@ -1,30 +1,46 @@
-use crate::stats::StatType;
+use crate::stats::{check_error::CheckError, StatType};
+use alice_protocol_reader::prelude::RDH;
 
 /// Helper function to format and report an error in ITS protocol
 ///
 /// Takes in the error string slice and the word slice
 /// Adds the memory position to the error string
-/// Sends the error to the stats channel
+/// Sends the error to the stats channel as a [CheckError] attributed to the FEE and link ID of the CDP
 #[inline]
 pub(super) fn report_error(
     mem_pos: u64,
     err: &str,
     word_slice: &[u8],
+    rdh: &impl RDH,
     sender: &flume::Sender<StatType>,
 ) {
     sender
-            .send(StatType::Error(format!(
-                "{mem_pos:#X}: {err} [{:02X} {:02X} {:02X} {:02X} {:02X} {:02X} {:02X} {:02X} {:02X} {:02X}]",
-                word_slice[0],
-                word_slice[1],
-                word_slice[2],
-                word_slice[3],
-                word_slice[4],
-                word_slice[5],
-                word_slice[6],
-                word_slice[7],
-                word_slice[8],
-                word_slice[9],
-                            ).into()))
-            .expect("Failed to send error to stats channel");
+        .send(StatType::CheckError(Box::new(check_error(
+            mem_pos, err, word_slice, rdh,
+        ))))
+        .expect("Failed to send error to stats channel");
+}
+
+/// Helper function to create a [CheckError] in ITS protocol with the word slice appended to the error string
+#[inline]
+pub(super) fn check_error(
+    mem_pos: u64,
+    err: &str,
+    word_slice: &[u8],
+    rdh: &impl RDH,
+) -> CheckError {
+    let err_with_word = format!(
+        "{err} [{:02X} {:02X} {:02X} {:02X} {:02X} {:02X} {:02X} {:02X} {:02X} {:02X}]",
+        word_slice[0],
+        word_slice[1],
+        word_slice[2],
+        word_slice[3],
+        word_slice[4],
+        word_slice[5],
+        word_slice[6],
+        word_slice[7],
+        word_slice[8],
+        word_slice[9],
+    );
+    CheckError::new(mem_pos, &err_with_word, rdh.fee_id(), rdh.link_id())
 }
@@ -1,18 +1,25 @@
 //! Contains utility functions for preprocessing the payload
+//!
+//! [preprocess_payload] is the entry point used by all the payload validators and views, and is also meant for library users that want to iterate over the GBT words of a payload.
+//! It detects the [DataFormat] of the payload with [detect_payload_data_format] and leaves out the end of payload 0xFF padding.
+//! [check_payload_padding] verifies that the padding is consistent with the data format, which is otherwise only asserted in debug builds.
 
 use crate::util::*;
 
-#[derive(Debug, PartialEq, Clone, Copy)]
-enum DataFormat {
+/// The data format of a payload, as detected from the padding of the GBT words.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DataFormat {
+    /// Data format 0: Each GBT word is padded to 16 bytes with 6 bytes of 0x00
     V0,
+    /// Data format 2: GBT words are not padded, the payload is padded to a multiple of 16 bytes with 0xFF
     V2,
 }
 
-/// Utility function to preprocess the payload and return an iterator over the GBT words
+/// Preprocess the payload and return an iterator over the GBT words
 ///
 /// Consists of the following steps:
 /// 1. Extract the end of payload 0xFF padding
-/// 2. Determine if padding is flavor 0 (6 bytes of 0x00 padding following GBT words) or flavor 1 (no padding)
+/// 2. Determine if padding is flavor 0 (6 bytes of 0x00 padding following GBT words) or flavor 1 (no padding) with [detect_payload_data_format]
 /// 3. Split the payload into GBT words sized slices, using chunks_exact to allow more compiler optimizations
 ///
 /// Arguments:
@@ -21,34 +28,56 @@ enum DataFormat {
 ///
 /// Returns:
 ///
-/// * An iterator over the GBT words
+/// * An iterator over the GBT words, each 16 bytes in data format 0 (10 bytes of the GBT word and 6 bytes of padding), and 10 bytes in data format 2
+/// * An error if the end of payload 0xFF padding is more than 15 bytes
+///
+/// # Example
+/// ```
+/// use fastpasta::analyze::validators::lib::preprocess_payload;
+///
+/// // Data format 2 payload with an IHW and a TDH, padded with 0xFF to 32 bytes
+/// let mut payload = vec![0x01; 20];
+/// payload[9] = 0xE0;
+/// payload[19] = 0xE8;
+/// payload.extend([0xFF; 12]);
+///
+/// let word_ids: Vec<u8> = preprocess_payload(&payload)
+///     .unwrap()
+///     .map(|gbt_word| gbt_word[9])
+///     .collect();
+/// assert_eq!(word_ids, [0xE0, 0xE8]);
+/// ```
 pub fn preprocess_payload(payload: &[u8]) -> Result<ChunksExact<'_, u8>, String> {
-    let ff_padding = extract_payload_ff_padding(payload)?;
+    let ff_padding_len = extract_payload_ff_padding_len(payload)?;
 
-    // Determine if padding is flavor 0 (6 bytes of 0x00 padding following GBT words) or flavor 1 (no padding)
     let detected_data_format = detect_payload_data_format(payload);
 
-    let gbt_word_chunks = chunkify_payload(payload, detected_data_format, &ff_padding);
+    let gbt_word_chunks = chunkify_payload(payload, detected_data_format, ff_padding_len);
     Ok(gbt_word_chunks)
 }
 
-/// Retrieve end of payload 0xFF padding, if it is more than 15 bytes, return an error
-fn extract_payload_ff_padding(payload: &[u8]) -> Result<Vec<&u8>, String> {
-    let ff_padding = payload
-        .iter()
-        .rev()
-        .take_while(|&x| *x == 0xFF)
-        .collect::<Vec<_>>();
+/// Returns the number of 0xFF padding bytes at the end of a data format 0 payload.
+///
+/// Data format 0 pads each GBT word to 16 bytes with 0x00, so end of payload 0xFF padding (used in data format 2) is not expected.
+pub fn data_format_0_ff_padding_len(payload: &[u8]) -> usize {
+    payload.iter().rev().take_while(|&x| *x == 0xFF).count()
+}
+
+/// Retrieve the length of the end of payload 0xFF padding, if it is more than 15 bytes, return an error
+fn extract_payload_ff_padding_len(payload: &[u8]) -> Result<usize, String> {
+    let ff_padding_len = data_format_0_ff_padding_len(payload);
     // Exceeds the maximum padding of 15 bytes that is required to pad to 16 bytes
-    if ff_padding.len() > 15 {
-        return Err(format!("End of payload 0xFF padding is {} bytes, exceeding max of 15 bytes: Skipping current payload",
-        ff_padding.len()));
+    if ff_padding_len > 15 {
+        return Err(format!("End of payload 0xFF padding is {ff_padding_len} bytes, exceeding max of 15 bytes: Skipping current payload"));
     }
-    Ok(ff_padding)
+    Ok(ff_padding_len)
 }
 
-/// Determine if padding is flavor 0 (6 bytes of 0x00 padding following GBT words) or flavor 1 (no padding)
-fn detect_payload_data_format(payload: &[u8]) -> DataFormat {
+/// Detects the [DataFormat] of a payload from the padding of the first GBT word.
+///
+/// If the first GBT word is followed by 6 bytes of 0x00 padding, the payload is [DataFormat::V0], otherwise [DataFormat::V2].
+/// Payloads that are too short to contain the padding, including empty payloads, are [DataFormat::V2].
+pub fn detect_payload_data_format(payload: &[u8]) -> DataFormat {
     // Using an iterator approach instead of indexing also supports the case where the payload is smaller than 16 bytes or even empty
     if payload
         .iter()
@@ -68,32 +97,70 @@ fn detect_payload_data_format(payload: &[u8]) -> DataFormat {
     }
 }
 
+/// Checks that the end of payload 0xFF padding is consistent with the [DataFormat] of the payload, used with `--strict-padding`
+///
+/// * Data format 0: The payload before the padding is a whole number of 16 byte GBT words
+/// * Data format 2: The payload before the padding is a whole number of 10 byte GBT words, and is padded to a multiple of 16 bytes
+///
+/// Returns an error message if the padding is malformed, or if it exceeds 15 bytes.
+pub fn check_payload_padding(payload: &[u8]) -> Result<(), String> {
+    let ff_padding_len = extract_payload_ff_padding_len(payload)?;
+    let data_format = detect_payload_data_format(payload);
+    check_whole_gbt_words(payload.len(), data_format, ff_padding_len)?;
+    if data_format == DataFormat::V2 {
+        let data_len = payload.len() - ff_padding_len;
+        let expected_padding_len = data_len.next_multiple_of(16) - data_len;
+        if ff_padding_len != expected_padding_len {
+            return Err(format!("[E90] End of payload 0xFF padding is {ff_padding_len} bytes, expected {expected_padding_len} bytes to pad {data_len} bytes of GBT words to a multiple of 16 bytes"));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the payload before the end of payload 0xFF padding is a whole number of GBT words of the [DataFormat]
+fn check_whole_gbt_words(
+    payload_len: usize,
+    data_format: DataFormat,
+    ff_padding_len: usize,
+) -> Result<(), String> {
+    let gbt_word_size = match data_format {
+        DataFormat::V0 => 16,
+        DataFormat::V2 => 10,
+    };
+    let data_len = payload_len - ff_padding_len;
+    if data_len % gbt_word_size != 0 {
+        return Err(format!("[E90] Payload before the end of payload 0xFF padding is {data_len} bytes, not a multiple of the {gbt_word_size} byte GBT words of data format {}", match data_format {
+            DataFormat::V0 => 0,
+            DataFormat::V2 => 2,
+        }));
+    }
+    Ok(())
+}
+
 /// Splits a payload into GBT words sized slices, using chunks_exact to allow more compiler optimizations
+///
+/// The payload is expected to be a whole number of GBT words followed by 0xFF padding, which is only asserted in debug builds, see [check_payload_padding]
 fn chunkify_payload<'a>(
     payload: &'a [u8],
     data_format: DataFormat,
-    ff_padding: &[&'a u8],
+    ff_padding_len: usize,
 ) -> ChunksExact<'a, u8> {
+    debug_assert!(check_whole_gbt_words(payload.len(), data_format, ff_padding_len).is_ok());
     match data_format {
         DataFormat::V0 => {
-            let chunks = payload.chunks_exact(16);
-            // If dataformat 0, dividing into 16 byte chunks should cut the payload up with no remainder
-            debug_assert!(chunks.remainder().is_empty());
-            chunks
+            // 0xFF padding is not expected in dataformat 0, but if it is present, it should not be processed as part of a GBT word
+            let last_idx_before_padding = payload.len() - ff_padding_len;
+            payload[..last_idx_before_padding].chunks_exact(16)
         }
         DataFormat::V2 => {
             // If dataformat 2, and the padding is more than 9 bytes, padding will be processed as a GBT word, therefor exclude it from the slice
             //    Before calling chunks_exact
-            if ff_padding.len() > 9 {
-                let last_idx_before_padding = payload.len() - ff_padding.len();
-                let chunks = payload[..last_idx_before_padding].chunks_exact(10);
-                debug_assert!(chunks.remainder().is_empty());
-                chunks
+            if ff_padding_len > 9 {
+                let last_idx_before_padding = payload.len() - ff_padding_len;
+                payload[..last_idx_before_padding].chunks_exact(10)
             } else {
-                // Simply divide into 10 byte chunks and assert that the remainder is padding bytes
-                let chunks = payload.chunks_exact(10);
-                debug_assert!(chunks.remainder().iter().all(|&x| x == 0xFF)); // Asserts that the payload padding is 0xFF
-                chunks
+                // Simply divide into 10 byte chunks, the remainder is padding bytes
+                payload.chunks_exact(10)
             }
         }
     }
@@ -117,13 +184,26 @@ mod tests {
 
     #[test]
     fn test_extract_payload_padding() {
-        let end_payload_flavor_0_padding =
-            extract_payload_ff_padding(&END_PAYLOAD_FLAVOR_0).unwrap();
-        let end_payload_flavor_2_padding =
-            extract_payload_ff_padding(&END_PAYLOAD_FLAVOR_2).unwrap();
+        let end_payload_flavor_0_padding_len =
+            extract_payload_ff_padding_len(&END_PAYLOAD_FLAVOR_0).unwrap();
+        let end_payload_flavor_2_padding_len =
+            extract_payload_ff_padding_len(&END_PAYLOAD_FLAVOR_2).unwrap();
+
+        assert_eq!(end_payload_flavor_0_padding_len, 0);
+        assert_eq!(end_payload_flavor_2_padding_len, 6);
+        assert!(extract_payload_ff_padding_len(&[0xFF; 16]).is_err());
+    }
 
-        assert!(end_payload_flavor_0_padding.is_empty());
-        assert_eq!(end_payload_flavor_2_padding.len(), 6);
+    #[test]
+    fn test_data_format_0_ff_padding() {
+        assert_eq!(data_format_0_ff_padding_len(&START_PAYLOAD_FLAVOR_0), 0);
+        assert_eq!(data_format_0_ff_padding_len(&END_PAYLOAD_FLAVOR_0), 0);
+
+        let padded_payload = [START_PAYLOAD_FLAVOR_0.as_slice(), &[0xFF; 6]].concat();
+        assert_eq!(data_format_0_ff_padding_len(&padded_payload), 6);
+        // The padding is not processed as part of a GBT word
+        let gbt_words = preprocess_payload(&padded_payload).unwrap();
+        assert_eq!(gbt_words.count(), 2);
     }
 
     #[test]
@@ -133,5 +213,34 @@ mod tests {
 
         assert_eq!(detected_data_format_f0, DataFormat::V0);
         assert_eq!(detected_data_format_f2, DataFormat::V2);
+        assert_eq!(detect_payload_data_format(&[]), DataFormat::V2);
+    }
+
+    #[test]
+    fn test_check_payload_padding() {
+        // IHW and TDH, padded to 32 bytes
+        let mut payload_f2 = vec![0x01; 20];
+        payload_f2[9] = 0xE0;
+        payload_f2[19] = 0xE8;
+        payload_f2.extend([0xFF; 12]);
+        assert!(check_payload_padding(&payload_f2).is_ok());
+        assert!(check_payload_padding(&START_PAYLOAD_FLAVOR_0).is_ok());
+
+        // Padding of 6 bytes does not pad the 20 bytes of GBT words to a multiple of 16 bytes
+        let short_padding = &payload_f2[..26];
+        let err = check_payload_padding(short_padding).unwrap_err();
+        assert!(err.starts_with("[E90]"), "{err}");
+        assert!(err.contains("expected 12 bytes"), "{err}");
+
+        // A partial GBT word before the padding
+        let mut partial_word = payload_f2[..20].to_vec();
+        partial_word.extend([0x01, 0x02, 0xFF, 0xFF]);
+        assert!(check_payload_padding(&partial_word)
+            .unwrap_err()
+            .contains("not a multiple of the 10 byte GBT words"));
+        let partial_word_f0 = [START_PAYLOAD_FLAVOR_0.as_slice(), &[0x01; 4]].concat();
+        assert!(check_payload_padding(&partial_word_f0)
+            .unwrap_err()
+            .contains("of data format 0"));
     }
 }
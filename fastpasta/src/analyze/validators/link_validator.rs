@@ -24,9 +24,58 @@ pub struct LinkValidator<T: RDH, C: ChecksOpt + FilterOpt + CustomChecksOpt + Ut
     /// Consumer channel to receive data from.
     data_recv_chan: crossbeam_channel::Receiver<CdpTuple<T>>,
     its_cdp_validator: CdpRunningValidator<T, C>,
+    ft0_validator: Ft0RunningValidator,
     rdh_running_validator: RdhCruRunningChecker<T>,
     rdh_sanity_validator: RdhCruSanityValidator<T>,
     prev_rdhs: ConstGenericRingBuffer<T, 2>,
+    /// Memory position of the most recent of the previous [RDH]s
+    prev_rdh_mem_pos: Option<u64>,
+    /// Set once the link reached the maximum errors per link, the rest of its data is not checked
+    max_errors_reached: bool,
+    /// Counts the errors of the link if the maximum errors per link is set.
+    ///
+    /// Declared after the subvalidators so it is dropped after them, and still forwards the stats they send when dropped.
+    error_counter: Option<LinkErrorCounter>,
+}
+
+/// Forwards the stats sent by the subvalidators of a link, counting the errors to enforce the maximum errors per link.
+struct LinkErrorCounter {
+    link_stats_recv: flume::Receiver<StatType>,
+    stats_send: flume::Sender<StatType>,
+    errors: u32,
+}
+
+impl LinkErrorCounter {
+    /// Creates the counter and the sender channel the subvalidators should send their stats through.
+    fn new(stats_send: flume::Sender<StatType>) -> (Self, flume::Sender<StatType>) {
+        let (link_stats_send, link_stats_recv) = flume::unbounded();
+        (
+            Self {
+                link_stats_recv,
+                stats_send,
+                errors: 0,
+            },
+            link_stats_send,
+        )
+    }
+
+    /// Forwards the stats sent since the last call, and returns the total errors of the link.
+    fn forward(&mut self) -> u32 {
+        for stat in self.link_stats_recv.try_iter() {
+            if matches!(stat, StatType::Error(_) | StatType::CheckError(_)) {
+                self.errors += 1;
+            }
+            // The receiver might already be gone if processing stopped early
+            _ = self.stats_send.send(stat);
+        }
+        self.errors
+    }
+}
+
+impl Drop for LinkErrorCounter {
+    fn drop(&mut self) {
+        _ = self.forward();
+    }
 }
 
 type CdpTuple<T> = (T, Vec<u8>, u64);
@@ -40,6 +89,8 @@ impl<T: RDH, C: 'static + ChecksOpt + FilterOpt + CustomChecksOpt + UtilOpt> Lin
         stats_send_chan: flume::Sender<StatType>,
     ) -> (Self, crossbeam_channel::Sender<CdpTuple<T>>) {
         let rdh_sanity_validator = RdhCruSanityValidator::new_from_config(global_config);
+        let (error_counter, stats_send_chan) =
+            Self::init_error_counter(global_config, stats_send_chan);
 
         let (data_send, data_recv) = crossbeam_channel::unbounded();
         (
@@ -47,18 +98,22 @@ impl<T: RDH, C: 'static + ChecksOpt + FilterOpt + CustomChecksOpt + UtilOpt> Lin
                 config: global_config,
                 running_checks: match global_config.check().unwrap() {
                     CheckCommands::All(_) => true,
-                    CheckCommands::Sanity(_) => false,
+                    CheckCommands::Sanity(_) | CheckCommands::Ordering(_) => false,
                 },
 
                 stats_send: stats_send_chan.clone(),
                 data_recv_chan: data_recv,
                 its_cdp_validator: its::cdp_running::CdpRunningValidator::new(
                     global_config,
-                    stats_send_chan,
+                    stats_send_chan.clone(),
                 ),
+                ft0_validator: Ft0RunningValidator::new(global_config, stats_send_chan),
                 rdh_running_validator: RdhCruRunningChecker::default(),
                 rdh_sanity_validator,
                 prev_rdhs: ConstGenericRingBuffer::<_, 2>::new(),
+                prev_rdh_mem_pos: None,
+                max_errors_reached: false,
+                error_counter,
             },
             data_send,
         )
@@ -73,6 +128,8 @@ impl<T: RDH, C: 'static + ChecksOpt + FilterOpt + CustomChecksOpt + UtilOpt> Lin
         chan_capacity: Option<usize>,
     ) -> (Self, crossbeam_channel::Sender<CdpTuple<T>>) {
         let rdh_sanity_validator = RdhCruSanityValidator::new_from_config(global_config);
+        let (error_counter, stats_send_chan) =
+            Self::init_error_counter(global_config, stats_send_chan);
 
         // Capacity of the channel (FIFO) to Link Validator threads in terms of CDPs (RDH, Payload, Memory position)
         //
@@ -89,27 +146,64 @@ impl<T: RDH, C: 'static + ChecksOpt + FilterOpt + CustomChecksOpt + UtilOpt> Lin
                 config: global_config,
                 running_checks: match global_config.check().unwrap() {
                     CheckCommands::All(_) => true,
-                    CheckCommands::Sanity(_) => false,
+                    CheckCommands::Sanity(_) | CheckCommands::Ordering(_) => false,
                 },
 
                 stats_send: stats_send_chan.clone(),
                 data_recv_chan: data_recv,
                 its_cdp_validator: its::cdp_running::CdpRunningValidator::new(
                     global_config,
-                    stats_send_chan,
+                    stats_send_chan.clone(),
                 ),
+                ft0_validator: Ft0RunningValidator::new(global_config, stats_send_chan),
                 rdh_running_validator: RdhCruRunningChecker::default(),
                 rdh_sanity_validator,
                 prev_rdhs: ConstGenericRingBuffer::<_, 2>::new(),
+                prev_rdh_mem_pos: None,
+                max_errors_reached: false,
+                error_counter,
             },
             data_send,
         )
     }
 
+    /// If a maximum of errors per link is set, returns a [LinkErrorCounter] and the sender channel to it, otherwise the sender channel is returned as is.
+    fn init_error_counter(
+        global_config: &'static C,
+        stats_send_chan: flume::Sender<StatType>,
+    ) -> (Option<LinkErrorCounter>, flume::Sender<StatType>) {
+        if global_config.max_errors_per_link().is_some() {
+            let (error_counter, link_stats_send) = LinkErrorCounter::new(stats_send_chan);
+            (Some(error_counter), link_stats_send)
+        } else {
+            (None, stats_send_chan)
+        }
+    }
+
     /// Event loop where data is received and validation starts
     pub fn run(&mut self) {
         while let Ok(cdp) = self.data_recv_chan.recv() {
+            // Keep receiving the data of the link after reaching the maximum errors, so the dispatcher can still send it
+            if self.max_errors_reached {
+                continue;
+            }
+            let (link_id, rdh_mem_pos) = (cdp.0.link_id(), cdp.2);
             self.do_checks(cdp);
+            self.check_max_errors_per_link(link_id, rdh_mem_pos);
+        }
+    }
+
+    /// Forwards the stats of the checks, and stops checking the link if it reached the maximum errors per link.
+    fn check_max_errors_per_link(&mut self, link_id: u8, rdh_mem_pos: u64) {
+        let (Some(error_counter), Some(max_errors)) = (
+            self.error_counter.as_mut(),
+            self.config.max_errors_per_link(),
+        ) else {
+            return;
+        };
+        if error_counter.forward() >= max_errors {
+            self.max_errors_reached = true;
+            log::warn!("{rdh_mem_pos:#X}: Link {link_id} reached the maximum of {max_errors} errors per link, the rest of its data is not checked");
         }
     }
 
@@ -129,34 +223,72 @@ impl<T: RDH, C: 'static + ChecksOpt + FilterOpt + CustomChecksOpt + UtilOpt> Lin
                         )
                         .unwrap();
                     }
-                } // Example of how to add a new system to the validator
-                  //
-                  // 1. Match on the system target in the config
-                  //  config::System::NewSystem => {
-                  //     if !payload.is_empty() {
-                  // 2. Call the do_payload_checks in the `new_system` module and pass the necessary arguments to do the checks
-                  //         super::new_system::lib::do_payload_checks(
-                  //             (&rdh, &payload, rdh_mem_pos),
-                  //             &self.stats_send_chan,
-                  //             &mut self.new_system_cdp_validator,
-                  //         );
-                  //     }
-                  // }
+                }
+                System::FT0 => self.ft0_validator.check(&rdh, &payload, rdh_mem_pos),
+                // Example of how to add a new system to the validator
+                //
+                // 1. Match on the system target in the config
+                //  config::System::NewSystem => {
+                //     if !payload.is_empty() {
+                // 2. Call the do_payload_checks in the `new_system` module and pass the necessary arguments to do the checks
+                //         super::new_system::lib::do_payload_checks(
+                //             (&rdh, &payload, rdh_mem_pos),
+                //             &self.stats_send_chan,
+                //             &mut self.new_system_cdp_validator,
+                //         );
+                //     }
+                // }
             }
         }
 
         self.prev_rdhs.push(rdh);
+        self.prev_rdh_mem_pos = Some(rdh_mem_pos);
     }
 
     fn do_rdh_checks(&mut self, rdh: &T, rdh_mem_pos: u64) {
         if let Err(e) = self.rdh_sanity_validator.sanity_check(rdh) {
             self.report_rdh_error(rdh, e, rdh_mem_pos);
         }
+        if let Err(e) = self.rdh_sanity_validator.cru_id_check(rdh) {
+            self.report_rdh_error(rdh, e, rdh_mem_pos);
+        }
+        if let Err(e) = self.rdh_sanity_validator.link_id_check(rdh) {
+            self.report_rdh_error(rdh, e, rdh_mem_pos);
+        }
+        match self.rdh_sanity_validator.memory_size_check(rdh) {
+            Ok(None) => (),
+            Ok(Some(warning)) => self
+                .stats_send
+                .send(StatType::Warning(
+                    format!("{rdh_mem_pos:#X}: {warning}").into(),
+                ))
+                .unwrap(),
+            Err(e) => self.report_rdh_error(rdh, e, rdh_mem_pos),
+        }
 
         if self.running_checks {
             if let Err(e) = self.rdh_running_validator.check(rdh) {
                 self.report_rdh_error(rdh, e, rdh_mem_pos);
             }
+            if let Err(e) = self.rdh_running_validator.check_repeated_orbit_bc(rdh) {
+                self.report_rdh_error(rdh, e, rdh_mem_pos);
+            }
+            if let Err(e) = self.rdh_running_validator.check_packet_counter(rdh) {
+                self.report_rdh_error(rdh, e, rdh_mem_pos);
+            }
+            if let Err(e) = self.check_duplicate_rdh(rdh) {
+                self.report_rdh_error(rdh, e, rdh_mem_pos);
+            }
+        }
+    }
+
+    /// Checks that the [RDH] is not an exact duplicate of the previous [RDH] of the link, e.g. the same orbit, bc, and packet counter.
+    fn check_duplicate_rdh(&self, rdh: &T) -> Result<(), String> {
+        match (self.prev_rdhs.back(), self.prev_rdh_mem_pos) {
+            (Some(prev_rdh), Some(prev_mem_pos)) if prev_rdh == rdh => Err(format!(
+                "[E16] duplicate RDH: identical to the previous RDH of the link at {prev_mem_pos:#X}"
+            )),
+            _ => Ok(()),
         }
     }
 
@@ -261,7 +393,9 @@ mod tests {
         // Check that the link validator has not sent any errors
         while let Ok(stats_msg) = stats_recv_chan.try_recv() {
             match stats_msg {
-                StatType::Error(_) => panic!("Received error message: {:?}", stats_msg),
+                StatType::Error(_) | StatType::CheckError(_) => {
+                    panic!("Received error message: {:?}", stats_msg)
+                }
                 _ => println!("Received stats message: {:?}", stats_msg),
             }
         }
@@ -305,7 +439,9 @@ mod tests {
         // Check that the link validator has not sent any errors
         while let Ok(stats_msg) = stats_recv_chan.try_recv() {
             match stats_msg {
-                StatType::Error(_) => panic!("Received error message: {:?}", stats_msg),
+                StatType::Error(_) | StatType::CheckError(_) => {
+                    panic!("Received error message: {:?}", stats_msg)
+                }
                 _ => println!("Received stats message: {:?}", stats_msg),
             }
         }
@@ -355,21 +491,74 @@ mod tests {
         // Check that the link validator has sent an error
         let stats_msg = stats_recv_chan.try_recv().unwrap();
         match stats_msg {
-            StatType::Error(_) => println!("Received error message: {:?}", stats_msg),
+            StatType::CheckError(_) => println!("Received error message: {:?}", stats_msg),
             _ => panic!("Received stats message: {:?}", stats_msg),
         }
 
         // Check that the link validator has not sent any more errors
         while let Ok(stat) = stats_recv_chan.try_recv() {
             match stat {
-                StatType::Error(msg) => panic!("Received error message: {msg}"),
+                StatType::Error(_) | StatType::CheckError(_) => {
+                    panic!("Received error message: {stat}")
+                }
                 _ => println!("Received stats message: {:?}", stat),
             }
         }
     }
 
+    static CFG_TEST_DUPLICATE_RDH: OnceLock<MockConfig> = OnceLock::new();
+
+    #[test]
+    fn test_duplicate_rdh() {
+        let mut mock_config = MockConfig::new();
+        mock_config.check = Some(CheckCommands::All(CheckModeArgs::default()));
+        mock_config.mute_errors = true;
+        CFG_TEST_DUPLICATE_RDH.set(mock_config).unwrap();
+        let (stats_send_chan, stats_recv_chan) = flume::unbounded();
+        let (mut link_validator, _cdp_tuple_send_ch) =
+            LinkValidator::new(CFG_TEST_DUPLICATE_RDH.get().unwrap(), stats_send_chan);
+
+        link_validator.do_checks((CORRECT_RDH_CRU_V7, Vec::new(), 0));
+        assert!(link_validator
+            .check_duplicate_rdh(&CORRECT_RDH_CRU_V7)
+            .is_err());
+        link_validator.do_checks((CORRECT_RDH_CRU_V7, Vec::new(), 0x40));
+
+        let duplicate_errors: Vec<String> = stats_recv_chan
+            .try_iter()
+            .filter_map(|stat| match stat {
+                StatType::Error(e) if e.contains("[E16]") => Some(e.to_string()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            duplicate_errors,
+            ["0x40: [E16] duplicate RDH: identical to the previous RDH of the link at 0x0"]
+        );
+    }
+
     static CFG_TEST_INIT_LINK_VALIDATOR_NO_CHECKS_ENABLED: OnceLock<MockConfig> = OnceLock::new();
 
+    #[test]
+    fn test_link_error_counter_forwards_on_drop() {
+        let (stats_send_chan, stats_recv_chan) = flume::unbounded();
+        let (mut error_counter, link_stats_send) = LinkErrorCounter::new(stats_send_chan);
+
+        link_stats_send
+            .send(StatType::Error("0x0: [E10] error".into()))
+            .unwrap();
+        link_stats_send.send(StatType::HBFsSeen(1)).unwrap();
+        assert_eq!(error_counter.forward(), 1);
+        assert_eq!(stats_recv_chan.len(), 2);
+
+        // Stats sent after the last forward are forwarded when dropped
+        link_stats_send
+            .send(StatType::Error("0x40: [E10] error".into()))
+            .unwrap();
+        drop(error_counter);
+        assert_eq!(stats_recv_chan.len(), 3);
+    }
+
     #[test]
     #[should_panic]
     fn test_init_link_validator_no_checks_enabled() {
@@ -0,0 +1,116 @@
+//! Contains the [OrderingValidator] that checks that the CDPs of a stream are globally ordered by an [OrderingKey].
+//!
+//! Unlike the running RDH checks that are done per link, the ordering check concerns the interleaving of CDPs across the entire stream.
+//! CDPs with the same key (e.g. the pages of an HBF) are considered ordered.
+
+use crate::config::check::OrderingKey;
+use crate::util::*;
+
+/// Checks that the CDPs of a stream are ordered by an [OrderingKey], and reports the first CDP that is out of order.
+#[derive(Debug, Clone, Copy)]
+pub struct OrderingValidator {
+    key: OrderingKey,
+    /// Key and memory position of the previous CDP
+    prev: Option<((u32, u32), u64)>,
+    violation_found: bool,
+}
+
+impl OrderingValidator {
+    /// Creates a new [OrderingValidator] that checks the ordering by the given [OrderingKey]
+    pub fn new(key: OrderingKey) -> Self {
+        Self {
+            key,
+            prev: None,
+            violation_found: false,
+        }
+    }
+
+    /// Checks the ordering of the CDPs in a [CdpArray].
+    ///
+    /// Only the first CDP that is out of order is reported, as all subsequent CDPs can be out of order as a consequence.
+    pub fn check_cdp_array<T: RDH, const CAP: usize>(
+        &mut self,
+        cdp_array: &CdpArray<T, CAP>,
+    ) -> Result<(), String> {
+        for (rdh, &mem_pos) in cdp_array
+            .rdh_slice()
+            .iter()
+            .zip(cdp_array.rdh_mem_pos_slice())
+        {
+            self.check(rdh, mem_pos)?;
+        }
+        Ok(())
+    }
+
+    /// Checks that the key of the RDH is not less than the key of the previous RDH
+    pub fn check<T: RDH>(&mut self, rdh: &T, mem_pos: u64) -> Result<(), String> {
+        if self.violation_found {
+            return Ok(());
+        }
+        let key = self.key.rdh_key(rdh);
+        if let Some((prev_key, prev_mem_pos)) = self.prev {
+            if key < prev_key {
+                self.violation_found = true;
+                return Err(format!(
+                    "{mem_pos:#X}: [E13] CDP is out of order by {fields}: ({}, {}) follows ({}, {}) at {prev_mem_pos:#X}",
+                    key.0,
+                    key.1,
+                    prev_key.0,
+                    prev_key.1,
+                    fields = self.key.field_names(),
+                ));
+            }
+        }
+        self.prev = Some((key, mem_pos));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alice_protocol_reader::prelude::test_data::RdhCruBuilder;
+    use pretty_assertions::assert_eq;
+
+    fn cdp_array_from_link_orbits(link_orbits: &[(u8, u32)]) -> CdpArray<RdhCru, 8> {
+        let mut cdp_array = CdpArray::new();
+        for (idx, &(link_id, orbit)) in link_orbits.iter().enumerate() {
+            cdp_array.push(
+                RdhCruBuilder::new().link_id(link_id).orbit(orbit).build(),
+                vec![],
+                idx as u64 * 0x40,
+            );
+        }
+        cdp_array
+    }
+
+    #[test]
+    fn test_ordered_by_link_orbit() {
+        let cdp_array = cdp_array_from_link_orbits(&[(0, 1), (0, 1), (0, 2), (1, 0), (2, 5)]);
+        let mut validator = OrderingValidator::new(OrderingKey::LinkOrbit);
+        assert!(validator.check_cdp_array(&cdp_array).is_ok());
+    }
+
+    #[test]
+    fn test_shuffled_reports_first_violation() {
+        // The 3rd CDP is out of order, and then the 5th CDP is out of order again
+        let cdp_array = cdp_array_from_link_orbits(&[(0, 1), (1, 1), (0, 2), (1, 2), (0, 3)]);
+        let mut validator = OrderingValidator::new(OrderingKey::LinkOrbit);
+        assert_eq!(
+            validator.check_cdp_array(&cdp_array).unwrap_err(),
+            "0x80: [E13] CDP is out of order by (link, orbit): (0, 2) follows (1, 1) at 0x40"
+        );
+        // Subsequent violations are not reported
+        assert!(validator.check_cdp_array(&cdp_array).is_ok());
+    }
+
+    #[test]
+    fn test_shuffled_ordered_by_orbit_link() {
+        let cdp_array = cdp_array_from_link_orbits(&[(0, 1), (1, 1), (0, 2), (1, 2), (1, 1)]);
+        let mut validator = OrderingValidator::new(OrderingKey::OrbitLink);
+        assert_eq!(
+            validator.check_cdp_array(&cdp_array).unwrap_err(),
+            "0x100: [E13] CDP is out of order by (orbit, link): (1, 1) follows (2, 1) at 0xC0"
+        );
+    }
+}
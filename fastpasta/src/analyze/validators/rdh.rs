@@ -10,6 +10,8 @@ use std::fmt::Write as _;
 pub enum SpecializeChecks {
     /// Specialize the checks for the Inner Tracking System.
     ITS,
+    /// Specialize the checks for the Fast Interaction Trigger T0 detector.
+    FT0,
 }
 
 /// Validator for the RDH CRU sanity checks.
@@ -20,7 +22,6 @@ pub struct RdhCruSanityValidator<T: RDH> {
     rdh3_validator: &'static Rdh3Validator,
     _phantom: PhantomData<T>,
     // valid_dataformat_reserved0: DataformatReserved,
-    // valid link IDs are 0-11 and 15
     // datawrapper ID is 0 or 1
 }
 
@@ -37,9 +38,27 @@ const RDH1_VALIDATOR: Rdh1Validator = Rdh1Validator {
 const RDH2_VALIDATOR: Rdh2Validator = Rdh2Validator {};
 const RDH3_VALIDATOR: Rdh3Validator = Rdh3Validator {};
 const FEE_ID_SANITY_VALIDATOR: FeeIdSanityValidator = FeeIdSanityValidator::new((0, 6), (0, 47));
+/// The CRU ID field is 12 bits, but the CRU IDs assigned in ALICE are all below 1024
+const CRU_ID_MAX: u16 = 0x3FF;
+/// The priority bit is set for priority readout
+const PRIORITY_BIT_MAX: u8 = 1;
+/// CRU links are numbered 0-11
+const LINK_ID_MAX: u8 = 11;
+/// Link ID used for data generated by the CRU itself
+const LINK_ID_CRU_INTERNAL: u8 = 15;
+
+/// Returns true if the link ID is a valid CRU link (0-11) or the CRU internal link (15).
+#[inline]
+pub(crate) fn valid_link_id(link_id: u8) -> bool {
+    link_id <= LINK_ID_MAX || link_id == LINK_ID_CRU_INTERNAL
+}
 
 /// Specialized for ITS
 const ITS_SYSTEM_ID: u8 = 32;
+/// Specialized for FT0
+const FT0_SYSTEM_ID: u8 = 34;
+/// FT0 has 18 Processing Modules (PM) and 1 Trigger and Clock Module (TCM), each with their own FEE ID
+const FT0_FEE_ID_MAX: u16 = 18;
 impl<T: RDH> RdhCruSanityValidator<T> {
     /// Creates a new [RdhCruSanityValidator] with default values.
     pub fn new() -> Self {
@@ -61,12 +80,14 @@ impl<T: RDH> RdhCruSanityValidator<T> {
                     System::ITS | System::ITS_Stave => {
                         validator.specialize(SpecializeChecks::ITS);
                     }
+                    System::FT0 => validator.specialize(SpecializeChecks::FT0),
                 }
             }
             validator
         } else if let Some(system) = config.check().unwrap().target() {
             match system {
                 System::ITS | System::ITS_Stave => Self::with_specialization(SpecializeChecks::ITS),
+                System::FT0 => Self::with_specialization(SpecializeChecks::FT0),
             }
         } else {
             Self::default()
@@ -80,8 +101,8 @@ impl<T: RDH> RdhCruSanityValidator<T> {
                 rdh0_validator: Rdh0Validator::new(
                     None,
                     Rdh0::HEADER_SIZE,
-                    FEE_ID_SANITY_VALIDATOR,
-                    0,
+                    FeeIdValidator::Its(FEE_ID_SANITY_VALIDATOR),
+                    PRIORITY_BIT_MAX,
                     Some(ITS_SYSTEM_ID),
                 ),
                 rdh1_validator: &RDH1_VALIDATOR,
@@ -89,6 +110,19 @@ impl<T: RDH> RdhCruSanityValidator<T> {
                 rdh3_validator: &RDH3_VALIDATOR,
                 _phantom: PhantomData,
             },
+            SpecializeChecks::FT0 => Self {
+                rdh0_validator: Rdh0Validator::new(
+                    None,
+                    Rdh0::HEADER_SIZE,
+                    FeeIdValidator::MaxId(FT0_FEE_ID_MAX),
+                    PRIORITY_BIT_MAX,
+                    Some(FT0_SYSTEM_ID),
+                ),
+                rdh1_validator: &RDH1_VALIDATOR,
+                rdh2_validator: &RDH2_VALIDATOR,
+                rdh3_validator: &RDH3_VALIDATOR,
+                _phantom: PhantomData,
+            },
         }
     }
 
@@ -101,8 +135,8 @@ impl<T: RDH> RdhCruSanityValidator<T> {
                 rdh0_validator: Rdh0Validator::new(
                     Some(rdh_version),
                     Rdh0::HEADER_SIZE,
-                    FEE_ID_SANITY_VALIDATOR,
-                    0,
+                    FeeIdValidator::Its(FEE_ID_SANITY_VALIDATOR),
+                    PRIORITY_BIT_MAX,
                     None,
                 ),
                 rdh1_validator: &RDH1_VALIDATOR,
@@ -121,6 +155,10 @@ impl<T: RDH> RdhCruSanityValidator<T> {
             SpecializeChecks::ITS => {
                 self.rdh0_validator.system_id = Some(ITS_SYSTEM_ID);
             }
+            SpecializeChecks::FT0 => {
+                self.rdh0_validator.system_id = Some(FT0_SYSTEM_ID);
+                self.rdh0_validator.fee_id = FeeIdValidator::MaxId(FT0_FEE_ID_MAX);
+            }
         }
     }
 
@@ -158,7 +196,75 @@ impl<T: RDH> RdhCruSanityValidator<T> {
 
         Ok(())
     }
+
+    /// Checks that the CRU ID of an [RDH] is within the range of CRU IDs in use in ALICE.
+    ///
+    /// Reported separately from the other sanity checks as an out-of-range CRU ID is a strong indicator of data corruption.
+    #[inline]
+    pub fn cru_id_check(&self, rdh: &T) -> Result<(), String> {
+        if rdh.cru_id() > CRU_ID_MAX {
+            return Err(format!(
+                "[E35] Implausible CRU ID: {cru_id} (expected 0-{CRU_ID_MAX})",
+                cru_id = rdh.cru_id()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that the link ID of an [RDH] is one of the CRU links (0-11) or the CRU internal link (15).
+    #[inline]
+    pub fn link_id_check(&self, rdh: &T) -> Result<(), String> {
+        if !valid_link_id(rdh.link_id()) {
+            return Err(format!(
+                "[E36] link_id out of range: {link_id} (expected 0-{LINK_ID_MAX} or {LINK_ID_CRU_INTERNAL})",
+                link_id = rdh.link_id()
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that the memory size of an [RDH] is consistent with its offset to the next [RDH].
+    ///
+    /// A memory size larger than the offset to next means the packet overlaps the next [RDH], and is an error.
+    /// A smaller memory size means the packet is followed by unused bytes, which is unusual packing but not invalid, and is returned as a warning.
+    #[inline]
+    pub fn memory_size_check(&self, rdh: &T) -> Result<Option<String>, String> {
+        // The payload size is the memory size minus the size of the RDH
+        let memory_size = u32::from(rdh.payload_size()) + u32::from(Rdh0::HEADER_SIZE);
+        let offset_to_next = u32::from(rdh.offset_to_next());
+        match memory_size.cmp(&offset_to_next) {
+            std::cmp::Ordering::Greater => Err(format!(
+                "[E18] RDH memory size {memory_size} exceeds the offset to next {offset_to_next}"
+            )),
+            std::cmp::Ordering::Less => Ok(Some(format!(
+                "[W04] RDH memory size {memory_size} is less than the offset to next {offset_to_next}, {unused} unused bytes after the packet",
+                unused = offset_to_next - memory_size
+            ))),
+            std::cmp::Ordering::Equal => Ok(None),
+        }
+    }
+}
+/// Validates the FEE ID according to the FEE ID format of the target system.
+enum FeeIdValidator {
+    /// ITS FEE ID with layer and stave number
+    Its(FeeIdSanityValidator),
+    /// Any FEE ID up to and including the max
+    MaxId(u16),
+}
+
+impl FeeIdValidator {
+    fn sanity_check(&self, fee_id: FeeId) -> Result<(), String> {
+        match self {
+            FeeIdValidator::Its(validator) => validator.sanity_check(fee_id),
+            FeeIdValidator::MaxId(max) if fee_id.0 > *max => Err(format!(
+                "{fee_id} (expected 0-{max})",
+                fee_id = { fee_id.0 }
+            )),
+            FeeIdValidator::MaxId(_) => Ok(()),
+        }
+    }
 }
+
 struct FeeIdSanityValidator {
     layer_min_max: (u8, u8),
     stave_number_min_max: (u8, u8),
@@ -220,15 +326,22 @@ impl FeeIdSanityValidator {
 pub struct Rdh0Validator {
     header_id: Option<u8>, // The first Rdh0 checked will determine what is a valid header_id
     header_size: u8,
-    fee_id: FeeIdSanityValidator,
-    priority_bit: u8,
+    fee_id: FeeIdValidator,
+    priority_bit_max: u8,
     system_id: Option<u8>,
     reserved0: u16,
+    sanity: RdhSanity,
 }
 
 impl Default for Rdh0Validator {
     fn default() -> Self {
-        Self::new(None, Rdh0::HEADER_SIZE, FEE_ID_SANITY_VALIDATOR, 0, None)
+        Self::new(
+            None,
+            Rdh0::HEADER_SIZE,
+            FeeIdValidator::Its(FEE_ID_SANITY_VALIDATOR),
+            PRIORITY_BIT_MAX,
+            None,
+        )
     }
 }
 
@@ -236,22 +349,40 @@ impl Rdh0Validator {
     fn new(
         header_id: Option<u8>,
         header_size: u8,
-        fee_id: FeeIdSanityValidator,
-        priority_bit: u8,
+        fee_id: FeeIdValidator,
+        priority_bit_max: u8,
         system_id: Option<u8>,
     ) -> Self {
         Self {
             header_id,
             header_size,
             fee_id,
-            priority_bit,
+            priority_bit_max,
             system_id,
             reserved0: 0,
+            sanity: RdhSanity::Strict,
+        }
+    }
+
+    /// Creates a [Rdh0Validator] with the default values and the given [RdhSanity] profile.
+    pub fn with_sanity(sanity: RdhSanity) -> Self {
+        Self {
+            sanity,
+            ..Self::default()
         }
     }
 
     /// Check consistency of a [Rdh0] RDH subword
+    ///
+    /// With the [lenient](RdhSanity::Lenient) profile, unusual but non-fatal values are not an error, see [sanity_check_with_warning](Self::sanity_check_with_warning).
     pub fn sanity_check(&mut self, rdh0: &Rdh0) -> Result<(), String> {
+        self.sanity_check_with_warning(rdh0).map(|_| ())
+    }
+
+    /// Check consistency of a [Rdh0] RDH subword, returns a warning describing the unusual but non-fatal values if the [RdhSanity] profile is lenient.
+    ///
+    /// The header size, FEE ID, priority bit, and reserved bits are non-fatal, as the RDH can still be decoded and the data processed.
+    pub fn sanity_check_with_warning(&mut self, rdh0: &Rdh0) -> Result<Option<String>, String> {
         if self.header_id.is_none() {
             self.header_id = Some(rdh0.header_id);
         }
@@ -265,29 +396,40 @@ impl Rdh0Validator {
             )
             .unwrap();
         }
-        if rdh0.header_size != self.header_size {
-            write!(err_str, "Header size = {:#x} ", rdh0.header_size).unwrap();
-        }
-        if let Err(e) = self.fee_id.sanity_check(FeeId(rdh0.fee_id())) {
-            write!(err_str, "FEE ID = [{}] ", e).unwrap();
-        }
-        if rdh0.priority_bit != self.priority_bit {
-            write!(err_str, "Priority bit = {:#x} ", rdh0.priority_bit).unwrap();
-        }
         if let Some(valid_system_id) = self.system_id {
             if rdh0.system_id != valid_system_id {
                 write!(err_str, "system_id = {:#x} ", rdh0.system_id).unwrap();
             }
         }
 
+        let mut non_fatal_str = String::new();
+        if rdh0.header_size != self.header_size {
+            write!(non_fatal_str, "Header size = {:#x} ", rdh0.header_size).unwrap();
+        }
+        if let Err(e) = self.fee_id.sanity_check(FeeId(rdh0.fee_id())) {
+            write!(non_fatal_str, "FEE ID = [{}] ", e).unwrap();
+        }
+        if rdh0.priority_bit > self.priority_bit_max {
+            write!(
+                non_fatal_str,
+                "Priority bit = {:#x} (expected 0-{}) ",
+                rdh0.priority_bit, self.priority_bit_max
+            )
+            .unwrap();
+        }
         if rdh0.reserved0 != self.reserved0 {
             let tmp = rdh0.reserved0;
-            write!(err_str, "reserved0 = {tmp:#x} ").unwrap();
+            write!(non_fatal_str, "reserved0 = {tmp:#x} ").unwrap();
         }
+
+        if self.sanity == RdhSanity::Lenient && err_str.is_empty() {
+            return Ok((!non_fatal_str.is_empty()).then(|| format!("RDH0: {non_fatal_str}")));
+        }
+        err_str.push_str(&non_fatal_str);
         if !err_str.is_empty() {
             return Err(format!("RDH0: {err_str}"));
         }
-        Ok(())
+        Ok(None)
     }
 }
 
@@ -379,7 +521,9 @@ impl Rdh3Validator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alice_protocol_reader::prelude::test_data::{CORRECT_RDH_CRU_V6, CORRECT_RDH_CRU_V7};
+    use alice_protocol_reader::prelude::test_data::{
+        RdhCruBuilder, CORRECT_RDH_CRU_V6, CORRECT_RDH_CRU_V7,
+    };
 
     const _CORRECT_RDH0: Rdh0 = Rdh0::new(7, Rdh0::HEADER_SIZE, FeeId(20522), 0, ITS_SYSTEM_ID, 0);
     const CORRECT_RDH1: Rdh1 = Rdh1::new(BcReserved(0), 0);
@@ -443,11 +587,11 @@ mod tests {
         let mut validator = Rdh0Validator::new(
             Some(7),
             0x40,
-            FeeIdSanityValidator {
+            FeeIdValidator::Its(FeeIdSanityValidator {
                 layer_min_max: (0, 7),
                 stave_number_min_max: (0, 47),
-            },
-            0,
+            }),
+            PRIORITY_BIT_MAX,
             Some(ITS_SYSTEM_ID),
         );
         let rdh0 = Rdh0::new(0x7, 0x40, FeeId(0x502A), 0, ITS_SYSTEM_ID, 0);
@@ -493,8 +637,8 @@ mod tests {
         let mut validator = Rdh0Validator::new(
             None,
             Rdh0::HEADER_SIZE,
-            FEE_ID_SANITY_VALIDATOR,
-            0,
+            FeeIdValidator::Its(FEE_ID_SANITY_VALIDATOR),
+            PRIORITY_BIT_MAX,
             Some(ITS_SYSTEM_ID),
         );
         let rdh0 = Rdh0::new(7, Rdh0::HEADER_SIZE, FeeId(0x502A), 0, 3, 0);
@@ -506,8 +650,13 @@ mod tests {
 
     #[test]
     fn validate_rdh0_non_its_system_id() {
-        let mut validator =
-            Rdh0Validator::new(None, Rdh0::HEADER_SIZE, FEE_ID_SANITY_VALIDATOR, 0, None);
+        let mut validator = Rdh0Validator::new(
+            None,
+            Rdh0::HEADER_SIZE,
+            FeeIdValidator::Its(FEE_ID_SANITY_VALIDATOR),
+            PRIORITY_BIT_MAX,
+            None,
+        );
 
         let rdh0 = Rdh0::new(7, Rdh0::HEADER_SIZE, FeeId(0x502A), 0, 0x99, 0);
 
@@ -516,13 +665,52 @@ mod tests {
         assert!(res.is_ok());
     }
 
+    #[test]
+    fn validate_rdh0_priority_bit() {
+        let mut validator = Rdh0Validator::default();
+        let rdh0_priority = Rdh0::new(7, Rdh0::HEADER_SIZE, FeeId(0x502A), 1, ITS_SYSTEM_ID, 0);
+        assert!(validator.sanity_check(&rdh0_priority).is_ok());
+
+        let rdh0_bad_priority = Rdh0::new(7, Rdh0::HEADER_SIZE, FeeId(0x502A), 2, ITS_SYSTEM_ID, 0);
+        assert_eq!(
+            validator.sanity_check(&rdh0_bad_priority).unwrap_err(),
+            "RDH0: Priority bit = 0x2 (expected 0-1) "
+        );
+    }
+
+    #[test]
+    fn validate_rdh0_ft0_fee_id_range() {
+        let mut validator =
+            RdhCruSanityValidator::<RdhCru>::with_specialization(SpecializeChecks::FT0);
+        let rdh0_tcm = Rdh0::new(
+            7,
+            Rdh0::HEADER_SIZE,
+            FeeId(FT0_FEE_ID_MAX),
+            0,
+            FT0_SYSTEM_ID,
+            0,
+        );
+        assert!(validator.rdh0_validator.sanity_check(&rdh0_tcm).is_ok());
+
+        let rdh0_bad_fee_id = Rdh0::new(7, Rdh0::HEADER_SIZE, FeeId(19), 0, FT0_SYSTEM_ID, 0);
+        let err = validator
+            .rdh0_validator
+            .sanity_check(&rdh0_bad_fee_id)
+            .unwrap_err();
+        assert!(err.contains("FEE ID = [19 (expected 0-18)]"), "{err}");
+
+        // ITS system ID is not valid for FT0
+        let rdh0_its = Rdh0::new(7, Rdh0::HEADER_SIZE, FeeId(3), 0, ITS_SYSTEM_ID, 0);
+        assert!(validator.rdh0_validator.sanity_check(&rdh0_its).is_err());
+    }
+
     #[test]
     fn invalidate_rdh0_bad_reserved0() {
         let mut validator = Rdh0Validator::new(
             None,
             Rdh0::HEADER_SIZE,
-            FEE_ID_SANITY_VALIDATOR,
-            0,
+            FeeIdValidator::Its(FEE_ID_SANITY_VALIDATOR),
+            PRIORITY_BIT_MAX,
             Some(ITS_SYSTEM_ID),
         );
         let rdh0 = Rdh0::new(7, Rdh0::HEADER_SIZE, FeeId(0x502A), 0, ITS_SYSTEM_ID, 0x3);
@@ -532,6 +720,31 @@ mod tests {
         assert!(res.is_err());
     }
 
+    #[test]
+    fn validate_rdh0_lenient_sanity() {
+        let mut validator = Rdh0Validator::with_sanity(RdhSanity::Lenient);
+        let rdh0 = Rdh0::new(7, Rdh0::HEADER_SIZE, FeeId(0x502A), 0, ITS_SYSTEM_ID, 0);
+        assert_eq!(validator.sanity_check_with_warning(&rdh0), Ok(None));
+
+        // Unexpected header size is only a warning
+        let rdh0_bad_header_size = Rdh0::new(7, 0x50, FeeId(0x502A), 0, ITS_SYSTEM_ID, 0);
+        assert_eq!(
+            validator.sanity_check_with_warning(&rdh0_bad_header_size),
+            Ok(Some("RDH0: Header size = 0x50 ".to_string()))
+        );
+        assert!(Rdh0Validator::default()
+            .sanity_check(&rdh0_bad_header_size)
+            .is_err());
+
+        // A different header ID than the first RDH0 is still an error
+        let rdh0_bad_header_id = Rdh0::new(6, 0x50, FeeId(0x502A), 0, ITS_SYSTEM_ID, 0);
+        let err = validator
+            .sanity_check_with_warning(&rdh0_bad_header_id)
+            .unwrap_err();
+        assert!(err.contains("Header ID = 6 (expected 7)"), "{err}");
+        assert!(err.contains("Header size = 0x50"), "{err}");
+    }
+
     // RDH1 sanity check
     #[test]
     fn validate_rdh1() {
@@ -822,4 +1035,80 @@ mod tests {
         println!("{res:?}");
         assert!(res.is_err());
     }
+
+    #[test]
+    fn validate_cru_id_real_data_values() {
+        let validator = RdhCruSanityValidator::<RdhCru>::default();
+        assert!(validator.cru_id_check(&CORRECT_RDH_CRU_V7).is_ok());
+        assert!(validator.cru_id_check(&CORRECT_RDH_CRU_V6).is_ok());
+    }
+
+    #[test]
+    fn invalidate_cru_id_out_of_range() {
+        let validator = RdhCruSanityValidator::default();
+        let rdh_cru = RdhCruBuilder::new().cruid_dw(60000).build();
+        let res = validator.cru_id_check(&rdh_cru);
+        println!("{res:?}");
+        assert!(res.unwrap_err().starts_with("[E35] Implausible CRU ID"));
+    }
+
+    #[test]
+    fn validate_memory_size_matches_offset_to_next() {
+        let validator = RdhCruSanityValidator::<RdhCru>::default();
+        assert_eq!(validator.memory_size_check(&CORRECT_RDH_CRU_V7), Ok(None));
+        assert_eq!(validator.memory_size_check(&CORRECT_RDH_CRU_V6), Ok(None));
+    }
+
+    #[test]
+    fn invalidate_memory_size_exceeds_offset_to_next() {
+        let validator = RdhCruSanityValidator::default();
+        let rdh_cru = RdhCruBuilder::new()
+            .offset_to_next(0x40)
+            .memory_size(0x50)
+            .build();
+        let res = validator.memory_size_check(&rdh_cru);
+        assert_eq!(
+            res.unwrap_err(),
+            "[E18] RDH memory size 80 exceeds the offset to next 64"
+        );
+    }
+
+    #[test]
+    fn warn_memory_size_less_than_offset_to_next() {
+        let validator = RdhCruSanityValidator::default();
+        let rdh_cru = RdhCruBuilder::new()
+            .offset_to_next(0x2000)
+            .memory_size(0x13E0)
+            .build();
+        let res = validator.memory_size_check(&rdh_cru);
+        assert_eq!(
+            res.unwrap().unwrap(),
+            "[W04] RDH memory size 5088 is less than the offset to next 8192, 3104 unused bytes after the packet"
+        );
+    }
+
+    #[test]
+    fn validate_link_id_in_range() {
+        let validator = RdhCruSanityValidator::<RdhCru>::default();
+        assert!(validator.link_id_check(&CORRECT_RDH_CRU_V7).is_ok());
+        assert!(validator.link_id_check(&CORRECT_RDH_CRU_V6).is_ok());
+        for link_id in (0..=11).chain([15]) {
+            assert!(valid_link_id(link_id), "{link_id}");
+        }
+    }
+
+    #[test]
+    fn invalidate_link_id_out_of_range() {
+        let validator = RdhCruSanityValidator::default();
+        let rdh_cru = RdhCruBuilder::new().link_id(20).build();
+        let res = validator.link_id_check(&rdh_cru);
+        println!("{res:?}");
+        assert_eq!(
+            res.unwrap_err(),
+            "[E36] link_id out of range: 20 (expected 0-11 or 15)"
+        );
+        for link_id in (12..=14).chain(16..=u8::MAX) {
+            assert!(!valid_link_id(link_id), "{link_id}");
+        }
+    }
 }
@@ -0,0 +1,146 @@
+//! Contains the [RdhOffsetValidator] that checks that each RDH of a stream is where the `offset_to_next` of the previous RDH points to.
+//!
+//! The memory positions of the RDHs are tracked by their `offset_to_next`, but if the payloads are read, the next RDH is read directly after the payload.
+//! If the `offset_to_next` of an RDH doesn't match the bytes the RDH and its payload occupy, e.g. it points into the middle of the payload,
+//! the next RDH is read from a different position than the tracked memory position, and the stream is out of sync.
+//!
+//! Like the ordering check, this concerns the entire stream and not a single link, so it cannot be used if any RDHs are filtered out.
+
+use crate::util::*;
+
+/// Size of an RDH in bytes
+const RDH_SIZE_BYTES: u64 = 64;
+
+/// Position and size of the previous RDH
+#[derive(Debug, Clone, Copy)]
+struct PrevRdh {
+    mem_pos: u64,
+    offset_to_next: u64,
+    /// Number of bytes read from the start of the RDH to the start of the next RDH
+    bytes_read: u64,
+}
+
+/// Checks that the byte distance between consecutive RDHs matches the `offset_to_next` of the previous RDH.
+#[derive(Debug, Clone, Copy)]
+pub struct RdhOffsetValidator {
+    /// If set, the scanner skips the payloads by seeking to `offset_to_next` instead of reading them
+    payload_skipped: bool,
+    prev: Option<PrevRdh>,
+}
+
+impl RdhOffsetValidator {
+    /// Creates a new [RdhOffsetValidator], `payload_skipped` should be set if the payloads are not read.
+    pub fn new(payload_skipped: bool) -> Self {
+        Self {
+            payload_skipped,
+            prev: None,
+        }
+    }
+
+    /// Checks the offsets of the RDHs in a [CdpArray], returning an error message for each RDH that is not at the offset of the previous RDH.
+    pub fn check_cdp_array<T: RDH, const CAP: usize>(
+        &mut self,
+        cdp_array: &CdpArray<T, CAP>,
+    ) -> Vec<String> {
+        cdp_array
+            .rdh_slice()
+            .iter()
+            .zip(cdp_array.rdh_mem_pos_slice())
+            .filter_map(|(rdh, &mem_pos)| self.check(rdh, mem_pos).err())
+            .collect()
+    }
+
+    /// Checks that the RDH is at the offset of the previous RDH
+    pub fn check<T: RDH>(&mut self, rdh: &T, mem_pos: u64) -> Result<(), String> {
+        let offset_to_next = u64::from(rdh.offset_to_next());
+        let bytes_read = if self.payload_skipped {
+            offset_to_next
+        } else {
+            RDH_SIZE_BYTES + u64::from(rdh.payload_size())
+        };
+        let prev = self.prev.replace(PrevRdh {
+            mem_pos,
+            offset_to_next,
+            bytes_read,
+        });
+        let Some(prev) = prev else {
+            return Ok(());
+        };
+        let distance = mem_pos.saturating_sub(prev.mem_pos);
+        if distance != prev.offset_to_next || prev.bytes_read != prev.offset_to_next {
+            return Err(format!(
+                "{mem_pos:#X}: [E14] RDH is not at the offset to next of the previous RDH at {prev_mem_pos:#X}: offset to next is {offset} but the RDH was read {read} bytes after it",
+                prev_mem_pos = prev.mem_pos,
+                offset = prev.offset_to_next,
+                read = if distance == prev.offset_to_next {
+                    prev.bytes_read
+                } else {
+                    distance
+                },
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alice_protocol_reader::prelude::test_data::RdhCruBuilder;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_consecutive_rdhs_at_offset() {
+        let mut validator = RdhOffsetValidator::new(false);
+        let rdh_0x60 = RdhCruBuilder::new()
+            .offset_to_next(0x60)
+            .memory_size(0x60)
+            .build();
+        let rdh_0x40 = RdhCruBuilder::new()
+            .offset_to_next(0x40)
+            .memory_size(0x40)
+            .build();
+        assert!(validator.check(&rdh_0x60, 0).is_ok());
+        assert!(validator.check(&rdh_0x40, 0x60).is_ok());
+        assert!(validator.check(&rdh_0x60, 0xA0).is_ok());
+    }
+
+    #[test]
+    fn test_offset_into_payload() {
+        let mut validator = RdhOffsetValidator::new(false);
+        let rdh_offset_into_payload = RdhCruBuilder::new()
+            .offset_to_next(0x50)
+            .memory_size(0x60)
+            .build();
+        let rdh_0x60 = RdhCruBuilder::new()
+            .offset_to_next(0x60)
+            .memory_size(0x60)
+            .build();
+        // The offset points into the middle of the payload, but the payload is read in full
+        assert!(validator.check(&rdh_offset_into_payload, 0).is_ok());
+        assert_eq!(
+            validator.check(&rdh_0x60, 0x50),
+            Err("0x50: [E14] RDH is not at the offset to next of the previous RDH at 0x0: offset to next is 80 but the RDH was read 96 bytes after it".to_string())
+        );
+    }
+
+    #[test]
+    fn test_payload_skipped_checks_distance() {
+        let mut validator = RdhOffsetValidator::new(true);
+        let rdh_offset_into_payload = RdhCruBuilder::new()
+            .offset_to_next(0x50)
+            .memory_size(0x60)
+            .build();
+        let rdh_0x60 = RdhCruBuilder::new()
+            .offset_to_next(0x60)
+            .memory_size(0x60)
+            .build();
+        // The scanner seeks to the offset, so the memory size doesn't matter
+        assert!(validator.check(&rdh_offset_into_payload, 0).is_ok());
+        assert!(validator.check(&rdh_0x60, 0x50).is_ok());
+        assert_eq!(
+            validator.check(&rdh_0x60, 0x100),
+            Err("0x100: [E14] RDH is not at the offset to next of the previous RDH at 0x50: offset to next is 96 but the RDH was read 176 bytes after it".to_string())
+        );
+    }
+}
@@ -1,8 +1,23 @@
 //! Performs running (stateful) checks on [RDH]s.
+//!
+//! ## Orbit and bc of HBFs
+//!
+//! All RDHs of an HBF carry the orbit and bc of the HB trigger that opened the HBF.
+//! The bc of the HB trigger is fixed for a given orbit, so if a link starts another HBF in an orbit it already started an HBF in,
+//! the bc has to be the same (a duplicated HBF). A different bc means that the timing fields of the RDHs are inconsistent.
+//!
+//! ## Packet counter
+//!
+//! The packet counter is incremented for each packet the CRU sends on a link, wrapping around after 255.
+//! Packets on the same link can be interleaved with packets on other data paths, so the counter can skip values,
+//! but within an HBF (until an RDH with the stop bit set) it has to increase from one RDH to the next.
 
 use crate::util::*;
 use std::fmt::Write;
 
+/// Number of previous HBFs that are remembered to check for repeated orbits with inconsistent bc
+const RECENT_HBFS: usize = 16;
+
 /// Performs running (stateful) checks on [RDH]s.
 pub struct RdhCruRunningChecker<T: RDH> {
     expect_pages_counter: u16,
@@ -11,6 +26,10 @@ pub struct RdhCruRunningChecker<T: RDH> {
     second_rdh_cru: Option<T>,
     expect_pages_counter_increment: u16,
     last_rdh_cru: Option<T>,
+    /// (orbit, bc) of the HB triggers of the most recent HBFs
+    recent_hbf_orbit_bc: ConstGenericRingBuffer<(u32, u16), RECENT_HBFS>,
+    /// (packet counter, stop bit) of the previous RDH
+    last_packet_counter_stop_bit: Option<(u8, u8)>,
 }
 
 impl<T: RDH> Default for RdhCruRunningChecker<T> {
@@ -28,6 +47,8 @@ impl<T: RDH> RdhCruRunningChecker<T> {
             second_rdh_cru: None,
             expect_pages_counter_increment: 1,
             last_rdh_cru: None,
+            recent_hbf_orbit_bc: ConstGenericRingBuffer::new(),
+            last_packet_counter_stop_bit: None,
         }
     }
 
@@ -68,6 +89,54 @@ impl<T: RDH> RdhCruRunningChecker<T> {
         Ok(())
     }
 
+    /// Checks that an HBF does not start in an orbit that a recent HBF started in, with a different bc.
+    ///
+    /// Only the first RDH of an HBF with the HB trigger bit set is considered.
+    #[inline]
+    pub fn check_repeated_orbit_bc(&mut self, rdh: &T) -> Result<(), String> {
+        if rdh.pages_counter() != 0 || rdh.trigger_type() & trigger_type_masks().hb == 0 {
+            return Ok(());
+        }
+        let orbit = rdh.rdh1().orbit;
+        let bc = rdh.rdh1().bc();
+        let inconsistent_bc = self
+            .recent_hbf_orbit_bc
+            .iter()
+            .find(|&&(prev_orbit, prev_bc)| prev_orbit == orbit && prev_bc != bc)
+            .map(|&(_, prev_bc)| prev_bc);
+        self.recent_hbf_orbit_bc.push((orbit, bc));
+        if let Some(prev_bc) = inconsistent_bc {
+            return Err(format!(
+                "[E49] repeated orbit with inconsistent bc: orbit {orbit:#X} started with bc {prev_bc:#X}, now bc {bc:#X}"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks that the packet counter increases within an HBF, accounting for the counter wrapping around after 255.
+    ///
+    /// The packet counter of the first RDH of an HBF (after an RDH with the stop bit set) is not checked.
+    #[inline]
+    pub fn check_packet_counter(&mut self, rdh: &T) -> Result<(), String> {
+        let packet_counter = rdh.packet_counter();
+        let Some((last_packet_counter, last_stop_bit)) = self
+            .last_packet_counter_stop_bit
+            .replace((packet_counter, rdh.stop_bit()))
+        else {
+            return Ok(());
+        };
+        // A wrapped around counter is at most half the counter range ahead
+        if last_stop_bit == 0
+            && !(1..=127).contains(&packet_counter.wrapping_sub(last_packet_counter))
+        {
+            return Err(format!(
+                "[E15] packet_counter did not increase within the HBF: packet_counter = {packet_counter} expected: {expected} or higher",
+                expected = last_packet_counter.wrapping_add(1)
+            ));
+        }
+        Ok(())
+    }
+
     /// # Check `stop_bit` and `pages_counter` across a CDP
     ///
     /// 1. If `stop_bit` is 0, page counter should be equal to either:
@@ -189,6 +258,7 @@ mod tests {
     use super::RdhCruRunningChecker;
     use alice_protocol_reader::prelude::test_data::*;
     use alice_protocol_reader::prelude::*;
+    use pretty_assertions::assert_eq;
 
     #[test]
     fn test_valid_rdh_crus() {
@@ -301,4 +371,70 @@ mod tests {
         assert!(err_str.contains("Trigger"));
         assert!(err_str.contains("FeeId"));
     }
+
+    #[test]
+    fn test_repeated_orbit_consistent_bc() {
+        let mut rdh_cru_checker = RdhCruRunningChecker::<RdhCru>::new();
+
+        for orbit in 0..3 {
+            assert!(rdh_cru_checker
+                .check_repeated_orbit_bc(&RdhCruBuilder::new().orbit(orbit).bc(0).build())
+                .is_ok());
+        }
+        // Same orbit and bc, e.g. a duplicated HBF
+        assert!(rdh_cru_checker
+            .check_repeated_orbit_bc(&RdhCruBuilder::new().orbit(1).bc(0).build())
+            .is_ok());
+        // Pages of an HBF are not considered
+        assert!(rdh_cru_checker
+            .check_repeated_orbit_bc(&CORRECT_RDH_CRU_V7_NEXT)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_repeated_orbit_inconsistent_bc() {
+        let mut rdh_cru_checker = RdhCruRunningChecker::<RdhCru>::new();
+
+        assert!(rdh_cru_checker
+            .check_repeated_orbit_bc(&RdhCruBuilder::new().orbit(0x10).bc(0).build())
+            .is_ok());
+        assert!(rdh_cru_checker
+            .check_repeated_orbit_bc(&RdhCruBuilder::new().orbit(0x11).bc(0).build())
+            .is_ok());
+        let res = rdh_cru_checker
+            .check_repeated_orbit_bc(&RdhCruBuilder::new().orbit(0x10).bc(0x2A).build());
+        assert_eq!(
+            res.unwrap_err(),
+            "[E49] repeated orbit with inconsistent bc: orbit 0x10 started with bc 0x0, now bc 0x2A"
+        );
+    }
+
+    #[test]
+    fn test_packet_counter_increases() {
+        let mut rdh_cru_checker = RdhCruRunningChecker::<RdhCru>::new();
+
+        for (packet_counter, stop_bit) in [(253, 0), (254, 0), (0, 1), (0, 0), (4, 1)] {
+            // Wraps around within the HBF, skips values and doesn't increase across HBFs
+            let rdh = RdhCruBuilder::new()
+                .packet_counter(packet_counter)
+                .stop_bit(stop_bit)
+                .build();
+            assert!(rdh_cru_checker.check_packet_counter(&rdh).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_packet_counter_not_increasing_within_hbf() {
+        let mut rdh_cru_checker = RdhCruRunningChecker::<RdhCru>::new();
+
+        assert!(rdh_cru_checker
+            .check_packet_counter(&RdhCruBuilder::new().packet_counter(7).stop_bit(0).build())
+            .is_ok());
+        let res = rdh_cru_checker
+            .check_packet_counter(&RdhCruBuilder::new().packet_counter(7).stop_bit(1).build());
+        assert_eq!(
+            res.unwrap_err(),
+            "[E15] packet_counter did not increase within the HBF: packet_counter = 7 expected: 8 or higher"
+        );
+    }
 }
@@ -1,4 +1,5 @@
 //! Contains the [ValidatorDispatcher], that manages [LinkValidator]s and iterates over and consumes a [`CdpArray<T>`], dispatching the data to the correct thread based on the Link ID running an instance of [LinkValidator].
+use super::rdh::valid_link_id;
 use crate::util::*;
 
 type CdpTuple<T> = (T, Vec<u8>, u64);
@@ -19,6 +20,8 @@ pub struct ValidatorDispatcher<T: RDH, C: Config + 'static> {
 enum DispatchId {
     FeeId(u16),
     GbtLink(u16),
+    /// Catch-all for RDHs with a link ID outside the valid range, avoids spawning a validator for each corrupted link ID
+    InvalidGbtLink,
 }
 
 impl DispatchId {
@@ -26,6 +29,7 @@ impl DispatchId {
     pub fn number(&self) -> u16 {
         match self {
             DispatchId::FeeId(x) | DispatchId::GbtLink(x) => *x,
+            DispatchId::InvalidGbtLink => u16::MAX,
         }
     }
 }
@@ -35,6 +39,7 @@ impl fmt::Display for DispatchId {
         match self {
             DispatchId::FeeId(id) => write!(f, "FEE ID {id}"),
             DispatchId::GbtLink(id) => write!(f, "GBT Link {id}"),
+            DispatchId::InvalidGbtLink => write!(f, "GBT Links out of range"),
         }
     }
 }
@@ -74,7 +79,13 @@ impl<T: RDH + 'static, C: Config + 'static> ValidatorDispatcher<T, C> {
             // Dispatch by FEE ID if system targeted for checks is ITS Stave (gonna be a lot of data to parse for each stave!)
             let id = match self.dispatch_by {
                 DispatchId::FeeId(_) => DispatchId::FeeId(rdh.fee_id()),
-                DispatchId::GbtLink(_) => DispatchId::GbtLink(rdh.link_id() as u16),
+                DispatchId::GbtLink(_) | DispatchId::InvalidGbtLink => {
+                    if valid_link_id(rdh.link_id()) {
+                        DispatchId::GbtLink(rdh.link_id() as u16)
+                    } else {
+                        DispatchId::InvalidGbtLink
+                    }
+                }
             };
 
             self.dispatch_by_id(rdh, data, mem_pos, id);
@@ -174,7 +185,7 @@ impl<T: RDH + 'static, C: Config + 'static> ValidatorDispatcher<T, C> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use alice_protocol_reader::prelude::test_data::CORRECT_RDH_CRU_V7;
+    use alice_protocol_reader::prelude::test_data::{RdhCruBuilder, CORRECT_RDH_CRU_V7};
 
     static CFG_TEST_DISPACTER: OnceLock<MockConfig> = OnceLock::new();
 
@@ -196,4 +207,35 @@ mod tests {
 
         disp.join();
     }
+
+    #[test]
+    fn test_dispatch_invalid_link_ids_to_catch_all() {
+        static CFG_TEST_DISPATCH_INVALID_LINK: OnceLock<MockConfig> = OnceLock::new();
+        let mut cfg = MockConfig::new();
+        cfg.check = Some(CheckCommands::Sanity(CheckModeArgs::default()));
+        CFG_TEST_DISPATCH_INVALID_LINK.set(cfg).unwrap();
+
+        let (stats_send, stats_recv) = flume::unbounded();
+        let mut disp: ValidatorDispatcher<RdhCru, MockConfig> =
+            ValidatorDispatcher::new(CFG_TEST_DISPATCH_INVALID_LINK.get().unwrap(), stats_send);
+
+        let rdh_with_link_id = |link_id| RdhCruBuilder::new().link_id(link_id).build();
+
+        let mut cdp_array = CdpArray::<RdhCru, 3>::new();
+        cdp_array.push(rdh_with_link_id(0), vec![], 0);
+        cdp_array.push(rdh_with_link_id(20), vec![], 0x40);
+        cdp_array.push(rdh_with_link_id(200), vec![], 0x80);
+        disp.dispatch_cdp_batch(cdp_array);
+
+        // One validator for link 0 and one catch-all validator for the invalid link IDs
+        assert_eq!(disp.processors.len(), 2);
+        disp.join();
+        drop(disp);
+
+        let link_id_errors = stats_recv
+            .try_iter()
+            .filter(|stat| matches!(stat, StatType::Error(e) if e.contains("[E36]")))
+            .count();
+        assert_eq!(link_id_errors, 2);
+    }
 }
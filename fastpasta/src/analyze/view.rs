@@ -1,4 +1,9 @@
 //! Contains the view generators for the human readable data views.
+pub mod alpide_view;
+mod ddw0_view;
+pub mod det_field_status_view;
+pub mod hbf_view;
 mod its_readout_frame;
 pub mod lib;
+pub mod rdh_html_view;
 mod rdh_view;
@@ -0,0 +1,180 @@
+//! Contains the [AlpideView] that prints the decoded ALPIDE chip headers and trailers of each ITS readout frame.
+//!
+//! The ALPIDE data of a readout frame is collected from the data words between the TDH that starts the frame and the TDT with packet done that ends it.
+//! Each lane is then decoded in the same way as when checking ALPIDE data with `check all its-stave`,
+//! and a row is printed for each chip with its chip ID, bunch counter, and the readout flags of its Chip Trailer.
+
+use super::its_readout_frame::{LANE_FAULTS_RED, MEM_POS_RED};
+use crate::util::*;
+use io::Write;
+
+const CHIP_TRAILER_BUSY_VIOLATION: u8 = 0b1000;
+const CHIP_TRAILER_DATA_OVERRUN: u8 = 0b1100;
+const CHIP_TRAILER_TRANSMISSION_IN_FATAL: u8 = 0b1110;
+
+/// Collects the ALPIDE data of readout frames that can span several CDPs, and prints the decoded chip data when a frame ends.
+pub struct AlpideView {
+    readout_frame: Option<AlpideReadoutFrame>,
+    disable_styled_view: bool,
+}
+
+impl AlpideView {
+    /// Creates the view and prints the header text.
+    pub fn new(disable_styled_view: bool) -> Result<Self, io::Error> {
+        print_alpide_header_text(&mut io::stdout().lock(), disable_styled_view)?;
+        Ok(Self {
+            readout_frame: None,
+            disable_styled_view,
+        })
+    }
+
+    /// Collects the ALPIDE data from the CDPs, printing each readout frame that ends in them.
+    pub fn view_cdps<T: RDH, const CAP: usize>(
+        &mut self,
+        cdp_array: &CdpArray<T, CAP>,
+    ) -> Result<(), Box<dyn error::Error>> {
+        let mut stdio_lock = io::stdout().lock();
+        for (rdh, payload, rdh_mem_pos) in cdp_array.iter() {
+            let layer = Layer::from_stave(&Stave::from_feeid(rdh.fee_id()));
+            let gbt_word_chunks = preprocess_payload(payload)?;
+            for (idx, gbt_word) in gbt_word_chunks.enumerate() {
+                let word = &gbt_word[..10];
+                let mem_pos = calc_current_word_mem_pos(idx, rdh.data_format(), rdh_mem_pos);
+                match ItsPayloadWord::from_id(word[9]) {
+                    // A TDH in continuation mode continues the current frame
+                    Ok(ItsPayloadWord::TDH)
+                        if self.readout_frame.is_none()
+                            && Tdh::from_buf(word)?.continuation() == 0 =>
+                    {
+                        self.readout_frame = Some(AlpideReadoutFrame::new(mem_pos));
+                    }
+                    Ok(ItsPayloadWord::DataWord) => {
+                        if let Some(frame) = self.readout_frame.as_mut() {
+                            frame.store_lane_data(word, layer);
+                        }
+                    }
+                    Ok(ItsPayloadWord::TDT) if Tdt::from_buf(word)?.packet_done() => {
+                        if let Some(mut frame) = self.readout_frame.take() {
+                            frame.close_frame(mem_pos);
+                            self.print_frame(&frame, rdh.fee_id(), &mut stdio_lock)?;
+                        }
+                    }
+                    _ => (),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn print_frame(
+        &self,
+        frame: &AlpideReadoutFrame,
+        fee_id: u16,
+        stdio_lock: &mut io::StdoutLock,
+    ) -> Result<(), io::Error> {
+        let mem_pos_str = format!("{:>8X}:", frame.start_mem_pos());
+        if frame.is_empty() {
+            let row = format!("{fee_id:>6}  No ALPIDE data in readout frame");
+            return self.print_row(&mem_pos_str, &row, true, stdio_lock);
+        }
+        let layer = frame.from_layer();
+        for lane_data_frame in frame.lane_data_frames_as_slice() {
+            let lane = lane_data_frame.lane_number(layer);
+            let mut analyzer = LaneAlpideFrameAnalyzer::new(layer, None, None);
+            analyzer.decode_alpide_frame(lane_data_frame);
+            for chip in analyzer.chip_data() {
+                let bunch_counter = chip
+                    .bunch_counter
+                    .map_or_else(|| "-".to_string(), |bc| bc.to_string());
+                let readout_flags = if chip.empty_frame {
+                    "Empty frame".to_string()
+                } else {
+                    chip.readout_flags
+                        .map_or_else(|| "No trailer".to_string(), readout_flags_as_string)
+                };
+                let is_flagged = !chip.empty_frame && chip.readout_flags != Some(0);
+                let row = format!(
+                    "{fee_id:>6}  {lane:>4}  {chip_id:>7}  {bunch_counter:>13}  {readout_flags}",
+                    chip_id = chip.chip_id
+                );
+                self.print_row(&mem_pos_str, &row, is_flagged, stdio_lock)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn print_row(
+        &self,
+        mem_pos_str: &str,
+        row: &str,
+        is_flagged: bool,
+        stdio_lock: &mut io::StdoutLock,
+    ) -> Result<(), io::Error> {
+        if self.disable_styled_view {
+            writeln!(stdio_lock, "{mem_pos_str} {row}")
+        } else if is_flagged {
+            writeln!(
+                stdio_lock,
+                "{} {}",
+                mem_pos_str.white().bg_rgb::<MEM_POS_RED, 0, 0>(),
+                row.white().bold().bg_rgb::<LANE_FAULTS_RED, 0, 0>()
+            )
+        } else {
+            writeln!(
+                stdio_lock,
+                "{} {row}",
+                mem_pos_str.white().bg_rgb::<MEM_POS_RED, 0, 0>()
+            )
+        }
+    }
+}
+
+fn print_alpide_header_text(
+    stdio_lock: &mut io::StdoutLock,
+    disable_styled_view: bool,
+) -> Result<(), io::Error> {
+    let header = "Memory    FEE ID  Lane  Chip ID  Bunch counter  Readout flags";
+    if disable_styled_view {
+        writeln!(stdio_lock, "\n{header}\nPosition\n")?;
+    } else {
+        writeln!(stdio_lock, "\n{}\nPosition\n", header.bold().white())?;
+    }
+    Ok(())
+}
+
+/// Describes the readout flags \[3:0\] of a Chip Trailer
+fn readout_flags_as_string(readout_flags: u8) -> String {
+    match readout_flags {
+        0 => "-".to_string(),
+        CHIP_TRAILER_BUSY_VIOLATION => "Busy violation".to_string(),
+        CHIP_TRAILER_DATA_OVERRUN => "Data overrun".to_string(),
+        CHIP_TRAILER_TRANSMISSION_IN_FATAL => "Transmission in fatal".to_string(),
+        // Any other combination of flags is a combination of the flags below
+        flags => [
+            (0b0100, "Flushed incomplete"),
+            (0b0010, "Strobe extended"),
+            (0b0001, "Busy transition"),
+        ]
+        .iter()
+        .filter(|(flag, _)| flags & flag != 0)
+        .map(|(_, description)| *description)
+        .join(", "),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_readout_flags_as_string() {
+        assert_eq!(readout_flags_as_string(0), "-");
+        assert_eq!(readout_flags_as_string(0b1000), "Busy violation");
+        assert_eq!(readout_flags_as_string(0b1110), "Transmission in fatal");
+        assert_eq!(
+            readout_flags_as_string(0b0101),
+            "Flushed incomplete, Busy transition"
+        );
+    }
+}
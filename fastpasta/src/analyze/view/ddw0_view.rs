@@ -0,0 +1,88 @@
+//! Contains the [ddw0_view] that prints the status of each lane reported by the ITS DDW0 words.
+//!
+//! Each DDW0 is printed as a row with its memory position, FEE ID, link ID, the status of each of the 28 lanes, and the most severe lane status.
+
+use super::its_readout_frame::{LANE_FAULTS_RED, MEM_POS_RED};
+use crate::util::*;
+use crate::words::its::status_words::util as sw_util;
+use io::Write;
+
+const LANE_STATUS_LEGEND: &str =
+    "Lane status (lanes 0-27, `-` OK, `W` warning, `E` error, `F` fatal)";
+
+pub(crate) fn ddw0_view<T: RDH, const CAP: usize>(
+    cdp_array: &CdpArray<T, CAP>,
+    disable_styled_view: bool,
+) -> Result<(), Box<dyn error::Error>> {
+    let mut stdio_lock = io::stdout().lock();
+    print_ddw0_header_text(&mut stdio_lock, disable_styled_view)?;
+    for (rdh, payload, rdh_mem_pos) in cdp_array.iter() {
+        let gbt_word_chunks = preprocess_payload(payload)?;
+        for (idx, gbt_word) in gbt_word_chunks.enumerate() {
+            let word = &gbt_word[..10];
+            if !matches!(ItsPayloadWord::from_id(word[9]), Ok(ItsPayloadWord::DDW0)) {
+                continue;
+            }
+            let mem_pos = calc_current_word_mem_pos(idx, rdh.data_format(), rdh_mem_pos);
+            print_ddw0_row(
+                word,
+                mem_pos,
+                rdh.fee_id(),
+                rdh.link_id(),
+                &mut stdio_lock,
+                disable_styled_view,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn print_ddw0_header_text(
+    stdio_lock: &mut io::StdoutLock,
+    disable_styled_view: bool,
+) -> Result<(), io::Error> {
+    let header = format!("Memory    FEE ID  Link  {LANE_STATUS_LEGEND}");
+    if disable_styled_view {
+        writeln!(stdio_lock, "\n{header}\nPosition\n")?;
+    } else {
+        writeln!(stdio_lock, "\n{}\nPosition\n", header.bold().white())?;
+    }
+    Ok(())
+}
+
+fn print_ddw0_row(
+    ddw0_slice: &[u8],
+    mem_pos: u64,
+    fee_id: u16,
+    link_id: u8,
+    stdio_lock: &mut io::StdoutLock,
+    disable_styled_view: bool,
+) -> Result<(), io::Error> {
+    let lane_statuses: String = sw_util::ddw0_tdt_lane_statuses(ddw0_slice)
+        .map(sw_util::lane_status_as_char)
+        .collect();
+    let most_severe = sw_util::ddw0_tdt_lane_status_as_string(ddw0_slice);
+    let mem_pos_str = format!("{mem_pos:>8X}:");
+    let ddw0_info_row = format!("{fee_id:>6}  {link_id:>4}  {lane_statuses}  {most_severe}");
+
+    if disable_styled_view {
+        writeln!(stdio_lock, "{mem_pos_str} {ddw0_info_row}")?;
+    } else if lane_statuses.chars().all(|status| status == '-') {
+        writeln!(
+            stdio_lock,
+            "{} {ddw0_info_row}",
+            mem_pos_str.white().bg_rgb::<MEM_POS_RED, 0, 0>()
+        )?;
+    } else {
+        writeln!(
+            stdio_lock,
+            "{} {}",
+            mem_pos_str.white().bg_rgb::<MEM_POS_RED, 0, 0>(),
+            ddw0_info_row
+                .white()
+                .bold()
+                .bg_rgb::<LANE_FAULTS_RED, 0, 0>()
+        )?;
+    }
+    Ok(())
+}
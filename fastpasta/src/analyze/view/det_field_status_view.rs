@@ -0,0 +1,123 @@
+//! Contains the [DetFieldStatusView] that prints a timeline of the lane status reported in the RDH detector field.
+//!
+//! The lane status (fatal, error, warning, missing data) is decoded from the detector field of each RDH,
+//! and a row is only printed when the status of a FEE ID changes, i.e. the first RDH of each FEE ID and each transition after that.
+
+use super::its_readout_frame::{LANE_FAULTS_RED, MEM_POS_RED};
+use super::lib::rdh_detector_field_lane_status_as_string;
+use crate::util::*;
+use io::Write;
+use std::collections::HashMap;
+
+/// Keeps the last lane status of each FEE ID between batches of CDPs, and prints the RDHs where the status changes.
+pub struct DetFieldStatusView {
+    last_status: HashMap<u16, Box<str>>,
+    disable_styled_view: bool,
+}
+
+impl DetFieldStatusView {
+    /// Creates the view and prints the header text.
+    pub fn new(disable_styled_view: bool) -> Result<Self, io::Error> {
+        print_det_field_status_header_text(&mut io::stdout().lock(), disable_styled_view)?;
+        Ok(Self {
+            last_status: HashMap::new(),
+            disable_styled_view,
+        })
+    }
+
+    /// Prints a row for each RDH in the CDPs where the lane status of its FEE ID changes.
+    pub fn view_cdps<T: RDH, const CAP: usize>(
+        &mut self,
+        cdp_array: &CdpArray<T, CAP>,
+    ) -> Result<(), io::Error> {
+        let mut stdio_lock = io::stdout().lock();
+        for (rdh, &rdh_mem_pos) in cdp_array
+            .rdh_slice()
+            .iter()
+            .zip(cdp_array.rdh_mem_pos_slice())
+        {
+            let status = rdh_detector_field_lane_status_as_string(rdh);
+            if self.record_status(rdh.fee_id(), &status) {
+                self.print_row(rdh, rdh_mem_pos, &status, &mut stdio_lock)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Records the lane status of a FEE ID, returns true if it differs from the last status of the FEE ID or if it is the first status seen for it.
+    fn record_status(&mut self, fee_id: u16, status: &str) -> bool {
+        if self
+            .last_status
+            .get(&fee_id)
+            .is_some_and(|last_status| **last_status == *status)
+        {
+            return false;
+        }
+        _ = self.last_status.insert(fee_id, status.into());
+        true
+    }
+
+    fn print_row<T: RDH>(
+        &self,
+        rdh: &T,
+        rdh_mem_pos: u64,
+        status: &str,
+        stdio_lock: &mut io::StdoutLock,
+    ) -> Result<(), io::Error> {
+        let orbit = rdh.rdh1().orbit;
+        let orbit_str = format!("{orbit:>#10x}");
+        let mem_pos_str = format!("{rdh_mem_pos:>8X}:");
+        let status_row = format!("{fee_id:>6}  {status}", fee_id = rdh.fee_id());
+        if self.disable_styled_view {
+            writeln!(stdio_lock, "{orbit_str}  {mem_pos_str} {status_row}")
+        } else if status.trim_end() == "-" {
+            writeln!(
+                stdio_lock,
+                "{orbit_str}  {} {status_row}",
+                mem_pos_str.white().bg_rgb::<MEM_POS_RED, 0, 0>()
+            )
+        } else {
+            writeln!(
+                stdio_lock,
+                "{orbit_str}  {} {}",
+                mem_pos_str.white().bg_rgb::<MEM_POS_RED, 0, 0>(),
+                status_row.white().bold().bg_rgb::<LANE_FAULTS_RED, 0, 0>()
+            )
+        }
+    }
+}
+
+fn print_det_field_status_header_text(
+    stdio_lock: &mut io::StdoutLock,
+    disable_styled_view: bool,
+) -> Result<(), io::Error> {
+    let header = "     Orbit  Memory    FEE ID  Lane status";
+    let sub_header = "            Position";
+    if disable_styled_view {
+        writeln!(stdio_lock, "\n{header}\n{sub_header}\n")?;
+    } else {
+        writeln!(stdio_lock, "\n{}\n{sub_header}\n", header.bold().white())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_status_only_transitions() {
+        let mut view = DetFieldStatusView {
+            last_status: HashMap::new(),
+            disable_styled_view: true,
+        };
+        // The first status of each FEE ID is always recorded
+        assert!(view.record_status(524, "-      "));
+        assert!(view.record_status(268, "-      "));
+        assert!(!view.record_status(524, "-      "));
+        assert!(view.record_status(524, "Error  "));
+        assert!(!view.record_status(524, "Error  "));
+        assert!(!view.record_status(268, "-      "));
+        assert!(view.record_status(524, "-      "));
+    }
+}
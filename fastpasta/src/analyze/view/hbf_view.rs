@@ -0,0 +1,210 @@
+//! Contains the [HbfView] that groups the RDHs into HeartBeat Frames (HBFs) and prints a block for each HBF.
+//!
+//! The RDHs of an HBF are the RDHs of a FEE ID from the first RDH after a stop bit, up to and including the next RDH with the stop bit set.
+//! The HBFs of different FEE IDs can be interleaved, so an HBF is kept for each FEE ID until its RDH with the stop bit is seen.
+//! An HBF that ends without a stop bit, because the orbit changes or the data ends, is printed as incomplete.
+
+use super::its_readout_frame::LANE_FAULTS_RED;
+use super::lib::trigger_type_string_from_int;
+use crate::util::*;
+use io::Write;
+use std::collections::HashMap;
+
+/// An HBF reconstructed from the RDHs of a FEE ID
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Hbf {
+    orbit: u32,
+    fee_id: u16,
+    link_id: u8,
+    first_mem_pos: u64,
+    last_mem_pos: u64,
+    rdh_count: u32,
+    payload_size: u64,
+    /// Trigger type of the first RDH of the HBF
+    trigger_type: u32,
+    /// Set if the HBF ended with an RDH with the stop bit set
+    complete: bool,
+}
+
+impl Hbf {
+    fn new<T: RDH>(rdh: &T, mem_pos: u64) -> Self {
+        Self {
+            orbit: rdh.rdh1().orbit,
+            fee_id: rdh.fee_id(),
+            link_id: rdh.link_id(),
+            first_mem_pos: mem_pos,
+            last_mem_pos: mem_pos,
+            rdh_count: 0,
+            payload_size: 0,
+            trigger_type: rdh.trigger_type(),
+            complete: false,
+        }
+    }
+
+    fn add_rdh<T: RDH>(&mut self, rdh: &T, mem_pos: u64) {
+        self.last_mem_pos = mem_pos;
+        self.rdh_count += 1;
+        self.payload_size += u64::from(rdh.payload_size());
+        self.complete = rdh.stop_bit() == 1;
+    }
+}
+
+/// Keeps the HBF of each FEE ID between batches of CDPs, and prints each HBF when its last RDH is seen.
+pub struct HbfView {
+    hbfs: HashMap<u16, Hbf>,
+    disable_styled_view: bool,
+}
+
+impl HbfView {
+    /// Creates the view and prints the header text.
+    pub fn new(disable_styled_view: bool) -> Result<Self, io::Error> {
+        print_hbf_header_text(&mut io::stdout().lock(), disable_styled_view)?;
+        Ok(Self {
+            hbfs: HashMap::new(),
+            disable_styled_view,
+        })
+    }
+
+    /// Adds the RDHs of the CDPs to the HBFs, printing each HBF that ends in them.
+    pub fn view_cdps<T: RDH, const CAP: usize>(
+        &mut self,
+        cdp_array: &CdpArray<T, CAP>,
+    ) -> Result<(), io::Error> {
+        let mut stdio_lock = io::stdout().lock();
+        for (rdh, &rdh_mem_pos) in cdp_array
+            .rdh_slice()
+            .iter()
+            .zip(cdp_array.rdh_mem_pos_slice())
+        {
+            for hbf in self.record_rdh(rdh, rdh_mem_pos) {
+                self.print_hbf(&hbf, &mut stdio_lock)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Prints the HBFs that did not end with an RDH with the stop bit set, ordered by memory position.
+    pub fn finish(self) -> Result<(), io::Error> {
+        let mut stdio_lock = io::stdout().lock();
+        for hbf in self.hbfs.values().sorted_by_key(|hbf| hbf.first_mem_pos) {
+            self.print_hbf(hbf, &mut stdio_lock)?;
+        }
+        Ok(())
+    }
+
+    /// Adds an RDH to the HBF of its FEE ID, returns the HBFs that ended.
+    ///
+    /// If the orbit of the RDH differs from the open HBF of its FEE ID, the open HBF ended without a stop bit.
+    fn record_rdh<T: RDH>(&mut self, rdh: &T, mem_pos: u64) -> Vec<Hbf> {
+        let mut ended_hbfs = Vec::new();
+        let orbit = rdh.rdh1().orbit;
+        if self
+            .hbfs
+            .get(&rdh.fee_id())
+            .is_some_and(|open_hbf| open_hbf.orbit != orbit)
+        {
+            ended_hbfs.extend(self.hbfs.remove(&rdh.fee_id()));
+        }
+        let hbf = self
+            .hbfs
+            .entry(rdh.fee_id())
+            .or_insert_with(|| Hbf::new(rdh, mem_pos));
+        hbf.add_rdh(rdh, mem_pos);
+        if hbf.complete {
+            ended_hbfs.push(*hbf);
+            _ = self.hbfs.remove(&rdh.fee_id());
+        }
+        ended_hbfs
+    }
+
+    fn print_hbf(&self, hbf: &Hbf, stdio_lock: &mut io::StdoutLock) -> Result<(), io::Error> {
+        let header = format!(
+            "HBF  Orbit {orbit:#x}  FEE ID {fee_id}  Link {link_id}",
+            orbit = hbf.orbit,
+            fee_id = hbf.fee_id,
+            link_id = hbf.link_id
+        );
+        let incomplete_note = "(incomplete, no RDH with stop bit)";
+        match (self.disable_styled_view, hbf.complete) {
+            (true, true) => writeln!(stdio_lock, "{header}")?,
+            (true, false) => writeln!(stdio_lock, "{header}  {incomplete_note}")?,
+            (false, true) => writeln!(stdio_lock, "{}", header.bold().white())?,
+            (false, false) => writeln!(
+                stdio_lock,
+                "{}  {}",
+                header.bold().white(),
+                incomplete_note
+                    .white()
+                    .bold()
+                    .bg_rgb::<LANE_FAULTS_RED, 0, 0>()
+            )?,
+        }
+        writeln!(
+            stdio_lock,
+            "     Memory position  {:X} - {:X}",
+            hbf.first_mem_pos, hbf.last_mem_pos
+        )?;
+        writeln!(stdio_lock, "     RDHs             {}", hbf.rdh_count)?;
+        writeln!(stdio_lock, "     Payload          {} B", hbf.payload_size)?;
+        writeln!(
+            stdio_lock,
+            "     Trigger type     {:#X} {}\n",
+            hbf.trigger_type,
+            trigger_type_string_from_int(hbf.trigger_type).trim_end()
+        )
+    }
+}
+
+fn print_hbf_header_text(
+    stdio_lock: &mut io::StdoutLock,
+    disable_styled_view: bool,
+) -> Result<(), io::Error> {
+    let header = "HeartBeat Frames, the trigger type is from the first RDH of each HBF";
+    if disable_styled_view {
+        writeln!(stdio_lock, "\n{header}\n")
+    } else {
+        writeln!(stdio_lock, "\n{}\n", header.bold().white())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alice_protocol_reader::prelude::test_data::RdhCruBuilder;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_record_rdh_groups_rdhs_until_stop_bit() {
+        let mut view = HbfView {
+            hbfs: HashMap::new(),
+            disable_styled_view: true,
+        };
+        let rdh = RdhCruBuilder::new().orbit(0x200).stop_bit(0).build();
+        let rdh_stop = RdhCruBuilder::new().orbit(0x200).stop_bit(1).build();
+        assert!(view.record_rdh(&rdh, 0).is_empty());
+        assert!(view.record_rdh(&rdh, 0x40).is_empty());
+        let ended = view.record_rdh(&rdh_stop, 0x80);
+        assert_eq!(ended.len(), 1);
+        assert!(ended[0].complete);
+        assert_eq!(ended[0].rdh_count, 3);
+        assert_eq!((ended[0].first_mem_pos, ended[0].last_mem_pos), (0, 0x80));
+        assert!(view.hbfs.is_empty());
+    }
+
+    #[test]
+    fn test_record_rdh_orbit_change_ends_hbf() {
+        let mut view = HbfView {
+            hbfs: HashMap::new(),
+            disable_styled_view: true,
+        };
+        let rdh = RdhCruBuilder::new().orbit(0x200).stop_bit(0).build();
+        let rdh_next_orbit = RdhCruBuilder::new().orbit(0x201).stop_bit(0).build();
+        assert!(view.record_rdh(&rdh, 0).is_empty());
+        // The stop bit of orbit 0x200 is missing
+        let ended = view.record_rdh(&rdh_next_orbit, 0x40);
+        assert_eq!(ended.len(), 1);
+        assert!(!ended[0].complete);
+        assert_eq!(ended[0].orbit, 0x200);
+        assert_eq!(view.hbfs.len(), 1);
+    }
+}
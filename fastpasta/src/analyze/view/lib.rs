@@ -14,19 +14,30 @@ pub fn generate_view<T: RDH, const CAP: usize>(
 ) -> Result<(), Box<dyn error::Error>> {
     let disable_styled_view = Cfg::global().disable_styled_views();
     match view {
+        ViewCommands::Rdh if Cfg::global().view_rdh_compact() => {
+            super::rdh_view::rdh_compact_view(cdp_array, disable_styled_view)?
+        }
         ViewCommands::Rdh => super::rdh_view::rdh_view(cdp_array, disable_styled_view)?,
         ViewCommands::ItsReadoutFrames => its_readout_frame_view(cdp_array, disable_styled_view)?,
         ViewCommands::ItsReadoutFramesData => {
             its_readout_frame_data_view(cdp_array, disable_styled_view)?
         }
+        ViewCommands::Ddw0 => super::ddw0_view::ddw0_view(cdp_array, disable_styled_view)?,
+        ViewCommands::Alpide => {
+            unreachable!(
+                "The ALPIDE view keeps state between batches and is generated by an AlpideView"
+            )
+        }
+        ViewCommands::DetFieldStatus => {
+            unreachable!("The detector field status view keeps state between batches and is generated by a DetFieldStatusView")
+        }
+        ViewCommands::Hbf => {
+            unreachable!("The HBF view keeps state between batches and is generated by an HbfView")
+        }
     }
     Ok(())
 }
 
-const PHT_BIT_MASK: u32 = 0b1_0000;
-const SOC_BIT_MASK: u32 = 0b10_0000_0000;
-const SOT_BIT_MASK: u32 = 0b1000_0000;
-const HB_BIT_MASK: u32 = 0b10;
 /// Takes in an RDH and returns a human readable description of the trigger type
 pub fn rdh_trigger_type_as_string<T: RDH>(rdh: &T) -> Box<str> {
     let trigger_type = rdh.trigger_type();
@@ -56,19 +67,29 @@ pub fn rdh_detector_field_lane_status_as_string<T: RDH>(rdh: &T) -> Box<str> {
 ///
 /// A trigger can be a combination of different types of triggers, so the description is
 /// prioritized in terms of what triggers are more significant to understand the trigger type
+///
+/// The trigger type is decoded with the configured [TriggerTypeMasks].
 pub fn trigger_type_string_from_int(trigger_type: u32) -> Box<str> {
+    trigger_type_string_from_int_with_masks(trigger_type, trigger_type_masks())
+}
+
+/// Takes in a [u32] and returns a human readable description of the trigger type, decoded with the given [TriggerTypeMasks]
+pub fn trigger_type_string_from_int_with_masks(
+    trigger_type: u32,
+    masks: &TriggerTypeMasks,
+) -> Box<str> {
     // Priorities describing the trigger as follows:
     // 1. SOC
     // 2. SOT
     // 3. HB
     // 4. PhT
-    let trigger_type_string = if trigger_type & SOC_BIT_MASK != 0 {
+    let trigger_type_string = if trigger_type & masks.soc != 0 {
         String::from("SOC  ")
-    } else if trigger_type & SOT_BIT_MASK != 0 {
+    } else if trigger_type & masks.sot != 0 {
         String::from("SOT  ")
-    } else if trigger_type & HB_BIT_MASK != 0 {
+    } else if trigger_type & masks.hb != 0 {
         String::from("HB   ")
-    } else if trigger_type & PHT_BIT_MASK != 0 {
+    } else if trigger_type & masks.pht != 0 {
         String::from("PhT  ")
     } else {
         String::from("Other")
@@ -113,3 +134,29 @@ pub fn format_word_slice(word_slice: &[u8]) -> String {
         word_slice[9],
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trigger_type_string_default_masks() {
+        assert_eq!(&*trigger_type_string_from_int(0b1_0000), "PhT  ");
+        assert_eq!(&*trigger_type_string_from_int(0b1000), "Other");
+        // SOC has priority over PhT
+        assert_eq!(&*trigger_type_string_from_int(0b10_0001_0000), "SOC  ");
+    }
+
+    #[test]
+    fn test_trigger_type_string_overridden_pht_mask() {
+        let masks = TriggerTypeMasks::from_toml_str("pht = 0b1000").unwrap();
+        assert_eq!(
+            &*trigger_type_string_from_int_with_masks(0b1000, &masks),
+            "PhT  "
+        );
+        assert_eq!(
+            &*trigger_type_string_from_int_with_masks(0b1_0000, &masks),
+            "Other"
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! Contains the [RdhHtmlView] that writes the RDH view as a self-contained HTML table.
+//!
+//! RDHs that fail the RDH sanity check are highlighted, with the error message as the tooltip of the row.
+use crate::util::*;
+use io::Write;
+
+const HTML_HEAD: &str = r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>fastPASTA RDH view</title>
+<style>
+table { border-collapse: collapse; font-family: monospace; }
+th, td { padding: 2px 8px; text-align: left; white-space: nowrap; }
+"#;
+
+// Same colors as the styled RDH view in the terminal
+const STYLED_CSS: &str = r#"th { color: white; }
+th:nth-child(even) { background: rgb(0, 99, 0); }
+th:nth-child(odd) { background: rgb(0, 0, 99); }
+td:first-child { background: rgb(51, 0, 51); color: white; font-weight: bold; }
+tr.error td { background: rgb(139, 0, 0); color: white; }
+"#;
+
+const UNSTYLED_CSS: &str = r#"th, td { border: 1px solid; }
+tr.error td { font-weight: bold; }
+"#;
+
+const TABLE_HEADER_COLUMNS: [&str; 15] = [
+    "Memory position",
+    "RDH version",
+    "Header size",
+    "FEE ID",
+    "System ID",
+    "Offset next",
+    "Link ID",
+    "Packet counter",
+    "BC",
+    "Orbit counter",
+    "Data format",
+    "Trigger type",
+    "Pages counter",
+    "Stop bit",
+    "Detector field",
+];
+
+const HTML_TAIL: &str = "</tbody>\n</table>\n</body>\n</html>\n";
+
+/// Writes RDHs as rows of an HTML table to a file.
+///
+/// The HTML is completed when [RdhHtmlView::finish] is called.
+pub struct RdhHtmlView<T: RDH> {
+    writer: io::BufWriter<fs::File>,
+    sanity_validator: RdhCruSanityValidator<T>,
+}
+
+impl<T: RDH> RdhHtmlView<T> {
+    /// Creates the HTML file and writes the head of the document and the table header.
+    ///
+    /// If `styled` is false, the table is not color-coded.
+    pub fn new(path: &Path, styled: bool) -> io::Result<Self> {
+        let mut writer = io::BufWriter::new(fs::File::create(path)?);
+        writer.write_all(HTML_HEAD.as_bytes())?;
+        writer.write_all(if styled { STYLED_CSS } else { UNSTYLED_CSS }.as_bytes())?;
+        writeln!(writer, "</style>\n</head>\n<body>\n<table>\n<thead>\n<tr>")?;
+        for column in TABLE_HEADER_COLUMNS {
+            writeln!(writer, "<th>{column}</th>")?;
+        }
+        writeln!(writer, "</tr>\n</thead>\n<tbody>")?;
+        Ok(Self {
+            writer,
+            sanity_validator: RdhCruSanityValidator::default(),
+        })
+    }
+
+    /// Write a table row for each RDH in the [CdpArray].
+    pub fn write_rows<const CAP: usize>(&mut self, cdp_array: &CdpArray<T, CAP>) -> io::Result<()> {
+        for (rdh, _, mem_pos) in cdp_array {
+            self.write_row(rdh, mem_pos)?;
+        }
+        Ok(())
+    }
+
+    fn write_row(&mut self, rdh: &T, mem_pos: u64) -> io::Result<()> {
+        match self.sanity_validator.sanity_check(rdh) {
+            Ok(_) => writeln!(self.writer, "<tr>")?,
+            Err(e) => writeln!(
+                self.writer,
+                "<tr class=\"error\" title=\"{}\">",
+                escape_html(&e)
+            )?,
+        }
+        let rdh0 = rdh.rdh0();
+        let (header_size, system_id) = (rdh0.header_size, rdh0.system_id);
+        let orbit = rdh.rdh1().orbit;
+        let detector_field = rdh.rdh3().detector_field;
+        let fields: [String; 15] = [
+            format!("{mem_pos:X}"),
+            rdh.version().to_string(),
+            header_size.to_string(),
+            rdh.fee_id().to_string(),
+            system_id.to_string(),
+            rdh.offset_to_next().to_string(),
+            rdh.link_id().to_string(),
+            rdh.packet_counter().to_string(),
+            rdh.rdh1().bc().to_string(),
+            format!("{orbit:#x}"),
+            rdh.data_format().to_string(),
+            format!("{:#x}", rdh.trigger_type()),
+            rdh.pages_counter().to_string(),
+            rdh.stop_bit().to_string(),
+            format!("{detector_field:#x}"),
+        ];
+        for field in fields {
+            writeln!(self.writer, "<td>{field}</td>")?;
+        }
+        writeln!(self.writer, "</tr>")
+    }
+
+    /// Close the table and the document, and flush it to the file.
+    pub fn finish(mut self) -> io::Result<()> {
+        self.writer.write_all(HTML_TAIL.as_bytes())?;
+        self.writer.flush()
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alice_protocol_reader::prelude::test_data::{RdhCruBuilder, CORRECT_RDH_CRU_V7};
+    use temp_dir::TempDir;
+
+    #[test]
+    fn test_rdh_html_view() {
+        let tmp_d = TempDir::new().unwrap();
+        let html_path = tmp_d.child("rdh_view.html");
+        let mut cdp_array = CdpArray::<RdhCru, 2>::new();
+        cdp_array.push(CORRECT_RDH_CRU_V7, vec![], 0);
+        cdp_array.push(CORRECT_RDH_CRU_V7, vec![], 0x40);
+
+        let mut html_view = RdhHtmlView::new(&html_path, true).unwrap();
+        html_view.write_rows(&cdp_array).unwrap();
+        html_view.finish().unwrap();
+
+        let html = fs::read_to_string(html_path).unwrap();
+        println!("{html}");
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.trim_end().ends_with("</html>"));
+        assert_eq!(html.matches("<tr>").count(), 3); // Header + 2 RDHs
+        assert_eq!(html.matches("<td>").count(), 2 * TABLE_HEADER_COLUMNS.len());
+        assert!(html.contains("<td>40</td>"));
+        assert!(!html.contains("class=\"error\""));
+    }
+
+    #[test]
+    fn test_rdh_html_view_error_highlighted_unstyled() {
+        let tmp_d = TempDir::new().unwrap();
+        let html_path = tmp_d.child("rdh_view.html");
+        let mut cdp_array = CdpArray::<RdhCru, 2>::new();
+        // Header size is 0x20 instead of 0x40
+        let bad_rdh = RdhCruBuilder::new().header_size(0x20).build();
+        cdp_array.push(bad_rdh, vec![], 0);
+
+        let mut html_view = RdhHtmlView::new(&html_path, false).unwrap();
+        html_view.write_rows(&cdp_array).unwrap();
+        html_view.finish().unwrap();
+
+        let html = fs::read_to_string(html_path).unwrap();
+        println!("{html}");
+        assert!(html.contains("<tr class=\"error\" title=\"[E10] RDH sanity check failed"));
+        assert!(!html.contains("rgb("));
+    }
+}
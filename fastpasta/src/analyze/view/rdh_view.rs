@@ -1,3 +1,4 @@
+use super::lib::rdh_trigger_type_as_string;
 use crate::util::*;
 use io::Write;
 
@@ -28,3 +29,67 @@ pub(crate) fn rdh_view<T: RDH, const CAP: usize>(
 
     Ok(())
 }
+
+/// Prints a single line per RDH with the memory position, version, FEE ID, link ID, trigger type, stop bit, page counter and payload size
+pub(crate) fn rdh_compact_view<T: RDH, const CAP: usize>(
+    cdp_array: &CdpArray<T, CAP>,
+    disable_styled_view: bool,
+) -> Result<(), io::Error> {
+    let mut stdio_lock = io::stdout().lock();
+
+    let header_text = compact_header_text();
+    if disable_styled_view {
+        writeln!(stdio_lock, "{header_text}")?;
+    } else {
+        writeln!(stdio_lock, "{}", header_text.bold().white())?;
+    }
+    for (rdh, _, mem_pos) in cdp_array {
+        let row = compact_rdh_row(rdh);
+        if disable_styled_view {
+            writeln!(stdio_lock, "{mem_pos:>8X}:  {row}")?;
+        } else {
+            writeln!(
+                stdio_lock,
+                "{memory_position} {row}",
+                memory_position = format_args!("{mem_pos:>8X}: ").bg_rgb::<51, 0, 51>().bold(),
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+fn compact_header_text() -> String {
+    format!(
+        "{:<11}{:>3}  {:>6}  {:>4}  {:<7}  {:>4}  {:>4}  {:>12}",
+        "Memory", "Ver", "FEE ID", "Link", "Trigger", "Stop", "Page", "Payload size"
+    )
+}
+
+fn compact_rdh_row<T: RDH>(rdh: &T) -> String {
+    format!(
+        "{version:>3}  {fee_id:>6}  {link_id:>4}  {trigger_type:<7}  {stop_bit:>4}  {page:>4}  {payload_size:>12}",
+        version = rdh.version(),
+        fee_id = rdh.fee_id(),
+        link_id = rdh.link_id(),
+        trigger_type = rdh_trigger_type_as_string(rdh),
+        stop_bit = rdh.stop_bit(),
+        page = rdh.pages_counter(),
+        payload_size = rdh.payload_size(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alice_protocol_reader::prelude::test_data::CORRECT_RDH_CRU_V7;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_compact_row_aligned_with_header() {
+        let row = format!("{:>8X}:  {}", 0xA0, compact_rdh_row(&CORRECT_RDH_CRU_V7));
+        println!("{}\n{row}", compact_header_text());
+        assert_eq!(row.len(), compact_header_text().len());
+        assert!(row.starts_with("      A0:    7"));
+    }
+}
@@ -13,10 +13,13 @@ use clap_complete::Shell;
 
 pub mod check;
 pub mod custom_checks;
+pub mod dry_run;
+pub mod filter_config;
 pub mod inputoutput;
 pub mod lib;
 pub mod prelude;
 pub mod test_util;
+pub mod trigger_type_masks;
 pub mod util;
 pub mod view;
 /// The [CONFIG] static variable is used to store the [Cfg] created from the parsed command line arguments
@@ -38,11 +41,53 @@ readable way, or filter the data.\n\
 Project home page: https://gitlab.cern.ch/mkonig/fastpasta"
 )]
 #[command(propagate_version = true)]
+#[command(subcommand_precedence_over_arg = true)]
 #[command(styles = lib::styles())]
 pub struct Cfg {
-    /// Input file (default: stdin)
+    /// Input file(s) (default: stdin), multiple files are read in order as one continuous stream
     #[arg(name = "Raw Data", global = true, value_hint = clap::ValueHint::FilePath)]
-    file: Option<PathBuf>,
+    files: Vec<PathBuf>,
+
+    /// Decompress gzip-compressed input from stdin (input files are detected as gzip-compressed by the `.gz` extension or gzip magic bytes)
+    #[arg(long, global = true, default_value_t = false)]
+    gzip: bool,
+
+    /// Decompress Zstandard-compressed input from stdin (input files are detected as Zstandard-compressed by the `.zst` extension or zstd magic bytes)
+    #[arg(long, global = true, default_value_t = false, conflicts_with = "gzip")]
+    zstd: bool,
+
+    /// Keep reading the input file as it grows, like `tail -f`, until stopped with Ctrl+C
+    #[arg(long, global = true, default_value_t = false)]
+    follow: bool,
+
+    /// Stop gracefully if no data is received for this long, e.g. 30s, for a live input that can stall, requires the `check` or `view` subcommand
+    #[arg(long, global = true, value_parser = parse_seconds, value_name = "SECONDS")]
+    recv_timeout: Option<Duration>,
+
+    /// If the first RDH fails the sanity check, e.g. because of junk at the start of the input, skip ahead byte by byte to the first valid RDH within the first MiB
+    #[arg(
+        long,
+        global = true,
+        default_value_t = false,
+        conflicts_with = "force_rdh_version"
+    )]
+    scan_for_rdh: bool,
+
+    /// Start reading the input file(s) at a byte offset, decimal or hex (e.g. 0x4A0000). The first RDH is expected at the offset, memory positions stay relative to the start of the input
+    #[arg(long, global = true, value_parser = parse_byte_offset, value_name = "OFFSET")]
+    input_offset: Option<u64>,
+
+    /// Stop reading after the CDP that reaches a number of bytes from the start (or the `--input-offset`), e.g. 512KB, 1MB or 2GiB, to quickly sample a large input
+    #[arg(long, global = true, value_parser = parse_byte_size, value_name = "SIZE")]
+    max_bytes: Option<u64>,
+
+    /// Only forward every Nth CDP to the sanity checks or view, e.g. 100 for a quick overview of a huge input. The RDHs of all CDPs are still counted in the stats
+    #[arg(long, global = true, value_name = "N")]
+    sample_every: Option<u64>,
+
+    /// Byte order of the words in the input data, big-endian RDHs and payloads are converted to little-endian as they are read
+    #[arg(long, global = true, default_value_t = Endianness::Little, value_name = "little|big")]
+    endianness: Endianness,
 
     /// Commands such as `Check` or `View` that accepts further subcommands
     #[command(subcommand)]
@@ -62,6 +107,14 @@ pub struct Cfg {
     )]
     max_tolerate_errors: u32,
 
+    /// Max errors to tolerate on each link (or FEE ID with `check all its-stave`), the rest of the data of a link that reaches it is not checked. Can be combined with `--max-tolerate-errors`, which still stops processing when the total errors reach it
+    #[arg(long, global = true, value_name = "N")]
+    max_errors_per_link: Option<u32>,
+
+    /// Report warnings about the input data, e.g. when the ITS payload state machine is reset, as errors that count towards the error total and the exit code, requires the `check` subcommand
+    #[arg(long, global = true, default_value_t = false)]
+    warnings_as_errors: bool,
+
     /// Set the exit code for if any errors are detected in the input data (cannot be 0)
     #[arg(
         short = 'E',
@@ -71,25 +124,41 @@ pub struct Cfg {
     )]
     any_errors_exit_code: Option<u8>,
 
-    /// Set CRU link ID to filter by (e.g. 5)
+    /// Set the exit code for if errors with a specific error code are detected (e.g. E30=5 or E30=5,E44=6).
+    /// If errors with several of the error codes are detected, the first mapping takes precedence. Takes precedence over the any errors exit code
+    #[arg(
+        long,
+        global = true,
+        value_delimiter = ',',
+        value_parser = parse_error_code_exit_code,
+        value_name = "ERROR_CODE=EXIT_CODE"
+    )]
+    exit_code_on_error_code: Vec<(u16, u8)>,
+
+    /// Set CRU link IDs to filter by (e.g. 5 or 0,3,7)
     #[arg(
         short = 'f',
         long,
         visible_alias = "link",
         global = true,
-        group = "filter"
+        group = "filter",
+        value_delimiter = ',',
+        value_name = "LINK_IDS"
     )]
-    filter_link: Option<u8>,
+    filter_link: Vec<u8>,
 
-    /// Set FEE ID to filter by (e.g. 20522)
+    /// Set FEE IDs to filter by, comma-separated FEE IDs and inclusive ranges (e.g. 20522,20523 or 20500-20600)
     #[arg(
         short = 'F',
         long,
         visible_alias = "fee",
         global = true,
-        group = "filter"
+        group = "filter",
+        value_delimiter = ',',
+        value_parser = parse_fee_id_range,
+        value_name = "FEE_IDS"
     )]
-    filter_fee: Option<u16>,
+    filter_fee: Vec<(u16, u16)>,
 
     /// Set ITS layer & stave to filter by (e.g. L5_42)
     #[arg(
@@ -102,25 +171,126 @@ pub struct Cfg {
     )]
     filter_its_stave: Option<String>,
 
+    /// Set trigger type to filter by, RDHs are kept if the trigger type bit is set (PhT, SOC, SOT or HB)
+    #[arg(long, global = true, value_name = "TRIGGER_TYPE")]
+    filter_trigger_type: Option<TriggerTypeMnemonic>,
+
+    /// Set an inclusive orbit range to filter by (e.g. 192796000-192796100), RDHs are kept if their orbit is within the range
+    #[arg(long, global = true, value_parser = parse_orbit_range, value_name = "START-END")]
+    filter_orbit_range: Option<(u32, u32)>,
+
+    /// Path to a TOML file with filters (links, fee, its_stave, trigger_type, orbit_range), filters set on the command line take precedence
+    #[arg(
+        long,
+        global = true,
+        value_hint = clap::ValueHint::FilePath,
+        value_name = "TOML FILE"
+    )]
+    filter_config: Option<PathBuf>,
+
+    /// Set an upper bound in bytes on the payload size of RDHs (e.g. 8192), larger payloads are reported as an error and skipped
+    #[arg(long, global = true, value_name = "BYTES")]
+    max_payload_size: Option<u16>,
+
+    /// Extract the RDHs at the specified 0-based indices (e.g. 5,17,42) and write them with their payloads to the output
+    #[arg(
+        long,
+        global = true,
+        group = "filter",
+        value_delimiter = ',',
+        num_args = 1..,
+        value_name = "INDICES"
+    )]
+    extract_rdhs: Vec<u64>,
+
     /// Enables checks on the ITS trigger period with the specified value, usable with the `check all its-stave` command
     #[arg(short = 'p', long, global = true, requires = "FILTER-ITS-STAVE")]
     its_trigger_period: Option<u16>,
 
+    /// Tally the frequency of each GBT word ID (10th byte) across all payloads and print a sorted histogram, requires the `check` subcommand
+    #[arg(long, global = true, default_value_t = false)]
+    word_id_histogram: bool,
+
+    /// Tally the trigger periods detected between consecutive TDHs with internal trigger set and print a histogram, requires the `check all its` or `check all its-stave` command
+    #[arg(long, global = true, default_value_t = false)]
+    trigger_period_histogram: bool,
+
+    /// Include the N GBT words before and after the offending word in ITS payload error messages, requires checking ITS or ITS-stave
+    #[arg(long, global = true, value_name = "N")]
+    error_context: Option<u8>,
+
+    /// Collect ALPIDE stats (e.g. busy violations) by decoding only the chip trailers, without the ALPIDE frame checks of `check all its-stave`, requires the `check all its` command
+    #[arg(long, global = true, default_value_t = false)]
+    alpide_stats_only: bool,
+
+    /// Allow the data format of the RDHs to change within the input, instead of reporting it as an error as it usually indicates corrupted data
+    #[arg(long, global = true, default_value_t = false)]
+    allow_mixed_data_format: bool,
+
+    /// Warn if more than one CRU ID is observed, for data that is expected to come from a single CRU
+    #[arg(long, global = true, default_value_t = false)]
+    expect_single_cru: bool,
+
+    /// Check that the end of payload 0xFF padding is consistent with the data format of ITS payloads and report malformed padding as an error, requires checking ITS or ITS-stave
+    #[arg(long, global = true, default_value_t = false)]
+    strict_padding: bool,
+
+    /// Check that the reserved bits of all ITS status words (IHW, TDH, TDT, DDW0 & CDW) are 0, reported with an error code per status word, requires checking ITS or ITS-stave
+    #[arg(long, global = true, default_value_t = false)]
+    check_reserved: bool,
+
+    /// Write the ITS payload words with an unrecognized ID to a file for offline study, and their memory position, FEE ID, link ID, and error code to a CSV file next to it (`<FILE stem>_index.csv`), requires checking ITS or ITS-stave
+    #[arg(long, global = true, value_name = "FILE")]
+    dump_ambiguous: Option<PathBuf>,
+
+    /// Only check the data words from the staves of an ITS barrel: `inner` (layers 0-2), `middle` (layers 3-4), `outer` (layers 5-6), or `all` (default), requires the `check all its-stave` command
+    #[arg(long, global = true, default_value_t = ItsBarrel::All, value_name = "BARREL")]
+    its_barrel: ItsBarrel,
+
     /// Output raw data (default: stdout), requires setting a filter option. If Checks or Views are enabled, the output is supressed.
     #[arg(
         name = "OUTPUT DATA",
         short = 'o',
         long = "output",
         visible_alias = "out",
-        global = true,
-        requires = "filter"
+        global = true
     )]
     output: Option<PathBuf>,
 
+    /// Split the output data into one file per link, e.g. `-o out.raw` writes `out_link0.raw`, `out_link3.raw`, etc.
+    #[arg(long, default_value_t = false, global = true, requires = "OUTPUT DATA")]
+    split_by_link: bool,
+
+    /// Split the output data into one file per HBF, e.g. `-o out.raw` writes `out_hbf0.raw`, `out_hbf1.raw`, etc. An HBF ends at an RDH with the stop bit set
+    #[arg(
+        long,
+        default_value_t = false,
+        global = true,
+        requires = "OUTPUT DATA",
+        conflicts_with = "split_by_link"
+    )]
+    split_by_hbf: bool,
+
+    /// Format of the output data: `raw` (default) writes the bytes as they were read, `ascii` writes a human-readable dump of each CDP, the decoded RDH followed by the payload words in hex
+    #[arg(long, global = true, default_value_t = DumpFormat::Raw, value_name = "FORMAT")]
+    dump_format: DumpFormat,
+
+    /// Read back the output data file after it is written, and report an error if it does not contain all the filtered RDHs, e.g. due to a truncated write
+    #[arg(long, default_value_t = false, global = true, requires = "OUTPUT DATA")]
+    verify_output: bool,
+
     /// Don't show error messages - helpful if there's a large amount of errors and you just want to see the report
     #[arg(short, long, default_value_t = false, global = true)]
     mute_errors: bool,
 
+    /// Only show the first N error messages, the rest are still counted and reported in the stats, followed by the number of error messages not shown
+    #[arg(long, global = true, value_name = "N")]
+    first_n_errors: Option<u32>,
+
+    /// Only print a one-line verdict, `PASS` or `FAIL (N errors)`, instead of the error messages and the report. The stats are still written with `--output-stats`. Exits with the `--any-errors-exit-code` (default: 1) if any errors are detected, requires the `check` subcommand
+    #[arg(long, default_value_t = false, global = true)]
+    quiet_stats: bool,
+
     /// Generate a check TOML file in the current directory that can be used as a template to configure checks against the raw data.
     #[arg(short, long, default_value_t = false, global = true, visible_aliases = ["gen-toml", "gen-checks"],)]
     generate_checks_toml: bool,
@@ -135,6 +305,16 @@ pub struct Cfg {
       )]
     checks_toml: Option<PathBuf>,
 
+    /// Path to a TOML file that overrides the bit masks used to decode the RDH trigger type (pht, soc, sot, hb).
+    /// Masks that are not specified keep their default value.
+    #[arg(
+        long,
+        global = true,
+        value_hint = clap::ValueHint::FilePath,
+        value_name = "TOML FILE"
+    )]
+    trigger_type_masks: Option<PathBuf>,
+
     /// Output stats (default: none), requires setting a data format option (JSON, TOML)
     #[arg(
         name = "OUTPUT FINAL STATS",
@@ -147,19 +327,27 @@ pub struct Cfg {
     )]
     stats_output: DataOutputMode,
 
-    /// Output stats format (JSON/TOML), requires setting a stats output option
+    /// Output stats format (JSON/TOML/CSV), requires setting a stats output option
     #[arg(
         name = "STATS FORMAT",
         short = 'D',
         long = "stats-format",
-        visible_alias = "stats-data-format",
+        visible_aliases = ["stats-data-format", "stats-output-format"],
         global = true,
         requires = "OUTPUT FINAL STATS"
     )]
     stats_output_format: Option<DataOutputFormat>,
 
+    /// Pretty-print all JSON output, default is compact JSON
+    #[arg(long, global = true, default_value_t = false)]
+    json_pretty: bool,
+
+    /// Periodically write a snapshot of the stats collected so far to the stats output file, e.g. every 5s, requires writing the stats to a file
+    #[arg(long, global = true, value_parser = parse_seconds, value_name = "SECONDS")]
+    stats_flush_interval: Option<Duration>,
+
     /// Input stats file to read from and verify match with collected stats at end of analysis.
-    /// The file extension determines the format (JSON/TOML)
+    /// The file extension determines the format (JSON/TOML/CSV)
     #[arg(
         name = "INPUT STATS FILE",
         short = 'i',
@@ -182,6 +370,20 @@ pub struct Cfg {
     )]
     show_error_codes: Vec<String>,
 
+    /// Ignore errors with the specified error codes (e.g. E44,E45), they are neither displayed nor counted
+    #[arg(
+        long,
+        global = true,
+        value_delimiter = ',',
+        value_parser = parse_error_code,
+        value_name = "ERROR_CODES"
+    )]
+    mute_error_codes: Vec<u16>,
+
+    /// Format of the error messages: `human` (default) prints them at the end of processing, `json` writes each error to stderr as one JSON object as soon as it is reported
+    #[arg(long, global = true, default_value_t = ErrorFormat::Human, value_name = "FORMAT")]
+    error_format: ErrorFormat,
+
     /// Generate completion scripts for the specified shell.
     /// Note: The completion script is printed to stdout
     #[arg(
@@ -191,9 +393,62 @@ pub struct Cfg {
     )]
     pub generate_completions: Option<clap_complete::Shell>,
 
+    /// Print a condensed per-link status line with RDH and error counts to stderr, refreshed every second, requires the `check` or `view` subcommand
+    #[arg(long, global = true, default_value_t = false)]
+    live_status: bool,
+
+    /// Show a progress bar of the bytes read on stderr, if input is from stdin a counter of the bytes read is shown instead
+    #[arg(
+        long,
+        global = true,
+        default_value_t = false,
+        conflicts_with = "live_status"
+    )]
+    progress: bool,
+
+    /// Assume the specified RDH version (e.g. 7) instead of detecting it from the first RDH, a failed sanity check of the first RDH is then only a warning
+    #[arg(long, global = true, value_name = "VERSION")]
+    force_rdh_version: Option<u8>,
+
+    /// How strict the sanity check of the first RDH is: `strict` (default) aborts on any unexpected value, `lenient` only warns about unusual but non-fatal values, e.g. an unexpected header size
+    #[arg(long, global = true, default_value_t = RdhSanity::Strict, value_name = "PROFILE")]
+    rdh_sanity: RdhSanity,
+
+    /// Log how often and for how long the reader was stalled waiting for the analysis to catch up, counting blocks longer than the threshold in milliseconds (e.g. 10)
+    #[arg(long, global = true, value_name = "MS")]
+    reader_stall_threshold: Option<u64>,
+
+    /// Process the input files independently of each other, using up to N files processed concurrently. Requires the `check` subcommand and input files
+    #[arg(long, global = true, value_name = "N")]
+    jobs: Option<usize>,
+
     /// Output from view commands is styled by default, set this flag to disable styled views
     #[arg(short = 'd', long, global = true, default_value_t = false)]
     disable_styled_views: bool,
+
+    /// Disable colors in error messages and views. Error messages are only colored if stderr is a terminal
+    #[arg(long, global = true, default_value_t = false)]
+    no_color: bool,
+
+    /// Prefix the log messages on stderr with a timestamp, e.g. for long monitoring sessions
+    #[arg(long, global = true, default_value_t = false)]
+    log_timestamps: bool,
+
+    /// Also write the log messages to a file, each prefixed with a timestamp. The file is appended to if it exists
+    #[arg(long, global = true, value_name = "PATH")]
+    log_file: Option<PathBuf>,
+
+    /// Print a summary of the input, checks, view, filters, and output that the command would use, then exit without reading any data
+    #[arg(long, global = true, default_value_t = false)]
+    dry_run: bool,
+
+    /// Write the output of `view rdh` to an HTML file instead of stdout
+    #[arg(long = "html", global = true, value_hint = clap::ValueHint::FilePath, value_name = "HTML FILE")]
+    view_html: Option<PathBuf>,
+
+    /// Print `view rdh` with a single line per RDH (memory position, version, FEE ID, link ID, trigger type, stop bit, page counter and payload size)
+    #[arg(long = "compact", global = true, conflicts_with = "view_html")]
+    view_rdh_compact: bool,
 }
 
 impl Cfg {
@@ -214,6 +469,50 @@ impl Cfg {
             self.generate_custom_checks_toml("custom_checks.toml");
         }
     }
+
+    /// If a trigger type masks TOML file is specified, parse it and set the trigger type masks used for decoding trigger types.
+    pub fn handle_trigger_type_masks(&self) -> Result<(), String> {
+        if let Some(masks_toml) = &self.trigger_type_masks {
+            trigger_type_masks::set_trigger_type_masks(
+                trigger_type_masks::TriggerTypeMasks::from_toml_path(masks_toml)?,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// If a filter config TOML file is specified, merge its filters into the filters set on the command line.
+    ///
+    /// Filters set on the command line take precedence, and if any of the link, FEE ID, or ITS stave filters are set on the command line,
+    /// the link, FEE ID, and ITS stave filters of the file are ignored, as only one of them can be set.
+    pub fn handle_filter_config(&mut self) -> Result<(), String> {
+        // An invalid path is reported when validating the arguments
+        let Some(toml_path) = self.filter_config.as_ref().filter(|path| path.is_file()) else {
+            return Ok(());
+        };
+        let filter_config = filter_config::FilterConfig::from_toml_path(toml_path)?;
+        let cli_target_filter_set = !self.filter_link.is_empty()
+            || !self.filter_fee.is_empty()
+            || self.filter_its_stave.is_some();
+        if cli_target_filter_set {
+            if filter_config.has_target_filter() {
+                log::info!("Link, FEE ID, and ITS stave filters of the filter config are overridden by the command line");
+            }
+        } else {
+            self.filter_link = filter_config.links.clone().unwrap_or_default();
+            self.filter_fee = filter_config
+                .fee
+                .map(|fee_id| vec![(fee_id, fee_id)])
+                .unwrap_or_default();
+            self.filter_its_stave = filter_config.its_stave.clone();
+        }
+        if self.filter_trigger_type.is_none() {
+            self.filter_trigger_type = filter_config.trigger_type()?;
+        }
+        if self.filter_orbit_range.is_none() {
+            self.filter_orbit_range = filter_config.orbit_range;
+        }
+        Ok(())
+    }
 }
 
 impl Cfg {
@@ -237,17 +536,34 @@ impl ViewOpt for Cfg {
         if let Some(sub_cmd) = &self.cmd {
             match sub_cmd {
                 Command::View(view_sub_cmd) => Some(view_sub_cmd.cmd),
-                Command::Check(_) => None,
+                Command::Check(_)
+                | Command::CompareRaw(_)
+                | Command::MergeStats
+                | Command::Diff(_)
+                | Command::PrintStatsSchema
+                | Command::ListSystems => None,
             }
         } else {
             None
         }
     }
+
+    #[inline]
+    fn view_html_output(&self) -> Option<&Path> {
+        self.view_html.as_deref()
+    }
+
+    #[inline]
+    fn view_rdh_compact(&self) -> bool {
+        self.view_rdh_compact
+    }
 }
 
 impl FilterOpt for Cfg {
     fn skip_payload(&self) -> bool {
         match (self.view(), self.check(), self.output_mode()) {
+            // The word ID histogram is made from the payloads
+            _ if self.word_id_histogram => false,
             // Skip payload in these cases
             (Some(ViewCommands::Rdh), _, _) => true,
             (_, Some(CheckCommands::All(arg)), _) | (_, Some(CheckCommands::Sanity(arg)), _)
@@ -255,18 +571,39 @@ impl FilterOpt for Cfg {
             {
                 true
             }
+            (_, Some(CheckCommands::Ordering(_)), _) => true,
             // Don't skip payload in all other cases than above
             (_, _, _) => false,
         }
     }
 
     #[inline]
-    fn filter_link(&self) -> Option<u8> {
-        self.filter_link
+    fn filter_links(&self) -> Option<&[u8]> {
+        if self.filter_link.is_empty() {
+            None
+        } else {
+            Some(&self.filter_link)
+        }
+    }
+
+    fn filter_fee(&self) -> Option<&[(u16, u16)]> {
+        if self.filter_fee.is_empty() {
+            None
+        } else {
+            Some(&self.filter_fee)
+        }
     }
 
-    fn filter_fee(&self) -> Option<u16> {
-        self.filter_fee
+    fn filter_trigger_type(&self) -> Option<u32> {
+        self.filter_trigger_type.map(|mnemonic| mnemonic.mask())
+    }
+
+    fn filter_orbit_range(&self) -> Option<(u32, u32)> {
+        self.filter_orbit_range
+    }
+
+    fn max_payload_size(&self) -> Option<u16> {
+        self.max_payload_size
     }
 
     fn filter_its_stave(&self) -> Option<u16> {
@@ -292,8 +629,14 @@ impl ChecksOpt for Cfg {
                 Command::Check(checks) => match checks.cmd.clone() {
                     CheckCommands::All(arg) => Some(CheckCommands::All(arg)),
                     CheckCommands::Sanity(arg) => Some(CheckCommands::Sanity(arg)),
+                    CheckCommands::Ordering(arg) => Some(CheckCommands::Ordering(arg)),
                 },
-                Command::View(_) => None,
+                Command::View(_)
+                | Command::CompareRaw(_)
+                | Command::MergeStats
+                | Command::Diff(_)
+                | Command::PrintStatsSchema
+                | Command::ListSystems => None,
             }
         } else {
             None
@@ -303,12 +646,84 @@ impl ChecksOpt for Cfg {
     fn check_its_trigger_period(&self) -> Option<u16> {
         self.its_trigger_period
     }
+
+    fn word_id_histogram(&self) -> bool {
+        self.word_id_histogram
+    }
+
+    fn trigger_period_histogram(&self) -> bool {
+        self.trigger_period_histogram
+    }
+
+    fn error_context(&self) -> Option<u8> {
+        self.error_context
+    }
+
+    fn alpide_stats_only(&self) -> bool {
+        self.alpide_stats_only
+    }
+
+    fn allow_mixed_data_format(&self) -> bool {
+        self.allow_mixed_data_format
+    }
+
+    fn expect_single_cru(&self) -> bool {
+        self.expect_single_cru
+    }
+
+    fn strict_padding(&self) -> bool {
+        self.strict_padding
+    }
+    fn check_reserved(&self) -> bool {
+        self.check_reserved
+    }
+    fn dump_ambiguous(&self) -> Option<&Path> {
+        self.dump_ambiguous.as_deref()
+    }
+    fn its_barrel(&self) -> ItsBarrel {
+        self.its_barrel
+    }
 }
 
 impl InputOutputOpt for Cfg {
     #[inline]
-    fn input_file(&self) -> Option<&Path> {
-        self.file.as_deref()
+    fn input_files(&self) -> &[PathBuf] {
+        &self.files
+    }
+    #[inline]
+    fn gzip(&self) -> bool {
+        self.gzip
+    }
+    #[inline]
+    fn zstd(&self) -> bool {
+        self.zstd
+    }
+    #[inline]
+    fn follow(&self) -> bool {
+        self.follow
+    }
+    #[inline]
+    fn recv_timeout(&self) -> Option<Duration> {
+        self.recv_timeout
+    }
+
+    fn scan_for_rdh(&self) -> bool {
+        self.scan_for_rdh
+    }
+    #[inline]
+    fn input_offset(&self) -> Option<u64> {
+        self.input_offset
+    }
+    #[inline]
+    fn max_bytes(&self) -> Option<u64> {
+        self.max_bytes
+    }
+    fn sample_every(&self) -> Option<u64> {
+        self.sample_every
+    }
+    #[inline]
+    fn endianness(&self) -> Endianness {
+        self.endianness
     }
     #[inline]
     fn output(&self) -> Option<&Path> {
@@ -322,6 +737,14 @@ impl InputOutputOpt for Cfg {
             if self.output().as_ref().unwrap().to_str() == Some("stdout") {
                 DataOutputMode::Stdout
             }
+            // if output is set to a file path and split by link, output to one file per link
+            else if self.split_by_link {
+                DataOutputMode::SplitByLink(self.output().unwrap().into())
+            }
+            // if output is set to a file path and split by HBF, output to one file per HBF
+            else if self.split_by_hbf {
+                DataOutputMode::SplitByHbf(self.output().unwrap().into())
+            }
             // if output is set and a file path is given, output to file
             else {
                 DataOutputMode::File(self.output().unwrap().into())
@@ -337,6 +760,34 @@ impl InputOutputOpt for Cfg {
         }
     }
 
+    #[inline]
+    fn split_by_link(&self) -> bool {
+        self.split_by_link
+    }
+
+    #[inline]
+    fn split_by_hbf(&self) -> bool {
+        self.split_by_hbf
+    }
+
+    #[inline]
+    fn dump_format(&self) -> DumpFormat {
+        self.dump_format
+    }
+
+    #[inline]
+    fn verify_output(&self) -> bool {
+        self.verify_output
+    }
+
+    fn extract_rdhs(&self) -> Option<&[u64]> {
+        if self.extract_rdhs.is_empty() {
+            None
+        } else {
+            Some(&self.extract_rdhs)
+        }
+    }
+
     fn stats_output_mode(&self) -> DataOutputMode {
         self.stats_output.clone()
     }
@@ -345,9 +796,52 @@ impl InputOutputOpt for Cfg {
         self.stats_output_format
     }
 
+    fn json_pretty(&self) -> bool {
+        self.json_pretty
+    }
+
+    fn stats_flush_interval(&self) -> Option<Duration> {
+        self.stats_flush_interval
+    }
+
     fn input_stats_file(&self) -> Option<&Path> {
         self.input_stats_file.as_deref()
     }
+
+    fn filter_config_file(&self) -> Option<&Path> {
+        self.filter_config.as_deref()
+    }
+
+    fn compare_raw_files(&self) -> Option<(&Path, &Path)> {
+        if let Some(Command::CompareRaw(args)) = &self.cmd {
+            Some((args.golden.as_path(), args.candidate.as_path()))
+        } else {
+            None
+        }
+    }
+
+    fn merge_stats_files(&self) -> Option<&[PathBuf]> {
+        if let Some(Command::MergeStats) = &self.cmd {
+            Some(&self.files)
+        } else {
+            None
+        }
+    }
+
+    fn diff_stats_files(&self) -> Option<(&Path, &Path)> {
+        if let Some(Command::Diff(args)) = &self.cmd {
+            Some((args.old.as_path(), args.new.as_path()))
+        } else {
+            None
+        }
+    }
+
+    fn diff_ignore_fields(&self) -> Option<&[String]> {
+        match &self.cmd {
+            Some(Command::Diff(args)) if !args.ignore.is_empty() => Some(&args.ignore),
+            _ => None,
+        }
+    }
 }
 
 impl UtilOpt for Cfg {
@@ -359,12 +853,24 @@ impl UtilOpt for Cfg {
     fn max_tolerate_errors(&self) -> u32 {
         self.max_tolerate_errors
     }
+    fn max_errors_per_link(&self) -> Option<u32> {
+        self.max_errors_per_link
+    }
+    fn warnings_as_errors(&self) -> bool {
+        self.warnings_as_errors
+    }
     fn any_errors_exit_code(&self) -> Option<u8> {
         self.any_errors_exit_code
     }
     fn mute_errors(&self) -> bool {
         self.mute_errors
     }
+    fn first_n_errors(&self) -> Option<u32> {
+        self.first_n_errors
+    }
+    fn quiet_stats(&self) -> bool {
+        self.quiet_stats
+    }
 
     fn error_code_filter(&self) -> Option<&[String]> {
         if self.show_error_codes.is_empty() {
@@ -374,9 +880,78 @@ impl UtilOpt for Cfg {
         }
     }
 
+    fn error_format(&self) -> ErrorFormat {
+        self.error_format
+    }
+
+    fn mute_error_codes(&self) -> Option<&[u16]> {
+        if self.mute_error_codes.is_empty() {
+            None
+        } else {
+            Some(&self.mute_error_codes)
+        }
+    }
+
     #[inline]
     fn disable_styled_views(&self) -> bool {
-        self.disable_styled_views
+        self.disable_styled_views || self.no_color
+    }
+
+    #[inline]
+    fn no_color(&self) -> bool {
+        self.no_color
+    }
+
+    fn log_timestamps(&self) -> bool {
+        self.log_timestamps
+    }
+
+    fn log_file(&self) -> Option<&Path> {
+        self.log_file.as_deref()
+    }
+
+    fn live_status(&self) -> bool {
+        self.live_status
+    }
+
+    fn show_progress(&self) -> bool {
+        self.progress
+    }
+
+    fn force_rdh_version(&self) -> Option<u8> {
+        self.force_rdh_version
+    }
+
+    fn rdh_sanity(&self) -> RdhSanity {
+        self.rdh_sanity
+    }
+
+    fn reader_stall_threshold(&self) -> Option<Duration> {
+        self.reader_stall_threshold.map(Duration::from_millis)
+    }
+
+    fn jobs(&self) -> Option<usize> {
+        self.jobs
+    }
+
+    fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    fn print_stats_schema(&self) -> bool {
+        matches!(self.cmd, Some(Command::PrintStatsSchema))
+    }
+
+    fn list_systems(&self) -> bool {
+        matches!(self.cmd, Some(Command::ListSystems))
+    }
+
+    fn error_code_exit_codes(&self) -> Option<&[(u16, u8)]> {
+        if self.exit_code_on_error_code.is_empty() {
+            None
+        } else {
+            Some(&self.exit_code_on_error_code)
+        }
     }
 }
 
@@ -415,6 +990,36 @@ impl CustomChecksOpt for Cfg {
         }
     }
 
+    fn hbfs(&'static self) -> Option<u32> {
+        if self.checks_toml.is_some() {
+            self.custom_checks()
+                .expect("Custom checks are not initialized")
+                .hbfs()
+        } else {
+            None
+        }
+    }
+
+    fn expected_links(&'static self) -> Option<&'static [u8]> {
+        if self.checks_toml.is_some() {
+            self.custom_checks()
+                .expect("Custom checks are not initialized")
+                .expected_links()
+        } else {
+            None
+        }
+    }
+
+    fn layer_stave_counts(&'static self) -> Option<&'static [(u8, u8)]> {
+        if self.checks_toml.is_some() {
+            self.custom_checks()
+                .expect("Custom checks are not initialized")
+                .layer_stave_counts()
+        } else {
+            None
+        }
+    }
+
     fn rdh_version(&'static self) -> Option<u8> {
         if self.checks_toml.is_some() {
             self.custom_checks()
@@ -463,6 +1068,31 @@ pub struct ViewArgs {
     cmd: ViewCommands,
 }
 
+/// Holds the reference and candidate files for the `compare-raw` subcommand
+#[derive(Debug, Args, Clone)]
+pub struct CompareRawArgs {
+    /// Reference raw data file the candidate is expected to match
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    golden: PathBuf,
+    /// Raw data file to compare against the reference
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    candidate: PathBuf,
+}
+
+/// Holds the stats files and the fields to ignore for the `diff` subcommand
+#[derive(Debug, Args, Clone)]
+pub struct DiffArgs {
+    /// Stats file (JSON/TOML) to compare against, e.g. from before a change
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    old: PathBuf,
+    /// Stats file (JSON/TOML) to compare, e.g. from after a change
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    new: PathBuf,
+    /// Stats fields that are expected to differ and are not compared, named as in `print-stats-schema` e.g. `rdh_stats.payload_size,error_stats`
+    #[arg(long, value_delimiter = ',', value_name = "FIELD")]
+    ignore: Vec<String>,
+}
+
 #[derive(Debug, Subcommand, Clone)]
 /// Subcommands to enable checks or views, needs to be followed by a [CheckCommands] (and optionally a target system) or [ViewCommands] subcommand.
 pub enum Command {
@@ -472,6 +1102,19 @@ pub enum Command {
     /// Enable view mode, requires a `focus` type subcommand e.g. `rdh` or `its-readout-frames`
     #[command(arg_required_else_help = true)]
     View(ViewArgs),
+    /// Compare a candidate raw data file against a reference (golden) file RDH-by-RDH and payload-by-payload, and report the first divergence
+    #[command(arg_required_else_help = true)]
+    CompareRaw(CompareRawArgs),
+    /// Merge the stats files (JSON/TOML) given as input files, e.g. written with `--output-stats` from chunks of a run, into one that is written to the file set with `-o` (default: stdout). The extension of the output file determines the format (JSON/TOML/CSV, default: JSON)
+    #[command(arg_required_else_help = true)]
+    MergeStats,
+    /// Compare two stats files (JSON/TOML) written with `--output-stats` field by field and print the fields that differ. Exits with 1 (or the `--any-errors-exit-code`) if any field differs
+    #[command(arg_required_else_help = true)]
+    Diff(DiffArgs),
+    /// Print the schema version and the fields with their types of the stats written with `--output-stats` (JSON/TOML), e.g. for tools consuming the stats to detect changes
+    PrintStatsSchema,
+    /// List the detector systems recognized from the system ID of the RDHs, with their numeric system ID and the `check` targets that support them
+    ListSystems,
 }
 
 impl CheckCommands {
@@ -479,13 +1122,124 @@ impl CheckCommands {
     pub fn target(&self) -> Option<check::System> {
         match self {
             CheckCommands::All(arg) | CheckCommands::Sanity(arg) => arg.target,
+            CheckCommands::Ordering(_) => None,
         }
     }
 }
 
-/// Get the [config][super::config::Cfg] from the command line arguments and set the static [CONFIG] variable.
+/// Prefix of the environment variables that can be used to set options, e.g. `FASTPASTA_FILTER_LINK=3`
+pub const ENV_VAR_PREFIX: &str = "FASTPASTA_";
+
+/// Get the name of the environment variable for an option, from the long name (or ID) of the option.
+///
+/// e.g. `filter-link` -> `FASTPASTA_FILTER_LINK`
+pub fn env_var_name(arg_name: &str) -> String {
+    let name: String = arg_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{ENV_VAR_PREFIX}{name}")
+}
+
+impl Cfg {
+    /// Parse the config from command line arguments, with environment variables as fallback.
+    ///
+    /// Options not set on the command line are read from `FASTPASTA_<OPTION>` environment variables, e.g. `FASTPASTA_FILTER_LINK=3`.
+    /// If no subcommand is given on the command line, it is read from `FASTPASTA_CHECK` (e.g. `all its`) or `FASTPASTA_VIEW` (e.g. `rdh`).
+    /// Command line arguments take precedence over environment variables.
+    pub fn parse_with_env<I, A>(args: I) -> Self
+    where
+        I: IntoIterator<Item = A>,
+        A: Into<std::ffi::OsString> + Clone,
+    {
+        let mut args: Vec<std::ffi::OsString> = args.into_iter().map(Into::into).collect();
+        let command = <Cfg as clap::CommandFactory>::command().mut_args(|arg| {
+            let env_name = env_var_name(arg.get_long().unwrap_or(arg.get_id().as_str()));
+            arg.env(env_name)
+        });
+
+        let cli_has_subcommand = command
+            .clone()
+            .try_get_matches_from(&args)
+            .is_ok_and(|matches| matches.subcommand().is_some());
+        if !cli_has_subcommand {
+            args.extend(env_subcommand_args());
+        }
+
+        let matches = command.get_matches_from(args);
+        <Cfg as clap::FromArgMatches>::from_arg_matches(&matches).unwrap_or_else(|e| e.exit())
+    }
+}
+
+/// Parses an orbit range of the form `START-END`, e.g. `192796000-192796100`
+fn parse_orbit_range(orbit_range: &str) -> Result<(u32, u32), String> {
+    let (start, end) = orbit_range
+        .split_once('-')
+        .ok_or_else(|| format!("Invalid orbit range: {orbit_range}, expected START-END"))?;
+    let parse_orbit = |orbit: &str| {
+        orbit
+            .trim()
+            .parse::<u32>()
+            .map_err(|e| format!("Invalid orbit in orbit range: {orbit}: {e}"))
+    };
+    Ok((parse_orbit(start)?, parse_orbit(end)?))
+}
+
+/// Parses a byte offset in decimal or in hex with the `0x` prefix, e.g. `4849664` or `0x4A0000`
+fn parse_byte_offset(offset: &str) -> Result<u64, String> {
+    let offset = offset.trim();
+    match offset
+        .strip_prefix("0x")
+        .or_else(|| offset.strip_prefix("0X"))
+    {
+        Some(hex_digits) => u64::from_str_radix(hex_digits, 16),
+        None => offset.parse::<u64>(),
+    }
+    .map_err(|e| format!("Invalid byte offset: {offset}: {e}, expected e.g. 0x4A0000 or 4849664"))
+}
+
+/// Parses a FEE ID or an inclusive range of FEE IDs, e.g. `20522` or `20500-20600`
+fn parse_fee_id_range(fee_id_range: &str) -> Result<(u16, u16), String> {
+    let parse_fee_id = |fee_id: &str| {
+        fee_id
+            .trim()
+            .parse::<u16>()
+            .map_err(|e| format!("Invalid FEE ID: {fee_id}: {e}"))
+    };
+    match fee_id_range.split_once('-') {
+        Some((start, end)) => Ok((parse_fee_id(start)?, parse_fee_id(end)?)),
+        None => parse_fee_id(fee_id_range).map(|fee_id| (fee_id, fee_id)),
+    }
+}
+
+/// Get the subcommand and its arguments from the `FASTPASTA_CHECK` or `FASTPASTA_VIEW` environment variable.
+fn env_subcommand_args() -> Vec<std::ffi::OsString> {
+    ["check", "view"]
+        .into_iter()
+        .find_map(|subcmd| {
+            std::env::var(env_var_name(subcmd)).ok().map(|val| {
+                std::iter::once(subcmd.to_string())
+                    .chain(val.split_whitespace().map(String::from))
+                    .map(Into::into)
+                    .collect()
+            })
+        })
+        .unwrap_or_default()
+}
+
+/// Get the [config][super::config::Cfg] from the command line arguments and environment variables, and set the static [CONFIG] variable.
 pub fn init_config() -> Result<(), String> {
-    let cfg = <super::config::Cfg as clap::Parser>::parse();
+    let mut cfg = Cfg::parse_with_env(std::env::args_os());
+    // Trigger type masks are set first, as they are used when resolving the trigger type filter
+    cfg.handle_trigger_type_masks()?;
+    // Filters from a filter config are merged before validation, so they are validated like the filters set on the command line
+    cfg.handle_filter_config()?;
     cfg.validate_args()?;
     cfg.handle_custom_checks();
     crate::config::CONFIG.set(cfg).unwrap();
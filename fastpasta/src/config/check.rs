@@ -7,6 +7,36 @@ pub trait ChecksOpt {
 
     /// Return the check on ITS trigger period if it is set.
     fn check_its_trigger_period(&self) -> Option<u16>;
+
+    /// Tally the frequency of each word ID (10th byte of a GBT word) across all payloads.
+    fn word_id_histogram(&self) -> bool;
+
+    /// Tally the trigger periods detected between consecutive TDHs with internal trigger set.
+    fn trigger_period_histogram(&self) -> bool;
+
+    /// Number of GBT words before and after the offending word to include in ITS payload error messages, if set.
+    fn error_context(&self) -> Option<u8>;
+
+    /// Decode only the ALPIDE chip trailers to collect ALPIDE stats, without the ALPIDE frame checks of `check all its-stave`.
+    fn alpide_stats_only(&self) -> bool;
+
+    /// Allow the data format to change between RDHs instead of reporting it as an error.
+    fn allow_mixed_data_format(&self) -> bool;
+
+    /// Warn if more than one CRU ID is observed, as the data is expected to come from a single CRU.
+    fn expect_single_cru(&self) -> bool;
+
+    /// Check that the end of payload 0xFF padding is consistent with the data format, instead of only asserting it in debug builds.
+    fn strict_padding(&self) -> bool;
+
+    /// Check that the reserved bits of all ITS status words are 0, reported with an error code per status word type.
+    fn check_reserved(&self) -> bool;
+
+    /// File to write the ITS payload words with an unrecognized ID to, along with a sidecar index of their memory positions, if set.
+    fn dump_ambiguous(&self) -> Option<&Path>;
+
+    /// The ITS barrel(s) to check the data words of with `check all its-stave`, data words from other barrels are skipped.
+    fn its_barrel(&self) -> ItsBarrel;
 }
 
 impl<T> ChecksOpt for &T
@@ -19,6 +49,36 @@ where
     fn check_its_trigger_period(&self) -> Option<u16> {
         (*self).check_its_trigger_period()
     }
+    fn word_id_histogram(&self) -> bool {
+        (*self).word_id_histogram()
+    }
+    fn trigger_period_histogram(&self) -> bool {
+        (*self).trigger_period_histogram()
+    }
+    fn error_context(&self) -> Option<u8> {
+        (*self).error_context()
+    }
+    fn alpide_stats_only(&self) -> bool {
+        (*self).alpide_stats_only()
+    }
+    fn allow_mixed_data_format(&self) -> bool {
+        (*self).allow_mixed_data_format()
+    }
+    fn expect_single_cru(&self) -> bool {
+        (*self).expect_single_cru()
+    }
+    fn strict_padding(&self) -> bool {
+        (*self).strict_padding()
+    }
+    fn check_reserved(&self) -> bool {
+        (*self).check_reserved()
+    }
+    fn dump_ambiguous(&self) -> Option<&Path> {
+        (*self).dump_ambiguous()
+    }
+    fn its_barrel(&self) -> ItsBarrel {
+        (*self).its_barrel()
+    }
 }
 
 impl<T> ChecksOpt for Box<T>
@@ -31,6 +91,36 @@ where
     fn check_its_trigger_period(&self) -> Option<u16> {
         (**self).check_its_trigger_period()
     }
+    fn word_id_histogram(&self) -> bool {
+        (**self).word_id_histogram()
+    }
+    fn trigger_period_histogram(&self) -> bool {
+        (**self).trigger_period_histogram()
+    }
+    fn error_context(&self) -> Option<u8> {
+        (**self).error_context()
+    }
+    fn alpide_stats_only(&self) -> bool {
+        (**self).alpide_stats_only()
+    }
+    fn allow_mixed_data_format(&self) -> bool {
+        (**self).allow_mixed_data_format()
+    }
+    fn expect_single_cru(&self) -> bool {
+        (**self).expect_single_cru()
+    }
+    fn strict_padding(&self) -> bool {
+        (**self).strict_padding()
+    }
+    fn check_reserved(&self) -> bool {
+        (**self).check_reserved()
+    }
+    fn dump_ambiguous(&self) -> Option<&Path> {
+        (**self).dump_ambiguous()
+    }
+    fn its_barrel(&self) -> ItsBarrel {
+        (**self).its_barrel()
+    }
 }
 impl<T> ChecksOpt for Arc<T>
 where
@@ -42,15 +132,49 @@ where
     fn check_its_trigger_period(&self) -> Option<u16> {
         (**self).check_its_trigger_period()
     }
+    fn word_id_histogram(&self) -> bool {
+        (**self).word_id_histogram()
+    }
+    fn trigger_period_histogram(&self) -> bool {
+        (**self).trigger_period_histogram()
+    }
+    fn error_context(&self) -> Option<u8> {
+        (**self).error_context()
+    }
+    fn alpide_stats_only(&self) -> bool {
+        (**self).alpide_stats_only()
+    }
+    fn allow_mixed_data_format(&self) -> bool {
+        (**self).allow_mixed_data_format()
+    }
+    fn expect_single_cru(&self) -> bool {
+        (**self).expect_single_cru()
+    }
+    fn strict_padding(&self) -> bool {
+        (**self).strict_padding()
+    }
+    fn check_reserved(&self) -> bool {
+        (**self).check_reserved()
+    }
+    fn dump_ambiguous(&self) -> Option<&Path> {
+        (**self).dump_ambiguous()
+    }
+    fn its_barrel(&self) -> ItsBarrel {
+        (**self).its_barrel()
+    }
 }
 
 /// Check subcommand to enable checks, needs to be followed by a check type subcommand and a target system
 #[derive(Subcommand, Debug, Clone, PartialEq)]
 pub enum CheckCommands {
     /// Perform sanity & running checks on RDH. If a target system is specified (e.g. 'ITS') checks implemented for the target is also performed. If no target system is specified, only the most generic checks are done.
+    ///
+    /// The packet counter of a link is checked to increase within an HBF [E15], by 1 to 127 as RDHs of the link can be missing from the data.
     All(CheckModeArgs),
     /// Perform only sanity checks on RDH. If a target system is specified (e.g. 'ITS') checks implemented for the target is also performed. If no target system is specified, only the most generic checks are done.
     Sanity(CheckModeArgs),
+    /// Check that the CDPs are globally ordered by a key, by default link ID then orbit. Reports the first CDP that is out of order.
+    Ordering(OrderingArgs),
 }
 
 #[derive(Debug, Args, Clone, PartialEq, Default)]
@@ -75,6 +199,72 @@ pub struct CmdPathArg {
     path: Option<PathBuf>,
 }
 
+/// Arguments to the Ordering check mode
+#[derive(Debug, Args, Clone, Copy, PartialEq, Default)]
+pub struct OrderingArgs {
+    /// The key the CDPs are expected to be sorted by (link-orbit, orbit-link, fee-orbit)
+    #[arg(short, long, default_value_t = OrderingKey::LinkOrbit)]
+    pub key: OrderingKey,
+}
+
+/// Keys that CDPs can be expected to be sorted by, the first field is the most significant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OrderingKey {
+    /// Sorted by link ID, then by orbit
+    #[default]
+    LinkOrbit,
+    /// Sorted by orbit, then by link ID
+    OrbitLink,
+    /// Sorted by FEE ID, then by orbit
+    FeeOrbit,
+}
+
+impl OrderingKey {
+    /// Returns the key of an RDH as a tuple of the most and least significant field
+    pub fn rdh_key<T: RDH>(&self, rdh: &T) -> (u32, u32) {
+        match self {
+            OrderingKey::LinkOrbit => (rdh.link_id() as u32, rdh.rdh1().orbit),
+            OrderingKey::OrbitLink => (rdh.rdh1().orbit, rdh.link_id() as u32),
+            OrderingKey::FeeOrbit => (rdh.fee_id() as u32, rdh.rdh1().orbit),
+        }
+    }
+
+    /// Returns the names of the fields that make up the key
+    pub fn field_names(&self) -> &'static str {
+        match self {
+            OrderingKey::LinkOrbit => "(link, orbit)",
+            OrderingKey::OrbitLink => "(orbit, link)",
+            OrderingKey::FeeOrbit => "(FEE ID, orbit)",
+        }
+    }
+}
+
+impl fmt::Display for OrderingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderingKey::LinkOrbit => write!(f, "link-orbit"),
+            OrderingKey::OrbitLink => write!(f, "orbit-link"),
+            OrderingKey::FeeOrbit => write!(f, "fee-orbit"),
+        }
+    }
+}
+
+impl FromStr for OrderingKey {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "link-orbit" => Ok(OrderingKey::LinkOrbit),
+            "orbit-link" => Ok(OrderingKey::OrbitLink),
+            "fee-orbit" => Ok(OrderingKey::FeeOrbit),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Invalid ordering key, expected one of: link-orbit, orbit-link, fee-orbit",
+            )),
+        }
+    }
+}
+
 /// List of supported systems to target for checks
 #[derive(Subcommand, Copy, Clone, Debug, PartialEq, Eq)]
 pub enum System {
@@ -82,4 +272,62 @@ pub enum System {
     ITS,
     /// Specify ITS stave as the target system for checks.
     ITS_Stave,
+    /// Specify FT0 as the target system for checks, currently only basic checks of the RDHs and payload size.
+    FT0,
+}
+
+/// The ITS barrels that the data words can be checked for, see [ChecksOpt::its_barrel]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ItsBarrel {
+    /// Inner barrel (layers 0-2)
+    Inner,
+    /// Middle layers of the outer barrel (layers 3-4)
+    Middle,
+    /// Outer layers of the outer barrel (layers 5-6)
+    Outer,
+    /// All barrels
+    #[default]
+    All,
+}
+
+impl ItsBarrel {
+    /// Returns true if the barrel includes the stave with the given FEE ID
+    ///
+    /// A FEE ID with an invalid layer number is included, as it is not from any particular barrel.
+    pub fn includes_fee_id(&self, fee_id: u16) -> bool {
+        match self {
+            ItsBarrel::All => true,
+            ItsBarrel::Inner => matches!(layer_from_feeid(fee_id), 0..=2 | 7),
+            ItsBarrel::Middle => matches!(layer_from_feeid(fee_id), 3 | 4 | 7),
+            ItsBarrel::Outer => matches!(layer_from_feeid(fee_id), 5..=7),
+        }
+    }
+}
+
+impl fmt::Display for ItsBarrel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ItsBarrel::Inner => write!(f, "inner"),
+            ItsBarrel::Middle => write!(f, "middle"),
+            ItsBarrel::Outer => write!(f, "outer"),
+            ItsBarrel::All => write!(f, "all"),
+        }
+    }
+}
+
+impl FromStr for ItsBarrel {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "inner" => Ok(ItsBarrel::Inner),
+            "middle" => Ok(ItsBarrel::Middle),
+            "outer" => Ok(ItsBarrel::Outer),
+            "all" => Ok(ItsBarrel::All),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Invalid ITS barrel, expected one of: inner, middle, outer, all",
+            )),
+        }
+    }
 }
@@ -35,6 +35,15 @@ pub trait CustomChecksOpt {
     /// Get the number of sent Triggers expected in the data, if it is set.
     fn triggers_pht(&'static self) -> Option<u32>;
 
+    /// Get the number of HBFs expected in the data, if it is set.
+    fn hbfs(&'static self) -> Option<u32>;
+
+    /// Get the links expected in the data, if it is set.
+    fn expected_links(&'static self) -> Option<&'static [u8]>;
+
+    /// Get the number of distinct staves expected per ITS layer as (layer, staves), if it is set.
+    fn layer_stave_counts(&'static self) -> Option<&'static [(u8, u8)]>;
+
     /// Get the expected RDH version, if it is set.
     fn rdh_version(&'static self) -> Option<u8>;
 
@@ -71,6 +80,18 @@ where
         (*self).triggers_pht()
     }
 
+    fn hbfs(&'static self) -> Option<u32> {
+        (*self).hbfs()
+    }
+
+    fn expected_links(&'static self) -> Option<&'static [u8]> {
+        (*self).expected_links()
+    }
+
+    fn layer_stave_counts(&'static self) -> Option<&'static [(u8, u8)]> {
+        (*self).layer_stave_counts()
+    }
+
     fn rdh_version(&'static self) -> Option<u8> {
         (*self).rdh_version()
     }
@@ -108,6 +129,18 @@ where
         (**self).triggers_pht()
     }
 
+    fn hbfs(&'static self) -> Option<u32> {
+        (**self).hbfs()
+    }
+
+    fn expected_links(&'static self) -> Option<&'static [u8]> {
+        (**self).expected_links()
+    }
+
+    fn layer_stave_counts(&'static self) -> Option<&'static [(u8, u8)]> {
+        (**self).layer_stave_counts()
+    }
+
     fn rdh_version(&'static self) -> Option<u8> {
         (**self).rdh_version()
     }
@@ -145,6 +178,18 @@ where
         (**self).triggers_pht()
     }
 
+    fn hbfs(&'static self) -> Option<u32> {
+        (**self).hbfs()
+    }
+
+    fn expected_links(&'static self) -> Option<&'static [u8]> {
+        (**self).expected_links()
+    }
+
+    fn layer_stave_counts(&'static self) -> Option<&'static [(u8, u8)]> {
+        (**self).layer_stave_counts()
+    }
+
     fn rdh_version(&'static self) -> Option<u8> {
         (**self).rdh_version()
     }
@@ -13,6 +13,18 @@ pub struct CustomChecks {
     #[example = "0, 10"]
     triggers_pht: Option<u32>,
 
+    #[description = "Number of Heartbeat Frames (HBFs) expected in the data"]
+    #[example = "5, 128"]
+    hbfs: Option<u32>,
+
+    #[description = "Links expected in the data, it is an error if any of the links are missing or any other link is observed"]
+    #[example = "[0, 1, 2], [8]"]
+    expected_links: Option<Vec<u8>>,
+
+    #[description = "Number of distinct staves expected per ITS layer as [layer, staves], it is an error if fewer staves are observed in a layer"]
+    #[example = "[[0, 12], [1, 16], [2, 20]]"]
+    layer_stave_counts: Option<Vec<(u8, u8)>>,
+
     #[description = "Legal Chip ordering for Outer Barrel (ML/OL). Needs to be a list of lists of chip IDs"]
     #[example = "[[0, 1, 2, 3, 4, 5, 6], [8, 9, 10, 11, 12, 13, 14], [1, 2, 3, 4, 5, 6, 7]]"]
     chip_orders_ob: Option<Vec<Vec<u8>>>,
@@ -38,6 +50,21 @@ impl CustomChecks {
         self.triggers_pht
     }
 
+    /// Get the number of HBFs expected in the data, if it is set.
+    pub fn hbfs(&self) -> Option<u32> {
+        self.hbfs
+    }
+
+    /// Get the links expected in the data, if it is set.
+    pub fn expected_links(&self) -> Option<&[u8]> {
+        self.expected_links.as_deref()
+    }
+
+    /// Get the number of distinct staves expected per ITS layer as (layer, staves), if it is set.
+    pub fn layer_stave_counts(&self) -> Option<&[(u8, u8)]> {
+        self.layer_stave_counts.as_deref()
+    }
+
     /// Get the number of chips expected in the data from Outer Barrel (ML/OL), if it is set.
     pub fn chip_count_ob(&self) -> Option<u8> {
         self.chip_count_ob
@@ -67,6 +94,9 @@ mod tests {
         let custom_checks = CustomChecks {
             cdps: Some(10),
             triggers_pht: Some(0),
+            hbfs: Some(5),
+            expected_links: Some(vec![0, 3, 11]),
+            layer_stave_counts: Some(vec![(0, 12), (1, 16)]),
             chip_orders_ob: Some(vec![
                 vec![0, 1, 2, 3, 4, 5, 6],
                 vec![8, 9, 10, 11, 12, 13, 14],
@@ -101,6 +131,18 @@ mod tests {
 # Example: 0, 10
 #triggers_pht = None [ u32 ] # (Uncomment and set to enable)
 
+# Number of Heartbeat Frames (HBFs) expected in the data
+# Example: 5, 128
+#hbfs = None [ u32 ] # (Uncomment and set to enable)
+
+# Links expected in the data, it is an error if any of the links are missing or any other link is observed
+# Example: [0, 1, 2], [8]
+#expected_links = None [ Vec < u8 > ] # (Uncomment and set to enable)
+
+# Number of distinct staves expected per ITS layer as [layer, staves], it is an error if fewer staves are observed in a layer
+# Example: [[0, 12], [1, 16], [2, 20]]
+#layer_stave_counts = None [ Vec < (u8, u8) > ] # (Uncomment and set to enable)
+
 # Legal Chip ordering for Outer Barrel (ML/OL). Needs to be a list of lists of chip IDs
 # Example: [[0, 1, 2, 3, 4, 5, 6], [8, 9, 10, 11, 12, 13, 14], [1, 2, 3, 4, 5, 6, 7]]
 #chip_orders_ob = None [ Vec < Vec < u8 > > ] # (Uncomment and set to enable)
@@ -167,6 +209,9 @@ rdh_version = 6
             CustomChecks {
                 cdps: Some(10),
                 triggers_pht: Some(0),
+                hbfs: None,
+                expected_links: None,
+                layer_stave_counts: None,
                 chip_orders_ob: Some(vec![
                     vec![0, 1, 2, 3, 4, 5, 6],
                     vec![8, 9, 10, 11, 12, 13, 14]
@@ -0,0 +1,357 @@
+//! Contains the [dry_run_summary] function that describes what a command line would do, without reading any data
+//!
+//! Used by the `--dry-run` flag to show the resolved [Config] after the arguments are validated,
+//! i.e. the input source, the enabled checks or view, the active filters, the output, and the custom checks loaded.
+
+use crate::util::*;
+
+/// Returns a human-readable summary of what processing with the config would do
+pub fn dry_run_summary(config: &'static impl Config) -> String {
+    let mut summary = String::from("Dry run, no data is read");
+    let mut add_line = |name: &str, value: String| {
+        summary.push_str(&format!("\n  {name:<15} {value}"));
+    };
+
+    add_line("Input:", describe_input(config));
+    add_line("Mode:", describe_mode(config));
+    add_line("Filters:", describe_filters(config));
+    let mut data_output = describe_output_mode(&config.output_mode());
+    if config.dump_format() == DumpFormat::Ascii && config.output_mode() != DataOutputMode::None {
+        data_output.push_str(" as an ASCII dump");
+    }
+    if config.verify_output() {
+        data_output.push_str(", read back after writing to verify it");
+    }
+    add_line("Data output:", data_output);
+    let stats_output = match config.stats_output_format() {
+        Some(format) => format!(
+            "{} as {format}",
+            describe_output_mode(&config.stats_output_mode())
+        ),
+        None => "None".to_string(),
+    };
+    add_line("Stats output:", stats_output);
+    if let Some(interval) = config.stats_flush_interval() {
+        add_line(
+            "Stats flush:",
+            format!("Every {}s during processing", interval.as_secs()),
+        );
+    }
+    if let Some(input_stats_file) = config.input_stats_file() {
+        add_line(
+            "Verify stats:",
+            format!("Against {}", input_stats_file.display()),
+        );
+    }
+    if let Some(log_file) = config.log_file() {
+        add_line("Log file:", log_file.display().to_string());
+    }
+    add_line("Errors:", describe_error_handling(config));
+    add_line("Custom checks:", describe_custom_checks(config));
+
+    summary
+}
+
+fn describe_input(config: &impl Config) -> String {
+    let files = config.input_files();
+    let mut input = match files.len() {
+        0 if config.gzip() => "stdin (gzip)".to_string(),
+        0 if config.zstd() => "stdin (zstd)".to_string(),
+        0 => "stdin".to_string(),
+        1 => files[0].display().to_string(),
+        n => format!(
+            "{n} files read as one stream: {}",
+            files.iter().map(|file| file.display()).join(", ")
+        ),
+    };
+    if config.follow() {
+        input.push_str(", following the file as it grows");
+    }
+    if let Some(timeout) = config.recv_timeout() {
+        input.push_str(&format!(
+            ", stopping if no data is received for {}s",
+            timeout.as_secs()
+        ));
+    }
+    if let Some(offset) = config.input_offset() {
+        input.push_str(&format!(", starting at byte offset {offset:#X}"));
+    }
+    if let Some(max_bytes) = config.max_bytes() {
+        input.push_str(&format!(", stopping after {max_bytes} bytes"));
+    }
+    if let Some(sample_every) = config.sample_every() {
+        input.push_str(&format!(", only analyzing 1 in {sample_every} CDPs"));
+    }
+    if config.endianness() == Endianness::Big {
+        input.push_str(", big-endian");
+    }
+    if config.rdh_sanity() == RdhSanity::Lenient {
+        input.push_str(", lenient sanity check of the first RDH");
+    }
+    if config.scan_for_rdh() {
+        input.push_str(", skipping ahead to the first valid RDH if the first RDH is invalid");
+    }
+    if let Some(jobs) = config.jobs() {
+        input.push_str(&format!(
+            ", processed independently with up to {jobs} files at a time"
+        ));
+    }
+    input
+}
+
+fn describe_mode(config: &impl Config) -> String {
+    if let Some((golden, candidate)) = config.compare_raw_files() {
+        return format!(
+            "Compare {} against {}",
+            candidate.display(),
+            golden.display()
+        );
+    }
+    if let Some(stats_files) = config.merge_stats_files() {
+        return format!("Merge {} stats files", stats_files.len());
+    }
+    if let Some((old, new)) = config.diff_stats_files() {
+        let mut mode = format!(
+            "Diff the stats of {} against {}",
+            new.display(),
+            old.display()
+        );
+        if let Some(ignore_fields) = config.diff_ignore_fields() {
+            mode.push_str(&format!(", ignoring {}", ignore_fields.join(",")));
+        }
+        return mode;
+    }
+    if let Some(check) = config.check() {
+        return describe_check(config, check);
+    }
+    if let Some(view) = config.view() {
+        let mut mode = format!("View {}", view_name(view));
+        if config.view_rdh_compact() {
+            mode.push_str(" with one line per RDH");
+        }
+        if let Some(html) = config.view_html_output() {
+            mode.push_str(&format!(" written as HTML to {}", html.display()));
+        }
+        return mode;
+    }
+    "Filter the data".to_string()
+}
+
+fn describe_check(config: &impl Config, check: CheckCommands) -> String {
+    let (mode, target) = match check {
+        CheckCommands::All(args) => ("sanity and running checks", args.target),
+        CheckCommands::Sanity(args) => ("sanity checks", args.target),
+        CheckCommands::Ordering(args) => {
+            return format!("Check that the CDPs are ordered by {}", args.key)
+        }
+    };
+    let mut description = match target {
+        Some(target) => format!("RDH and {} {mode}", system_name(target)),
+        None => format!("RDH {mode}"),
+    };
+    if let Some(period) = config.check_its_trigger_period() {
+        description.push_str(&format!(", ITS trigger period {period}"));
+    }
+    if config.its_barrel() != ItsBarrel::All {
+        description.push_str(&format!(
+            ", only data words from the {} barrel",
+            config.its_barrel()
+        ));
+    }
+    if config.alpide_stats_only() {
+        description.push_str(", ALPIDE stats only");
+    }
+    if config.word_id_histogram() {
+        description.push_str(", word ID histogram");
+    }
+    if config.trigger_period_histogram() {
+        description.push_str(", trigger period histogram");
+    }
+    if config.strict_padding() {
+        description.push_str(", strict payload padding");
+    }
+    if config.check_reserved() {
+        description.push_str(", reserved bits of status words");
+    }
+    if let Some(path) = config.dump_ambiguous() {
+        description.push_str(&format!(
+            ", words with an unrecognized ID dumped to {}",
+            path.display()
+        ));
+    }
+    if let Some(words) = config.error_context() {
+        description.push_str(&format!(", {words} words of error context"));
+    }
+    format!("Check {description}")
+}
+
+fn system_name(system: System) -> &'static str {
+    match system {
+        System::ITS => "ITS",
+        System::ITS_Stave => "ITS stave",
+        System::FT0 => "FT0",
+    }
+}
+
+fn view_name(view: ViewCommands) -> &'static str {
+    match view {
+        ViewCommands::Rdh => "rdh",
+        ViewCommands::ItsReadoutFrames => "its-readout-frames",
+        ViewCommands::ItsReadoutFramesData => "its-readout-frames-data",
+        ViewCommands::Ddw0 => "ddw0",
+        ViewCommands::Alpide => "alpide",
+        ViewCommands::DetFieldStatus => "det-field-status",
+        ViewCommands::Hbf => "hbf",
+    }
+}
+
+fn describe_filters(config: &impl Config) -> String {
+    let mut filters = Vec::new();
+    if let Some(links) = config.filter_links() {
+        filters.push(format!("links {}", links.iter().join(",")));
+    }
+    if let Some(fee_id_ranges) = config.filter_fee() {
+        filters.push(format!("FEE IDs {}", format_fee_id_ranges(fee_id_ranges)));
+    }
+    if let Some(fee_id) = config.filter_its_stave() {
+        filters.push(format!(
+            "ITS stave {} (FEE ID {fee_id})",
+            Stave::from_feeid(fee_id)
+        ));
+    }
+    if let Some(mask) = config.filter_trigger_type() {
+        filters.push(format!("trigger type mask {mask:#X}"));
+    }
+    if let Some((start, end)) = config.filter_orbit_range() {
+        filters.push(format!("orbits {start}-{end}"));
+    }
+    if let Some(indices) = config.extract_rdhs() {
+        filters.push(format!("RDHs at indices {}", indices.iter().join(",")));
+    }
+    if let Some(max_payload_size) = config.max_payload_size() {
+        filters.push(format!("payloads above {max_payload_size} bytes skipped"));
+    }
+    if filters.is_empty() {
+        "None".to_string()
+    } else {
+        filters.join(", ")
+    }
+}
+
+fn format_fee_id_ranges(fee_id_ranges: &[(u16, u16)]) -> String {
+    fee_id_ranges
+        .iter()
+        .map(|&(start, end)| {
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{start}-{end}")
+            }
+        })
+        .join(",")
+}
+
+fn describe_output_mode(output_mode: &DataOutputMode) -> String {
+    match output_mode {
+        DataOutputMode::File(path) => path.display().to_string(),
+        DataOutputMode::SplitByLink(path) => format!("One file per link of {}", path.display()),
+        DataOutputMode::SplitByHbf(path) => format!("One file per HBF of {}", path.display()),
+        DataOutputMode::Stdout => "stdout".to_string(),
+        DataOutputMode::None => "None".to_string(),
+    }
+}
+
+fn describe_error_handling(config: &impl Config) -> String {
+    let mut error_handling = match config.max_tolerate_errors() {
+        0 => "No limit".to_string(),
+        max => format!("Stop after {max} errors"),
+    };
+    if let Some(max_errors_per_link) = config.max_errors_per_link() {
+        error_handling.push_str(&format!(
+            ", stop checking a link after {max_errors_per_link} errors"
+        ));
+    }
+    if config.warnings_as_errors() {
+        error_handling.push_str(", warnings counted as errors");
+    }
+    if let Some(exit_code) = config.any_errors_exit_code() {
+        error_handling.push_str(&format!(", exit code {exit_code} if any errors"));
+    }
+    if let Some(mappings) = config.error_code_exit_codes() {
+        error_handling.push_str(&format!(
+            ", exit code {}",
+            mappings
+                .iter()
+                .map(|(error_code, exit_code)| format!("{exit_code} if E{error_code}"))
+                .join(" else ")
+        ));
+    }
+    if config.allow_mixed_data_format() {
+        error_handling.push_str(", mixed data formats allowed");
+    }
+    if config.expect_single_cru() {
+        error_handling.push_str(", warn if more than one CRU ID");
+    }
+    if config.mute_errors() {
+        error_handling.push_str(", messages muted");
+    }
+    if let Some(first_n_errors) = config.first_n_errors() {
+        error_handling.push_str(&format!(", only the first {first_n_errors} messages shown"));
+    }
+    if config.quiet_stats() {
+        error_handling.push_str(", only a PASS/FAIL verdict is printed");
+    }
+    if let Some(codes) = config.error_code_filter() {
+        error_handling.push_str(&format!(", only showing codes {}", codes.join(",")));
+    }
+    if let Some(codes) = config.mute_error_codes() {
+        error_handling.push_str(&format!(
+            ", ignoring codes {}",
+            codes.iter().map(|code| format!("E{code}")).join(",")
+        ));
+    }
+    error_handling
+}
+
+fn describe_custom_checks(config: &'static impl Config) -> String {
+    let Some(custom_checks) = config.custom_checks() else {
+        return "None".to_string();
+    };
+    // Only the checks that are set are serialized
+    match toml::to_string(custom_checks) {
+        Ok(toml) if !toml.trim().is_empty() => {
+            toml.lines().map(|line| line.trim().to_string()).join(", ")
+        }
+        _ => "None".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_util::MockConfig;
+
+    static CFG_TEST_DRY_RUN_SUMMARY: OnceLock<MockConfig> = OnceLock::new();
+
+    #[test]
+    fn test_dry_run_summary() {
+        let mut cfg = MockConfig::new();
+        cfg.input_files = vec![PathBuf::from("input.raw")];
+        cfg.check = Some(CheckCommands::All(CheckModeArgs {
+            target: Some(System::ITS),
+            ..Default::default()
+        }));
+        cfg.filter_links = vec![3, 8];
+        cfg.max_tolerate_errors = 10;
+        CFG_TEST_DRY_RUN_SUMMARY.set(cfg).unwrap();
+
+        let summary = dry_run_summary(CFG_TEST_DRY_RUN_SUMMARY.get().unwrap());
+        println!("{summary}");
+
+        assert!(summary.starts_with("Dry run, no data is read"));
+        assert!(summary.contains("Input:          input.raw"));
+        assert!(summary.contains("Mode:           Check RDH and ITS sanity and running checks"));
+        assert!(summary.contains("Filters:        links 3,8"));
+        assert!(summary.contains("Errors:         Stop after 10 errors"));
+    }
+}
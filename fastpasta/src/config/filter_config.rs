@@ -0,0 +1,144 @@
+//! Contains the [FilterConfig] struct with filters loaded from a TOML file, e.g. to reuse standardized filtering profiles.
+//!
+//! The filters are merged into the filters set on the command line, filters set on the command line take precedence.
+//! Like on the command line, only one of `links`, `fee` and `its_stave` can be set.
+//!
+//! Example of a TOML file that keeps the physics triggers of links 0, 3 and 7 within an orbit range:
+//! ```toml
+//! links = [0, 3, 7]
+//! trigger_type = "PhT"
+//! orbit_range = [192796000, 192796100]
+//! ```
+
+use crate::util::*;
+use crate::words::its::layer_stave_string_to_feeid;
+
+/// Filters that can be set in a filter configuration TOML file, filters that are not specified are not set.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FilterConfig {
+    /// CRU link IDs to filter by
+    pub links: Option<Vec<u8>>,
+    /// FEE ID to filter by
+    pub fee: Option<u16>,
+    /// ITS layer & stave to filter by (e.g. L5_42)
+    pub its_stave: Option<String>,
+    /// Trigger type to filter by (PhT, SOC, SOT or HB)
+    pub trigger_type: Option<String>,
+    /// Inclusive orbit range to filter by as [start, end]
+    pub orbit_range: Option<(u32, u32)>,
+}
+
+impl FilterConfig {
+    /// Parse a [FilterConfig] from a TOML file.
+    pub fn from_toml_path(toml_path: &Path) -> Result<Self, String> {
+        let toml = fs::read_to_string(toml_path).map_err(|e| {
+            format!(
+                "Failed to read filter config TOML file {}: {e}",
+                toml_path.display()
+            )
+        })?;
+        Self::from_toml_str(&toml)
+    }
+
+    /// Parse a [FilterConfig] from a TOML string, and validate the filters.
+    pub fn from_toml_str(toml: &str) -> Result<Self, String> {
+        let filter_config: Self =
+            toml::from_str(toml).map_err(|e| format!("Failed to parse filter config TOML: {e}"))?;
+        filter_config.validate()?;
+        Ok(filter_config)
+    }
+
+    /// Returns if any of the link, FEE ID, or ITS stave filters are set, only one of them can be set at a time.
+    pub fn has_target_filter(&self) -> bool {
+        self.links.is_some() || self.fee.is_some() || self.its_stave.is_some()
+    }
+
+    /// Returns the trigger type to filter by, if it is set.
+    pub fn trigger_type(&self) -> Result<Option<TriggerTypeMnemonic>, String> {
+        self.trigger_type
+            .as_deref()
+            .map(|trigger_type| {
+                TriggerTypeMnemonic::from_str(trigger_type).map_err(|e| {
+                    format!("Invalid trigger type in filter config: {trigger_type}: {e}")
+                })
+            })
+            .transpose()
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        let target_filters = [
+            self.links.is_some(),
+            self.fee.is_some(),
+            self.its_stave.is_some(),
+        ];
+        if target_filters.iter().filter(|&&is_set| is_set).count() > 1 {
+            return Err(
+                "Invalid filter config: Only one of `links`, `fee`, and `its_stave` can be set"
+                    .to_string(),
+            );
+        }
+        if let Some(its_stave) = &self.its_stave {
+            let is_valid_format = its_stave.to_uppercase().starts_with('L')
+                && its_stave.split('_').count() == 2
+                && layer_stave_string_to_feeid(its_stave).is_some();
+            if !is_valid_format {
+                return Err(format!(
+                    "Invalid filter config: Invalid ITS layer & stave: {its_stave}, expected L[x]_[y], e.g. L2_13"
+                ));
+            }
+        }
+        if let Some((start, end)) = self.orbit_range {
+            if start > end {
+                return Err(format!(
+                    "Invalid filter config: Orbit range start {start} is greater than the end {end}"
+                ));
+            }
+        }
+        self.trigger_type().map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_from_toml_str() {
+        let filter_config = FilterConfig::from_toml_str(
+            "links = [0, 3, 7]\ntrigger_type = \"PhT\"\norbit_range = [192796000, 192796100]",
+        )
+        .unwrap();
+        assert_eq!(
+            filter_config,
+            FilterConfig {
+                links: Some(vec![0, 3, 7]),
+                trigger_type: Some("PhT".to_string()),
+                orbit_range: Some((192796000, 192796100)),
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            filter_config.trigger_type(),
+            Ok(Some(TriggerTypeMnemonic::PhT))
+        );
+        assert!(filter_config.has_target_filter());
+    }
+
+    #[test]
+    fn test_invalid_filter_configs() {
+        for invalid_toml in [
+            "link = [0]",
+            "links = [0]\nfee = 20522",
+            "its_stave = \"L5\"",
+            "trigger_type = \"XYZ\"",
+            "orbit_range = [10, 5]",
+        ] {
+            assert!(
+                FilterConfig::from_toml_str(invalid_toml).is_err(),
+                "{invalid_toml}"
+            );
+        }
+    }
+}
@@ -1,29 +1,98 @@
-//! Contains the [InputOutputOpt] Trait for all input/output options and the [DataOutputMode] enum for the output mode
+//! Contains the [InputOutputOpt] Trait for all input/output options, the [DataOutputMode] enum for the output mode, and the [DumpFormat] enum for the format of the data output
 
 use crate::util::*;
 
 /// Input/Output option set by a user
 pub trait InputOutputOpt {
-    /// Input file to read from.
-    fn input_file(&self) -> Option<&Path>;
+    /// Input files to read from, in order as one continuous stream. Empty if the input is read from stdin.
+    fn input_files(&self) -> &[PathBuf];
+    /// Decompress gzip-compressed input from stdin, input files are decompressed if they are detected as gzip-compressed.
+    fn gzip(&self) -> bool;
+    /// Decompress Zstandard-compressed input from stdin, input files are decompressed if they are detected as Zstandard-compressed.
+    fn zstd(&self) -> bool;
+    /// Follow the input file as it grows, like `tail -f`, until stopped with Ctrl+C.
+    fn follow(&self) -> bool;
+    /// Stop processing gracefully if no data is received for this long, if set.
+    fn recv_timeout(&self) -> Option<Duration>;
+    /// If the first RDH fails the sanity check, scan forward byte by byte for the first valid RDH instead of giving up.
+    fn scan_for_rdh(&self) -> bool;
+    /// Byte offset to start reading the input at, if set.
+    fn input_offset(&self) -> Option<u64>;
+    /// Number of bytes to read before stopping, the CDP that reaches the limit is still read in full.
+    fn max_bytes(&self) -> Option<u64>;
+    /// Only every Nth CDP is forwarded to analysis if set, the RDHs of all CDPs are still counted in the stats.
+    fn sample_every(&self) -> Option<u64>;
+    /// Byte order of the words in the input data, big-endian data is converted to little-endian as it is read.
+    fn endianness(&self) -> Endianness;
     /// Output file to write to.
     fn output(&self) -> Option<&Path>;
-    /// Output mode of the data writing (file, stdout, none)
+    /// Output mode of the data writing (file, file per link, stdout, none)
     fn output_mode(&self) -> DataOutputMode;
+    /// Split the data output into one file per link.
+    fn split_by_link(&self) -> bool;
+    /// Split the data output into one file per HBF.
+    fn split_by_hbf(&self) -> bool;
+    /// Format of the data output, raw bytes or a human-readable ASCII dump.
+    fn dump_format(&self) -> DumpFormat;
+    /// Read back the output data file after it is written and check that it contains all the filtered RDHs.
+    fn verify_output(&self) -> bool;
+    /// 0-based indices of the RDHs to extract and write to the output.
+    fn extract_rdhs(&self) -> Option<&[u64]>;
     /// Stats output mode (file, stdout, none)
     fn stats_output_mode(&self) -> DataOutputMode;
     /// Stats output format (JSON, TOML)
     fn stats_output_format(&self) -> Option<DataOutputFormat>;
+    /// Pretty-print JSON output instead of the default compact JSON.
+    fn json_pretty(&self) -> bool;
+    /// Interval to periodically write a snapshot of the stats collected so far to the stats output file, if set
+    fn stats_flush_interval(&self) -> Option<Duration>;
     /// Input stats file to read from and verify match with collected stats at end of analysis.
     fn input_stats_file(&self) -> Option<&Path>;
+    /// Path to a TOML file with filters that are merged into the filters set on the command line, if set
+    fn filter_config_file(&self) -> Option<&Path>;
+    /// Reference and candidate raw data files to compare with the `compare-raw` subcommand.
+    fn compare_raw_files(&self) -> Option<(&Path, &Path)>;
+    /// Stats files to merge into one with the `merge-stats` subcommand.
+    fn merge_stats_files(&self) -> Option<&[PathBuf]>;
+    /// Old and new stats files to compare with the `diff` subcommand.
+    fn diff_stats_files(&self) -> Option<(&Path, &Path)>;
+    /// Stats fields that are not compared with the `diff` subcommand, if set.
+    fn diff_ignore_fields(&self) -> Option<&[String]>;
 }
 
 impl<T> InputOutputOpt for &T
 where
     T: InputOutputOpt,
 {
-    fn input_file(&self) -> Option<&Path> {
-        (*self).input_file()
+    fn input_files(&self) -> &[PathBuf] {
+        (*self).input_files()
+    }
+    fn gzip(&self) -> bool {
+        (*self).gzip()
+    }
+    fn zstd(&self) -> bool {
+        (*self).zstd()
+    }
+    fn follow(&self) -> bool {
+        (*self).follow()
+    }
+    fn recv_timeout(&self) -> Option<Duration> {
+        (*self).recv_timeout()
+    }
+    fn scan_for_rdh(&self) -> bool {
+        (*self).scan_for_rdh()
+    }
+    fn input_offset(&self) -> Option<u64> {
+        (*self).input_offset()
+    }
+    fn max_bytes(&self) -> Option<u64> {
+        (*self).max_bytes()
+    }
+    fn sample_every(&self) -> Option<u64> {
+        (*self).sample_every()
+    }
+    fn endianness(&self) -> Endianness {
+        (*self).endianness()
     }
     fn output(&self) -> Option<&Path> {
         (*self).output()
@@ -31,23 +100,86 @@ where
     fn output_mode(&self) -> DataOutputMode {
         (*self).output_mode()
     }
+    fn split_by_link(&self) -> bool {
+        (*self).split_by_link()
+    }
+    fn split_by_hbf(&self) -> bool {
+        (*self).split_by_hbf()
+    }
+    fn dump_format(&self) -> DumpFormat {
+        (*self).dump_format()
+    }
+    fn verify_output(&self) -> bool {
+        (*self).verify_output()
+    }
+    fn extract_rdhs(&self) -> Option<&[u64]> {
+        (*self).extract_rdhs()
+    }
     fn stats_output_mode(&self) -> DataOutputMode {
         (*self).stats_output_mode()
     }
     fn stats_output_format(&self) -> Option<DataOutputFormat> {
         (*self).stats_output_format()
     }
+    fn json_pretty(&self) -> bool {
+        (*self).json_pretty()
+    }
+    fn stats_flush_interval(&self) -> Option<Duration> {
+        (*self).stats_flush_interval()
+    }
     fn input_stats_file(&self) -> Option<&Path> {
         (*self).input_stats_file()
     }
+    fn filter_config_file(&self) -> Option<&Path> {
+        (*self).filter_config_file()
+    }
+    fn compare_raw_files(&self) -> Option<(&Path, &Path)> {
+        (*self).compare_raw_files()
+    }
+    fn merge_stats_files(&self) -> Option<&[PathBuf]> {
+        (*self).merge_stats_files()
+    }
+    fn diff_stats_files(&self) -> Option<(&Path, &Path)> {
+        (*self).diff_stats_files()
+    }
+    fn diff_ignore_fields(&self) -> Option<&[String]> {
+        (*self).diff_ignore_fields()
+    }
 }
 
 impl<T> InputOutputOpt for Box<T>
 where
     T: InputOutputOpt,
 {
-    fn input_file(&self) -> Option<&Path> {
-        (**self).input_file()
+    fn input_files(&self) -> &[PathBuf] {
+        (**self).input_files()
+    }
+    fn gzip(&self) -> bool {
+        (**self).gzip()
+    }
+    fn zstd(&self) -> bool {
+        (**self).zstd()
+    }
+    fn follow(&self) -> bool {
+        (**self).follow()
+    }
+    fn recv_timeout(&self) -> Option<Duration> {
+        (**self).recv_timeout()
+    }
+    fn scan_for_rdh(&self) -> bool {
+        (**self).scan_for_rdh()
+    }
+    fn input_offset(&self) -> Option<u64> {
+        (**self).input_offset()
+    }
+    fn max_bytes(&self) -> Option<u64> {
+        (**self).max_bytes()
+    }
+    fn sample_every(&self) -> Option<u64> {
+        (**self).sample_every()
+    }
+    fn endianness(&self) -> Endianness {
+        (**self).endianness()
     }
     fn output(&self) -> Option<&Path> {
         (**self).output()
@@ -55,22 +187,85 @@ where
     fn output_mode(&self) -> DataOutputMode {
         (**self).output_mode()
     }
+    fn split_by_link(&self) -> bool {
+        (**self).split_by_link()
+    }
+    fn split_by_hbf(&self) -> bool {
+        (**self).split_by_hbf()
+    }
+    fn dump_format(&self) -> DumpFormat {
+        (**self).dump_format()
+    }
+    fn verify_output(&self) -> bool {
+        (**self).verify_output()
+    }
+    fn extract_rdhs(&self) -> Option<&[u64]> {
+        (**self).extract_rdhs()
+    }
     fn stats_output_mode(&self) -> DataOutputMode {
         (**self).stats_output_mode()
     }
     fn stats_output_format(&self) -> Option<DataOutputFormat> {
         (**self).stats_output_format()
     }
+    fn json_pretty(&self) -> bool {
+        (**self).json_pretty()
+    }
+    fn stats_flush_interval(&self) -> Option<Duration> {
+        (**self).stats_flush_interval()
+    }
     fn input_stats_file(&self) -> Option<&Path> {
         (**self).input_stats_file()
     }
+    fn filter_config_file(&self) -> Option<&Path> {
+        (**self).filter_config_file()
+    }
+    fn compare_raw_files(&self) -> Option<(&Path, &Path)> {
+        (**self).compare_raw_files()
+    }
+    fn merge_stats_files(&self) -> Option<&[PathBuf]> {
+        (**self).merge_stats_files()
+    }
+    fn diff_stats_files(&self) -> Option<(&Path, &Path)> {
+        (**self).diff_stats_files()
+    }
+    fn diff_ignore_fields(&self) -> Option<&[String]> {
+        (**self).diff_ignore_fields()
+    }
 }
 impl<T> InputOutputOpt for Arc<T>
 where
     T: InputOutputOpt,
 {
-    fn input_file(&self) -> Option<&Path> {
-        (**self).input_file()
+    fn input_files(&self) -> &[PathBuf] {
+        (**self).input_files()
+    }
+    fn gzip(&self) -> bool {
+        (**self).gzip()
+    }
+    fn zstd(&self) -> bool {
+        (**self).zstd()
+    }
+    fn follow(&self) -> bool {
+        (**self).follow()
+    }
+    fn recv_timeout(&self) -> Option<Duration> {
+        (**self).recv_timeout()
+    }
+    fn scan_for_rdh(&self) -> bool {
+        (**self).scan_for_rdh()
+    }
+    fn input_offset(&self) -> Option<u64> {
+        (**self).input_offset()
+    }
+    fn max_bytes(&self) -> Option<u64> {
+        (**self).max_bytes()
+    }
+    fn sample_every(&self) -> Option<u64> {
+        (**self).sample_every()
+    }
+    fn endianness(&self) -> Endianness {
+        (**self).endianness()
     }
     fn output(&self) -> Option<&Path> {
         (**self).output()
@@ -78,15 +273,51 @@ where
     fn output_mode(&self) -> DataOutputMode {
         (**self).output_mode()
     }
+    fn split_by_link(&self) -> bool {
+        (**self).split_by_link()
+    }
+    fn split_by_hbf(&self) -> bool {
+        (**self).split_by_hbf()
+    }
+    fn dump_format(&self) -> DumpFormat {
+        (**self).dump_format()
+    }
+    fn verify_output(&self) -> bool {
+        (**self).verify_output()
+    }
+    fn extract_rdhs(&self) -> Option<&[u64]> {
+        (**self).extract_rdhs()
+    }
     fn stats_output_mode(&self) -> DataOutputMode {
         (**self).stats_output_mode()
     }
     fn stats_output_format(&self) -> Option<DataOutputFormat> {
         (**self).stats_output_format()
     }
+    fn json_pretty(&self) -> bool {
+        (**self).json_pretty()
+    }
+    fn stats_flush_interval(&self) -> Option<Duration> {
+        (**self).stats_flush_interval()
+    }
     fn input_stats_file(&self) -> Option<&Path> {
         (**self).input_stats_file()
     }
+    fn filter_config_file(&self) -> Option<&Path> {
+        (**self).filter_config_file()
+    }
+    fn compare_raw_files(&self) -> Option<(&Path, &Path)> {
+        (**self).compare_raw_files()
+    }
+    fn merge_stats_files(&self) -> Option<&[PathBuf]> {
+        (**self).merge_stats_files()
+    }
+    fn diff_stats_files(&self) -> Option<(&Path, &Path)> {
+        (**self).diff_stats_files()
+    }
+    fn diff_ignore_fields(&self) -> Option<&[String]> {
+        (**self).diff_ignore_fields()
+    }
 }
 
 /// Enum for all possible data output modes.
@@ -94,6 +325,10 @@ where
 pub enum DataOutputMode {
     /// Write to a file.
     File(Box<Path>),
+    /// Write to one file per link, named by appending the link ID to the file stem, e.g. `out.raw` -> `out_link3.raw`.
+    SplitByLink(Box<Path>),
+    /// Write to one file per HBF, named by appending the HBF index to the file stem, e.g. `out.raw` -> `out_hbf0.raw`.
+    SplitByHbf(Box<Path>),
     /// Write to stdout.
     Stdout,
     /// Do not write data out.
@@ -104,6 +339,8 @@ impl fmt::Display for DataOutputMode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             DataOutputMode::File(p) => write!(f, "File({})", p.display()),
+            DataOutputMode::SplitByLink(p) => write!(f, "SplitByLink({})", p.display()),
+            DataOutputMode::SplitByHbf(p) => write!(f, "SplitByHbf({})", p.display()),
             DataOutputMode::Stdout => write!(f, "Stdout"),
             DataOutputMode::None => write!(f, "None"),
         }
@@ -122,6 +359,40 @@ impl FromStr for DataOutputMode {
     }
 }
 
+/// Enum for the formats of the data output.
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub enum DumpFormat {
+    /// The raw bytes of the CDPs, as they were read.
+    #[default]
+    Raw,
+    /// A human-readable dump of each CDP, the decoded RDH followed by the payload words in hex.
+    Ascii,
+}
+
+impl fmt::Display for DumpFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DumpFormat::Raw => write!(f, "raw"),
+            DumpFormat::Ascii => write!(f, "ascii"),
+        }
+    }
+}
+
+impl FromStr for DumpFormat {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "RAW" => Ok(DumpFormat::Raw),
+            "ASCII" => Ok(DumpFormat::Ascii),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Invalid dump format, expected one of: raw, ascii",
+            )),
+        }
+    }
+}
+
 /// Enum for all possible data output formats.
 #[derive(PartialEq, Debug, Clone, Copy)]
 pub enum DataOutputFormat {
@@ -129,6 +400,8 @@ pub enum DataOutputFormat {
     JSON,
     /// TOML format.
     TOML,
+    /// Key-value CSV format, only contains a flat subset of the stats.
+    CSV,
 }
 
 impl fmt::Display for DataOutputFormat {
@@ -136,6 +409,7 @@ impl fmt::Display for DataOutputFormat {
         match self {
             DataOutputFormat::JSON => write!(f, "JSON"),
             DataOutputFormat::TOML => write!(f, "TOML"),
+            DataOutputFormat::CSV => write!(f, "CSV"),
         }
     }
 }
@@ -147,6 +421,7 @@ impl FromStr for DataOutputFormat {
         match s.to_uppercase().as_str() {
             "JSON" => Ok(DataOutputFormat::JSON),
             "TOML" => Ok(DataOutputFormat::TOML),
+            "CSV" => Ok(DataOutputFormat::CSV),
             _ => Err(io::Error::new(
                 io::ErrorKind::InvalidInput,
                 "Invalid data output format",
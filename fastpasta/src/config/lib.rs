@@ -2,6 +2,8 @@
 //!
 //! Implementing the [Config] super trait is required by configs passed to structs in other modules as part of instantiation.
 
+use crate::analyze::validators::rdh::valid_link_id;
+use crate::stats::stats_schema::STATS_SCHEMA;
 use crate::util::*;
 
 // Styles for the help message
@@ -43,6 +45,300 @@ where
                 return Err("Invalid config: Specifying trigger period has to be done with the `check all its-stave` command".to_string());
             }
         }
+        if self.word_id_histogram() && self.check().is_none() {
+            return Err(
+                "Invalid config: Word ID histogram requires the `check` subcommand".to_string(),
+            );
+        }
+        if self.trigger_period_histogram()
+            && !matches!(
+                self.check(),
+                Some(CheckCommands::All(CheckModeArgs {
+                    target: Some(System::ITS | System::ITS_Stave),
+                    ..
+                }))
+            )
+        {
+            return Err("Invalid config: Trigger period histogram requires the `check all its` or `check all its-stave` command".to_string());
+        }
+        let checks_its = matches!(
+            self.check(),
+            Some(
+                CheckCommands::All(CheckModeArgs {
+                    target: Some(System::ITS | System::ITS_Stave),
+                    ..
+                }) | CheckCommands::Sanity(CheckModeArgs {
+                    target: Some(System::ITS | System::ITS_Stave),
+                    ..
+                })
+            )
+        );
+        if self.error_context().is_some() && !checks_its {
+            return Err("Invalid config: Error context requires checking ITS or ITS-stave, e.g. `check all its`".to_string());
+        }
+        if self.strict_padding() && !checks_its {
+            return Err("Invalid config: `--strict-padding` requires checking ITS or ITS-stave, e.g. `check sanity its`".to_string());
+        }
+        if self.check_reserved() && !checks_its {
+            return Err("Invalid config: `--check-reserved` requires checking ITS or ITS-stave, e.g. `check sanity its`".to_string());
+        }
+        if self.dump_ambiguous().is_some() && !checks_its {
+            return Err("Invalid config: `--dump-ambiguous` requires checking ITS or ITS-stave, e.g. `check sanity its`".to_string());
+        }
+        if self.its_barrel() != ItsBarrel::All
+            && !matches!(
+                self.check(),
+                Some(CheckCommands::All(CheckModeArgs {
+                    target: Some(System::ITS_Stave),
+                    ..
+                }))
+            )
+        {
+            return Err(
+                "Invalid config: `--its-barrel` requires the `check all its-stave` command"
+                    .to_string(),
+            );
+        }
+        if let Some(version) = self.force_rdh_version() {
+            if !(3..=100).contains(&version) {
+                return Err(format!(
+                    "Invalid config: RDH version to force is out of range: {version} (expected 3-100)"
+                ));
+            }
+        }
+        if let Some(stats_files) = self.merge_stats_files() {
+            if stats_files.len() < 2 {
+                return Err(
+                    "Invalid config: Merging stats requires at least two stats files".to_string(),
+                );
+            }
+        } else if self.output().is_some() && !self.filter_enabled() && self.extract_rdhs().is_none()
+        {
+            // The output of `merge-stats` is the merged stats, otherwise it is the filtered raw data
+            return Err("Invalid config: Output raw data requires setting a filter option (--filter-link, --filter-fee, --filter-its-stave, --filter-trigger-type, --filter-orbit-range or --extract-rdhs)".to_string());
+        }
+        if let Some(ignore_fields) = self.diff_ignore_fields() {
+            // Fields of maps, e.g. `error_stats.error_code_counts.E44`, can also be ignored
+            let is_stats_field = |field: &str| {
+                STATS_SCHEMA.iter().any(|&(name, field_type)| {
+                    field == name
+                        || (field_type.starts_with('{')
+                            && field
+                                .strip_prefix(name)
+                                .is_some_and(|key| key.starts_with('.')))
+                })
+            };
+            if let Some(unknown) = ignore_fields.iter().find(|field| !is_stats_field(field)) {
+                return Err(format!("Invalid config: Unknown stats field to ignore: {unknown}, see `print-stats-schema` for the fields of the stats"));
+            }
+        }
+        if let Some(jobs) = self.jobs() {
+            if jobs == 0 {
+                return Err("Invalid config: Number of jobs must be at least 1".to_string());
+            }
+            if self.check().is_none() {
+                return Err("Invalid config: Processing files independently with `--jobs` requires the `check` subcommand".to_string());
+            }
+            if self.input_files().is_empty() {
+                return Err("Invalid config: Processing files independently with `--jobs` requires input files, it cannot be used with stdin".to_string());
+            }
+            if self.show_progress() {
+                return Err("Invalid config: Progress bar is not supported when processing files independently with `--jobs`".to_string());
+            }
+        }
+        if self.input_offset().is_some() {
+            if self.input_files().is_empty() {
+                return Err("Invalid config: `--input-offset` requires input files, it cannot be used with stdin".to_string());
+            }
+            if self.jobs().is_some() {
+                return Err(
+                    "Invalid config: `--input-offset` cannot be used with `--jobs`".to_string(),
+                );
+            }
+        }
+        if self.quiet_stats() && self.check().is_none() {
+            return Err(
+                "Invalid config: `--quiet-stats` requires the `check` subcommand".to_string(),
+            );
+        }
+        if self.alpide_stats_only()
+            && !matches!(
+                self.check(),
+                Some(CheckCommands::All(CheckModeArgs {
+                    target: Some(System::ITS),
+                    ..
+                }))
+            )
+        {
+            return Err("Invalid config: `--alpide-stats-only` requires the `check all its` command, `check all its-stave` already collects ALPIDE stats".to_string());
+        }
+        if self.follow() {
+            if self.input_files().len() != 1 {
+                return Err("Invalid config: Following a growing file with `--follow` requires exactly one input file, it cannot be used with stdin".to_string());
+            }
+            if self.jobs().is_some() {
+                return Err("Invalid config: `--follow` cannot be used with `--jobs`".to_string());
+            }
+            if self.merge_stats_files().is_some()
+                || self.compare_raw_files().is_some()
+                || self.diff_stats_files().is_some()
+            {
+                return Err(
+                    "Invalid config: `--follow` requires the `check` or `view` subcommand"
+                        .to_string(),
+                );
+            }
+        }
+        if let Some(max_errors_per_link) = self.max_errors_per_link() {
+            if max_errors_per_link == 0 {
+                return Err(
+                    "Invalid config: `--max-errors-per-link` must be at least 1".to_string()
+                );
+            }
+            if self.check().is_none() {
+                return Err(
+                    "Invalid config: `--max-errors-per-link` requires the `check` subcommand"
+                        .to_string(),
+                );
+            }
+        }
+        if let Some(first_n_errors) = self.first_n_errors() {
+            if first_n_errors == 0 {
+                return Err("Invalid config: `--first-n-errors` must be at least 1, use `--mute-errors` to not show any error messages".to_string());
+            }
+            if self.check().is_none() {
+                return Err(
+                    "Invalid config: `--first-n-errors` requires the `check` subcommand"
+                        .to_string(),
+                );
+            }
+        }
+        if self.live_status() && self.check().is_none() && self.view().is_none() {
+            // The status is updated from the CDPs that are analyzed, which are only analyzed when checking or viewing data
+            return Err(
+                "Invalid config: `--live-status` requires the `check` or `view` subcommand"
+                    .to_string(),
+            );
+        }
+        if self.warnings_as_errors() && self.check().is_none() {
+            return Err(
+                "Invalid config: `--warnings-as-errors` requires the `check` subcommand"
+                    .to_string(),
+            );
+        }
+        if let Some(sample_every) = self.sample_every() {
+            if sample_every == 0 {
+                return Err("Invalid config: `--sample-every` must be at least 1".to_string());
+            }
+            // The running checks keep state between CDPs, that is meaningless if CDPs are skipped
+            match self.check() {
+                Some(CheckCommands::All(_)) => {
+                    return Err("Invalid config: `--sample-every` cannot be used with `check all`, the running checks require every CDP, use `check sanity` instead".to_string());
+                }
+                Some(CheckCommands::Sanity(_)) => (),
+                _ if self.view().is_some() => (),
+                _ => {
+                    return Err("Invalid config: `--sample-every` requires `check sanity` or the `view` subcommand".to_string());
+                }
+            }
+        }
+        if self.endianness() == Endianness::Big && self.scan_for_rdh() {
+            return Err(
+                "Invalid config: `--scan-for-rdh` is not supported with `--endianness big`"
+                    .to_string(),
+            );
+        }
+        if self.view() == Some(ViewCommands::Alpide) && self.filter_its_stave().is_none() {
+            return Err("Invalid config: `view alpide` requires filtering by an ITS stave with `--filter-its-stave`".to_string());
+        }
+        if self.view_html_output().is_some() && self.view() != Some(ViewCommands::Rdh) {
+            return Err(
+                "Invalid config: HTML output is only supported with `view rdh`".to_string(),
+            );
+        }
+        if self.view_rdh_compact() && self.view() != Some(ViewCommands::Rdh) {
+            return Err(
+                "Invalid config: Compact output is only supported with `view rdh`".to_string(),
+            );
+        }
+        if let Some(links) = self.filter_links() {
+            if let Some(link_id) = links.iter().find(|&&id| !valid_link_id(id)) {
+                return Err(format!(
+                    "Invalid config: Link ID to filter by is out of range: {link_id} (expected 0-11 or 15)"
+                ));
+            }
+            if let Some(link_id) = links.iter().duplicates().next() {
+                return Err(format!(
+                    "Invalid config: Duplicate link ID to filter by: {link_id}"
+                ));
+            }
+        }
+        if let Some(fee_id_ranges) = self.filter_fee() {
+            if let Some((start, end)) = fee_id_ranges.iter().find(|(start, end)| start > end) {
+                return Err(format!(
+                    "Invalid config: Start of FEE ID range to filter by is after the end: {start}-{end}"
+                ));
+            }
+        }
+        if let Some((start, end)) = self.filter_orbit_range() {
+            if start > end {
+                return Err(format!(
+                    "Invalid config: Start of orbit range to filter by is after the end: {start}-{end}"
+                ));
+            }
+        }
+        if self.split_by_link() && self.output_mode() == DataOutputMode::Stdout {
+            return Err(
+                "Invalid config: Splitting output by link requires an output file, not stdout"
+                    .to_string(),
+            );
+        }
+        if self.dump_format() == DumpFormat::Ascii && self.split_by_link() {
+            return Err(
+                "Invalid config: An ASCII dump cannot be split by link, use `--dump-format raw`"
+                    .to_string(),
+            );
+        }
+        if self.split_by_hbf() && self.output_mode() == DataOutputMode::Stdout {
+            return Err(
+                "Invalid config: Splitting output by HBF requires an output file, not stdout"
+                    .to_string(),
+            );
+        }
+        if self.dump_format() == DumpFormat::Ascii && self.split_by_hbf() {
+            return Err(
+                "Invalid config: An ASCII dump cannot be split by HBF, use `--dump-format raw`"
+                    .to_string(),
+            );
+        }
+        if self.verify_output() {
+            if !matches!(self.output_mode(), DataOutputMode::File(_)) {
+                return Err("Invalid config: `--verify-output` requires writing the output data to a single file, not stdout or split files".to_string());
+            }
+            if self.check().is_some() || self.view().is_some() {
+                return Err("Invalid config: `--verify-output` cannot be used with the `check` or `view` subcommand, as the output data is not written".to_string());
+            }
+            if self.extract_rdhs().is_some() {
+                return Err("Invalid config: `--verify-output` cannot be used with `--extract-rdhs`, as only the extracted RDHs are written".to_string());
+            }
+            if self.dump_format() == DumpFormat::Ascii {
+                return Err(
+                    "Invalid config: An ASCII dump cannot be verified, use `--dump-format raw`"
+                        .to_string(),
+                );
+            }
+        }
+        if self.recv_timeout().is_some() && self.check().is_none() && self.view().is_none() {
+            return Err(
+                "Invalid config: `--recv-timeout` requires the `check` or `view` subcommand"
+                    .to_string(),
+            );
+        }
+        if self.stats_flush_interval().is_some()
+            && !matches!(self.stats_output_mode(), DataOutputMode::File(_))
+        {
+            return Err("Invalid config: Flushing stats periodically with `--stats-flush-interval` requires writing the stats to a file with `--output-stats`".to_string());
+        }
         if self.any_errors_exit_code().is_some_and(|val| val == 0) {
             return Err("Invalid config: Exit code for any errors cannot be 0".to_string());
         }
@@ -60,9 +356,24 @@ where
                 ));
             } else if path_str.extension().unwrap() != "json"
                 && path_str.extension().unwrap() != "toml"
+                && path_str.extension().unwrap() != "csv"
             {
                 return Err(format!(
-                    "Invalid config: Input stats file has invalid extension, should be JSON or TOML, got: {})",
+                    "Invalid config: Input stats file has invalid extension, should be JSON, TOML or CSV, got: {})",
+                    path_str.to_string_lossy()
+                ));
+            }
+        }
+        // Validate filter config file
+        if let Some(path_str) = self.filter_config_file() {
+            if !path_str.is_file() {
+                return Err(format!(
+                    "Invalid config: Filter config file does not exist (got: {})",
+                    path_str.to_string_lossy()
+                ));
+            } else if !matches!(path_str.extension(), Some(ext) if ext == "toml") {
+                return Err(format!(
+                    "Invalid config: Filter config file should be a TOML file (got: {})",
                     path_str.to_string_lossy()
                 ));
             }
@@ -75,6 +386,11 @@ where
         self.check()
             .is_some_and(|c| c.target().is_some_and(|s| s == System::ITS_Stave))
     }
+
+    /// Check if ALPIDE stats are collected, either by the ALPIDE checks or with `--alpide-stats-only`
+    fn alpide_stats_enabled(&self) -> bool {
+        self.alpide_checks_enabled() || self.alpide_stats_only()
+    }
 }
 
 impl<T> Config for &T
@@ -88,6 +404,10 @@ where
     fn alpide_checks_enabled(&self) -> bool {
         (*self).alpide_checks_enabled()
     }
+
+    fn alpide_stats_enabled(&self) -> bool {
+        (*self).alpide_stats_enabled()
+    }
 }
 
 impl<T> Config for Box<T>
@@ -101,6 +421,10 @@ where
     fn alpide_checks_enabled(&self) -> bool {
         (**self).alpide_checks_enabled()
     }
+
+    fn alpide_stats_enabled(&self) -> bool {
+        (**self).alpide_stats_enabled()
+    }
 }
 impl<T> Config for Arc<T>
 where
@@ -113,4 +437,8 @@ where
     fn alpide_checks_enabled(&self) -> bool {
         (**self).alpide_checks_enabled()
     }
+
+    fn alpide_stats_enabled(&self) -> bool {
+        (**self).alpide_stats_enabled()
+    }
 }
@@ -2,12 +2,17 @@
 
 pub use super::check::CheckCommands;
 pub use super::check::ChecksOpt;
+pub use super::check::ItsBarrel;
 pub use super::check::System;
 pub use super::custom_checks::CustomChecksOpt;
 pub use super::inputoutput::DataOutputMode;
+pub use super::inputoutput::DumpFormat;
 pub use super::inputoutput::InputOutputOpt;
 pub use super::lib::Config;
 pub use super::test_util::MockConfig;
-pub use super::util::UtilOpt;
+pub use super::util::{
+    parse_byte_size, parse_error_code, parse_error_code_exit_code, parse_seconds, ErrorFormat,
+    RdhSanity, UtilOpt,
+};
 pub use super::view::ViewCommands;
 pub use super::view::ViewOpt;
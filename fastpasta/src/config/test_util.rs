@@ -7,24 +7,78 @@ use crate::util::*;
 pub struct MockConfig {
     pub check: Option<CheckCommands>,
     pub view: Option<ViewCommands>,
-    pub filter_link: Option<u8>,
-    pub filter_fee: Option<u16>,
+    pub view_html_output: Option<PathBuf>,
+    pub view_rdh_compact: bool,
+    pub filter_links: Vec<u8>,
+    pub filter_fee: Vec<(u16, u16)>,
     pub filter_its_stave: Option<String>,
+    pub filter_trigger_type: Option<u32>,
+    pub filter_orbit_range: Option<(u32, u32)>,
+    pub max_payload_size: Option<u16>,
     pub verbosity: u8,
     pub max_tolerate_errors: u32,
-    pub input_file: Option<PathBuf>,
+    pub max_errors_per_link: Option<u32>,
+    pub warnings_as_errors: bool,
+    pub input_files: Vec<PathBuf>,
+    pub gzip: bool,
+    pub zstd: bool,
+    pub follow: bool,
+    pub recv_timeout: Option<Duration>,
+    pub scan_for_rdh: bool,
+    pub input_offset: Option<u64>,
+    pub max_bytes: Option<u64>,
+    pub sample_every: Option<u64>,
+    pub endianness: Endianness,
     pub skip_payload: bool,
     pub output: Option<PathBuf>,
     pub output_mode: DataOutputMode,
+    pub split_by_link: bool,
+    pub split_by_hbf: bool,
+    pub verify_output: bool,
+    pub dump_format: DumpFormat,
     pub its_trigger_period: Option<u16>,
     pub exit_code_any_errors: Option<u8>,
     pub mute_errors: bool,
+    pub first_n_errors: Option<u32>,
+    pub quiet_stats: bool,
     pub generate_checks_toml: bool,
     pub custom_checks: Option<CustomChecks>,
     pub stats_output_mode: DataOutputMode,
     pub stats_output_format: Option<DataOutputFormat>,
+    pub json_pretty: bool,
+    pub stats_flush_interval: Option<Duration>,
+    pub live_status: bool,
+    pub show_progress: bool,
+    pub force_rdh_version: Option<u8>,
+    pub rdh_sanity: RdhSanity,
+    pub log_timestamps: bool,
+    pub log_file: Option<PathBuf>,
+    pub reader_stall_threshold: Option<Duration>,
+    pub jobs: Option<usize>,
+    pub dry_run: bool,
+    pub print_stats_schema: bool,
+    pub list_systems: bool,
+    pub filter_config_file: Option<PathBuf>,
+    pub error_code_exit_codes: Vec<(u16, u8)>,
+    pub extract_rdhs: Option<Vec<u64>>,
     pub stats_input_file: Option<PathBuf>,
     pub show_error_codes: Vec<String>,
+    pub error_format: ErrorFormat,
+    pub mute_error_codes: Vec<u16>,
+    pub word_id_histogram: bool,
+    pub trigger_period_histogram: bool,
+    pub error_context: Option<u8>,
+    pub alpide_stats_only: bool,
+    pub allow_mixed_data_format: bool,
+    pub expect_single_cru: bool,
+    pub strict_padding: bool,
+    pub check_reserved: bool,
+    pub dump_ambiguous: Option<PathBuf>,
+    pub its_barrel: ItsBarrel,
+    pub compare_raw_files: Option<(PathBuf, PathBuf)>,
+    pub merge_stats_files: Option<Vec<PathBuf>>,
+    pub diff_stats_files: Option<(PathBuf, PathBuf)>,
+    pub diff_ignore_fields: Vec<String>,
 }
 
 impl Default for MockConfig {
@@ -38,24 +92,78 @@ impl MockConfig {
         Self {
             check: None,
             view: None,
-            filter_link: None,
-            filter_fee: None,
+            view_html_output: None,
+            view_rdh_compact: false,
+            filter_links: Vec::new(),
+            filter_fee: Vec::new(),
             filter_its_stave: None,
+            filter_trigger_type: None,
+            filter_orbit_range: None,
+            max_payload_size: None,
             verbosity: 0,
             max_tolerate_errors: 0,
-            input_file: None,
+            max_errors_per_link: None,
+            warnings_as_errors: false,
+            input_files: Vec::new(),
+            gzip: false,
+            zstd: false,
+            follow: false,
+            recv_timeout: None,
+            scan_for_rdh: false,
+            input_offset: None,
+            max_bytes: None,
+            sample_every: None,
+            endianness: Endianness::Little,
             skip_payload: false,
             output: None,
             output_mode: DataOutputMode::None,
+            split_by_link: false,
+            split_by_hbf: false,
+            verify_output: false,
+            dump_format: DumpFormat::Raw,
             its_trigger_period: None,
             exit_code_any_errors: None,
             mute_errors: false,
+            first_n_errors: None,
+            quiet_stats: false,
             generate_checks_toml: false,
             custom_checks: None,
             stats_output_mode: DataOutputMode::None,
             stats_output_format: None,
+            json_pretty: false,
+            stats_flush_interval: None,
+            live_status: false,
+            show_progress: false,
+            force_rdh_version: None,
+            rdh_sanity: RdhSanity::Strict,
+            log_timestamps: false,
+            log_file: None,
+            reader_stall_threshold: None,
+            jobs: None,
+            dry_run: false,
+            print_stats_schema: false,
+            list_systems: false,
+            filter_config_file: None,
+            error_code_exit_codes: Vec::new(),
+            extract_rdhs: None,
             stats_input_file: None,
             show_error_codes: Vec::new(),
+            error_format: ErrorFormat::Human,
+            mute_error_codes: Vec::new(),
+            word_id_histogram: false,
+            trigger_period_histogram: false,
+            error_context: None,
+            alpide_stats_only: false,
+            allow_mixed_data_format: false,
+            expect_single_cru: false,
+            strict_padding: false,
+            check_reserved: false,
+            dump_ambiguous: None,
+            its_barrel: ItsBarrel::All,
+            compare_raw_files: None,
+            merge_stats_files: None,
+            diff_stats_files: None,
+            diff_ignore_fields: Vec::new(),
         }
     }
 
@@ -79,23 +187,84 @@ impl ChecksOpt for MockConfig {
     fn check_its_trigger_period(&self) -> Option<u16> {
         self.its_trigger_period
     }
+    fn word_id_histogram(&self) -> bool {
+        self.word_id_histogram
+    }
+    fn trigger_period_histogram(&self) -> bool {
+        self.trigger_period_histogram
+    }
+
+    fn error_context(&self) -> Option<u8> {
+        self.error_context
+    }
+
+    fn alpide_stats_only(&self) -> bool {
+        self.alpide_stats_only
+    }
+
+    fn allow_mixed_data_format(&self) -> bool {
+        self.allow_mixed_data_format
+    }
+
+    fn expect_single_cru(&self) -> bool {
+        self.expect_single_cru
+    }
+
+    fn strict_padding(&self) -> bool {
+        self.strict_padding
+    }
+    fn check_reserved(&self) -> bool {
+        self.check_reserved
+    }
+    fn dump_ambiguous(&self) -> Option<&Path> {
+        self.dump_ambiguous.as_deref()
+    }
+    fn its_barrel(&self) -> ItsBarrel {
+        self.its_barrel
+    }
 }
 impl ViewOpt for MockConfig {
     fn view(&self) -> Option<ViewCommands> {
         self.view
     }
+    fn view_html_output(&self) -> Option<&Path> {
+        self.view_html_output.as_deref()
+    }
+    fn view_rdh_compact(&self) -> bool {
+        self.view_rdh_compact
+    }
 }
 impl FilterOpt for MockConfig {
     fn skip_payload(&self) -> bool {
         self.skip_payload
     }
 
-    fn filter_link(&self) -> Option<u8> {
-        self.filter_link
+    fn filter_links(&self) -> Option<&[u8]> {
+        if self.filter_links.is_empty() {
+            None
+        } else {
+            Some(&self.filter_links)
+        }
+    }
+
+    fn filter_fee(&self) -> Option<&[(u16, u16)]> {
+        if self.filter_fee.is_empty() {
+            None
+        } else {
+            Some(&self.filter_fee)
+        }
+    }
+
+    fn filter_trigger_type(&self) -> Option<u32> {
+        self.filter_trigger_type
+    }
+
+    fn filter_orbit_range(&self) -> Option<(u32, u32)> {
+        self.filter_orbit_range
     }
 
-    fn filter_fee(&self) -> Option<u16> {
-        self.filter_fee
+    fn max_payload_size(&self) -> Option<u16> {
+        self.max_payload_size
     }
 
     fn filter_its_stave(&self) -> Option<u16> {
@@ -124,6 +293,13 @@ impl UtilOpt for MockConfig {
         self.max_tolerate_errors
     }
 
+    fn max_errors_per_link(&self) -> Option<u32> {
+        self.max_errors_per_link
+    }
+    fn warnings_as_errors(&self) -> bool {
+        self.warnings_as_errors
+    }
+
     fn any_errors_exit_code(&self) -> Option<u8> {
         self.exit_code_any_errors
     }
@@ -131,6 +307,12 @@ impl UtilOpt for MockConfig {
     fn mute_errors(&self) -> bool {
         self.mute_errors
     }
+    fn first_n_errors(&self) -> Option<u32> {
+        self.first_n_errors
+    }
+    fn quiet_stats(&self) -> bool {
+        self.quiet_stats
+    }
 
     fn error_code_filter(&self) -> Option<&[String]> {
         if self.show_error_codes.is_empty() {
@@ -140,13 +322,100 @@ impl UtilOpt for MockConfig {
         }
     }
 
+    fn error_format(&self) -> ErrorFormat {
+        self.error_format
+    }
+
+    fn mute_error_codes(&self) -> Option<&[u16]> {
+        if self.mute_error_codes.is_empty() {
+            None
+        } else {
+            Some(&self.mute_error_codes)
+        }
+    }
+
+    fn live_status(&self) -> bool {
+        self.live_status
+    }
+    fn show_progress(&self) -> bool {
+        self.show_progress
+    }
+    fn force_rdh_version(&self) -> Option<u8> {
+        self.force_rdh_version
+    }
+    fn rdh_sanity(&self) -> RdhSanity {
+        self.rdh_sanity
+    }
+    fn reader_stall_threshold(&self) -> Option<Duration> {
+        self.reader_stall_threshold
+    }
+    fn jobs(&self) -> Option<usize> {
+        self.jobs
+    }
+    fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+    fn print_stats_schema(&self) -> bool {
+        self.print_stats_schema
+    }
+    fn list_systems(&self) -> bool {
+        self.list_systems
+    }
+    fn error_code_exit_codes(&self) -> Option<&[(u16, u8)]> {
+        if self.error_code_exit_codes.is_empty() {
+            None
+        } else {
+            Some(&self.error_code_exit_codes)
+        }
+    }
     fn disable_styled_views(&self) -> bool {
         true
     }
+    fn no_color(&self) -> bool {
+        true
+    }
+    fn log_timestamps(&self) -> bool {
+        self.log_timestamps
+    }
+    fn log_file(&self) -> Option<&Path> {
+        self.log_file.as_deref()
+    }
 }
 impl InputOutputOpt for MockConfig {
-    fn input_file(&self) -> Option<&Path> {
-        self.input_file.as_deref()
+    fn input_files(&self) -> &[PathBuf] {
+        &self.input_files
+    }
+    fn gzip(&self) -> bool {
+        self.gzip
+    }
+    fn zstd(&self) -> bool {
+        self.zstd
+    }
+    fn follow(&self) -> bool {
+        self.follow
+    }
+    fn recv_timeout(&self) -> Option<Duration> {
+        self.recv_timeout
+    }
+
+    fn scan_for_rdh(&self) -> bool {
+        self.scan_for_rdh
+    }
+
+    fn input_offset(&self) -> Option<u64> {
+        self.input_offset
+    }
+
+    fn max_bytes(&self) -> Option<u64> {
+        self.max_bytes
+    }
+
+    fn sample_every(&self) -> Option<u64> {
+        self.sample_every
+    }
+
+    fn endianness(&self) -> Endianness {
+        self.endianness
     }
 
     fn output(&self) -> Option<&Path> {
@@ -157,6 +426,20 @@ impl InputOutputOpt for MockConfig {
         self.output_mode.clone()
     }
 
+    fn split_by_link(&self) -> bool {
+        self.split_by_link
+    }
+    fn split_by_hbf(&self) -> bool {
+        self.split_by_hbf
+    }
+    fn verify_output(&self) -> bool {
+        self.verify_output
+    }
+
+    fn dump_format(&self) -> DumpFormat {
+        self.dump_format
+    }
+
     fn stats_output_mode(&self) -> DataOutputMode {
         self.stats_output_mode.clone()
     }
@@ -164,10 +447,47 @@ impl InputOutputOpt for MockConfig {
     fn stats_output_format(&self) -> Option<super::inputoutput::DataOutputFormat> {
         self.stats_output_format
     }
+    fn json_pretty(&self) -> bool {
+        self.json_pretty
+    }
+    fn stats_flush_interval(&self) -> Option<Duration> {
+        self.stats_flush_interval
+    }
+    fn extract_rdhs(&self) -> Option<&[u64]> {
+        self.extract_rdhs.as_deref()
+    }
 
     fn input_stats_file(&self) -> Option<&Path> {
         self.stats_input_file.as_deref()
     }
+
+    fn filter_config_file(&self) -> Option<&Path> {
+        self.filter_config_file.as_deref()
+    }
+
+    fn compare_raw_files(&self) -> Option<(&Path, &Path)> {
+        self.compare_raw_files
+            .as_ref()
+            .map(|(golden, candidate)| (golden.as_path(), candidate.as_path()))
+    }
+
+    fn merge_stats_files(&self) -> Option<&[PathBuf]> {
+        self.merge_stats_files.as_deref()
+    }
+
+    fn diff_stats_files(&self) -> Option<(&Path, &Path)> {
+        self.diff_stats_files
+            .as_ref()
+            .map(|(old, new)| (old.as_path(), new.as_path()))
+    }
+
+    fn diff_ignore_fields(&self) -> Option<&[String]> {
+        if self.diff_ignore_fields.is_empty() {
+            None
+        } else {
+            Some(&self.diff_ignore_fields)
+        }
+    }
 }
 
 impl CustomChecksOpt for MockConfig {
@@ -200,6 +520,22 @@ impl CustomChecksOpt for MockConfig {
         }
     }
 
+    fn hbfs(&self) -> Option<u32> {
+        self.custom_checks.as_ref().and_then(CustomChecks::hbfs)
+    }
+
+    fn expected_links(&'static self) -> Option<&'static [u8]> {
+        self.custom_checks
+            .as_ref()
+            .and_then(CustomChecks::expected_links)
+    }
+
+    fn layer_stave_counts(&'static self) -> Option<&'static [(u8, u8)]> {
+        self.custom_checks
+            .as_ref()
+            .and_then(CustomChecks::layer_stave_counts)
+    }
+
     fn rdh_version(&self) -> Option<u8> {
         if self.custom_checks.is_some() {
             self.custom_checks.as_ref().unwrap().rdh_version()
@@ -0,0 +1,162 @@
+//! Contains the [TriggerTypeMasks] struct with the bit masks used to decode the RDH trigger type.
+//!
+//! The masks default to the trigger bit assignments of the CRU firmware, but can be overridden by a TOML file,
+//! e.g. for data from detectors or firmware versions with different trigger bit assignments.
+//!
+//! Example of a TOML file that moves the physics trigger to bit 3, the remaining masks keep their default values:
+//! ```toml
+//! pht = 0b1000
+//! ```
+
+use crate::util::*;
+
+/// The [TRIGGER_TYPE_MASKS] static variable stores the [TriggerTypeMasks] used for decoding trigger types.
+static TRIGGER_TYPE_MASKS: OnceLock<TriggerTypeMasks> = OnceLock::new();
+
+/// Bit masks for the trigger types in the RDH trigger type field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TriggerTypeMasks {
+    /// Physics trigger
+    pub pht: u32,
+    /// Start of continuous
+    pub soc: u32,
+    /// Start of triggered
+    pub sot: u32,
+    /// Heartbeat
+    pub hb: u32,
+}
+
+impl Default for TriggerTypeMasks {
+    fn default() -> Self {
+        Self {
+            pht: 0b1_0000,
+            soc: 0b10_0000_0000,
+            sot: 0b1000_0000,
+            hb: 0b10,
+        }
+    }
+}
+
+impl TriggerTypeMasks {
+    /// Parse [TriggerTypeMasks] from a TOML file, masks that are not specified keep their default value.
+    pub fn from_toml_path(toml_path: &Path) -> Result<Self, String> {
+        let toml = fs::read_to_string(toml_path).map_err(|e| {
+            format!(
+                "Failed to read trigger type masks TOML file {}: {e}",
+                toml_path.display()
+            )
+        })?;
+        Self::from_toml_str(&toml)
+    }
+
+    /// Parse [TriggerTypeMasks] from a TOML string, masks that are not specified keep their default value.
+    pub fn from_toml_str(toml: &str) -> Result<Self, String> {
+        toml::from_str(toml).map_err(|e| format!("Failed to parse trigger type masks TOML: {e}"))
+    }
+}
+
+/// Mnemonics of the trigger types that have a bit mask in [TriggerTypeMasks]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerTypeMnemonic {
+    /// Physics trigger
+    PhT,
+    /// Start of continuous
+    SOC,
+    /// Start of triggered
+    SOT,
+    /// Heartbeat
+    HB,
+}
+
+impl TriggerTypeMnemonic {
+    /// Returns the bit mask of the trigger type from the [TriggerTypeMasks] used for decoding trigger types
+    pub fn mask(&self) -> u32 {
+        let masks = trigger_type_masks();
+        match self {
+            TriggerTypeMnemonic::PhT => masks.pht,
+            TriggerTypeMnemonic::SOC => masks.soc,
+            TriggerTypeMnemonic::SOT => masks.sot,
+            TriggerTypeMnemonic::HB => masks.hb,
+        }
+    }
+}
+
+impl fmt::Display for TriggerTypeMnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TriggerTypeMnemonic::PhT => write!(f, "PhT"),
+            TriggerTypeMnemonic::SOC => write!(f, "SOC"),
+            TriggerTypeMnemonic::SOT => write!(f, "SOT"),
+            TriggerTypeMnemonic::HB => write!(f, "HB"),
+        }
+    }
+}
+
+impl FromStr for TriggerTypeMnemonic {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "PHT" => Ok(TriggerTypeMnemonic::PhT),
+            "SOC" => Ok(TriggerTypeMnemonic::SOC),
+            "SOT" => Ok(TriggerTypeMnemonic::SOT),
+            "HB" => Ok(TriggerTypeMnemonic::HB),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Unrecognized trigger type, expected one of: PhT, SOC, SOT, HB",
+            )),
+        }
+    }
+}
+
+/// Set the [TriggerTypeMasks] used for decoding trigger types, fails if they were already set or used.
+pub fn set_trigger_type_masks(masks: TriggerTypeMasks) -> Result<(), String> {
+    TRIGGER_TYPE_MASKS
+        .set(masks)
+        .map_err(|_| String::from("Trigger type masks are already initialized"))
+}
+
+/// Get the [TriggerTypeMasks] used for decoding trigger types.
+///
+/// Returns the default masks if no masks were set.
+pub fn trigger_type_masks() -> &'static TriggerTypeMasks {
+    TRIGGER_TYPE_MASKS.get_or_init(TriggerTypeMasks::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_trigger_type_masks_from_toml_partial_override() {
+        let masks = TriggerTypeMasks::from_toml_str("pht = 0b1000").unwrap();
+        assert_eq!(
+            masks,
+            TriggerTypeMasks {
+                pht: 0b1000,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_trigger_type_mnemonic_from_str() {
+        assert_eq!(
+            "PhT".parse::<TriggerTypeMnemonic>().unwrap(),
+            TriggerTypeMnemonic::PhT
+        );
+        assert_eq!(
+            "soc".parse::<TriggerTypeMnemonic>().unwrap(),
+            TriggerTypeMnemonic::SOC
+        );
+        assert!("PhysicsTrigger".parse::<TriggerTypeMnemonic>().is_err());
+    }
+
+    #[test]
+    fn test_trigger_type_masks_from_toml_unknown_field() {
+        let err = TriggerTypeMasks::from_toml_str("physics = 0b1000").unwrap_err();
+        assert!(err.contains("unknown field"), "{err}");
+    }
+}
@@ -1,6 +1,6 @@
 //! Contains the [UtilOpt] Trait for all small utility options set by a user, that are not specific to any other subfunctionality.
 
-use std::sync::Arc;
+use std::{fmt, io, path::Path, str::FromStr, sync::Arc, time::Duration};
 
 /// Trait for all small utility options that are not specific to any other trait
 pub trait UtilOpt {
@@ -8,14 +8,52 @@ pub trait UtilOpt {
     fn verbosity(&self) -> u8;
     /// Maximum number of errors to tolerate before exiting
     fn max_tolerate_errors(&self) -> u32;
+    /// Maximum number of errors to tolerate on each link before the rest of its data is no longer checked, if set
+    fn max_errors_per_link(&self) -> Option<u32>;
+    /// If set, warnings about the input data are reported and counted as errors
+    fn warnings_as_errors(&self) -> bool;
     /// Set the exit code for if any errors are detected in the input data
     fn any_errors_exit_code(&self) -> Option<u8>;
     /// If set, error messages are not displayed
     fn mute_errors(&self) -> bool;
+    /// If set, only the first N error messages are displayed, all errors are still counted
+    fn first_n_errors(&self) -> Option<u32>;
+    /// If set, only a one-line pass/fail verdict is printed instead of the error messages and the report
+    fn quiet_stats(&self) -> bool;
     /// Allows specifying any number of error codes to filter by
     fn error_code_filter(&self) -> Option<&[String]>;
+    /// Format of the error messages, human-readable or one JSON object per error
+    fn error_format(&self) -> ErrorFormat;
+    /// Error codes of errors that are ignored, they are neither displayed nor counted
+    fn mute_error_codes(&self) -> Option<&[u16]>;
     /// Sets whether view output should be styled or not
     fn disable_styled_views(&self) -> bool;
+    /// If set, error messages and views are never colored, otherwise error messages are colored if stderr is a terminal
+    fn no_color(&self) -> bool;
+    /// If set, log messages on stderr are prefixed with a timestamp
+    fn log_timestamps(&self) -> bool;
+    /// File to also write the log messages to, each prefixed with a timestamp, if set
+    fn log_file(&self) -> Option<&Path>;
+    /// If set, a condensed per-link status line is printed to stderr during processing
+    fn live_status(&self) -> bool;
+    /// If set, a progress bar of the bytes read is shown on stderr during processing
+    fn show_progress(&self) -> bool;
+    /// RDH version to assume instead of detecting it from the first RDH, if set
+    fn force_rdh_version(&self) -> Option<u8>;
+    /// How strict the sanity check of the first RDH is, before processing the data
+    fn rdh_sanity(&self) -> RdhSanity;
+    /// Sending CDPs from the reader thread blocking for longer than this is counted as a stall, if set
+    fn reader_stall_threshold(&self) -> Option<Duration>;
+    /// Number of input files to process concurrently, each file independently of the others, if set
+    fn jobs(&self) -> Option<usize>;
+    /// Exit codes to use if errors with specific error codes are detected, as (error code, exit code) in order of precedence
+    fn error_code_exit_codes(&self) -> Option<&[(u16, u8)]>;
+    /// If set, a summary of what would be checked, viewed, or filtered is printed instead of reading any data
+    fn dry_run(&self) -> bool;
+    /// If set, the schema of the serialized stats is printed instead of reading any data
+    fn print_stats_schema(&self) -> bool;
+    /// If set, the recognized detector systems are listed instead of reading any data
+    fn list_systems(&self) -> bool;
 }
 
 impl<T> UtilOpt for &T
@@ -28,6 +66,12 @@ where
     fn max_tolerate_errors(&self) -> u32 {
         (*self).max_tolerate_errors()
     }
+    fn max_errors_per_link(&self) -> Option<u32> {
+        (*self).max_errors_per_link()
+    }
+    fn warnings_as_errors(&self) -> bool {
+        (*self).warnings_as_errors()
+    }
 
     fn any_errors_exit_code(&self) -> Option<u8> {
         (*self).any_errors_exit_code()
@@ -36,14 +80,65 @@ where
     fn mute_errors(&self) -> bool {
         (*self).mute_errors()
     }
+    fn first_n_errors(&self) -> Option<u32> {
+        (*self).first_n_errors()
+    }
+    fn quiet_stats(&self) -> bool {
+        (*self).quiet_stats()
+    }
 
     fn error_code_filter(&self) -> Option<&[String]> {
         (*self).error_code_filter()
     }
+    fn error_format(&self) -> ErrorFormat {
+        (*self).error_format()
+    }
+    fn mute_error_codes(&self) -> Option<&[u16]> {
+        (*self).mute_error_codes()
+    }
 
     fn disable_styled_views(&self) -> bool {
         (*self).disable_styled_views()
     }
+    fn no_color(&self) -> bool {
+        (*self).no_color()
+    }
+    fn log_timestamps(&self) -> bool {
+        (*self).log_timestamps()
+    }
+    fn log_file(&self) -> Option<&Path> {
+        (*self).log_file()
+    }
+    fn live_status(&self) -> bool {
+        (*self).live_status()
+    }
+    fn show_progress(&self) -> bool {
+        (*self).show_progress()
+    }
+    fn force_rdh_version(&self) -> Option<u8> {
+        (*self).force_rdh_version()
+    }
+    fn rdh_sanity(&self) -> RdhSanity {
+        (*self).rdh_sanity()
+    }
+    fn reader_stall_threshold(&self) -> Option<Duration> {
+        (*self).reader_stall_threshold()
+    }
+    fn jobs(&self) -> Option<usize> {
+        (*self).jobs()
+    }
+    fn dry_run(&self) -> bool {
+        (*self).dry_run()
+    }
+    fn print_stats_schema(&self) -> bool {
+        (*self).print_stats_schema()
+    }
+    fn list_systems(&self) -> bool {
+        (*self).list_systems()
+    }
+    fn error_code_exit_codes(&self) -> Option<&[(u16, u8)]> {
+        (*self).error_code_exit_codes()
+    }
 }
 
 impl<T> UtilOpt for &mut T
@@ -56,18 +151,75 @@ where
     fn max_tolerate_errors(&self) -> u32 {
         (**self).max_tolerate_errors()
     }
+    fn max_errors_per_link(&self) -> Option<u32> {
+        (**self).max_errors_per_link()
+    }
+    fn warnings_as_errors(&self) -> bool {
+        (**self).warnings_as_errors()
+    }
     fn any_errors_exit_code(&self) -> Option<u8> {
         (**self).any_errors_exit_code()
     }
     fn mute_errors(&self) -> bool {
         (**self).mute_errors()
     }
+    fn first_n_errors(&self) -> Option<u32> {
+        (**self).first_n_errors()
+    }
+    fn quiet_stats(&self) -> bool {
+        (**self).quiet_stats()
+    }
     fn error_code_filter(&self) -> Option<&[String]> {
         (**self).error_code_filter()
     }
+    fn error_format(&self) -> ErrorFormat {
+        (**self).error_format()
+    }
+    fn mute_error_codes(&self) -> Option<&[u16]> {
+        (**self).mute_error_codes()
+    }
     fn disable_styled_views(&self) -> bool {
         (**self).disable_styled_views()
     }
+    fn no_color(&self) -> bool {
+        (**self).no_color()
+    }
+    fn log_timestamps(&self) -> bool {
+        (**self).log_timestamps()
+    }
+    fn log_file(&self) -> Option<&Path> {
+        (**self).log_file()
+    }
+    fn live_status(&self) -> bool {
+        (**self).live_status()
+    }
+    fn show_progress(&self) -> bool {
+        (**self).show_progress()
+    }
+    fn force_rdh_version(&self) -> Option<u8> {
+        (**self).force_rdh_version()
+    }
+    fn rdh_sanity(&self) -> RdhSanity {
+        (**self).rdh_sanity()
+    }
+    fn reader_stall_threshold(&self) -> Option<Duration> {
+        (**self).reader_stall_threshold()
+    }
+    fn jobs(&self) -> Option<usize> {
+        (**self).jobs()
+    }
+    fn dry_run(&self) -> bool {
+        (**self).dry_run()
+    }
+    fn print_stats_schema(&self) -> bool {
+        (**self).print_stats_schema()
+    }
+    fn list_systems(&self) -> bool {
+        (**self).list_systems()
+    }
+    fn error_code_exit_codes(&self) -> Option<&[(u16, u8)]> {
+        (**self).error_code_exit_codes()
+    }
 }
 
 impl<T> UtilOpt for Box<T>
@@ -81,6 +233,12 @@ where
     fn max_tolerate_errors(&self) -> u32 {
         (**self).max_tolerate_errors()
     }
+    fn max_errors_per_link(&self) -> Option<u32> {
+        (**self).max_errors_per_link()
+    }
+    fn warnings_as_errors(&self) -> bool {
+        (**self).warnings_as_errors()
+    }
 
     fn any_errors_exit_code(&self) -> Option<u8> {
         (**self).any_errors_exit_code()
@@ -88,12 +246,63 @@ where
     fn mute_errors(&self) -> bool {
         (**self).mute_errors()
     }
+    fn first_n_errors(&self) -> Option<u32> {
+        (**self).first_n_errors()
+    }
+    fn quiet_stats(&self) -> bool {
+        (**self).quiet_stats()
+    }
     fn error_code_filter(&self) -> Option<&[String]> {
         (**self).error_code_filter()
     }
+    fn error_format(&self) -> ErrorFormat {
+        (**self).error_format()
+    }
+    fn mute_error_codes(&self) -> Option<&[u16]> {
+        (**self).mute_error_codes()
+    }
     fn disable_styled_views(&self) -> bool {
         (**self).disable_styled_views()
     }
+    fn no_color(&self) -> bool {
+        (**self).no_color()
+    }
+    fn log_timestamps(&self) -> bool {
+        (**self).log_timestamps()
+    }
+    fn log_file(&self) -> Option<&Path> {
+        (**self).log_file()
+    }
+    fn live_status(&self) -> bool {
+        (**self).live_status()
+    }
+    fn show_progress(&self) -> bool {
+        (**self).show_progress()
+    }
+    fn force_rdh_version(&self) -> Option<u8> {
+        (**self).force_rdh_version()
+    }
+    fn rdh_sanity(&self) -> RdhSanity {
+        (**self).rdh_sanity()
+    }
+    fn reader_stall_threshold(&self) -> Option<Duration> {
+        (**self).reader_stall_threshold()
+    }
+    fn jobs(&self) -> Option<usize> {
+        (**self).jobs()
+    }
+    fn dry_run(&self) -> bool {
+        (**self).dry_run()
+    }
+    fn print_stats_schema(&self) -> bool {
+        (**self).print_stats_schema()
+    }
+    fn list_systems(&self) -> bool {
+        (**self).list_systems()
+    }
+    fn error_code_exit_codes(&self) -> Option<&[(u16, u8)]> {
+        (**self).error_code_exit_codes()
+    }
 }
 
 impl<T> UtilOpt for Arc<T>
@@ -107,6 +316,12 @@ where
     fn max_tolerate_errors(&self) -> u32 {
         (**self).max_tolerate_errors()
     }
+    fn max_errors_per_link(&self) -> Option<u32> {
+        (**self).max_errors_per_link()
+    }
+    fn warnings_as_errors(&self) -> bool {
+        (**self).warnings_as_errors()
+    }
 
     fn any_errors_exit_code(&self) -> Option<u8> {
         (**self).any_errors_exit_code()
@@ -115,10 +330,247 @@ where
     fn mute_errors(&self) -> bool {
         (**self).mute_errors()
     }
+    fn first_n_errors(&self) -> Option<u32> {
+        (**self).first_n_errors()
+    }
+    fn quiet_stats(&self) -> bool {
+        (**self).quiet_stats()
+    }
     fn error_code_filter(&self) -> Option<&[String]> {
         (**self).error_code_filter()
     }
+    fn error_format(&self) -> ErrorFormat {
+        (**self).error_format()
+    }
+    fn mute_error_codes(&self) -> Option<&[u16]> {
+        (**self).mute_error_codes()
+    }
     fn disable_styled_views(&self) -> bool {
         (**self).disable_styled_views()
     }
+    fn no_color(&self) -> bool {
+        (**self).no_color()
+    }
+    fn log_timestamps(&self) -> bool {
+        (**self).log_timestamps()
+    }
+    fn log_file(&self) -> Option<&Path> {
+        (**self).log_file()
+    }
+    fn live_status(&self) -> bool {
+        (**self).live_status()
+    }
+    fn show_progress(&self) -> bool {
+        (**self).show_progress()
+    }
+    fn force_rdh_version(&self) -> Option<u8> {
+        (**self).force_rdh_version()
+    }
+    fn rdh_sanity(&self) -> RdhSanity {
+        (**self).rdh_sanity()
+    }
+    fn reader_stall_threshold(&self) -> Option<Duration> {
+        (**self).reader_stall_threshold()
+    }
+    fn jobs(&self) -> Option<usize> {
+        (**self).jobs()
+    }
+    fn dry_run(&self) -> bool {
+        (**self).dry_run()
+    }
+    fn print_stats_schema(&self) -> bool {
+        (**self).print_stats_schema()
+    }
+    fn list_systems(&self) -> bool {
+        (**self).list_systems()
+    }
+    fn error_code_exit_codes(&self) -> Option<&[(u16, u8)]> {
+        (**self).error_code_exit_codes()
+    }
+}
+
+/// Enum for the possible formats of error messages.
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub enum ErrorFormat {
+    /// Human-readable error messages, printed at the end of processing sorted by memory position.
+    #[default]
+    Human,
+    /// One JSON object per error written to stderr as soon as the error is reported (NDJSON).
+    JSON,
+}
+
+impl fmt::Display for ErrorFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorFormat::Human => write!(f, "human"),
+            ErrorFormat::JSON => write!(f, "json"),
+        }
+    }
+}
+
+impl FromStr for ErrorFormat {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "HUMAN" => Ok(ErrorFormat::Human),
+            "JSON" => Ok(ErrorFormat::JSON),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Invalid error format, expected one of: human, json",
+            )),
+        }
+    }
+}
+
+/// Enum for the profiles of the sanity check of the first RDH.
+#[derive(PartialEq, Debug, Clone, Copy, Default)]
+pub enum RdhSanity {
+    /// Any unexpected value in the first RDH aborts processing.
+    #[default]
+    Strict,
+    /// Unusual but non-fatal values in the first RDH, e.g. an unexpected header size, are only a warning.
+    Lenient,
+}
+
+impl fmt::Display for RdhSanity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RdhSanity::Strict => write!(f, "strict"),
+            RdhSanity::Lenient => write!(f, "lenient"),
+        }
+    }
+}
+
+impl FromStr for RdhSanity {
+    type Err = io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_uppercase().as_str() {
+            "STRICT" => Ok(RdhSanity::Strict),
+            "LENIENT" => Ok(RdhSanity::Lenient),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Invalid RDH sanity profile, expected one of: strict, lenient",
+            )),
+        }
+    }
+}
+
+/// Parses an error code with or without the `E` prefix, e.g. `E44` or `44`
+pub fn parse_error_code(error_code: &str) -> Result<u16, String> {
+    let digits = error_code
+        .strip_prefix(['E', 'e'])
+        .unwrap_or(error_code)
+        .trim();
+    digits
+        .parse()
+        .map_err(|_| format!("Invalid error code: {error_code}, expected e.g. E44 or 44"))
+}
+
+/// Parses an error code and the exit code to use if errors with the error code are detected, e.g. `E30=5`
+pub fn parse_error_code_exit_code(mapping: &str) -> Result<(u16, u8), String> {
+    let (error_code, exit_code) = mapping.split_once('=').ok_or_else(|| {
+        format!("Invalid error code to exit code mapping: {mapping}, expected e.g. E30=5")
+    })?;
+    let exit_code = match exit_code.trim().parse::<u8>() {
+        Ok(0) | Err(_) => {
+            return Err(format!(
+                "Invalid exit code: {exit_code}, expected a number between 1 and 255"
+            ))
+        }
+        Ok(exit_code) => exit_code,
+    };
+    Ok((parse_error_code(error_code)?, exit_code))
+}
+
+/// Parses a positive number of seconds with or without the `s` suffix, e.g. `5s` or `5`
+pub fn parse_seconds(seconds: &str) -> Result<Duration, String> {
+    let digits = seconds.strip_suffix('s').unwrap_or(seconds).trim();
+    match digits.parse::<u64>() {
+        Ok(0) | Err(_) => Err(format!(
+            "Invalid number of seconds: {seconds}, expected a positive number, e.g. 5s or 5"
+        )),
+        Ok(secs) => Ok(Duration::from_secs(secs)),
+    }
+}
+
+/// Parses a positive number of bytes with an optional unit, e.g. `512`, `100KB`, `1MB` or `2GiB`
+///
+/// KB, MB and GB are powers of 1000, KiB, MiB and GiB are powers of 1024, the unit is case-insensitive.
+pub fn parse_byte_size(size: &str) -> Result<u64, String> {
+    const UNITS: [(&str, u64); 7] = [
+        ("KIB", 1 << 10),
+        ("MIB", 1 << 20),
+        ("GIB", 1 << 30),
+        ("KB", 1_000),
+        ("MB", 1_000_000),
+        ("GB", 1_000_000_000),
+        ("B", 1),
+    ];
+    let upper_size = size.trim().to_uppercase();
+    let (digits, multiplier) = UNITS
+        .iter()
+        .find_map(|(unit, multiplier)| {
+            upper_size
+                .strip_suffix(unit)
+                .map(|digits| (digits, *multiplier))
+        })
+        .unwrap_or((&upper_size, 1));
+    match digits.trim().parse::<u64>() {
+        Ok(0) | Err(_) => Err(format!(
+            "Invalid size: {size}, expected a positive number of bytes with an optional unit, e.g. 512, 100KB, 1MB or 2GiB"
+        )),
+        Ok(value) => value
+            .checked_mul(multiplier)
+            .ok_or_else(|| format!("Invalid size: {size}, too large")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_code() {
+        assert_eq!(parse_error_code("E44"), Ok(44));
+        assert_eq!(parse_error_code("e9001"), Ok(9001));
+        assert_eq!(parse_error_code("45"), Ok(45));
+        assert!(parse_error_code("E").is_err());
+        assert!(parse_error_code("W02").is_err());
+    }
+
+    #[test]
+    fn test_parse_error_code_exit_code() {
+        assert_eq!(parse_error_code_exit_code("E30=5"), Ok((30, 5)));
+        assert_eq!(parse_error_code_exit_code("44=255"), Ok((44, 255)));
+        assert!(parse_error_code_exit_code("E30").is_err());
+        assert!(parse_error_code_exit_code("E30=0").is_err());
+        assert!(parse_error_code_exit_code("E30=256").is_err());
+        assert!(parse_error_code_exit_code("W02=5").is_err());
+    }
+
+    #[test]
+    fn test_parse_seconds() {
+        assert_eq!(parse_seconds("5s"), Ok(Duration::from_secs(5)));
+        assert_eq!(parse_seconds("60"), Ok(Duration::from_secs(60)));
+        assert!(parse_seconds("0s").is_err());
+        assert!(parse_seconds("5m").is_err());
+        assert!(parse_seconds("s").is_err());
+    }
+
+    #[test]
+    fn test_parse_byte_size() {
+        assert_eq!(parse_byte_size("512"), Ok(512));
+        assert_eq!(parse_byte_size("512B"), Ok(512));
+        assert_eq!(parse_byte_size("100KB"), Ok(100_000));
+        assert_eq!(parse_byte_size("1mb"), Ok(1_000_000));
+        assert_eq!(parse_byte_size("2 GB"), Ok(2_000_000_000));
+        assert_eq!(parse_byte_size("1MiB"), Ok(1 << 20));
+        assert_eq!(parse_byte_size("4KiB"), Ok(4096));
+        assert!(parse_byte_size("0MB").is_err());
+        assert!(parse_byte_size("MB").is_err());
+        assert!(parse_byte_size("1TB").is_err());
+        assert!(parse_byte_size("99999999999GB").is_err());
+    }
 }
@@ -1,7 +1,7 @@
 //! Contains the Trait [ViewOpt] for all view options, and the [ViewCommands] enum for the view mode
 
 use clap::Subcommand;
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
 /// Data views that can be generated
 #[derive(Subcommand, Copy, Clone, Debug, PartialEq, Eq)]
@@ -12,12 +12,24 @@ pub enum ViewCommands {
     ItsReadoutFrames,
     /// Print formatted ITS readout frames with Data Words to stdout
     ItsReadoutFramesData,
+    /// Print the lane status of each ITS DDW0 to stdout
+    Ddw0,
+    /// Print the decoded ALPIDE chip headers and trailers of each ITS readout frame to stdout, requires `--filter-its-stave`
+    Alpide,
+    /// Print a timeline of the lane status in the RDH detector field to stdout, only printing when the status of a FEE ID changes
+    DetFieldStatus,
+    /// Print each HBF with its orbit, RDH count, total payload size, and trigger type to stdout, the RDHs of a FEE ID are grouped into HBFs by the stop bit
+    Hbf,
 }
 
 /// Trait for all view options set by the user.
 pub trait ViewOpt {
     /// Type of View to generate.
     fn view(&self) -> Option<ViewCommands>;
+    /// Path to write the RDH view to as HTML, instead of printing it to stdout.
+    fn view_html_output(&self) -> Option<&Path>;
+    /// Print `view rdh` with a single line per RDH instead of the full RDH.
+    fn view_rdh_compact(&self) -> bool;
 }
 
 impl<T> ViewOpt for &T
@@ -27,6 +39,12 @@ where
     fn view(&self) -> Option<ViewCommands> {
         (*self).view()
     }
+    fn view_html_output(&self) -> Option<&Path> {
+        (*self).view_html_output()
+    }
+    fn view_rdh_compact(&self) -> bool {
+        (*self).view_rdh_compact()
+    }
 }
 
 impl<T> ViewOpt for Box<T>
@@ -36,6 +54,12 @@ where
     fn view(&self) -> Option<ViewCommands> {
         (**self).view()
     }
+    fn view_html_output(&self) -> Option<&Path> {
+        (**self).view_html_output()
+    }
+    fn view_rdh_compact(&self) -> bool {
+        (**self).view_rdh_compact()
+    }
 }
 
 impl<T> ViewOpt for Arc<T>
@@ -45,4 +69,10 @@ where
     fn view(&self) -> Option<ViewCommands> {
         (**self).view()
     }
+    fn view_html_output(&self) -> Option<&Path> {
+        (**self).view_html_output()
+    }
+    fn view_rdh_compact(&self) -> bool {
+        (**self).view_rdh_compact()
+    }
 }
@@ -6,13 +6,15 @@
 
 use super::*;
 use crate::stats::err_printer::ErrPrinter;
+use crate::stats::finding::error_code;
+use crate::stats::stats_collector::csv_stats::CsvStats;
 use std::io::Write;
 
 /// Spawns a thread with the [Controller] running, and returns the thread handle, the channel to send stats to, and the stop flag.
 pub fn init_controller<C: Config + 'static>(
     config: &'static C,
 ) -> (
-    JoinHandle<()>,
+    JoinHandle<Option<u8>>,
     flume::Sender<StatType>,
     Arc<AtomicBool>,
     Arc<AtomicBool>,
@@ -27,6 +29,7 @@ pub fn init_controller<C: Config + 'static>(
         .name("stats_thread".to_string())
         .spawn(move || {
             stats.run();
+            stats.error_code_exit_code()
         })
         .expect("Failed to spawn stats thread");
     (
@@ -55,6 +58,12 @@ pub struct Controller<C: Config + 'static> {
     any_errors_flag: Arc<AtomicBool>,
     spinner: Option<ProgressBar>,
     spinner_message: String,
+    word_id_histogram: Option<Box<WordIdHistogram>>,
+    trigger_period_histogram: Option<Box<TriggerPeriodHistogram>>,
+    live_status: Option<LiveStatus>,
+    progress_bar: Option<ProgressBar>,
+    // Number of errors written to stderr as they are reported (JSON error format), to stop after the first N if set
+    json_errors_written: u32,
 }
 impl<C: Config + 'static> Controller<C> {
     /// Creates a new [Controller] from a [Config], a [flume::Receiver] for [StatType], and a [Arc] of an [AtomicBool] that is used to signal to other threads to exit if a fatal error occurs.
@@ -64,8 +73,8 @@ impl<C: Config + 'static> Controller<C> {
             flume::Receiver<StatType>,
         ) = flume::unbounded();
         Controller {
-            // Only collect alpide stats if alpide checks are enabled
-            stats_collector: if global_config.alpide_checks_enabled() {
+            // Only collect alpide stats if alpide checks or alpide stats only are enabled
+            stats_collector: if global_config.alpide_stats_enabled() {
                 StatsCollector::with_alpide_stats()
             } else {
                 StatsCollector::default()
@@ -77,12 +86,26 @@ impl<C: Config + 'static> Controller<C> {
             stats_send_chan: Some(stats_send_chan),
             end_processing_flag: Arc::new(AtomicBool::new(false)),
             any_errors_flag: Arc::new(AtomicBool::new(false)),
-            spinner: if global_config.view().is_some() {
+            // The spinner would overwrite the live status line or the progress bar
+            spinner: if global_config.view().is_some()
+                || global_config.live_status()
+                || global_config.show_progress()
+                || global_config.quiet_stats()
+            {
                 None
             } else {
                 Some(new_styled_spinner())
             },
             spinner_message: String::new(),
+            word_id_histogram: None,
+            trigger_period_histogram: None,
+            live_status: global_config.live_status().then(LiveStatus::new),
+            progress_bar: global_config.show_progress().then(|| {
+                new_progress_bar(
+                    input_file_sizes(global_config.input_files()).map(|sizes| sizes.iter().sum()),
+                )
+            }),
+            json_errors_written: 0,
         }
     }
 
@@ -107,15 +130,63 @@ impl<C: Config + 'static> Controller<C> {
         self.any_errors_flag.clone()
     }
 
+    /// Returns the exit code of the first error code to exit code mapping with an error code that errors were reported with, if any.
+    pub fn error_code_exit_code(&self) -> Option<u8> {
+        self.config
+            .error_code_exit_codes()?
+            .iter()
+            .find(|(error_code, _)| {
+                self.stats_collector
+                    .error_stats()
+                    .has_error_code(*error_code)
+            })
+            .map(|&(_, exit_code)| exit_code)
+    }
+
     /// Starts the event loop for the Controller
     /// This function will block until the channel is closed
     pub fn run(&mut self) {
         // Set the send stats channel to none so that no new producers can be added, and so the loop breaks when all producers have dropped their channel.
         self.stats_send_chan = None;
-
-        // While loop breaks when an error is received from the channel, which means the channel is disconnected
-        while let Ok(stats_update) = self.stats_recv_chan.recv() {
-            self.update(stats_update);
+        let run_start = Instant::now();
+        let stats_flush_interval = self.config.stats_flush_interval();
+        let mut last_stats_flush = Instant::now();
+
+        // Loop breaks when the channel is disconnected
+        loop {
+            let stats_update = match stats_flush_interval {
+                // Wake up in time to write the next snapshot of the stats even if no stats are received
+                Some(interval) => self
+                    .stats_recv_chan
+                    .recv_timeout(interval.saturating_sub(last_stats_flush.elapsed())),
+                None => self
+                    .stats_recv_chan
+                    .recv()
+                    .map_err(|_| flume::RecvTimeoutError::Disconnected),
+            };
+            match stats_update {
+                Ok(stats_update) => {
+                    self.update(stats_update);
+                    if let Some(live_status) = self.live_status.as_mut() {
+                        live_status.print_if_due();
+                    }
+                }
+                Err(flume::RecvTimeoutError::Timeout) => (),
+                Err(flume::RecvTimeoutError::Disconnected) => break,
+            }
+            if stats_flush_interval.is_some_and(|interval| last_stats_flush.elapsed() >= interval) {
+                self.write_stats_snapshot(run_start.elapsed());
+                last_stats_flush = Instant::now();
+            }
+        }
+        // All producers disconnected, processing is done
+        self.stats_collector
+            .record_processing_time(run_start.elapsed());
+        if let Some(live_status) = &self.live_status {
+            live_status.finish();
+        }
+        if let Some(progress_bar) = self.progress_bar.take() {
+            progress_bar.abandon();
         }
 
         if self.config.custom_checks_enabled() {
@@ -130,7 +201,7 @@ impl<C: Config + 'static> Controller<C> {
             self.process_stats();
 
             // Print the summary report if any RDHs were seen. If not, it's likely that an early error occurred and no data was processed.
-            if self.stats_collector.any_rdhs_seen() {
+            if self.stats_collector.any_rdhs_seen() && !self.config.quiet_stats() {
                 // New spinner/progress bar
                 self.new_spinner_with_prefix("Generating report".to_string());
                 self.print();
@@ -145,38 +216,60 @@ impl<C: Config + 'static> Controller<C> {
             self.stats_collector.write_stats(
                 &self.config.stats_output_mode(),
                 self.config.stats_output_format().unwrap(),
+                self.config.json_pretty(),
             );
         }
 
         // User supplied a stats file to compare against, validate the match
         if let Some(input_stats) = self.config.input_stats_file() {
             log::info!("Validating input stats file against collected stats");
-            let input_stats_str =
-                fs::read_to_string(input_stats).expect("Failed to read input stats file");
-
-            let input_stats_collector: StatsCollector = if input_stats.extension().unwrap()
-                == "json"
-            {
-                serde_json::from_str(&input_stats_str)
-                    .expect("Failed to deserialize input stats file")
-            } else if input_stats.extension().unwrap() == "toml" {
-                toml::from_str(&input_stats_str).expect("Failed to deserialize input stats file")
+            let validation_result = if input_stats.extension().unwrap() == "csv" {
+                let input_stats_str =
+                    fs::read_to_string(input_stats).expect("Failed to read input stats file");
+                let input_csv_stats = CsvStats::from_csv_str(&input_stats_str)
+                    .expect("Failed to deserialize input stats file");
+                self.stats_collector
+                    .validate_other_csv_stats(&input_csv_stats, self.config.mute_errors())
             } else {
-                // Should've already been validated when parsing the command-line arguments
-                panic!("Invalid input stats file extension, must be .json or .toml")
+                // The extension is already validated when parsing the command-line arguments
+                let input_stats_collector = StatsCollector::from_stats_file(input_stats)
+                    .expect("Failed to deserialize input stats file");
+                self.stats_collector
+                    .validate_other_stats(&input_stats_collector, self.config.mute_errors())
             };
 
-            if self
-                .stats_collector
-                .validate_other_stats(&input_stats_collector, self.config.mute_errors())
-                .is_err()
-            {
+            if validation_result.is_err() {
                 self.any_errors_flag.store(true, Ordering::SeqCst);
                 log::warn!("Input stats did not match collected stats");
             } else {
                 log::info!("Input stats matched collected stats");
             }
         }
+
+        if self.config.quiet_stats() {
+            println!("{}", self.verdict());
+        }
+    }
+
+    /// Returns the one-line verdict printed instead of the report with `--quiet-stats`
+    fn verdict(&self) -> String {
+        if self.any_errors_flag.load(Ordering::SeqCst) || self.stats_collector.any_fatal_err() {
+            format!("FAIL ({} errors)", self.stats_collector.err_count())
+        } else {
+            "PASS".to_string()
+        }
+    }
+
+    /// Writes a snapshot of the stats collected so far to the stats output file, the final stats overwrite it at the end of processing.
+    fn write_stats_snapshot(&self, processing_time: Duration) {
+        log::debug!("Writing snapshot of the stats collected so far");
+        self.stats_collector
+            .snapshot(self.config.mute_errors(), processing_time)
+            .write_stats(
+                &self.config.stats_output_mode(),
+                self.config.stats_output_format().unwrap(),
+                self.config.json_pretty(),
+            );
     }
 
     fn update(&mut self, stat: StatType) {
@@ -190,10 +283,26 @@ impl<C: Config + 'static> Controller<C> {
             | StatType::LayerStaveSeen { .. }
             | StatType::SystemId(_)
             | StatType::FeeId(_)
+            | StatType::Dw(_)
             | StatType::TriggerType(_)
-            | StatType::AlpideStats(_) => {
+            | StatType::AlpideStats(_)
+            | StatType::AlpideLaneErrors { .. }
+            | StatType::RdhPositions { .. }
+            | StatType::LinkPayloadStats(_)
+            | StatType::PayloadWordsSeen(_) => {
                 self.stats_collector.collect(stat);
             }
+            StatType::InputFileStats(stats) => {
+                if let Err(conflicts) = self.stats_collector.merge(&stats) {
+                    self.update(StatType::Fatal(
+                        format!(
+                            "Failed to combine the stats of the input files: {}",
+                            conflicts.join(", ")
+                        )
+                        .into(),
+                    ));
+                }
+            }
             StatType::HBFsSeen(_) => {
                 self.stats_collector.collect(stat);
                 if self.spinner.is_some() {
@@ -203,36 +312,64 @@ impl<C: Config + 'static> Controller<C> {
                     ))
                 };
             }
+            // Input files processed independently each have their own histogram, they are combined into one
+            StatType::WordIdHistogram(histogram) => match self.word_id_histogram.as_mut() {
+                Some(combined) => combined.merge(&histogram),
+                None => self.word_id_histogram = Some(histogram),
+            },
+            // Each link has its own histogram, they are combined into one
+            StatType::TriggerPeriodHistogram(histogram) => {
+                match self.trigger_period_histogram.as_mut() {
+                    Some(combined) => combined.merge(&histogram),
+                    None => self.trigger_period_histogram = Some(histogram),
+                }
+            }
+            StatType::CdpsSeen(cdps) => {
+                if let Some(live_status) = self.live_status.as_mut() {
+                    live_status.record_cdps(&cdps);
+                }
+            }
+            StatType::BytesRead(bytes_read) => {
+                if let Some(progress_bar) = self.progress_bar.as_ref() {
+                    // The final position can be past the end of the input if the last payload is truncated
+                    progress_bar.set_position(
+                        progress_bar
+                            .length()
+                            .map_or(bytes_read, |len| bytes_read.min(len)),
+                    );
+                }
+            }
+            StatType::CruId(cru_id) => {
+                self.stats_collector.collect(stat);
+                let cru_ids = self.stats_collector.rdh_stats().cru_ids_as_slice();
+                if self.config.expect_single_cru() && cru_ids.len() > 1 {
+                    let warning = format!(
+                        "[W03] CRU ID {cru_id} observed, but the data is expected to come from a single CRU (CRU IDs seen: {})",
+                        cru_ids.iter().join(", ")
+                    );
+                    self.report_warning(warning.into());
+                }
+            }
+            StatType::DataFormatChanged { mem_pos, from, to } => {
+                if self.config.allow_mixed_data_format() {
+                    log::debug!("{mem_pos:#X}: Data format changed from {from} to {to}");
+                } else {
+                    self.report_error(
+                        format!("{mem_pos:#X}: [E17] Data format changed from {from} to {to}, mixed data formats usually indicate corrupted data").into(),
+                        None,
+                    );
+                }
+            }
             StatType::RunTriggerType((raw_tt, tt_str)) => {
                 log::debug!("Run trigger type determined to be {raw_tt:#0x}: {tt_str}");
                 self.stats_collector
                     .collect(StatType::RunTriggerType((raw_tt, tt_str)));
             }
-            StatType::Error(msg) => {
-                // Stop processing any error messages
-                if self.stats_collector.any_fatal_err() {
-                    log::trace!("Fatal error already seen, ignoring error: {msg}");
-                    return;
-                }
-
-                self.stats_collector.collect(StatType::Error(msg));
-
-                self.set_spinner_msg(
-                    format!(
-                        "{err_cnt} Errors in data!",
-                        err_cnt = self.stats_collector.err_count()
-                    )
-                    .red()
-                    .to_string(),
-                );
-
-                if self.max_tolerate_errors > 0 {
-                    log::trace!("Error count: {}", self.stats_collector.err_count());
-                    if self.stats_collector.err_count() == self.max_tolerate_errors as u64 {
-                        log::trace!("Errors reached maximum tolerated errors, exiting...");
-                        self.end_processing_flag.store(true, Ordering::SeqCst);
-                    }
-                }
+            StatType::Error(msg) => self.report_error(msg, None),
+            StatType::Warning(msg) => self.report_warning(msg),
+            StatType::CheckError(check_error) => {
+                let msg = check_error.to_string().into_boxed_str();
+                self.report_error(msg, Some(*check_error));
             }
             StatType::Fatal(err) => {
                 // Stop processing any error messages
@@ -247,9 +384,79 @@ impl<C: Config + 'static> Controller<C> {
         }
     }
 
+    /// Logs a warning about the input data, or reports it as an error if warnings are treated as errors
+    fn report_warning(&mut self, msg: Box<str>) {
+        if self.config.warnings_as_errors() {
+            self.report_error(msg, None);
+        } else {
+            log::warn!("{msg}");
+        }
+    }
+
+    /// Collects a non-fatal error, and stops processing if the maximum number of tolerated errors is reached
+    ///
+    /// If the error format is JSON, the error is also written to stderr as a single JSON object, using the structured form if available
+    /// Errors with a muted error code are ignored
+    fn report_error(&mut self, msg: Box<str>, check_error: Option<CheckError>) {
+        // Stop processing any error messages
+        if self.stats_collector.any_fatal_err() {
+            log::trace!("Fatal error already seen, ignoring error: {msg}");
+            return;
+        }
+
+        let err_code = check_error
+            .as_ref()
+            .map_or_else(|| error_code(&msg), |check_error| check_error.code);
+        if let Some(muted_codes) = self.config.mute_error_codes() {
+            if err_code.is_some_and(|code| muted_codes.contains(&code)) {
+                log::trace!("Error code muted, ignoring error: {msg}");
+                return;
+            }
+        }
+
+        if self.config.error_format() == ErrorFormat::JSON && !self.config.mute_errors() {
+            let structured_error = check_error.unwrap_or_else(|| CheckError::from_error_msg(&msg));
+            if self.config.error_code_filter().map_or(true, |filter| {
+                err_code.is_some_and(|code| filter.contains(&code.to_string()))
+            }) && self
+                .config
+                .first_n_errors()
+                .map_or(true, |n| self.json_errors_written < n)
+            {
+                eprintln!("{}", structured_error.to_json_line());
+                self.json_errors_written += 1;
+            }
+        }
+
+        if let Some(live_status) = self.live_status.as_mut() {
+            live_status.record_error(&msg);
+        }
+        self.stats_collector.collect(StatType::Error(msg));
+        if let Some(code) = err_code {
+            self.stats_collector.record_error_code(code);
+        }
+
+        self.set_spinner_msg(
+            format!(
+                "{err_cnt} Errors in data!",
+                err_cnt = self.stats_collector.err_count()
+            )
+            .red()
+            .to_string(),
+        );
+
+        if self.max_tolerate_errors > 0 {
+            log::trace!("Error count: {}", self.stats_collector.err_count());
+            if self.stats_collector.err_count() == self.max_tolerate_errors as u64 {
+                log::trace!("Errors reached maximum tolerated errors, exiting...");
+                self.end_processing_flag.store(true, Ordering::SeqCst);
+            }
+        }
+    }
+
     fn process_stats(&mut self) {
         // New spinner/progress bar if there's any errors
-        if self.stats_collector.err_count() > 0 {
+        if self.stats_collector.err_count() > 0 && !self.config.quiet_stats() {
             self.new_spinner_with_prefix(
                 format!(
                     "Processing {err_count} error messages",
@@ -264,20 +471,39 @@ impl<C: Config + 'static> Controller<C> {
             self.stats_collector.finalize(self.config.mute_errors());
         }
 
-        if self.stats_collector.any_errors() && !self.config.mute_errors() {
-            // Print the errors, limited if there's a max error limit set
-            ErrPrinter::new(
-                if self.config.max_tolerate_errors() > 0 {
-                    Some(self.config.max_tolerate_errors())
-                } else {
-                    None
-                },
-                self.config.error_code_filter(),
-            )
-            .print(
+        // Errors in JSON format are already written to stderr as they are reported
+        if self.stats_collector.any_errors()
+            && !self.config.mute_errors()
+            && !self.config.quiet_stats()
+            && self.config.error_format() == ErrorFormat::Human
+        {
+            // Print the errors, limited if there's a max error limit set, or only the first N errors are shown
+            let max_errors = match (
+                self.config.max_tolerate_errors(),
+                self.config.first_n_errors(),
+            ) {
+                (0, first_n_errors) => first_n_errors,
+                (max_tolerate_errors, None) => Some(max_tolerate_errors),
+                (max_tolerate_errors, Some(first_n_errors)) => {
+                    Some(max_tolerate_errors.min(first_n_errors))
+                }
+            };
+            let not_shown = ErrPrinter::new(max_errors, self.config.error_code_filter()).print(
                 self.stats_collector.error_stats().errors_as_slice_iter(),
                 self.stats_collector.unique_error_codes_as_slice(),
             );
+            if let Some(first_n_errors) = self.config.first_n_errors() {
+                // Errors beyond the max tolerated errors are not shown regardless, so they are not counted as suppressed
+                let suppressed = match self.config.max_tolerate_errors() {
+                    0 => not_shown,
+                    max_tolerate_errors => {
+                        not_shown.min(max_tolerate_errors.saturating_sub(first_n_errors) as usize)
+                    }
+                };
+                if suppressed > 0 {
+                    log::warn!("... and {suppressed} more errors suppressed");
+                }
+            }
         }
     }
 
@@ -287,6 +513,8 @@ impl<C: Config + 'static> Controller<C> {
             self.processing_time.elapsed(),
             &mut self.stats_collector,
             self.config.filter_target(),
+            self.config.filter_trigger_type(),
+            self.config.filter_orbit_range(),
         );
         self.append_spinner_msg("... completed");
         if self.spinner.is_some() {
@@ -301,6 +529,33 @@ impl<C: Config + 'static> Controller<C> {
                 log::error!("Failed to write report to stdout: {e}");
             }
         }
+        let link_payload_stats = self.stats_collector.rdh_stats().link_payload_stats();
+        if !link_payload_stats.is_empty() {
+            let table = stats::stats_report::format_link_payload_stats(link_payload_stats);
+            if let Err(e) = writeln!(lock, "{table}") {
+                log::error!("Failed to write link payload stats to stdout: {e}");
+            }
+        }
+        let error_code_histogram = self.stats_collector.error_stats().error_code_histogram();
+        if !error_code_histogram.is_empty() {
+            let histogram_line = error_code_histogram
+                .iter()
+                .map(|(code, count)| format!("{code}: {count}"))
+                .join(", ");
+            if let Err(e) = writeln!(lock, "Error codes: {histogram_line}") {
+                log::error!("Failed to write error code histogram to stdout: {e}");
+            }
+        }
+        if let Some(histogram) = &self.word_id_histogram {
+            if let Err(e) = writeln!(lock, "{histogram}") {
+                log::error!("Failed to write word ID histogram to stdout: {e}");
+            }
+        }
+        if let Some(histogram) = &self.trigger_period_histogram {
+            if let Err(e) = writeln!(lock, "{histogram}") {
+                log::error!("Failed to write trigger period histogram to stdout: {e}");
+            }
+        }
     }
 
     /// Add completed message to current spinner and abandon it
@@ -360,6 +615,24 @@ fn new_styled_spinner() -> ProgressBar {
     pb
 }
 
+/// Creates a progress bar of the bytes read on stderr, if the input size is not known (e.g. stdin) a counter of the bytes read is shown instead
+fn new_progress_bar(input_size: Option<u64>) -> ProgressBar {
+    let template = if input_size.is_some() {
+        "[ {prefix:.bold.blue} ] [{bar:40.cyan/blue}] {bytes}/{total_bytes} ({bytes_per_sec}, {eta})"
+    } else {
+        "{spinner} [ {prefix:.bold.blue} ] {bytes} read ({bytes_per_sec})"
+    };
+    let pb = ProgressBar::with_draw_target(input_size, ProgressDrawTarget::stderr());
+    pb.set_style(
+        ProgressStyle::with_template(template)
+            .unwrap()
+            .progress_chars("=> "),
+    );
+    pb.set_prefix("Reading input");
+    pb.enable_steady_tick(Duration::from_millis(120));
+    pb
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -399,10 +672,47 @@ mod tests {
         // Stop the controller by dropping the sender channel
         drop(send_ch);
 
-        // Wait for the controller to stop
-        handle.join().unwrap();
+        // Wait for the controller to stop, no error code to exit code mappings are set
+        assert_eq!(handle.join().unwrap(), None);
 
         // Stop flag should be true
         assert!(stop_flag.load(Ordering::SeqCst));
     }
+
+    static CONFIG_TEST_STATS_FLUSH_INTERVAL: OnceLock<MockConfig> = OnceLock::new();
+
+    #[test]
+    fn test_stats_flush_interval_writes_snapshot() {
+        let tmp_dir = temp_dir::TempDir::new().unwrap();
+        let stats_path = tmp_dir.child("stats.json");
+        let mock_config = MockConfig {
+            stats_output_mode: DataOutputMode::File(stats_path.clone().into_boxed_path()),
+            stats_output_format: Some(DataOutputFormat::JSON),
+            stats_flush_interval: Some(Duration::from_millis(50)),
+            ..Default::default()
+        };
+        CONFIG_TEST_STATS_FLUSH_INTERVAL.set(mock_config).unwrap();
+
+        let (handle, send_ch, _stop_flag, _errors_flag) =
+            init_controller(CONFIG_TEST_STATS_FLUSH_INTERVAL.get().unwrap());
+        send_ch.send(StatType::RdhVersion(7)).unwrap();
+        send_ch.send(StatType::DataFormat(2)).unwrap();
+        send_ch
+            .send(StatType::RunTriggerType((0xBEEF, "BEEF".to_owned().into())))
+            .unwrap();
+        send_ch.send(StatType::RDHSeen(3)).unwrap();
+
+        // The snapshot is written while the controller is still running
+        thread::sleep(Duration::from_millis(300));
+        let snapshot = StatsCollector::from_stats_file(&stats_path).unwrap();
+        assert_eq!(snapshot.rdhs_seen(), 3);
+
+        send_ch.send(StatType::RDHSeen(2)).unwrap();
+        drop(send_ch);
+        assert_eq!(handle.join().unwrap(), None);
+
+        // The final stats overwrite the snapshot
+        let final_stats = StatsCollector::from_stats_file(&stats_path).unwrap();
+        assert_eq!(final_stats.rdhs_seen(), 5);
+    }
 }
@@ -1,7 +1,12 @@
 //! Contains the [run] function that is the entry point for fastPASTA
 use self::lib::{init_ctrlc_handler, init_error_logger};
-use crate::{config::init_config, controller::init_controller, init_processing, util::*};
-use alice_protocol_reader::init_reader;
+use crate::analyze::compare_raw::{compare_raw_files, CompareOutcome};
+use crate::{
+    config::init_config, controller::init_controller, init_processing, init_processing_parallel,
+    util::*,
+};
+use alice_protocol_reader::forward_reader_seeker::InputCompression;
+use alice_protocol_reader::{init_compressed_stdin_reader, init_reader};
 
 /// Entry point for fastPASTA
 pub fn run() -> ExitCode {
@@ -25,6 +30,37 @@ pub fn run() -> ExitCode {
         return ExitCode::from(0);
     }
 
+    if Cfg::global().print_stats_schema() {
+        println!("{}", crate::stats::stats_schema::stats_schema_text());
+        return ExitCode::SUCCESS;
+    }
+
+    if Cfg::global().list_systems() {
+        println!("{}", crate::stats::systems_text());
+        return ExitCode::SUCCESS;
+    }
+
+    if Cfg::global().dry_run() {
+        println!("{}", crate::config::dry_run::dry_run_summary(Cfg::global()));
+        return ExitCode::SUCCESS;
+    }
+
+    if let Some((golden, candidate)) = Cfg::global().compare_raw_files() {
+        return run_compare_raw(golden, candidate);
+    }
+
+    if let Some(stats_files) = Cfg::global().merge_stats_files() {
+        return run_merge_stats(stats_files);
+    }
+
+    if let Some((old, new)) = Cfg::global().diff_stats_files() {
+        return run_diff_stats(
+            old,
+            new,
+            Cfg::global().diff_ignore_fields().unwrap_or_default(),
+        );
+    }
+
     // Launch controller thread
     // If max allowed errors is reached, the controller thread signals every other thread to stop
     let (controller, stat_send_chan, stop_flag, any_errors_flag) = init_controller(Cfg::global());
@@ -32,7 +68,37 @@ pub fn run() -> ExitCode {
     // Handles SIGINT, SIGTERM and SIGHUP (as the `termination` feature is  enabled)
     init_ctrlc_handler(stop_flag.clone());
 
-    let exit_code: u8 = match init_reader(Cfg::global().input_file()) {
+    let exit_code: u8 = if let Some(jobs) = Cfg::global().jobs() {
+        match init_processing_parallel(Cfg::global(), jobs, stat_send_chan, stop_flag) {
+            Ok(_) => 0,
+            Err(e) => {
+                log::error!("Processing input files failed: {e}");
+                1
+            }
+        }
+    } else {
+        run_processing(stat_send_chan, stop_flag)
+    };
+
+    let error_code_exit_code = controller.join().expect("Failed to join stats thread");
+
+    lib::exit(exit_code, &any_errors_flag, error_code_exit_code)
+}
+
+/// Process the input from stdin or all the input files as one continuous stream, returns the exit code
+fn run_processing(stat_send_chan: flume::Sender<StatType>, stop_flag: Arc<AtomicBool>) -> u8 {
+    let stdin_compression = match (Cfg::global().gzip(), Cfg::global().zstd()) {
+        (true, _) => InputCompression::Gzip,
+        (_, true) => InputCompression::Zstd,
+        _ => InputCompression::None,
+    };
+    let reader =
+        if stdin_compression != InputCompression::None && Cfg::global().input_files().is_empty() {
+            init_compressed_stdin_reader(stdin_compression)
+        } else {
+            init_reader(Cfg::global().input_files())
+        };
+    match reader {
         Ok(readable) => match init_processing(Cfg::global(), readable, stat_send_chan, stop_flag) {
             Ok(_) => 0,
             Err(e) => {
@@ -47,9 +113,87 @@ pub fn run() -> ExitCode {
             drop(stat_send_chan);
             1
         }
+    }
+}
+
+/// Compare the candidate file against the golden file and print the outcome
+fn run_compare_raw(golden: &Path, candidate: &Path) -> ExitCode {
+    match compare_raw_files(golden, candidate) {
+        Ok(CompareOutcome::Identical { cdps }) => {
+            println!("Files are identical ({cdps} CDPs compared)");
+            ExitCode::SUCCESS
+        }
+        Ok(CompareOutcome::Diverged(divergence)) => {
+            println!("Files diverge\n{divergence}");
+            ExitCode::from(Cfg::global().any_errors_exit_code().unwrap_or(1))
+        }
+        Err(e) => {
+            log::error!("Failed comparing files: {e}");
+            ExitCode::from(1)
+        }
+    }
+}
+
+/// Compare the stats files field by field and print the fields that differ
+fn run_diff_stats(old: &Path, new: &Path, ignore_fields: &[String]) -> ExitCode {
+    let read_stats = |path: &Path| {
+        StatsCollector::from_stats_file(path)
+            .map_err(|e| log::error!("Failed reading stats file {}: {e}", path.display()))
     };
+    let (Ok(old_stats), Ok(new_stats)) = (read_stats(old), read_stats(new)) else {
+        return ExitCode::from(1);
+    };
+    match crate::stats::stats_diff::diff_stats(&old_stats, &new_stats, ignore_fields) {
+        Ok(diffs) if diffs.is_empty() => {
+            println!("Stats are identical");
+            ExitCode::SUCCESS
+        }
+        Ok(diffs) => {
+            diffs.iter().for_each(|diff| println!("{diff}"));
+            println!("{} fields differ", diffs.len());
+            ExitCode::from(Cfg::global().any_errors_exit_code().unwrap_or(1))
+        }
+        Err(e) => {
+            log::error!("Failed comparing stats files: {e}");
+            ExitCode::from(1)
+        }
+    }
+}
 
-    controller.join().expect("Failed to join stats thread");
+/// Merge the stats files into one and write it to the output (default: stdout)
+fn run_merge_stats(stats_files: &[PathBuf]) -> ExitCode {
+    let mut merged_stats: Option<StatsCollector> = None;
+    for path in stats_files {
+        let stats = match StatsCollector::from_stats_file(path) {
+            Ok(stats) => stats,
+            Err(e) => {
+                log::error!("Failed reading stats file {}: {e}", path.display());
+                return ExitCode::from(1);
+            }
+        };
+        if let Some(merged) = merged_stats.as_mut() {
+            if let Err(conflicts) = merged.merge(&stats) {
+                conflicts.iter().for_each(|conflict| {
+                    log::error!("Cannot merge stats file {}: {conflict}", path.display())
+                });
+                return ExitCode::from(1);
+            }
+        } else {
+            merged_stats = Some(stats);
+        }
+    }
 
-    lib::exit(exit_code, &any_errors_flag)
+    let output_mode = Cfg::global().output_mode();
+    let format = match &output_mode {
+        DataOutputMode::File(path) => match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => DataOutputFormat::TOML,
+            Some("csv") => DataOutputFormat::CSV,
+            _ => DataOutputFormat::JSON,
+        },
+        _ => DataOutputFormat::JSON,
+    };
+    merged_stats
+        .expect("At least two stats files are required")
+        .write_stats(&output_mode, format, Cfg::global().json_pretty());
+    ExitCode::SUCCESS
 }
@@ -78,13 +78,29 @@
 //! ```
 
 use crate::util::*;
+use alice_protocol_reader::forward_reader_seeker::InputCompression;
 use analyze::validators::rdh::Rdh0Validator;
+use stats::finding::error_mem_pos;
+
+/// Whether error messages are colored, set once when the logger is initialized.
+static COLORED_ERRORS: OnceLock<bool> = OnceLock::new();
+
+/// Sets whether error messages written with [display_error] are colored, only the first call has an effect.
+///
+/// If never set, error messages are colored if stderr is a terminal.
+pub fn set_colored_errors(colored: bool) {
+    _ = COLORED_ERRORS.set(colored);
+}
 
 /// Write an error message to stderr.
 /// All error messages should be written through this function to ensure consistency.
 #[inline]
 pub fn display_error(err_msg: &str) {
-    log::error!("{}", owo_colors::OwoColorize::red(&err_msg));
+    if *COLORED_ERRORS.get_or_init(|| io::stderr().is_terminal()) {
+        log::error!("{}", owo_colors::OwoColorize::red(&err_msg));
+    } else {
+        log::error!("{err_msg}");
+    }
 }
 
 pub mod analyze;
@@ -96,25 +112,157 @@ pub mod util;
 pub mod words;
 pub mod write;
 
+/// How long to wait for more data to be written to the input file when following it with `--follow`
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// How far into the input to scan for the first valid RDH with `--scan-for-rdh`, if the first RDH fails the sanity check
+const RDH_SCAN_WINDOW_BYTES: u64 = 1024 * 1024;
+
+/// How long to wait for the reader to stop after processing is stopped, before leaving it blocked on reading the input, e.g. a stalled stdin
+const READER_STOP_GRACE_PERIOD: Duration = Duration::from_secs(1);
+
+/// Returns the size of each input file in bytes, or [None] if the input is stdin, compressed, or a file size cannot be determined.
+pub(crate) fn input_file_sizes(input_files: &[PathBuf]) -> Option<Vec<u64>> {
+    if input_files.is_empty()
+        || input_files
+            .iter()
+            .any(|path| !matches!(InputCompression::detect(path), Ok(InputCompression::None)))
+    {
+        return None;
+    }
+    input_files
+        .iter()
+        .map(|path| fs::metadata(path).ok().map(|metadata| metadata.len()))
+        .collect()
+}
+
 /// Does the initial setup for input data processing
-#[allow(clippy::needless_pass_by_value)] // We need to pass the reader by value to avoid lifetime issues (thread just spins) unless user drops the sender after calling which is not intuitive
 pub fn init_processing(
+    config: &'static impl Config,
+    reader: Box<dyn BufferedReaderWrapper>,
+    stat_send: flume::Sender<StatType>,
+    stop_flag: Arc<atomic::AtomicBool>,
+) -> io::Result<()> {
+    init_processing_input(
+        config,
+        reader,
+        config.input_files(),
+        stat_send,
+        stop_flag,
+        None,
+    )
+}
+
+/// Does the initial setup for input data processing like [init_processing], and calls the `rdh_hook` with each RDH and its memory position.
+///
+/// The hook is called from the analysis thread before the checks or views, which lets a library consumer aggregate the RDHs in-process.
+/// It is not called if the filtered data is written out without a check or view.
+pub fn init_processing_with_rdh_hook(
+    config: &'static impl Config,
+    reader: Box<dyn BufferedReaderWrapper>,
+    stat_send: flume::Sender<StatType>,
+    stop_flag: Arc<atomic::AtomicBool>,
+    rdh_hook: analyze::lib::RdhHook,
+) -> io::Result<()> {
+    init_processing_input(
+        config,
+        reader,
+        config.input_files(),
+        stat_send,
+        stop_flag,
+        Some(rdh_hook),
+    )
+}
+
+/// Does the initial setup for processing the input read from the given input files (empty if the input is stdin)
+#[allow(clippy::needless_pass_by_value)] // We need to pass the reader by value to avoid lifetime issues (thread just spins) unless user drops the sender after calling which is not intuitive
+fn init_processing_input(
     config: &'static impl Config,
     mut reader: Box<dyn BufferedReaderWrapper>,
+    input_files: &[PathBuf],
     stat_send: flume::Sender<StatType>,
     stop_flag: Arc<atomic::AtomicBool>,
+    rdh_hook: Option<analyze::lib::RdhHook>,
 ) -> io::Result<()> {
+    let input_offset = config.input_offset().unwrap_or(0);
+    if config.follow() {
+        if input_file_sizes(input_files).is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "Following a compressed input file is not supported",
+            ));
+        }
+        // The followed file might not contain a full RDH yet
+        while fs::metadata(&input_files[0])?.len() < input_offset + 64 {
+            if stop_flag.load(atomic::Ordering::SeqCst) {
+                return Ok(());
+            }
+            std::thread::sleep(FOLLOW_POLL_INTERVAL);
+        }
+    }
+    if input_offset > 0 {
+        if let Some(input_size) =
+            input_file_sizes(input_files).map(|sizes| sizes.iter().sum::<u64>())
+        {
+            if input_offset >= input_size && !config.follow() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Input offset {input_offset:#X} is beyond the end of the input ({input_size} bytes)"),
+                ));
+            }
+            if input_offset + u64::from(RDH_CRU_SIZE_BYTES) > input_size && !config.follow() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("Input offset {input_offset:#X} leaves less than a full RDH ({RDH_CRU_SIZE_BYTES} bytes) before the end of the input ({input_size} bytes)"),
+                ));
+            }
+        }
+        let relative_offset = i64::try_from(input_offset).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("Input offset {input_offset:#X} is too large"),
+            )
+        })?;
+        reader.seek_relative_offset(relative_offset)?;
+    }
     // Load the first few bytes that should contain RDH0 and do a basic sanity check before continuing.
-    // Early exit if the check fails.
-    let rdh0 = Rdh0::load(&mut reader).expect("Failed to read first RDH0");
-    if let Err(e) = Rdh0Validator::default().sanity_check(&rdh0) {
-        return Err(io::Error::new(
-            io::ErrorKind::InvalidData,
-            format!("Initial RDH0 deserialization failed sanity check: {e}"),
-        ));
+    // Early exit if the check fails, unless the RDH version is forced, then the data is processed anyway,
+    // or scanning for an RDH is enabled, then the bytes before the first valid RDH0 are skipped.
+    // With the lenient RDH sanity profile, unusual but non-fatal values are only a warning.
+    let mut rdh0 = Rdh0::load_with_endianness(&mut reader, config.endianness())?;
+    let mut skipped_bytes = 0;
+    let mut rdh0_validator = Rdh0Validator::with_sanity(config.rdh_sanity());
+    match rdh0_validator.sanity_check_with_warning(&rdh0) {
+        Ok(Some(warning)) => log::warn!(
+            "Initial RDH0 has unusual values, continuing as `--rdh-sanity` is lenient: {}",
+            warning.trim_end()
+        ),
+        Ok(None) => (),
+        Err(e) => {
+            let err_msg = format!("Initial RDH0 deserialization failed sanity check: {e}");
+            if config.force_rdh_version().is_some() {
+                log::warn!("{err_msg}");
+            } else if config.scan_for_rdh() {
+                let err_msg = err_msg.trim_end();
+                log::warn!("{err_msg}, scanning for a valid RDH");
+                (rdh0, skipped_bytes) = scan_for_valid_rdh0(rdh0, &mut reader)?
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("{err_msg}, and no valid RDH was found within the first {RDH_SCAN_WINDOW_BYTES} bytes"),
+                    )
+                })?;
+                log::warn!(
+                    "{mem_pos:#X}: Found a valid RDH after skipping {skipped_bytes} bytes",
+                    mem_pos = input_offset + skipped_bytes
+                );
+            } else {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, err_msg));
+            }
+        }
     }
-    // Determine RDH version
-    let rdh_version = rdh0.header_id;
+    // Determine RDH version, unless it is forced
+    let rdh_version = config.force_rdh_version().unwrap_or(rdh0.header_id);
 
     // Send RDH version to stats thread
     stat_send.send(StatType::RdhVersion(rdh_version)).unwrap();
@@ -125,7 +273,33 @@ pub fn init_processing(
         flume::Receiver<InputStatType>,
     ) = flume::unbounded();
     // Create input scanner from the already read RDH0 (to avoid seeking back and reading it twice, which would also break with stdin piping)
-    let loader = InputScanner::new_from_rdh0(config, reader, Some(input_stats_send), rdh0);
+    let mut loader = InputScanner::new_from_rdh0(config, reader, Some(input_stats_send), rdh0)
+        .with_start_offset(input_offset + skipped_bytes)
+        .with_endianness(config.endianness());
+    // If the input is uncompressed file(s), the input scanner can report trailing bytes that are not part of any RDH
+    if config.follow() {
+        // The input size is not known while the file is growing
+        loader = loader.with_follow(FOLLOW_POLL_INTERVAL);
+    } else if let Some(file_sizes) = input_file_sizes(input_files) {
+        if input_files.len() > 1 {
+            // Memory positions are relative to the start of the first file, log where each file starts to make them traceable
+            let mut file_start = 0;
+            for (path, size) in input_files.iter().zip(&file_sizes) {
+                log::info!("{file_start:#X}: start of input file {}", path.display());
+                file_start += size;
+            }
+        }
+        loader = loader.with_input_size(file_sizes.iter().sum());
+    }
+    if let Some(stall_threshold) = config.reader_stall_threshold() {
+        loader = loader.with_stall_threshold(stall_threshold);
+    }
+    if let Some(max_bytes) = config.max_bytes() {
+        loader = loader.with_max_bytes(max_bytes);
+    }
+    if let Some(sample_every) = config.sample_every() {
+        loader = loader.with_sample_every(sample_every);
+    }
 
     // Choose the rest of the execution based on the RDH version
     // Necessary to prevent heap allocation and allow static dispatch as the type cannot be known at compile time
@@ -141,6 +315,7 @@ pub fn init_processing(
                 Some(&input_stats_recv),
                 &stat_send,
                 stop_flag,
+                rdh_hook,
             ) {
                 Ok(_) => Ok(()),
                 Err(e) => {
@@ -158,6 +333,211 @@ pub fn init_processing(
     }
 }
 
+/// Scans forward from an initial [Rdh0] that failed the sanity check, for the first [Rdh0] within [RDH_SCAN_WINDOW_BYTES] that passes it and has a supported RDH version.
+///
+/// Returns the valid [Rdh0] and the number of bytes skipped before it, if one is found.
+fn scan_for_valid_rdh0(
+    invalid_rdh0: Rdh0,
+    reader: &mut Box<dyn BufferedReaderWrapper>,
+) -> io::Result<Option<(Rdh0, u64)>> {
+    Rdh0::scan_for_valid(invalid_rdh0, reader, RDH_SCAN_WINDOW_BYTES, |rdh0| {
+        (3..=100).contains(&rdh0.header_id) && Rdh0Validator::default().sanity_check(rdh0).is_ok()
+    })
+}
+
+/// Processes each input file independently of the others like [init_processing], with up to `jobs` input files processed concurrently.
+///
+/// Each input file gets its own reader and analysis, and the stats of each file are collected separately and merged into the stats of the [Controller](controller::Controller) with [StatsCollector::merge] once the file is processed.
+/// Errors are forwarded as they are reported, with the memory positions offset as if the input files were concatenated, unless some input files are compressed.
+#[allow(clippy::needless_pass_by_value)] // The sender is dropped when all input files are processed, which lets the controller know that processing is done
+pub fn init_processing_parallel(
+    config: &'static impl Config,
+    jobs: usize,
+    stat_send: flume::Sender<StatType>,
+    stop_flag: Arc<atomic::AtomicBool>,
+) -> io::Result<()> {
+    let input_files = config.input_files();
+    // Position of the first byte of each input file, as if they were concatenated
+    let file_starts: Vec<u64> = if let Some(file_sizes) = input_file_sizes(input_files) {
+        let mut file_start = 0;
+        file_sizes
+            .iter()
+            .map(|size| {
+                let start = file_start;
+                file_start += size;
+                start
+            })
+            .collect()
+    } else {
+        log::warn!("Some input files are compressed, memory positions are relative to the start of each input file");
+        vec![0; input_files.len()]
+    };
+    for (path, file_start) in input_files.iter().zip(&file_starts) {
+        log::info!("{file_start:#X}: start of input file {}", path.display());
+    }
+
+    let next_file = atomic::AtomicUsize::new(0);
+    let failed_files = atomic::AtomicUsize::new(0);
+    thread::scope(|scope| {
+        for worker_id in 0..jobs.min(input_files.len()) {
+            let _ = thread::Builder::new()
+                .name(format!("job_{worker_id}"))
+                .spawn_scoped(scope, || {
+                    // Each worker processes the next input file that is not yet processed, until all are processed
+                    loop {
+                        let file_idx = next_file.fetch_add(1, Ordering::SeqCst);
+                        let Some(path) = input_files.get(file_idx) else {
+                            break;
+                        };
+                        if stop_flag.load(Ordering::SeqCst) {
+                            break;
+                        }
+                        if let Err(e) = process_input_file(
+                            config,
+                            path,
+                            file_starts[file_idx],
+                            &stat_send,
+                            stop_flag.clone(),
+                        ) {
+                            log::error!("Processing {} failed: {e}", path.display());
+                            let _ = failed_files.fetch_add(1, Ordering::SeqCst);
+                        }
+                    }
+                })
+                .expect("Failed to spawn job thread");
+        }
+    });
+
+    match failed_files.into_inner() {
+        0 => Ok(()),
+        failed => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{failed} input file(s) failed to process"),
+        )),
+    }
+}
+
+/// Processes a single input file with its own reader and analysis, the stats are forwarded to the [Controller](controller::Controller) by [forward_input_file_stats]
+fn process_input_file(
+    config: &'static impl Config,
+    path: &PathBuf,
+    file_start: u64,
+    stat_send: &flume::Sender<StatType>,
+    stop_flag: Arc<atomic::AtomicBool>,
+) -> io::Result<()> {
+    let input_file = std::slice::from_ref(path);
+    let reader = alice_protocol_reader::init_reader(input_file)?;
+    let (file_stat_send, file_stat_recv): (flume::Sender<StatType>, flume::Receiver<StatType>) =
+        flume::unbounded();
+    thread::scope(|scope| {
+        let forwarder = scope
+            .spawn(|| forward_input_file_stats(config, &file_stat_recv, file_start, stat_send));
+        let processing_result =
+            init_processing_input(config, reader, input_file, file_stat_send, stop_flag, None);
+        forwarder
+            .join()
+            .expect("Failed to join input file stats thread");
+        processing_result
+    })
+}
+
+/// Collects the stats of an input file processed independently, and sends them to the [Controller](controller::Controller) when all the stats of the file are received.
+///
+/// Errors and stats that are not collected (e.g. histograms) are forwarded as they are received, with memory positions offset by the start of the file.
+fn forward_input_file_stats(
+    config: &impl Config,
+    file_stats_recv: &flume::Receiver<StatType>,
+    file_start: u64,
+    stats_send: &flume::Sender<StatType>,
+) {
+    let mut file_stats = if config.alpide_stats_enabled() {
+        StatsCollector::with_alpide_stats()
+    } else {
+        StatsCollector::default()
+    };
+    while let Ok(stat) = file_stats_recv.recv() {
+        match stat {
+            StatType::Error(msg) => stats_send
+                .send(StatType::Error(offset_error_mem_pos(&msg, file_start)))
+                .unwrap(),
+            StatType::CheckError(mut check_error) => {
+                if let Some(mem_pos) = check_error.mem_pos.as_mut() {
+                    *mem_pos += file_start;
+                }
+                stats_send.send(StatType::CheckError(check_error)).unwrap()
+            }
+            StatType::RdhPositions {
+                mut first,
+                mut last,
+            } => {
+                first.mem_pos += file_start;
+                last.mem_pos += file_start;
+                file_stats.collect(StatType::RdhPositions { first, last });
+            }
+            StatType::CdpsSeen(cdps) => stats_send
+                .send(StatType::CdpsSeen(
+                    cdps.iter()
+                        .map(|&(mem_pos, link_id, fee_id)| (mem_pos + file_start, link_id, fee_id))
+                        .collect(),
+                ))
+                .unwrap(),
+            StatType::Fatal(_)
+            | StatType::WordIdHistogram(_)
+            | StatType::TriggerPeriodHistogram(_) => stats_send.send(stat).unwrap(),
+            // Progress is not shown when input files are processed independently
+            StatType::BytesRead(_) => (),
+            _ => file_stats.collect(stat),
+        }
+    }
+    stats_send
+        .send(StatType::InputFileStats(Box::new(file_stats)))
+        .unwrap();
+}
+
+/// Offsets the memory position at the start of an error message, e.g. `0x40: [E30] ...` becomes `0x1040: [E30] ...` with an offset of `0x1000`
+fn offset_error_mem_pos(error_msg: &str, offset: u64) -> Box<str> {
+    match error_mem_pos(error_msg).zip(error_msg.split_once(':')) {
+        Some((mem_pos, (_, msg))) if offset > 0 => format!("{:#X}:{msg}", mem_pos + offset).into(),
+        _ => error_msg.into(),
+    }
+}
+
+/// Processes the input like [init_processing], but collects the stats and errors instead of sending them to a [Controller](controller::Controller).
+///
+/// Returns the finalized stats along with a [Finding](stats::finding::Finding) for each error, giving programmatic access to the errors without parsing the error messages.
+pub fn init_processing_collect(
+    config: &'static impl Config,
+    reader: Box<dyn BufferedReaderWrapper>,
+) -> io::Result<stats::finding::Collected> {
+    let (stat_send, stat_recv): (flume::Sender<StatType>, flume::Receiver<StatType>) =
+        flume::unbounded();
+    let mute_errors = config.mute_errors();
+    let collector_handle = thread::Builder::new()
+        .name("Collector".to_string())
+        .spawn(move || stats::finding::collect_findings(&stat_recv, mute_errors))?;
+
+    // Lets the collector attribute errors to the link and FEE ID of the CDP they are found in
+    let cdps_seen_send = stat_send.clone();
+    let rdh_hook: analyze::lib::RdhHook = Box::new(move |rdh, mem_pos| {
+        let _ = cdps_seen_send.send(StatType::CdpsSeen(Box::new([(
+            mem_pos,
+            rdh.link_id(),
+            rdh.fee_id(),
+        )])));
+    });
+    let processing_result = init_processing_with_rdh_hook(
+        config,
+        reader,
+        stat_send,
+        Arc::new(atomic::AtomicBool::new(false)),
+        rdh_hook,
+    );
+    let collected = collector_handle
+        .join()
+        .expect("Failed to join collector thread");
+    processing_result.map(|_| collected)
+}
+
 /// Entry point for scanning the input and delegating to checkers, view generators and/or writers depending on [Config]
 ///
 /// Follows these steps:
@@ -166,38 +546,61 @@ pub fn init_processing(
 ///     - Validate data by dispatching it to validators with [ValidatorDispatcher][crate::analyze::validators::validator_dispatcher::ValidatorDispatcher].
 ///     - Generate views of data with [analyze::view::lib::generate_view].
 ///     - Write data to `file` or `stdout` with [write::lib::spawn_writer].
+///
+/// If an `rdh_hook` is given, it is called with each RDH in the analysis thread, see [init_processing_with_rdh_hook].
 pub fn process<T: RDH + 'static, const CAP: usize>(
     config: &'static impl Config,
     loader: InputScanner<impl BufferedReaderWrapper + ?Sized + 'static>,
     input_stats_recv: Option<&flume::Receiver<InputStatType>>,
     stats_send: &flume::Sender<StatType>,
     stop_flag: Arc<atomic::AtomicBool>,
+    rdh_hook: Option<analyze::lib::RdhHook>,
 ) -> io::Result<()> {
+    // The writer and the analysis would compete for the data, so the RDH hook is dropped if the data is written out without a check or view
+    let data_written_out = config.check().is_none()
+        && config.view().is_none()
+        && (config.filter_enabled() || config.extract_rdhs().is_some())
+        && config.output_mode() != DataOutputMode::None;
+    if rdh_hook.is_some() && data_written_out {
+        log::warn!("The RDH hook is not called when the filtered data is written out without a check or view");
+    }
+    let rdh_hook = rdh_hook.filter(|_| !data_written_out);
+
+    // Checked while forwarding input stats, to not wait on a reader that is blocked on input after processing is stopped
+    let processing_stop_flag = stop_flag.clone();
+
     // 1. Launch reader thread to read data from file or stdin
     let (reader_handle, reader_data_recv): (
         thread::JoinHandle<()>,
         crossbeam_channel::Receiver<CdpArray<T, CAP>>,
     ) = alice_protocol_reader::spawn_reader(stop_flag.clone(), loader);
 
-    // 2. Launch analysis thread if an analysis action is set (view or check)
-    let analysis_handle = if config.check().is_some() || config.view().is_some() {
-        debug_assert!(config.output_mode() == DataOutputMode::None || config.filter_enabled(),);
-        let handle = analyze::lib::spawn_analysis(
-            config,
-            stop_flag.clone(),
-            stats_send.clone(),
-            reader_data_recv.clone(),
-        )?;
-        Some(handle)
-    } else {
-        None
-    };
+    // 2. Launch analysis thread if an analysis action is set (view or check), or an RDH hook is set
+    let analysis_handle =
+        if config.check().is_some() || config.view().is_some() || rdh_hook.is_some() {
+            debug_assert!(
+                config.output_mode() == DataOutputMode::None
+                    || rdh_hook.is_some()
+                    || config.filter_enabled()
+                    || config.extract_rdhs().is_some()
+            );
+            let handle = analyze::lib::spawn_analysis(
+                config,
+                stop_flag.clone(),
+                stats_send.clone(),
+                reader_data_recv.clone(),
+                rdh_hook,
+            )?;
+            Some(handle)
+        } else {
+            None
+        };
 
     // 3. Write data out only in the case where no analysis is performed and a filter link is set
     let output_handle: Option<thread::JoinHandle<()>> = match (
         config.check(),
         config.view(),
-        config.filter_enabled(),
+        config.filter_enabled() || config.extract_rdhs().is_some(),
         config.output_mode(),
     ) {
         (None, None, true, output_mode) if output_mode != DataOutputMode::None => Some(
@@ -219,11 +622,22 @@ pub fn process<T: RDH + 'static, const CAP: usize>(
         }
     };
 
-    // While loop breaks when an error is received from the channel, which means the channel is disconnected
-    if let Some(input_stats_recv_chan) = input_stats_recv.as_ref() {
-        forward_input_stats_to_stats_collector(input_stats_recv_chan, stats_send);
+    // Returns when the channel is disconnected, or if the reader does not stop after processing is stopped
+    let rdhs_filtered = input_stats_recv.map(|input_stats_recv_chan| {
+        forward_input_stats_to_stats_collector(
+            input_stats_recv_chan,
+            stats_send,
+            &processing_stop_flag,
+        )
+    });
+    if matches!(rdhs_filtered, Some(None)) {
+        // Reading cannot be interrupted, the thread ends with the process
+        log::warn!(
+            "Reader did not stop within {READER_STOP_GRACE_PERIOD:?}, it is left waiting for input"
+        );
+    } else {
+        reader_handle.join().expect("Error joining reader thread");
     }
-    reader_handle.join().expect("Error joining reader thread");
 
     if let Some(handle) = analysis_handle {
         if let Err(e) = handle.join() {
@@ -232,6 +646,14 @@ pub fn process<T: RDH + 'static, const CAP: usize>(
     }
     if let Some(output) = output_handle {
         output.join().expect("Could not join writer thread");
+        if config.verify_output() {
+            verify_written_output::<T>(
+                config,
+                rdhs_filtered.flatten(),
+                stats_send,
+                &processing_stop_flag,
+            );
+        }
     }
     Ok(())
 }
@@ -239,19 +661,51 @@ pub fn process<T: RDH + 'static, const CAP: usize>(
 // This is basically a "glue" function that takes the stats types that the reader sends
 // handles the transformation needed to send them in the format the the stats collector expects
 // and sends them
+//
+// Returns the number of filtered RDHs when the reader is done and the channel is disconnected,
+// or None if processing is stopped and the reader does not send anything within the grace period, e.g. as it is blocked on a stalled stdin
 fn forward_input_stats_to_stats_collector(
     input_stats_recv: &flume::Receiver<InputStatType>,
     stats_send: &flume::Sender<StatType>,
-) {
-    while let Ok(input_stat) = input_stats_recv.recv() {
+    stop_flag: &atomic::AtomicBool,
+) -> Option<u64> {
+    let mut rdhs_filtered: u64 = 0;
+    loop {
+        let input_stat = match input_stats_recv.recv_timeout(READER_STOP_GRACE_PERIOD) {
+            Ok(input_stat) => input_stat,
+            Err(flume::RecvTimeoutError::Timeout) => {
+                if stop_flag.load(atomic::Ordering::SeqCst) {
+                    return None;
+                }
+                continue;
+            }
+            Err(flume::RecvTimeoutError::Disconnected) => return Some(rdhs_filtered),
+        };
         match input_stat {
             InputStatType::LinksObserved(val) => {
                 stats_send.send(StatType::LinksObserved(val)).unwrap()
             }
             InputStatType::FeeId(val) => stats_send.send(StatType::FeeId(val)).unwrap(),
+            InputStatType::CruId(val) => stats_send.send(StatType::CruId(val)).unwrap(),
+            InputStatType::Dw(val) => stats_send.send(StatType::Dw(val)).unwrap(),
             InputStatType::RDHSeen(val) => stats_send.send(StatType::RDHSeen(val)).unwrap(),
             InputStatType::PayloadSize(val) => stats_send.send(StatType::PayloadSize(val)).unwrap(),
-            InputStatType::RDHFiltered(val) => stats_send.send(StatType::RDHFiltered(val)).unwrap(),
+            InputStatType::HBFsSeen(val) => stats_send.send(StatType::HBFsSeen(val)).unwrap(),
+            InputStatType::LinkPayload {
+                link_id,
+                rdh_count,
+                payload_size,
+            } => stats_send
+                .send(StatType::LinkPayloadStats(Box::new([LinkPayloadStats {
+                    link_id,
+                    rdh_count,
+                    payload_size,
+                }])))
+                .unwrap(),
+            InputStatType::RDHFiltered(val) => {
+                rdhs_filtered += u64::from(val);
+                stats_send.send(StatType::RDHFiltered(val)).unwrap()
+            }
             InputStatType::RunTriggerType(val) => stats_send
                 .send(StatType::RunTriggerType((
                     val,
@@ -259,6 +713,9 @@ fn forward_input_stats_to_stats_collector(
                 )))
                 .unwrap(),
             InputStatType::DataFormat(val) => stats_send.send(StatType::DataFormat(val)).unwrap(),
+            InputStatType::DataFormatChanged { mem_pos, from, to } => stats_send
+                .send(StatType::DataFormatChanged { mem_pos, from, to })
+                .unwrap(),
             InputStatType::SystemId(sys_id) => {
                 match stats::SystemId::from_system_id(sys_id) {
                     Ok(id) => {
@@ -274,17 +731,53 @@ fn forward_input_stats_to_stats_collector(
                 };
             }
             InputStatType::Error(e) => stats_send.send(StatType::Error(e)).unwrap(),
+            InputStatType::Warning(w) => stats_send.send(StatType::Warning(w)).unwrap(),
             InputStatType::Fatal(e) => stats_send.send(StatType::Fatal(e)).unwrap(),
+            InputStatType::BytesRead(val) => stats_send.send(StatType::BytesRead(val)).unwrap(),
+            InputStatType::ReaderStalls { stalls, stall_time } => {
+                if stalls > 0 {
+                    log::warn!("Reader stalled {stalls} time(s) waiting for the analysis to catch up, total stall time: {stall_time:.2?}");
+                } else {
+                    log::info!("Reader never stalled waiting for the analysis to catch up");
+                }
+            }
         }
     }
 }
 
+/// Reads back the output file once the writer is done, and reports an error if it does not contain all the filtered RDHs.
+///
+/// Skipped if processing was stopped early, as not all the filtered RDHs are written then.
+fn verify_written_output<T: RDH>(
+    config: &impl Config,
+    rdhs_filtered: Option<u64>,
+    stats_send: &flume::Sender<StatType>,
+    stop_flag: &atomic::AtomicBool,
+) {
+    let DataOutputMode::File(path) = config.output_mode() else {
+        return;
+    };
+    let Some(rdhs_filtered) = rdhs_filtered.filter(|_| !stop_flag.load(atomic::Ordering::SeqCst))
+    else {
+        log::warn!("Processing was stopped early, the output is not verified");
+        return;
+    };
+    match write::verify_output::verify_output::<T>(&path, rdhs_filtered) {
+        Ok(()) => log::info!(
+            "Output verified: {rdhs_filtered} RDHs read back from {}",
+            path.display()
+        ),
+        Err(e) => stats_send.send(StatType::Error(e.into())).unwrap(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use alice_protocol_reader::init_reader;
     use alice_protocol_reader::prelude::test_data::CORRECT_RDH_CRU_V7;
     use pretty_assertions::{assert_eq, assert_ne};
+    use stats::finding::Severity;
 
     static CFG_TEST_INIT_PROCESSING: OnceLock<MockConfig> = OnceLock::new();
 
@@ -293,12 +786,12 @@ mod tests {
         // Setup Mock Config
         let mut mock_config = MockConfig::new();
         // Set input file from one of the files used for regression testing
-        mock_config.input_file = Some(PathBuf::from("../tests/test-data/10_rdh.raw"));
+        mock_config.input_files = vec![PathBuf::from("../tests/test-data/10_rdh.raw")];
 
         CFG_TEST_INIT_PROCESSING.set(mock_config).unwrap();
 
         // Setup a reader
-        let reader = init_reader(CFG_TEST_INIT_PROCESSING.get().unwrap().input_file()).unwrap();
+        let reader = init_reader(CFG_TEST_INIT_PROCESSING.get().unwrap().input_files()).unwrap();
 
         let (sender, receiver): (flume::Sender<StatType>, flume::Receiver<StatType>) =
             flume::unbounded();
@@ -333,6 +826,7 @@ mod tests {
                 StatType::Error(e) | StatType::Fatal(e) => {
                     panic!("Error or Fatal: {}", e)
                 }
+                StatType::CheckError(e) => panic!("Error: {e}"),
                 _ => (),
             }
         }
@@ -342,6 +836,128 @@ mod tests {
         assert!(!stop_flag.load(Ordering::SeqCst));
     }
 
+    static CFG_TEST_INIT_PROCESSING_WITH_RDH_HOOK: OnceLock<MockConfig> = OnceLock::new();
+
+    #[test]
+    fn test_init_processing_with_rdh_hook() {
+        let cfg = CFG_TEST_INIT_PROCESSING_WITH_RDH_HOOK.get_or_init(|| {
+            let mut mock_config = MockConfig::new();
+            mock_config.input_files = vec![PathBuf::from("../tests/test-data/10_rdh.raw")];
+            mock_config
+        });
+        let reader = init_reader(cfg.input_files()).unwrap();
+        let (sender, receiver): (flume::Sender<StatType>, flume::Receiver<StatType>) =
+            flume::unbounded();
+        let seen_rdhs: Arc<std::sync::Mutex<Vec<(u64, u16, u8)>>> = Arc::default();
+        let hook_seen_rdhs = Arc::clone(&seen_rdhs);
+
+        init_processing_with_rdh_hook(
+            cfg,
+            reader,
+            sender,
+            Arc::new(AtomicBool::new(false)),
+            Box::new(move |rdh, mem_pos| {
+                hook_seen_rdhs
+                    .lock()
+                    .unwrap()
+                    .push((mem_pos, rdh.fee_id(), rdh.stop_bit()));
+            }),
+        )
+        .unwrap();
+        while receiver.recv().is_ok() {}
+
+        let seen_rdhs = seen_rdhs.lock().unwrap();
+        assert_eq!(seen_rdhs.len(), 10);
+        assert_eq!(seen_rdhs[0], (0, 524, 0));
+        assert_eq!(seen_rdhs[1], (0xA0, 524, 1));
+        assert_eq!(seen_rdhs[9].0, 0x460);
+    }
+
+    static CFG_TEST_INIT_PROCESSING_COLLECT: OnceLock<MockConfig> = OnceLock::new();
+
+    #[test]
+    fn test_init_processing_collect() {
+        let cfg = CFG_TEST_INIT_PROCESSING_COLLECT.get_or_init(|| {
+            let mut mock_config = MockConfig::new();
+            mock_config.input_files = vec![PathBuf::from("../tests/test-data/1_hbf_bad_tdt.raw")];
+            mock_config.check = Some(CheckCommands::Sanity(CheckModeArgs {
+                target: Some(System::ITS),
+                ..Default::default()
+            }));
+            mock_config
+        });
+        let reader = init_reader(cfg.input_files()).unwrap();
+
+        let collected = init_processing_collect(cfg, reader).unwrap();
+
+        let findings = collected
+            .findings
+            .iter()
+            .map(|finding| {
+                (
+                    finding.severity,
+                    finding.mem_pos.unwrap(),
+                    finding.code.unwrap(),
+                    finding.link_id.unwrap(),
+                    finding.fee_id.unwrap(),
+                )
+            })
+            .sorted()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            findings,
+            vec![
+                (Severity::Error, 0x90, 70, 8, 524),
+                (Severity::Error, 0x90, 991, 8, 524),
+                (Severity::Error, 0xE0, 70, 8, 524),
+                (Severity::Error, 0xE0, 991, 8, 524),
+            ]
+        );
+        assert!(collected.findings[0].message.ends_with("01 F1]"));
+        assert_eq!(collected.stats.err_count(), 4);
+        assert_eq!(collected.stats.rdhs_seen(), 2);
+    }
+
+    static CFG_TEST_INIT_PROCESSING_COLLECT_RDH_ERRORS: OnceLock<MockConfig> = OnceLock::new();
+
+    #[test]
+    fn test_init_processing_collect_attributes_rdh_errors() {
+        let cfg = CFG_TEST_INIT_PROCESSING_COLLECT_RDH_ERRORS.get_or_init(|| {
+            let mut mock_config = MockConfig::new();
+            mock_config.input_files = vec![PathBuf::from("../tests/test-data/err_not_hbf.raw")];
+            mock_config.check = Some(CheckCommands::All(CheckModeArgs::default()));
+            mock_config
+        });
+        let reader = init_reader(cfg.input_files()).unwrap();
+
+        let collected = init_processing_collect(cfg, reader).unwrap();
+
+        // The RDH errors are reported without the link and FEE ID, they are attributed from the CDP they are found in
+        assert_eq!(collected.findings.len(), 3);
+        assert!(collected
+            .findings
+            .iter()
+            .all(|finding| finding.mem_pos == Some(0xA0)
+                && finding.link_id.is_some()
+                && finding.fee_id.is_some()));
+    }
+
+    #[test]
+    fn test_offset_error_mem_pos() {
+        assert_eq!(
+            &*offset_error_mem_pos("0x40: [E30] ID is not 0xE0", 0x1000),
+            "0x1040: [E30] ID is not 0xE0"
+        );
+        assert_eq!(
+            &*offset_error_mem_pos("0x40: [E30] ID is not 0xE0", 0),
+            "0x40: [E30] ID is not 0xE0"
+        );
+        assert_eq!(
+            &*offset_error_mem_pos("Failed to parse system ID", 0x1000),
+            "Failed to parse system ID"
+        );
+    }
+
     static CFG_TEST_SPAWN_ANALYSIS: OnceLock<MockConfig> = OnceLock::new();
 
     #[test]
@@ -362,6 +978,7 @@ mod tests {
             stop_flag.clone(),
             stat_sender,
             data_receiver,
+            None,
         )
         .unwrap();
         data_sender.send(cdp_batch).unwrap();
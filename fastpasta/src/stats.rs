@@ -2,11 +2,18 @@
 
 use crate::util::*;
 
+pub mod check_error;
 pub mod err_printer;
+pub mod finding;
 pub mod lib;
+pub mod live_status;
 pub mod stats_collector;
+pub mod stats_diff;
 pub(super) mod stats_report;
+pub mod stats_schema;
 mod stats_validation;
+pub mod trigger_period_histogram;
+pub mod word_id_histogram;
 
 #[derive(Debug, Clone, PartialEq)]
 /// Possible stats that can be sent for stats collection.
@@ -15,6 +22,10 @@ pub enum StatType {
     Fatal(Box<str>),
     /// Non-fatal error, reported but processing continues.
     Error(Box<str>),
+    /// Non-fatal error in structured form, collected as an [Error](StatType::Error) with the formatted message.
+    CheckError(Box<CheckError>),
+    /// Warning about the input data, logged, or reported as an [Error](StatType::Error) if warnings are treated as errors.
+    Warning(Box<str>),
     /// The first trigger type observed is the type of run the data comes from
     ///
     /// Contains the raw value and the string description summarizing the trigger type
@@ -35,6 +46,15 @@ pub enum StatType {
     RdhVersion(u8),
     /// Record the data format detected.
     DataFormat(u8),
+    /// The data format changed from the data format of the previous RDH, mixed data formats are reported as an error unless allowed.
+    DataFormatChanged {
+        /// Memory position of the RDH with the new data format.
+        mem_pos: u64,
+        /// The data format of the previous RDH.
+        from: u8,
+        /// The new data format.
+        to: u8,
+    },
     /// Increment the total HBFs seen.
     HBFsSeen(u32),
     /// Record a layer/stave combination seen.
@@ -46,8 +66,40 @@ pub enum StatType {
     },
     /// Record the generic FEE ID
     FeeId(u16),
+    /// Record the CRU ID
+    CruId(u16),
+    /// Record the DW (data wrapper ID) of the CRU
+    Dw(u8),
     /// Stats from ALPIDE data analysis
     AlpideStats(AlpideStats),
+    /// Errors found in the ALPIDE data of the lanes of a readout frame
+    AlpideLaneErrors {
+        /// FEE ID of the stave the readout frame is from.
+        fee_id: u16,
+        /// The errors as (lane number, error).
+        lane_errors: Box<[(u8, AlpideLaneError)]>,
+    },
+    /// Frequency of each word ID observed in the payloads
+    WordIdHistogram(Box<WordIdHistogram>),
+    /// Frequency of each trigger period detected between TDHs with internal trigger set
+    TriggerPeriodHistogram(Box<TriggerPeriodHistogram>),
+    /// The first and last RDH of a batch of CDPs, used to record the first and last RDH seen
+    RdhPositions {
+        /// The first RDH of the batch.
+        first: RdhPosition,
+        /// The last RDH of the batch.
+        last: RdhPosition,
+    },
+    /// RDHs seen and payload size of each link in a batch of CDPs
+    LinkPayloadStats(Box<[LinkPayloadStats]>),
+    /// CDPs seen as (RDH memory position, link ID, FEE ID), used to attribute errors to links
+    CdpsSeen(Box<[(u64, u8, u16)]>),
+    /// Number of bytes of the input read so far, used to show progress
+    BytesRead(u64),
+    /// Increment the total payload words seen, only counted for systems that don't decode the payload words (e.g. FT0)
+    PayloadWordsSeen(u64),
+    /// Stats collected from an input file processed independently of the other input files, merged into the collected stats
+    InputFileStats(Box<StatsCollector>),
 }
 
 impl fmt::Display for StatType {
@@ -62,16 +114,50 @@ impl fmt::Display for StatType {
             StatType::LinksObserved(id) => write!(f, "Link observed: {id}"),
             StatType::RdhVersion(v) => write!(f, "RDH version: {v}"),
             StatType::DataFormat(format) => write!(f, "Data format: {format}"),
+            StatType::DataFormatChanged { mem_pos, from, to } => {
+                write!(f, "Data format changed from {from} to {to} at {mem_pos:#X}")
+            }
             StatType::HBFsSeen(val) => write!(f, "{val} HBFs seen"),
             StatType::LayerStaveSeen {
                 layer: layer_id,
                 stave: stave_id,
             } => write!(f, "Layer/stave seen: {layer_id}/{stave_id}"),
             StatType::FeeId(id) => write!(f, "FEE ID: {id}"),
+            StatType::CruId(id) => write!(f, "CRU ID: {id}"),
+            StatType::Dw(dw) => write!(f, "DW: {dw}"),
             StatType::TriggerType(trig_val) => write!(f, "Trigger type: {trig_val:#X}"),
             StatType::AlpideStats(alpide_stats) => write!(f, "ALPIDE stats {alpide_stats:?}"),
+            StatType::AlpideLaneErrors {
+                fee_id,
+                lane_errors,
+            } => write!(
+                f,
+                "FEE ID:{fee_id} ALPIDE lane errors: {}",
+                lane_errors.len()
+            ),
+            StatType::WordIdHistogram(histogram) => write!(f, "{histogram}"),
+            StatType::TriggerPeriodHistogram(histogram) => write!(f, "{histogram}"),
+            StatType::RdhPositions { first, last } => write!(
+                f,
+                "RDHs seen from {first_pos:#X} (orbit {first_orbit:#X}) to {last_pos:#X} (orbit {last_orbit:#X})",
+                first_pos = first.mem_pos,
+                first_orbit = first.orbit,
+                last_pos = last.mem_pos,
+                last_orbit = last.orbit
+            ),
+            StatType::LinkPayloadStats(links) => {
+                write!(f, "Payload stats of {} links", links.len())
+            }
+            StatType::CdpsSeen(cdps) => write!(f, "{} CDPs seen", cdps.len()),
+            StatType::BytesRead(bytes) => write!(f, "{bytes} bytes read"),
+            StatType::PayloadWordsSeen(words) => write!(f, "{words} payload words seen"),
+            StatType::InputFileStats(stats) => {
+                write!(f, "Input file stats: {} RDHs seen", stats.rdhs_seen())
+            }
             StatType::SystemId(s_id) => write!(f, "System ID: {s_id}"),
             StatType::Error(e) => write!(f, "Error: {e}"),
+            StatType::CheckError(e) => write!(f, "Error: {e}"),
+            StatType::Warning(w) => write!(f, "Warning: {w}"),
             StatType::Fatal(e) => write!(f, "Fatal error: {e}"),
         }
     }
@@ -131,6 +217,33 @@ impl SystemId {
             _ => Err(format!("Unknown system ID {sys_id}")),
         }
     }
+
+    /// Returns the targets of the `check` subcommand that support the system, empty if the system has no check support
+    pub fn check_targets(&self) -> &'static [&'static str] {
+        match self {
+            SystemId::ITS => &["its", "its-stave"],
+            SystemId::FT0 => &["ft0"],
+            _ => &[],
+        }
+    }
+}
+
+/// Returns a table of the systems recognized from the system ID of the RDHs, with their numeric system ID and the `check` targets that support them
+pub fn systems_text() -> String {
+    let mut text = format!("{:>3}  {:<8}  Check targets", "ID", "System");
+    for (sys_id, system) in
+        (0..=u8::MAX).filter_map(|id| SystemId::from_system_id(id).ok().map(|s| (id, s)))
+    {
+        let check_targets = match system.check_targets() {
+            [] => "-".to_string(),
+            targets => targets.join(", "),
+        };
+        text.push_str(&format!(
+            "\n{sys_id:>3}  {system:<8}  {check_targets}",
+            system = system.to_string()
+        ));
+    }
+    text
 }
 
 impl fmt::Display for SystemId {
@@ -0,0 +1,118 @@
+//! Contains the [CheckError] struct, the structured form of an error reported by a validator.
+//!
+//! It is displayed as the usual human-readable error message, e.g. `0x40: [E30] ID is not 0xE0...`,
+//! and serialized to a single JSON object for the machine-readable `--error-format json`, e.g.
+//! ```json
+//! {"code":30,"mem_pos":64,"fee_id":524,"link_id":8,"message":"ID is not 0xE0..."}
+//! ```
+
+use crate::stats::finding::{error_mem_pos, split_error_code};
+use crate::util::*;
+
+/// An error reported by a validator, with its error code, memory position, and the FEE and link ID of the CDP it was found in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckError {
+    /// The numeric error code, e.g. `30` for `[E30]`, if the error message has one
+    pub code: Option<u16>,
+    /// Memory position of the error in the input data, if known
+    pub mem_pos: Option<u64>,
+    /// FEE ID of the CDP the error was found in, if known
+    pub fee_id: Option<u16>,
+    /// Link ID of the CDP the error was found in, if known
+    pub link_id: Option<u8>,
+    /// The error message without the memory position and error code prefixes
+    pub message: String,
+}
+
+impl CheckError {
+    /// Creates a [CheckError] from an error message of the form `[E30] msg` found at `mem_pos` in a CDP with the given FEE and link ID.
+    pub fn new(mem_pos: u64, error_msg: &str, fee_id: u16, link_id: u8) -> Self {
+        let (code, message) = match split_error_code(error_msg) {
+            Some((code, message)) => (Some(code), message),
+            None => (None, error_msg),
+        };
+        Self {
+            code,
+            mem_pos: Some(mem_pos),
+            fee_id: Some(fee_id),
+            link_id: Some(link_id),
+            message: message.to_string(),
+        }
+    }
+
+    /// Parses a [CheckError] from a formatted error message of the form `0x40: [E30] msg`, both prefixes are optional.
+    ///
+    /// The FEE and link ID are not known from the message and are left as [None].
+    pub fn from_error_msg(error_msg: &str) -> Self {
+        let mem_pos = error_mem_pos(error_msg);
+        let msg = match mem_pos {
+            Some(_) => error_msg.split_once(':').map_or(error_msg, |(_, msg)| msg),
+            None => error_msg,
+        }
+        .trim_start();
+        let (code, message) = match split_error_code(msg) {
+            Some((code, message)) => (Some(code), message),
+            None => (None, msg),
+        };
+        Self {
+            code,
+            mem_pos,
+            fee_id: None,
+            link_id: None,
+            message: message.to_string(),
+        }
+    }
+
+    /// Serializes the error to a single line of JSON
+    pub fn to_json_line(&self) -> String {
+        serde_json::to_string(self).expect("Failed to serialize error to JSON")
+    }
+}
+
+impl fmt::Display for CheckError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(mem_pos) = self.mem_pos {
+            write!(f, "{mem_pos:#X}: ")?;
+        }
+        if let Some(code) = self.code {
+            write!(f, "[E{code}] ")?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_check_error_display_matches_error_msg() {
+        let check_error = CheckError::new(0x40, "[E30] ID is not 0xE0: 0x01", 524, 8);
+        assert_eq!(check_error.code, Some(30));
+        assert_eq!(check_error.message, "ID is not 0xE0: 0x01");
+        assert_eq!(check_error.to_string(), "0x40: [E30] ID is not 0xE0: 0x01");
+        assert_eq!(
+            CheckError::from_error_msg(&check_error.to_string()),
+            CheckError {
+                fee_id: None,
+                link_id: None,
+                ..check_error
+            }
+        );
+    }
+
+    #[test]
+    fn test_check_error_to_json_line() {
+        let check_error = CheckError::new(0x40, "[E30] ID is not 0xE0", 524, 8);
+        assert_eq!(
+            check_error.to_json_line(),
+            r#"{"code":30,"mem_pos":64,"fee_id":524,"link_id":8,"message":"ID is not 0xE0"}"#
+        );
+        let unstructured_error = CheckError::from_error_msg("Failed to parse system ID");
+        assert_eq!(
+            unstructured_error.to_json_line(),
+            r#"{"code":null,"mem_pos":null,"fee_id":null,"link_id":null,"message":"Failed to parse system ID"}"#
+        );
+    }
+}
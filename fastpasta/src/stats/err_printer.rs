@@ -24,26 +24,32 @@ impl<'a> ErrPrinter<'a> {
     /// If the max errors is set, only the first `max_errors` are displayed
     ///
     /// The unique error codes are used to minify the error code filter to avoid doing unnecessary comparisons
+    ///
+    /// Returns the number of error messages that would have been displayed if not for the max errors
     pub fn print<E: Iterator<Item = &'a Box<str>>>(
         &self,
-        err_msgs: E,
+        mut err_msgs: E,
         unique_error_codes: &[String],
-    ) {
-        match self.error_code_filter {
-            Some(filter) => {
-                // Reduce the error code filter to codes that were actually seen
-                let min_filter = self.minify_filter(filter, unique_error_codes);
-                // Filter the error messages and take the first `max_errors` if set
-                self.filter_error_msgs(self.max_errors, &min_filter, err_msgs)
-                    .for_each(|e| crate::display_error(e));
-            }
-            None => {
-                // Take the first `max_errors` if set
-                err_msgs
-                    .take(self.max_errors.unwrap_or(u32::MAX) as usize)
-                    .for_each(|e| crate::display_error(e));
-            }
-        };
+    ) -> usize {
+        let max_errors = self.max_errors.unwrap_or(u32::MAX) as usize;
+        if let Some(filter) = self.error_code_filter {
+            // Reduce the error code filter to codes that were actually seen
+            let min_filter = self.minify_filter(filter, unique_error_codes);
+            // Filter the error messages and take the first `max_errors` if set
+            let mut filtered_err_msgs = self.filter_error_msgs(None, &min_filter, err_msgs);
+            filtered_err_msgs
+                .by_ref()
+                .take(max_errors)
+                .for_each(|e| crate::display_error(e));
+            filtered_err_msgs.count()
+        } else {
+            // Take the first `max_errors` if set
+            err_msgs
+                .by_ref()
+                .take(max_errors)
+                .for_each(|e| crate::display_error(e));
+            err_msgs.count()
+        }
     }
 
     // Reduce the error code filter to codes that were actually seen in the error messages
@@ -258,4 +264,45 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_print_returns_number_not_shown() {
+        let err_printer = ErrPrinter::new(Some(2), None);
+
+        let err_msgs = [
+            "Error message [E1]".into(),
+            "Error message [E2]".into(),
+            "Error message [E3]".into(),
+            "Error message [E4]".into(),
+            "Error message [E5]".into(),
+        ];
+
+        assert_eq!(err_printer.print(err_msgs.iter(), &[]), 3);
+    }
+
+    #[test]
+    fn test_print_filtered_returns_number_not_shown() {
+        let err_code_filter = vec!["1".into(), "2".into()];
+        let err_printer = ErrPrinter::new(Some(1), Some(&err_code_filter));
+
+        let err_msgs = [
+            "Error message [E1]".into(),
+            "Error message [E2]".into(),
+            "Error message [E3]".into(),
+            "Error message [E1]".into(),
+        ];
+        let unique_error_codes: Vec<String> = vec!["1".into(), "2".into(), "3".into()];
+
+        // 3 messages match the filter, 1 is shown
+        assert_eq!(err_printer.print(err_msgs.iter(), &unique_error_codes), 2);
+    }
+
+    #[test]
+    fn test_print_no_max_errors_shows_all() {
+        let err_printer = ErrPrinter::new(None, None);
+
+        let err_msgs = ["Error message [E1]".into(), "Error message [E2]".into()];
+
+        assert_eq!(err_printer.print(err_msgs.iter(), &[]), 0);
+    }
 }
@@ -0,0 +1,261 @@
+//! Contains the [Finding] type that gives programmatic access to the errors reported during processing, and the [Collected] results of [init_processing_collect](crate::init_processing_collect).
+//!
+//! Errors are reported internally as strings of the form `0x1A0: [E10] RDH sanity check failed...`.
+//! A [Finding] is parsed from such a string, and attributed to the link and FEE ID of the CDP that contains the memory position of the error.
+
+use crate::util::*;
+use std::collections::BTreeMap;
+
+/// Maximum number of CDP locations kept to attribute errors to the link and FEE ID of their CDP, the CDPs at the lowest memory positions are dropped first
+pub(crate) const MAX_CDP_LOCATIONS: usize = 1 << 16;
+
+/// Severity of a [Finding]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Severity {
+    /// Error that is reported while processing continues
+    Error,
+    /// Fatal error that stops processing
+    Fatal,
+}
+
+/// A single error reported during processing
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Finding {
+    /// Severity of the finding
+    pub severity: Severity,
+    /// The numeric error code, e.g. `10` for `[E10]`, if the error message has one
+    pub code: Option<u16>,
+    /// Memory position of the error in the input data, if the error message has one
+    pub mem_pos: Option<u64>,
+    /// Link ID of the CDP that contains the memory position of the error
+    pub link_id: Option<u8>,
+    /// FEE ID of the CDP that contains the memory position of the error
+    pub fee_id: Option<u16>,
+    /// The error message without the memory position and error code prefixes
+    pub message: String,
+}
+
+impl Finding {
+    /// Parses a [Finding] from an error message of the form `0x1A0: [E10] msg`, both prefixes are optional.
+    ///
+    /// The link and FEE ID are not known from the message and are left as [None].
+    pub fn from_error_msg(severity: Severity, error_msg: &str) -> Self {
+        Self::from_check_error(severity, CheckError::from_error_msg(error_msg))
+    }
+
+    /// Creates a [Finding] from the structured form of an error.
+    pub fn from_check_error(severity: Severity, check_error: CheckError) -> Self {
+        Self {
+            severity,
+            code: check_error.code,
+            mem_pos: check_error.mem_pos,
+            link_id: check_error.link_id,
+            fee_id: check_error.fee_id,
+            message: check_error.message,
+        }
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(mem_pos) = self.mem_pos {
+            write!(f, "{mem_pos:#X}: ")?;
+        }
+        if let Some(code) = self.code {
+            write!(f, "[E{code}] ")?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+/// The results of processing with [init_processing_collect](crate::init_processing_collect)
+#[derive(Debug, Default)]
+pub struct Collected {
+    /// The finalized stats, e.g. RDHs and HBFs seen
+    pub stats: StatsCollector,
+    /// All errors reported during processing, sorted by memory position
+    pub findings: Vec<Finding>,
+}
+
+/// Receives stats until all senders are dropped, and collects them along with a [Finding] for each error.
+///
+/// A [Finding] without a link and FEE ID is attributed to the CDP that contains its memory position, if the CDP is among the last [MAX_CDP_LOCATIONS] CDPs seen.
+pub(crate) fn collect_findings(
+    stats_recv: &flume::Receiver<StatType>,
+    mute_errors: bool,
+) -> Collected {
+    let mut collected = Collected::default();
+    // Memory position of the start of the most recently seen CDPs mapped to their link and FEE ID.
+    // A CDP is seen before the errors found in it are reported, so errors are attributed as they are received.
+    let mut cdp_locations: BTreeMap<u64, (u8, u16)> = BTreeMap::new();
+    while let Ok(stat) = stats_recv.recv() {
+        let finding = match stat {
+            StatType::CdpsSeen(cdps) => {
+                for &(mem_pos, link_id, fee_id) in cdps.iter() {
+                    let _ = cdp_locations.insert(mem_pos, (link_id, fee_id));
+                }
+                while cdp_locations.len() > MAX_CDP_LOCATIONS {
+                    let _ = cdp_locations.pop_first();
+                }
+                continue;
+            }
+            StatType::Error(ref msg) => Finding::from_error_msg(Severity::Error, msg),
+            StatType::CheckError(ref check_error) => {
+                Finding::from_check_error(Severity::Error, (**check_error).clone())
+            }
+            StatType::Fatal(ref msg) => Finding::from_error_msg(Severity::Fatal, msg),
+            _ => {
+                collected.stats.collect(stat);
+                continue;
+            }
+        };
+        collected
+            .findings
+            .push(attribute_to_cdp(finding, &cdp_locations));
+        collected.stats.collect(stat);
+    }
+    // Findings without a memory position are placed last
+    collected
+        .findings
+        .sort_by_key(|finding| finding.mem_pos.unwrap_or(u64::MAX));
+    collected.stats.finalize(mute_errors);
+    collected
+}
+
+/// Sets the link and FEE ID of a [Finding] that doesn't have them, from the CDP that contains its memory position
+fn attribute_to_cdp(mut finding: Finding, cdp_locations: &BTreeMap<u64, (u8, u16)>) -> Finding {
+    if finding.link_id.is_some() {
+        return finding;
+    }
+    if let Some(&(link_id, fee_id)) = finding
+        .mem_pos
+        .and_then(|mem_pos| cdp_locations.range(..=mem_pos).next_back())
+        .map(|(_, location)| location)
+    {
+        finding.link_id = Some(link_id);
+        finding.fee_id = Some(fee_id);
+    }
+    finding
+}
+
+/// Parses the memory position at the start of an error message, e.g. `0x1A0: [E10] ...`
+pub(crate) fn error_mem_pos(error: &str) -> Option<u64> {
+    let (mem_pos, _) = error.strip_prefix("0x")?.split_once(':')?;
+    u64::from_str_radix(mem_pos, 16).ok()
+}
+
+/// Parses the error code of an error message, e.g. `10` from `0x1A0: [E10] ...`
+pub(crate) fn error_code(error: &str) -> Option<u16> {
+    let msg = match error_mem_pos(error) {
+        Some(_) => error.split_once(':')?.1,
+        None => error,
+    };
+    split_error_code(msg.trim_start()).map(|(code, _)| code)
+}
+
+/// Splits an error code from the start of an error message, e.g. `[E10] msg` into `(10, "msg")`
+pub(crate) fn split_error_code(msg: &str) -> Option<(u16, &str)> {
+    let (code, message) = msg.strip_prefix("[E")?.split_once(']')?;
+    Some((code.parse().ok()?, message.trim_start()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_finding_from_error_msg() {
+        let finding = Finding::from_error_msg(
+            Severity::Error,
+            "0x90: [E70] ID is invalid: 0xF1 [00 00 00 00 00 00 00 00 01 F1]",
+        );
+        assert_eq!(finding.severity, Severity::Error);
+        assert_eq!(finding.code, Some(70));
+        assert_eq!(finding.mem_pos, Some(0x90));
+        assert_eq!(
+            finding.message,
+            "ID is invalid: 0xF1 [00 00 00 00 00 00 00 00 01 F1]"
+        );
+        assert_eq!(
+            finding.to_string(),
+            "0x90: [E70] ID is invalid: 0xF1 [00 00 00 00 00 00 00 00 01 F1]"
+        );
+    }
+
+    #[test]
+    fn test_finding_from_error_msg_no_prefixes() {
+        let finding = Finding::from_error_msg(Severity::Fatal, "Failed to parse system ID");
+        assert_eq!(finding.code, None);
+        assert_eq!(finding.mem_pos, None);
+        assert_eq!(finding.message, "Failed to parse system ID");
+    }
+
+    #[test]
+    fn test_collect_findings_attributes_link_and_fee() {
+        let (send, recv) = flume::unbounded();
+        send.send(StatType::CdpsSeen(Box::new([(0, 3, 524), (0xA0, 5, 8)])))
+            .unwrap();
+        send.send(StatType::Error("0xB0: [E30] Payload error".into()))
+            .unwrap();
+        send.send(StatType::Error("0x10: [E10] RDH error".into()))
+            .unwrap();
+        drop(send);
+
+        let collected = collect_findings(&recv, false);
+        let locations = collected
+            .findings
+            .iter()
+            .map(|finding| {
+                (
+                    finding.mem_pos,
+                    finding.code,
+                    finding.link_id,
+                    finding.fee_id,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            locations,
+            vec![
+                (Some(0x10), Some(10), Some(3), Some(524)),
+                (Some(0xB0), Some(30), Some(5), Some(8)),
+            ]
+        );
+        assert!(collected.stats.any_errors());
+    }
+
+    #[test]
+    fn test_collect_findings_cdp_locations_capped() {
+        let (send, recv) = flume::unbounded();
+        let cdps_seen = (0..=MAX_CDP_LOCATIONS as u64)
+            .map(|cdp| (cdp * 0x100, 3, 524))
+            .collect();
+        send.send(StatType::CdpsSeen(cdps_seen)).unwrap();
+        send.send(StatType::Error("0x10: [E10] RDH error".into()))
+            .unwrap();
+        send.send(StatType::Error("0x110: [E10] RDH error".into()))
+            .unwrap();
+        drop(send);
+
+        let collected = collect_findings(&recv, false);
+        // The first CDP is dropped, so the error in it is not attributed
+        assert_eq!(collected.findings[0].link_id, None);
+        assert_eq!(collected.findings[1].link_id, Some(3));
+        assert_eq!(collected.findings[1].fee_id, Some(524));
+    }
+
+    #[test]
+    fn test_error_mem_pos() {
+        assert_eq!(error_mem_pos("0x1A0: [E10] msg"), Some(0x1A0));
+        assert_eq!(error_mem_pos("[E10] msg"), None);
+        assert_eq!(error_mem_pos("0xZZ: [E10] msg"), None);
+    }
+
+    #[test]
+    fn test_error_code() {
+        assert_eq!(error_code("0x1A0: [E10] msg"), Some(10));
+        assert_eq!(error_code("[E9001] msg"), Some(9001));
+        assert_eq!(error_code("0x1A0: msg without code"), None);
+    }
+}
@@ -0,0 +1,153 @@
+//! Contains the [LiveStatus] that prints a condensed, periodically refreshed, per-link status line to stderr during processing.
+//!
+//! Errors are attributed to a link by the memory position in the error message, which is looked up in the memory positions of the CDPs seen on each link.
+
+use super::finding::{error_mem_pos, MAX_CDP_LOCATIONS};
+use crate::util::*;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+
+/// How often the status line is refreshed
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct LinkStatus {
+    rdhs: u64,
+    errors: u64,
+}
+
+/// Accumulates per-link RDH and error counts and prints them as a single line to stderr.
+#[derive(Debug)]
+pub struct LiveStatus {
+    links: BTreeMap<u8, LinkStatus>,
+    /// Memory position of the start of the most recently seen CDPs mapped to their link ID, at most [MAX_CDP_LOCATIONS]
+    cdp_links: BTreeMap<u64, u8>,
+    /// Errors that could not be attributed to a link
+    other_errors: u64,
+    last_print: Option<Instant>,
+}
+
+impl Default for LiveStatus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LiveStatus {
+    /// Creates a new [LiveStatus] with no links seen.
+    pub fn new() -> Self {
+        Self {
+            links: BTreeMap::new(),
+            cdp_links: BTreeMap::new(),
+            other_errors: 0,
+            last_print: None,
+        }
+    }
+
+    /// Record CDPs as (RDH memory position, link ID, FEE ID).
+    pub fn record_cdps(&mut self, cdps: &[(u64, u8, u16)]) {
+        for &(mem_pos, link_id, _) in cdps {
+            self.links.entry(link_id).or_default().rdhs += 1;
+            let _ = self.cdp_links.insert(mem_pos, link_id);
+        }
+        while self.cdp_links.len() > MAX_CDP_LOCATIONS {
+            let _ = self.cdp_links.pop_first();
+        }
+    }
+
+    /// Record an error, attributing it to the link of the CDP that contains the memory position of the error.
+    ///
+    /// Errors in CDPs that are no longer among the most recently seen CDPs are counted as other errors.
+    pub fn record_error(&mut self, error: &str) {
+        let link_id = error_mem_pos(error).and_then(|mem_pos| {
+            self.cdp_links
+                .range(..=mem_pos)
+                .next_back()
+                .map(|(_, &link_id)| link_id)
+        });
+        match link_id.and_then(|id| self.links.get_mut(&id)) {
+            Some(link_status) => link_status.errors += 1,
+            None => self.other_errors += 1,
+        }
+    }
+
+    /// Returns the condensed status line.
+    pub fn status_line(&self) -> String {
+        let mut line = self
+            .links
+            .iter()
+            .map(|(link_id, status)| {
+                format!(
+                    "L{link_id}: {rdhs} RDHs {errors} err",
+                    rdhs = status.rdhs,
+                    errors = status.errors
+                )
+            })
+            .join(" | ");
+        if self.other_errors > 0 {
+            if !line.is_empty() {
+                line.push_str(" | ");
+            }
+            write!(line, "other: {} err", self.other_errors).unwrap();
+        }
+        line
+    }
+
+    /// Refresh the status line on stderr if it was not refreshed within the last second.
+    pub fn print_if_due(&mut self) {
+        if self.links.is_empty() && self.other_errors == 0
+            || self
+                .last_print
+                .is_some_and(|last_print| last_print.elapsed() < REFRESH_INTERVAL)
+        {
+            return;
+        }
+        self.last_print = Some(Instant::now());
+        // Carriage return and clear line to overwrite the previous status line
+        eprint!("\r\x1b[2K{}", self.status_line());
+    }
+
+    /// Print the final status line, terminated by a newline.
+    pub fn finish(&self) {
+        eprintln!("\r\x1b[2K{}", self.status_line());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_live_status_line() {
+        let mut live_status = LiveStatus::new();
+        live_status.record_cdps(&[(0, 0, 0), (0xA0, 1, 0), (0x140, 0, 0)]);
+        live_status.record_error("0x0: [E10] RDH sanity check failed");
+        live_status.record_error("0x150: [E30] Payload error");
+        live_status.record_error("0xA0: [E11] RDH running check failed");
+        live_status.record_error("Payload error without a memory position");
+
+        assert_eq!(
+            live_status.status_line(),
+            "L0: 2 RDHs 2 err | L1: 1 RDHs 1 err | other: 1 err"
+        );
+    }
+
+    #[test]
+    fn test_live_status_cdp_links_capped() {
+        let mut live_status = LiveStatus::new();
+        let cdps = (0..=MAX_CDP_LOCATIONS as u64)
+            .map(|cdp| (cdp * 0x100, 3, 524))
+            .collect::<Vec<_>>();
+        live_status.record_cdps(&cdps);
+        assert_eq!(live_status.cdp_links.len(), MAX_CDP_LOCATIONS);
+        // The first CDP is dropped
+        live_status.record_error("0x10: [E10] RDH sanity check failed");
+        live_status.record_error("0x110: [E10] RDH sanity check failed");
+
+        assert_eq!(
+            live_status.status_line(),
+            format!("L3: {} RDHs 1 err | other: 1 err", MAX_CDP_LOCATIONS + 1)
+        );
+    }
+}
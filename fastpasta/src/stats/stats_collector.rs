@@ -1,4 +1,5 @@
 //! Contains the [StatsCollector] that collects stats from analysis.
+pub mod csv_stats;
 pub mod error_stats;
 pub mod its_stats;
 pub mod rdh_stats;
@@ -6,11 +7,23 @@ pub mod trigger_stats;
 
 use super::stats_validation::validate_custom_stats;
 use crate::util::*;
+use csv_stats::CsvStats;
 use error_stats::ErrorStats;
 
+/// Version of the layout of the serialized stats, written as `schema_version` in the stats output.
+///
+/// Bump this when fields are added, removed or change type, and update the [STATS_SCHEMA](super::stats_schema::STATS_SCHEMA) accordingly.
+pub const STATS_SCHEMA_VERSION: u32 = 4;
+
 /// Collects stats from analysis.
-#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+///
+/// Equality ignores the schema version, processing time and throughput, as they vary between runs on the same data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatsCollector {
+    /// Version of the layout of the serialized stats, see [STATS_SCHEMA_VERSION].
+    /// 0 if the stats were written before the layout was versioned.
+    #[serde(default)]
+    schema_version: u32,
     /// If the stats collection is finalized.
     /// If finalized, no more stats can be collected.
     /// If it is NOT finalized, it is not valid to read the stats.
@@ -18,6 +31,44 @@ pub struct StatsCollector {
     rdh_stats: RdhStats,
     error_stats: ErrorStats,
     alpide_stats: Option<AlpideStats>,
+    /// Counts of each kind of ALPIDE lane error per FEE ID and lane, collected along with the ALPIDE stats.
+    #[serde(default)]
+    alpide_lane_errors: Option<AlpideLaneErrors>,
+    #[serde(default)]
+    payload_words_seen: u64,
+    /// Wall-clock time from processing started until all data processing threads finished.
+    #[serde(default)]
+    processing_time: Duration,
+    /// Processed payload size per second of processing time in MB/s.
+    #[serde(default)]
+    throughput_mb_s: f64,
+}
+
+impl PartialEq for StatsCollector {
+    fn eq(&self, other: &Self) -> bool {
+        self.is_finalized == other.is_finalized
+            && self.rdh_stats == other.rdh_stats
+            && self.error_stats == other.error_stats
+            && self.alpide_stats == other.alpide_stats
+            && self.alpide_lane_errors == other.alpide_lane_errors
+            && self.payload_words_seen == other.payload_words_seen
+    }
+}
+
+impl Default for StatsCollector {
+    fn default() -> Self {
+        Self {
+            schema_version: STATS_SCHEMA_VERSION,
+            is_finalized: false,
+            rdh_stats: RdhStats::default(),
+            error_stats: ErrorStats::default(),
+            alpide_stats: None,
+            alpide_lane_errors: None,
+            payload_words_seen: 0,
+            processing_time: Duration::default(),
+            throughput_mb_s: 0.0,
+        }
+    }
 }
 
 impl StatsCollector {
@@ -26,6 +77,7 @@ impl StatsCollector {
     pub fn with_alpide_stats() -> Self {
         Self {
             alpide_stats: Some(AlpideStats::default()),
+            alpide_lane_errors: Some(AlpideLaneErrors::default()),
             ..Default::default()
         }
     }
@@ -39,6 +91,8 @@ impl StatsCollector {
             StatType::LinksObserved(id) => self.rdh_stats.record_link(id),
             StatType::RdhVersion(v) => self.rdh_stats.record_rdh_version(v),
             StatType::FeeId(id) => self.rdh_stats.record_fee_observed(id),
+            StatType::CruId(id) => self.rdh_stats.record_cru_id_observed(id),
+            StatType::Dw(dw) => self.rdh_stats.record_dw_observed(dw),
             StatType::RunTriggerType((raw_trigger_type, trigger_type_str)) => self
                 .rdh_stats
                 .record_run_trigger_type((raw_trigger_type, trigger_type_str)),
@@ -49,12 +103,83 @@ impl StatsCollector {
                 self.rdh_stats.record_layer_stave_seen((layer, stave))
             }
             StatType::RDHFiltered(e) => self.rdh_stats.add_rdhs_filtered(e),
+            StatType::RdhPositions { first, last } => {
+                self.rdh_stats.record_rdh_positions(first, last)
+            }
+            StatType::LinkPayloadStats(links) => self.rdh_stats.record_link_payload_stats(&links),
             StatType::AlpideStats(s) => self.alpide_stats.as_mut().unwrap().sum(s),
+            StatType::AlpideLaneErrors {
+                fee_id,
+                lane_errors,
+            } => {
+                let alpide_lane_errors =
+                    self.alpide_lane_errors.get_or_insert_with(Default::default);
+                lane_errors
+                    .iter()
+                    .for_each(|(lane, err)| alpide_lane_errors.record(fee_id, *lane, err.name()));
+            }
+            StatType::PayloadWordsSeen(words) => self.payload_words_seen += words,
+            StatType::InputFileStats(stats) => {
+                if let Err(conflicts) = self.merge(&stats) {
+                    self.error_stats.add_fatal_err(conflicts.join(", ").into());
+                }
+            }
             StatType::Error(m) => self.error_stats.add_err(m),
+            StatType::CheckError(e) => self.error_stats.add_err(e.to_string().into()),
             StatType::Fatal(m) => self.error_stats.add_fatal_err(m),
+            // Not part of the collected stats, reported as an error by the controller
+            StatType::DataFormatChanged { .. } => (),
+            // Not part of the collected stats, logged or reported as an error by the controller
+            StatType::Warning(_) => (),
+            // Not part of the collected stats, printed by the controller
+            StatType::WordIdHistogram(_)
+            | StatType::TriggerPeriodHistogram(_)
+            | StatType::CdpsSeen(_)
+            | StatType::BytesRead(_) => (),
         }
     }
 
+    /// Add the stats collected by another [StatsCollector] to this one, e.g. to combine the stats of input files processed independently, or of chunks of a run.
+    ///
+    /// Counts are added, and the observed links, FEE IDs, layer/staves etc. are combined.
+    /// Values that are only recorded once (RDH version, data format and system ID) are kept if this collector already recorded them, otherwise they are taken from the other.
+    ///
+    /// Returns a description of each conflict and leaves this collector unchanged if the values that are only recorded once differ,
+    /// or if only one of the collectors is finalized.
+    pub fn merge(&mut self, other: &StatsCollector) -> Result<(), Vec<String>> {
+        let mut conflicts = self.rdh_stats.merge_conflicts(&other.rdh_stats);
+        if self.is_finalized != other.is_finalized {
+            conflicts
+                .push("Cannot merge finalized stats with stats that are not finalized".to_string());
+        }
+        if !conflicts.is_empty() {
+            return Err(conflicts);
+        }
+
+        // The merged stats are written with the current layout, fields missing from older stats files are defaulted
+        self.schema_version = STATS_SCHEMA_VERSION;
+        self.rdh_stats.merge(&other.rdh_stats);
+        self.error_stats.merge(&other.error_stats);
+        if let Some(other_alpide_stats) = other.alpide_stats {
+            match self.alpide_stats.as_mut() {
+                Some(alpide_stats) => alpide_stats.sum(other_alpide_stats),
+                None => self.alpide_stats = Some(other_alpide_stats),
+            }
+        }
+        if let Some(other_alpide_lane_errors) = &other.alpide_lane_errors {
+            self.alpide_lane_errors
+                .get_or_insert_with(Default::default)
+                .merge(other_alpide_lane_errors);
+        }
+        self.payload_words_seen += other.payload_words_seen;
+        self.record_processing_time(self.processing_time + other.processing_time);
+        if self.is_finalized {
+            // Keep the observed links sorted
+            self.rdh_stats.finalize();
+        }
+        Ok(())
+    }
+
     pub(crate) fn validate_custom_stats(&mut self, custom_checks: &'static impl CustomChecksOpt) {
         if let Err(e) = validate_custom_stats(custom_checks, &self.rdh_stats) {
             e.into_iter().for_each(|error_msg| {
@@ -83,6 +208,39 @@ impl StatsCollector {
         self.is_finalized = true;
     }
 
+    /// Returns a finalized copy of the stats collected so far, with the processing time up to now.
+    ///
+    /// Used to write out the stats while processing is still ongoing, without finalizing the stats that are still being collected.
+    pub fn snapshot(&self, mute_errors: bool, processing_time: Duration) -> Self {
+        let mut snapshot = self.clone();
+        snapshot.record_processing_time(processing_time);
+        snapshot.finalize(mute_errors);
+        snapshot
+    }
+
+    /// Record the wall-clock processing time and calculate the throughput from the processed payload size.
+    ///
+    /// Only called once at the end of processing so it doesn't slow down collecting stats.
+    pub fn record_processing_time(&mut self, processing_time: Duration) {
+        self.processing_time = processing_time;
+        let secs = processing_time.as_secs_f64();
+        self.throughput_mb_s = if secs > 0.0 {
+            self.payload_size() as f64 / 1_000_000.0 / secs
+        } else {
+            0.0
+        };
+    }
+
+    /// Returns the wall-clock processing time.
+    pub fn processing_time(&self) -> Duration {
+        self.processing_time
+    }
+
+    /// Returns the throughput of the processed payload in MB/s.
+    pub fn throughput_mb_s(&self) -> f64 {
+        self.throughput_mb_s
+    }
+
     /// Returns a reference to the [RdhStats].
     pub fn rdh_stats(&self) -> &RdhStats {
         &self.rdh_stats
@@ -103,6 +261,11 @@ impl StatsCollector {
         self.rdh_stats.hbfs_seen()
     }
 
+    /// Returns the number of payload words counted by validators that don't decode the payload words (e.g. FT0).
+    pub fn payload_words_seen(&self) -> u64 {
+        self.payload_words_seen
+    }
+
     /// Returns if any RDHs were seen in the processed data.
     pub fn any_rdhs_seen(&self) -> bool {
         self.rdh_stats.rdhs_seen() > 0
@@ -128,6 +291,11 @@ impl StatsCollector {
         self.error_stats.err_count()
     }
 
+    /// Record the error code of a reported error, tallied in the error code histogram.
+    pub fn record_error_code(&mut self, code: u16) {
+        self.error_stats.add_error_code(code);
+    }
+
     /// Return if any errors were reported.
     pub fn any_errors(&self) -> bool {
         self.error_stats.err_count() > 0
@@ -153,24 +321,57 @@ impl StatsCollector {
         self.error_stats.staves_with_errors_as_slice()
     }
 
+    /// Returns the version of the layout of the stats, 0 if they were read from stats written before the layout was versioned.
+    pub fn schema_version(&self) -> u32 {
+        self.schema_version
+    }
+
     /// Returns a reference to the [AlpideStats] instance.
     pub fn alpide_stats(&self) -> Option<&AlpideStats> {
         self.alpide_stats.as_ref()
     }
 
-    pub(crate) fn write_stats(&self, mode: &DataOutputMode, format: DataOutputFormat) {
+    /// Returns a reference to the [AlpideLaneErrors] instance.
+    pub fn alpide_lane_errors(&self) -> Option<&AlpideLaneErrors> {
+        self.alpide_lane_errors.as_ref()
+    }
+
+    pub(crate) fn write_stats(
+        &self,
+        mode: &DataOutputMode,
+        format: DataOutputFormat,
+        json_pretty: bool,
+    ) {
         if *mode == DataOutputMode::None {
             return;
         }
         match format {
             DataOutputFormat::JSON => write_stats_str(
                 mode,
-                &serde_json::to_string_pretty(&self).expect("Failed to serialize stats to JSON"),
+                &if json_pretty {
+                    serde_json::to_string_pretty(&self)
+                } else {
+                    serde_json::to_string(&self)
+                }
+                .expect("Failed to serialize stats to JSON"),
             ),
             DataOutputFormat::TOML => write_stats_str(
                 mode,
                 &toml::to_string_pretty(&self).expect("Failed to serialize stats to TOML"),
             ),
+            DataOutputFormat::CSV => {
+                write_stats_str(mode, &CsvStats::from_stats_collector(self).to_csv_string())
+            }
+        }
+    }
+
+    /// Read stats from a file written with `--output-stats`, the file extension determines the format (JSON/TOML).
+    pub fn from_stats_file(path: &Path) -> Result<Self, String> {
+        let stats_str = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => serde_json::from_str(&stats_str).map_err(|e| e.to_string()),
+            Some("toml") => toml::from_str(&stats_str).map_err(|e| e.to_string()),
+            _ => Err("Invalid stats file extension, must be .json or .toml".to_string()),
         }
     }
 
@@ -201,6 +402,15 @@ impl StatsCollector {
             log::warn!("Input stats contains ALPIDE stats but the chosen analysis did not collect ALPIDE stats (did you mean to use `check all its-stave`?)");
         }
 
+        // Stats written before the ALPIDE lane errors were collected don't contain them
+        if let (Some(alpide_lane_errors), Some(other_alpide_lane_errors)) =
+            (self.alpide_lane_errors(), other.alpide_lane_errors())
+        {
+            if let Err(mut err_msgs) = alpide_lane_errors.validate_other(other_alpide_lane_errors) {
+                errs.append(&mut err_msgs);
+            }
+        }
+
         if errs.is_empty() {
             Ok(())
         } else {
@@ -217,9 +427,33 @@ impl StatsCollector {
     }
 }
 
+impl StatsCollector {
+    /// Validate that the other stats read from CSV (from user input) matches the collected stats.
+    pub fn validate_other_csv_stats(
+        &self,
+        other: &CsvStats,
+        mute_errors: bool,
+    ) -> Result<(), io::Error> {
+        if let Err(errs) = CsvStats::from_stats_collector(self).validate_other(other) {
+            if !mute_errors {
+                errs.iter().for_each(|err| {
+                    crate::display_error(err);
+                });
+            }
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Stats validation failed",
+            ));
+        }
+        Ok(())
+    }
+}
+
 fn write_stats_str(mode: &DataOutputMode, stats_str: &str) {
     match mode {
-        DataOutputMode::File(path) => {
+        DataOutputMode::File(path)
+        | DataOutputMode::SplitByLink(path)
+        | DataOutputMode::SplitByHbf(path) => {
             fs::write(path, stats_str).expect("Failed writing stats output file")
         }
         DataOutputMode::Stdout => println!("{stats_str}"),
@@ -251,6 +485,7 @@ mod tests {
         stats_collector.collect(StatType::LayerStaveSeen { layer: 6, stave: 7 });
         stats_collector.collect(StatType::FeeId(8));
         stats_collector.collect(StatType::AlpideStats(AlpideStats::default()));
+        stats_collector.record_processing_time(Duration::from_millis(1500));
         stats_collector.finalize(false);
 
         let json = serde_json::to_string(&stats_collector).unwrap();
@@ -265,6 +500,102 @@ mod tests {
         let from_toml = toml::from_str::<StatsCollector>(&toml).unwrap();
         println!("{toml}");
         assert_eq!(stats_collector, from_toml);
+
+        assert_eq!(from_json.processing_time(), Duration::from_millis(1500));
+        assert_eq!(
+            from_json.throughput_mb_s(),
+            stats_collector.throughput_mb_s()
+        );
+        assert_eq!(from_toml.processing_time(), Duration::from_millis(1500));
+        assert_eq!(
+            from_toml.throughput_mb_s(),
+            stats_collector.throughput_mb_s()
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut stats_collector = StatsCollector::default();
+        stats_collector.collect(StatType::RdhVersion(7));
+        stats_collector.collect(StatType::SystemId(SystemId::ITS));
+        stats_collector.collect(StatType::RDHSeen(10));
+        stats_collector.collect(StatType::HBFsSeen(2));
+        stats_collector.collect(StatType::PayloadSize(100));
+        stats_collector.collect(StatType::LinksObserved(8));
+        stats_collector.collect(StatType::FeeId(524));
+        stats_collector.collect(StatType::CruId(23));
+        stats_collector.collect(StatType::Dw(0));
+        stats_collector.collect(StatType::TriggerType(0x6A03));
+        stats_collector.collect(StatType::Error("0x40: [E10] error".into()));
+        stats_collector.record_error_code(10);
+
+        let mut other = StatsCollector::with_alpide_stats();
+        other.collect(StatType::RdhVersion(7));
+        other.collect(StatType::SystemId(SystemId::ITS));
+        other.collect(StatType::RDHSeen(2));
+        other.collect(StatType::HBFsSeen(1));
+        other.collect(StatType::PayloadSize(50));
+        other.collect(StatType::LinksObserved(8));
+        other.collect(StatType::LinksObserved(3));
+        other.collect(StatType::FeeId(524));
+        other.collect(StatType::FeeId(8));
+        other.collect(StatType::CruId(23));
+        other.collect(StatType::CruId(24));
+        other.collect(StatType::Dw(1));
+        other.collect(StatType::TriggerType(0x6A03));
+        other.collect(StatType::Error("0x80: [E10] error".into()));
+        other.record_error_code(10);
+
+        stats_collector.merge(&other).unwrap();
+        stats_collector.finalize(false);
+
+        assert_eq!(stats_collector.rdhs_seen(), 12);
+        assert_eq!(stats_collector.hbfs_seen(), 3);
+        assert_eq!(stats_collector.payload_size(), 150);
+        assert_eq!(stats_collector.rdh_stats().rdh_version(), 7);
+        assert_eq!(stats_collector.rdh_stats().links_as_slice(), &[3, 8]);
+        assert_eq!(stats_collector.rdh_stats().fee_ids_as_slice(), &[524, 8]);
+        assert_eq!(stats_collector.rdh_stats().cru_ids_as_slice(), &[23, 24]);
+        assert_eq!(stats_collector.rdh_stats().dws_as_slice(), &[0, 1]);
+        assert_eq!(stats_collector.rdh_stats().trigger_stats().soc(), 2);
+        assert_eq!(stats_collector.err_count(), 2);
+        assert_eq!(
+            stats_collector.error_stats().error_code_histogram(),
+            vec![("E10", 2)]
+        );
+        assert!(stats_collector.alpide_stats().is_some());
+    }
+
+    #[test]
+    fn test_merge_conflicts() {
+        let mut stats_collector = StatsCollector::default();
+        stats_collector.collect(StatType::RdhVersion(7));
+        stats_collector.collect(StatType::DataFormat(0));
+        stats_collector.collect(StatType::SystemId(SystemId::ITS));
+        stats_collector.collect(StatType::RDHSeen(10));
+
+        let mut other = StatsCollector::default();
+        other.collect(StatType::RdhVersion(7));
+        other.collect(StatType::DataFormat(2));
+        other.collect(StatType::SystemId(SystemId::TPC));
+        other.collect(StatType::RDHSeen(2));
+
+        assert_eq!(
+            stats_collector.merge(&other).unwrap_err(),
+            vec![
+                "Data format mismatch: 0 and 2".to_string(),
+                "System ID mismatch: ITS and TPC".to_string()
+            ]
+        );
+        // Nothing is merged if there are conflicts
+        assert_eq!(stats_collector.rdhs_seen(), 10);
+
+        other.finalize(false);
+        let conflicts = StatsCollector::default().merge(&other).unwrap_err();
+        assert_eq!(
+            conflicts,
+            vec!["Cannot merge finalized stats with stats that are not finalized".to_string()]
+        );
     }
 
     #[test]
@@ -354,4 +685,16 @@ mod tests {
             .validate_other_stats(&other_stats_collector, false)
             .is_ok());
     }
+
+    #[test]
+    fn test_record_processing_time_throughput() {
+        let mut stats_collector = StatsCollector::default();
+        stats_collector.collect(StatType::PayloadSize(3_000_000));
+        stats_collector.record_processing_time(Duration::from_secs(2));
+        assert_eq!(stats_collector.processing_time(), Duration::from_secs(2));
+        assert_eq!(stats_collector.throughput_mb_s(), 1.5);
+
+        stats_collector.record_processing_time(Duration::ZERO);
+        assert_eq!(stats_collector.throughput_mb_s(), 0.0);
+    }
 }
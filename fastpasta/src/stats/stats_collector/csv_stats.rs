@@ -0,0 +1,178 @@
+//! Contains the [CsvStats] struct, a flat subset of the [StatsCollector] that is written as key-value CSV
+//!
+//! Each line is a `stat,value` pair, lists of values are separated by spaces, e.g.
+//! ```text
+//! stat,value
+//! rdhs_seen,10
+//! rdhs_filtered,0
+//! payload_size,1856
+//! hbfs_seen,5
+//! links,0 3 7
+//! layer_staves_seen,L0_12 L5_42
+//! ```
+
+use crate::util::*;
+
+const CSV_HEADER: &str = "stat,value";
+
+/// Flat subset of the [StatsCollector] that can be written to and read from CSV.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct CsvStats {
+    rdhs_seen: u64,
+    rdhs_filtered: u64,
+    payload_size: u64,
+    hbfs_seen: u32,
+    links: Vec<u8>,
+    layer_staves_seen: Vec<(u8, u8)>,
+}
+
+impl CsvStats {
+    /// Creates [CsvStats] from the stats in a [StatsCollector]
+    pub fn from_stats_collector(stats: &StatsCollector) -> Self {
+        let rdh_stats = stats.rdh_stats();
+        Self {
+            rdhs_seen: rdh_stats.rdhs_seen(),
+            rdhs_filtered: rdh_stats.rdhs_filtered(),
+            payload_size: rdh_stats.payload_size(),
+            hbfs_seen: rdh_stats.hbfs_seen(),
+            links: rdh_stats.links_as_slice().to_vec(),
+            layer_staves_seen: rdh_stats.layer_staves_as_slice().to_vec(),
+        }
+    }
+
+    /// Serializes the stats to a key-value CSV string
+    pub fn to_csv_string(&self) -> String {
+        let links = self.links.iter().join(" ");
+        let layer_staves = self
+            .layer_staves_seen
+            .iter()
+            .map(|(layer, stave)| format!("L{layer}_{stave}"))
+            .join(" ");
+        format!(
+            "{CSV_HEADER}\n\
+            rdhs_seen,{rdhs_seen}\n\
+            rdhs_filtered,{rdhs_filtered}\n\
+            payload_size,{payload_size}\n\
+            hbfs_seen,{hbfs_seen}\n\
+            links,{links}\n\
+            layer_staves_seen,{layer_staves}",
+            rdhs_seen = self.rdhs_seen,
+            rdhs_filtered = self.rdhs_filtered,
+            payload_size = self.payload_size,
+            hbfs_seen = self.hbfs_seen,
+        )
+    }
+
+    /// Parses stats from a key-value CSV string, stats that are not specified are left at their default value
+    pub fn from_csv_str(csv: &str) -> Result<Self, String> {
+        let mut stats = Self::default();
+        let mut lines = csv.lines().filter(|line| !line.trim().is_empty());
+        if lines.next().map(str::trim) != Some(CSV_HEADER) {
+            return Err(format!("Invalid stats CSV: expected header `{CSV_HEADER}`"));
+        }
+        for line in lines {
+            let (stat, value) = line
+                .split_once(',')
+                .ok_or_else(|| format!("Invalid stats CSV line: {line}"))?;
+            let value = value.trim();
+            let parse_err = |e: &dyn fmt::Display| format!("Invalid value for {stat}: {e}");
+            match stat.trim() {
+                "rdhs_seen" => stats.rdhs_seen = value.parse().map_err(|e| parse_err(&e))?,
+                "rdhs_filtered" => {
+                    stats.rdhs_filtered = value.parse().map_err(|e| parse_err(&e))?
+                }
+                "payload_size" => stats.payload_size = value.parse().map_err(|e| parse_err(&e))?,
+                "hbfs_seen" => stats.hbfs_seen = value.parse().map_err(|e| parse_err(&e))?,
+                "links" => {
+                    stats.links = value
+                        .split_whitespace()
+                        .map(str::parse)
+                        .collect::<Result<_, _>>()
+                        .map_err(|e| parse_err(&e))?
+                }
+                "layer_staves_seen" => {
+                    stats.layer_staves_seen = value
+                        .split_whitespace()
+                        .map(parse_layer_stave)
+                        .collect::<Result<_, _>>()
+                        .map_err(|e| parse_err(&e))?
+                }
+                unknown => return Err(format!("Unknown stat in stats CSV: {unknown}")),
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Validate that the other stats (from user input) matches these stats.
+    pub fn validate_other(&self, other: &Self) -> Result<(), Vec<String>> {
+        self.validate_fields(other)
+    }
+
+    // Implementation of the `validate_fields` macro
+    // Remember to add new fields here as well!
+    crate::validate_fields!(
+        CsvStats,
+        rdhs_seen,
+        rdhs_filtered,
+        payload_size,
+        hbfs_seen,
+        links,
+        layer_staves_seen
+    );
+}
+
+/// Parses a layer/stave of the form `L5_42`
+fn parse_layer_stave(layer_stave: &str) -> Result<(u8, u8), String> {
+    let invalid = || format!("expected L[layer]_[stave], got {layer_stave}");
+    let (layer, stave) = layer_stave
+        .strip_prefix('L')
+        .and_then(|layer_stave| layer_stave.split_once('_'))
+        .ok_or_else(invalid)?;
+    Ok((
+        layer.parse().map_err(|_| invalid())?,
+        stave.parse().map_err(|_| invalid())?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_csv_roundtrip() {
+        let mut stats_collector = StatsCollector::default();
+        stats_collector.collect(StatType::RDHSeen(10));
+        stats_collector.collect(StatType::HBFsSeen(5));
+        stats_collector.collect(StatType::PayloadSize(1856));
+        stats_collector.collect(StatType::LinksObserved(3));
+        stats_collector.collect(StatType::LinksObserved(0));
+        stats_collector.collect(StatType::LayerStaveSeen {
+            layer: 5,
+            stave: 42,
+        });
+
+        let csv_stats = CsvStats::from_stats_collector(&stats_collector);
+        let csv = csv_stats.to_csv_string();
+        assert_eq!(
+            csv,
+            "stat,value\nrdhs_seen,10\nrdhs_filtered,0\npayload_size,1856\nhbfs_seen,5\nlinks,3 0\nlayer_staves_seen,L5_42"
+        );
+        assert_eq!(CsvStats::from_csv_str(&csv).unwrap(), csv_stats);
+    }
+
+    #[test]
+    fn test_csv_validate_other_mismatch() {
+        let csv_stats = CsvStats::from_csv_str("stat,value\nrdhs_seen,10\nlinks,0 3").unwrap();
+        let other = CsvStats::from_csv_str("stat,value\nrdhs_seen,12\nlinks,0 3").unwrap();
+        let errs = csv_stats.validate_other(&other).unwrap_err();
+        assert_eq!(errs, vec!["rdhs_seen mismatch! expected: 12, got: 10"]);
+    }
+
+    #[test]
+    fn test_csv_invalid() {
+        assert!(CsvStats::from_csv_str("rdhs_seen,10").is_err());
+        assert!(CsvStats::from_csv_str("stat,value\nrdhs,10").is_err());
+        assert!(CsvStats::from_csv_str("stat,value\nlayer_staves_seen,5_42").is_err());
+    }
+}
@@ -1,5 +1,6 @@
 //! Contains the [ErrorStats] struct which stores error messages observed in the raw data and related data
 use crate::util::*;
+use std::collections::BTreeMap;
 
 type LayerStave = (u8, u8);
 
@@ -11,6 +12,9 @@ pub struct ErrorStats {
     custom_checks_stats_errors: Vec<Box<str>>,
     total_errors: u64,
     unique_error_codes: Vec<String>,
+    // Number of errors reported with each error code, e.g. `E44` -> 812
+    #[serde(default)]
+    error_code_counts: BTreeMap<String, u64>,
     // Only applicable if the data is from ITS
     staves_with_errors: Option<Vec<LayerStave>>,
 }
@@ -102,6 +106,67 @@ impl ErrorStats {
         self.reported_errors.push(error_msg);
     }
 
+    pub(super) fn add_error_code(&mut self, code: u16) {
+        *self
+            .error_code_counts
+            .entry(format!("E{code}"))
+            .or_default() += 1;
+    }
+
+    /// Returns if any errors were reported with the error code.
+    pub fn has_error_code(&self, code: u16) -> bool {
+        self.error_code_counts.contains_key(&format!("E{code}"))
+    }
+
+    /// Returns each error code and the number of errors reported with it, sorted by count in descending order.
+    ///
+    /// Error codes with the same count are sorted by error code in ascending order.
+    pub fn error_code_histogram(&self) -> Vec<(&str, u64)> {
+        let mut code_counts: Vec<(&str, u64)> = self
+            .error_code_counts
+            .iter()
+            .map(|(code, &count)| (code.as_str(), count))
+            .collect();
+        code_counts.sort_by_key(|&(code, count)| {
+            (
+                std::cmp::Reverse(count),
+                code[1..].parse::<u16>().unwrap_or(u16::MAX),
+            )
+        });
+        code_counts
+    }
+
+    /// Adds the errors of another [ErrorStats] to this one.
+    ///
+    /// The fatal error is kept if one is already reported, otherwise it is taken from the other.
+    /// The unique error codes and staves with errors are combined, they are only set if the stats are finalized.
+    pub(super) fn merge(&mut self, other: &Self) {
+        if self.fatal_error.is_none() {
+            self.fatal_error = other.fatal_error.clone();
+        }
+        self.reported_errors
+            .extend(other.reported_errors.iter().cloned());
+        self.custom_checks_stats_errors
+            .extend(other.custom_checks_stats_errors.iter().cloned());
+        self.total_errors += other.total_errors;
+        other.error_code_counts.iter().for_each(|(code, count)| {
+            *self.error_code_counts.entry(code.clone()).or_default() += count
+        });
+        other.unique_error_codes.iter().for_each(|code| {
+            if !self.unique_error_codes.contains(code) {
+                self.unique_error_codes.push(code.clone());
+            }
+        });
+        if let Some(other_staves_with_errors) = &other.staves_with_errors {
+            let staves_with_errors = self.staves_with_errors.get_or_insert_with(Vec::new);
+            other_staves_with_errors.iter().for_each(|stave| {
+                if !staves_with_errors.contains(stave) {
+                    staves_with_errors.push(*stave);
+                }
+            });
+        }
+    }
+
     pub(super) fn add_custom_check_error(&mut self, error_msg: Box<str>) {
         self.total_errors += 1;
         self.custom_checks_stats_errors.push(error_msg);
@@ -156,6 +221,7 @@ impl ErrorStats {
             custom_checks_stats_errors: other.custom_checks_stats_errors.clone(),
             total_errors: other.total_errors,
             unique_error_codes: other.unique_error_codes.clone(),
+            error_code_counts: other.error_code_counts.clone(),
             staves_with_errors: other.staves_with_errors.clone(),
         };
 
@@ -169,6 +235,7 @@ impl ErrorStats {
         custom_checks_stats_errors,
         total_errors,
         unique_error_codes,
+        error_code_counts,
         staves_with_errors
     );
 }
@@ -205,6 +272,7 @@ mod tests {
         let mut error_stats = ErrorStats::default();
 
         error_stats.add_err("0xE0: [E0001] Error message".into());
+        error_stats.add_error_code(1);
         error_stats.finalize_stats(false, None);
 
         let error_stats_ser_json = serde_json::to_string(&error_stats).unwrap();
@@ -217,4 +285,18 @@ mod tests {
         assert_eq!(error_stats, error_stats_de_toml);
         println!("{error_stats_ser_toml}");
     }
+
+    #[test]
+    fn test_error_code_histogram_sorted() {
+        let mut error_stats = ErrorStats::default();
+        for code in [30, 44, 44, 100, 44, 11, 100] {
+            error_stats.add_error_code(code);
+        }
+        assert_eq!(
+            error_stats.error_code_histogram(),
+            vec![("E44", 3), ("E100", 2), ("E11", 1), ("E30", 1)]
+        );
+        assert!(error_stats.has_error_code(100));
+        assert!(!error_stats.has_error_code(10));
+    }
 }
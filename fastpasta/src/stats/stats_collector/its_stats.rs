@@ -1,9 +1,10 @@
 //! Contains the [ItsStats] struct which stores ITS specific data observed in the raw data
 use serde::{Deserialize, Serialize};
+pub mod alpide_lane_errors;
 pub mod alpide_stats;
 
 /// Stores ITS specific data observed through RDHs
-#[derive(Default, PartialEq, Debug, Serialize, Deserialize)]
+#[derive(Default, Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct ItsStats {
     /// Holds a layer/stave combinations observed in the raw data
     layer_staves_seen: Vec<(u8, u8)>,
@@ -24,6 +25,14 @@ impl ItsStats {
         &self.layer_staves_seen
     }
 
+    /// Adds the layer/staves seen in another [ItsStats] that are not already seen.
+    pub(super) fn merge(&mut self, other: &Self) {
+        other
+            .layer_staves_seen
+            .iter()
+            .for_each(|&layer_stave| self.record_layer_stave_seen(layer_stave));
+    }
+
     pub(super) fn validate_other(&self, other: &Self) -> Result<(), Vec<String>> {
         // Do this (syntax) to ensure that adding a new field to the struct doesn't break the validation
         // If a new field is added, this will fail to compile, before explicitly adding the new field to this instantiation
@@ -0,0 +1,163 @@
+//! Contains the [AlpideLaneErrors] that counts the errors found in the ALPIDE data per FEE ID and lane
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Counts of each kind of ALPIDE lane error, per FEE ID and lane
+///
+/// Serialized as a list of the lanes with errors, sorted by FEE ID and lane.
+#[derive(Default, Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AlpideLaneErrors {
+    lanes: Vec<LaneErrorCounts>,
+}
+
+/// Counts of each kind of ALPIDE error in a lane of a FEE ID
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LaneErrorCounts {
+    fee_id: u16,
+    lane: u8,
+    /// Number of errors of each kind, keyed by the name of the kind, e.g. `chip_count_mismatch`
+    error_counts: BTreeMap<String, u64>,
+}
+
+impl LaneErrorCounts {
+    /// Returns the FEE ID of the stave the lane is from
+    pub fn fee_id(&self) -> u16 {
+        self.fee_id
+    }
+
+    /// Returns the lane number
+    pub fn lane(&self) -> u8 {
+        self.lane
+    }
+
+    /// Returns the number of errors of each kind
+    pub fn error_counts(&self) -> &BTreeMap<String, u64> {
+        &self.error_counts
+    }
+}
+
+impl AlpideLaneErrors {
+    /// Record an error of the given kind in a lane of a FEE ID
+    pub(crate) fn record(&mut self, fee_id: u16, lane: u8, kind: &str) {
+        self.add(fee_id, lane, kind, 1);
+    }
+
+    fn add(&mut self, fee_id: u16, lane: u8, kind: &str, count: u64) {
+        let idx = match self
+            .lanes
+            .binary_search_by_key(&(fee_id, lane), |l| (l.fee_id, l.lane))
+        {
+            Ok(idx) => idx,
+            Err(idx) => {
+                self.lanes.insert(
+                    idx,
+                    LaneErrorCounts {
+                        fee_id,
+                        lane,
+                        error_counts: BTreeMap::new(),
+                    },
+                );
+                idx
+            }
+        };
+        *self.lanes[idx]
+            .error_counts
+            .entry(kind.to_string())
+            .or_default() += count;
+    }
+
+    /// Returns the lanes with errors, sorted by FEE ID and lane
+    pub fn lanes(&self) -> &[LaneErrorCounts] {
+        &self.lanes
+    }
+
+    /// Add the error counts of another [AlpideLaneErrors] to this one.
+    pub(crate) fn merge(&mut self, other: &Self) {
+        other.lanes.iter().for_each(|lane| {
+            lane.error_counts.iter().for_each(|(kind, count)| {
+                self.add(lane.fee_id, lane.lane, kind, *count);
+            })
+        });
+    }
+
+    pub(crate) fn validate_other(&self, other: &Self) -> Result<(), Vec<String>> {
+        // Do this (syntax) to ensure that adding a new field to the struct doesn't break the validation
+        // If a new field is added, this will fail to compile, before explicitly adding the new field to this instantiation
+        let other = Self {
+            lanes: other.lanes.clone(),
+        };
+        self.validate_fields(&other)
+    }
+    // Implementation of the `validate_fields` macro
+    // Remember to add new fields here as well!
+    crate::validate_fields!(AlpideLaneErrors, lanes);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_record_sorted_by_fee_id_and_lane() {
+        let mut lane_errors = AlpideLaneErrors::default();
+        lane_errors.record(0x502A, 3, "chip_count_mismatch");
+        lane_errors.record(0x502A, 1, "chip_count_mismatch");
+        lane_errors.record(0x502A, 3, "chip_count_mismatch");
+        lane_errors.record(0x502A, 3, "chip_bunch_counter_mismatch");
+        lane_errors.record(524, 8, "chip_id_order_mismatch");
+
+        let lanes = lane_errors
+            .lanes()
+            .iter()
+            .map(|l| (l.fee_id(), l.lane()))
+            .collect::<Vec<_>>();
+        assert_eq!(lanes, [(524, 8), (0x502A, 1), (0x502A, 3)]);
+        assert_eq!(
+            lane_errors.lanes()[2].error_counts(),
+            &BTreeMap::from([
+                ("chip_bunch_counter_mismatch".to_string(), 1),
+                ("chip_count_mismatch".to_string(), 2)
+            ])
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut lane_errors = AlpideLaneErrors::default();
+        lane_errors.record(524, 8, "chip_count_mismatch");
+        let mut other = AlpideLaneErrors::default();
+        other.record(524, 8, "chip_count_mismatch");
+        other.record(524, 9, "lane_bunch_counter_mismatch");
+
+        lane_errors.merge(&other);
+
+        assert_eq!(lane_errors.lanes().len(), 2);
+        assert_eq!(
+            lane_errors.lanes()[0].error_counts()["chip_count_mismatch"],
+            2
+        );
+        assert_eq!(
+            lane_errors.lanes()[1].error_counts()["lane_bunch_counter_mismatch"],
+            1
+        );
+    }
+
+    #[test]
+    fn test_serde_consistency() {
+        let mut lane_errors = AlpideLaneErrors::default();
+        lane_errors.record(524, 8, "chip_count_mismatch");
+        lane_errors.record(524, 9, "lane_bunch_counter_mismatch");
+
+        let lane_errors_ser_json = serde_json::to_string(&lane_errors).unwrap();
+        assert_eq!(
+            lane_errors_ser_json,
+            r#"[{"fee_id":524,"lane":8,"error_counts":{"chip_count_mismatch":1}},{"fee_id":524,"lane":9,"error_counts":{"lane_bunch_counter_mismatch":1}}]"#
+        );
+        let lane_errors_de_json: AlpideLaneErrors =
+            serde_json::from_str(&lane_errors_ser_json).unwrap();
+        assert_eq!(lane_errors, lane_errors_de_json);
+    }
+}
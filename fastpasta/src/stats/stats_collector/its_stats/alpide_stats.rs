@@ -6,6 +6,8 @@ use serde::{Deserialize, Serialize};
 #[derive(Default, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AlpideStats {
     readout_flags: ReadoutFlags,
+    #[serde(default)]
+    lane_occupancy: LaneOccupancy,
 }
 
 impl AlpideStats {
@@ -18,8 +20,18 @@ impl AlpideStats {
         &self.readout_flags
     }
 
+    pub(crate) fn log_lane_occupancy(&mut self, lanes_present: u8, lanes_active: u8) {
+        self.lane_occupancy.log(lanes_present, lanes_active);
+    }
+
+    /// Returns a reference to the lane occupancy of the readout frames
+    pub fn lane_occupancy(&self) -> &LaneOccupancy {
+        &self.lane_occupancy
+    }
+
     pub(crate) fn sum(&mut self, other: AlpideStats) {
         self.readout_flags = self.readout_flags.sum(other.readout_flags);
+        self.lane_occupancy = self.lane_occupancy.sum(other.lane_occupancy);
     }
 
     pub(crate) fn validate_other(&self, other: &Self) -> Result<(), Vec<String>> {
@@ -28,12 +40,16 @@ impl AlpideStats {
         if let Err(mut sub_errs) = self.readout_flags.validate_other(&other.readout_flags) {
             errs.append(&mut sub_errs);
         }
+        if let Err(mut sub_errs) = self.lane_occupancy.validate_other(&other.lane_occupancy) {
+            errs.append(&mut sub_errs);
+        }
 
         // Do this (syntax) to ensure that adding a new field to the struct doesn't break the validation
         // If a new field is added, this will fail to compile, before explicitly adding the new field to this instantiation
-        // unused right now as there's only a sub struct which is validated above
+        // unused right now as there's only sub structs which are validated above
         let _other = Self {
             readout_flags: ReadoutFlags::default(),
+            lane_occupancy: LaneOccupancy::default(),
         };
 
         if errs.is_empty() {
@@ -46,7 +62,7 @@ impl AlpideStats {
     }
     // Implementation of the `validate_fields` macro
     // Remember to add new fields here as well!
-    // Commented out as there's only a sub struct as of now
+    // Commented out as there's only sub structs as of now
     //crate::validate_fields!(AlpideStats, readout_flags);
 }
 
@@ -164,6 +180,100 @@ impl ReadoutFlags {
     );
 }
 
+/// Struct to store the lane occupancy of ITS readout frames, i.e. the fraction of the active lanes (from the IHW) that have data in a frame
+///
+/// A chip that quietly drops out shows up as a minimum occupancy below 1.0, without any of the chip trailers reporting it.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LaneOccupancy {
+    frames_seen: u32,
+    occupancy_sum: f64,
+    min_occupancy: f64,
+    max_occupancy: f64,
+}
+
+impl LaneOccupancy {
+    /// Log the lanes present in a readout frame out of the lanes active
+    pub fn log(&mut self, lanes_present: u8, lanes_active: u8) {
+        if lanes_active == 0 {
+            return;
+        }
+        let occupancy = f64::from(lanes_present) / f64::from(lanes_active);
+        self.sum_occupancies(1, occupancy, occupancy, occupancy);
+    }
+
+    /// Returns the number of readout frames the occupancy was logged for
+    pub fn frames_seen(&self) -> u32 {
+        self.frames_seen
+    }
+
+    /// Returns the lowest lane occupancy of a readout frame
+    pub fn min(&self) -> f64 {
+        self.min_occupancy
+    }
+
+    /// Returns the average lane occupancy of the readout frames
+    pub fn avg(&self) -> f64 {
+        if self.frames_seen == 0 {
+            0.0
+        } else {
+            self.occupancy_sum / f64::from(self.frames_seen)
+        }
+    }
+
+    /// Returns the highest lane occupancy of a readout frame
+    pub fn max(&self) -> f64 {
+        self.max_occupancy
+    }
+
+    fn sum_occupancies(&mut self, frames: u32, sum: f64, min: f64, max: f64) {
+        if self.frames_seen == 0 {
+            self.min_occupancy = min;
+            self.max_occupancy = max;
+        } else {
+            self.min_occupancy = self.min_occupancy.min(min);
+            self.max_occupancy = self.max_occupancy.max(max);
+        }
+        self.frames_seen += frames;
+        self.occupancy_sum += sum;
+    }
+
+    fn sum(mut self, other: LaneOccupancy) -> Self {
+        if other.frames_seen != 0 {
+            self.sum_occupancies(
+                other.frames_seen,
+                other.occupancy_sum,
+                other.min_occupancy,
+                other.max_occupancy,
+            );
+        }
+        self
+    }
+
+    pub(super) fn validate_other(&self, other: &Self) -> Result<(), Vec<String>> {
+        // Do this (syntax) to ensure that adding a new field to the struct doesn't break the validation
+        // If a new field is added, this will fail to compile, before explicitly adding the new field to this instantiation
+        // Remebmer to add new fields to the `validate_fields` macro as well!
+        let other = Self {
+            frames_seen: other.frames_seen,
+            occupancy_sum: other.occupancy_sum,
+            min_occupancy: other.min_occupancy,
+            max_occupancy: other.max_occupancy,
+        };
+
+        self.validate_fields(&other)
+    }
+
+    // Implementation of the `validate_fields` macro
+    // Remember to add new fields here as well!
+    crate::validate_fields!(
+        LaneOccupancy,
+        frames_seen,
+        occupancy_sum,
+        min_occupancy,
+        max_occupancy
+    );
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -188,4 +298,25 @@ mod tests {
         assert_eq!(alpide_stats, alpide_stats_de_toml);
         println!("{alpide_stats_ser_toml}");
     }
+
+    #[test]
+    fn test_lane_occupancy() {
+        let mut alpide_stats = AlpideStats::default();
+        alpide_stats.log_lane_occupancy(7, 7);
+        alpide_stats.log_lane_occupancy(6, 7);
+        // No active lanes, not logged
+        alpide_stats.log_lane_occupancy(0, 0);
+
+        let mut other_alpide_stats = AlpideStats::default();
+        other_alpide_stats.log_lane_occupancy(0, 7);
+        alpide_stats.sum(other_alpide_stats);
+        // Summing empty stats doesn't change the min/max
+        alpide_stats.sum(AlpideStats::default());
+
+        let lane_occupancy = alpide_stats.lane_occupancy();
+        assert_eq!(lane_occupancy.frames_seen(), 3);
+        assert_eq!(lane_occupancy.min(), 0.0);
+        assert_eq!(lane_occupancy.max(), 1.0);
+        assert_eq!(lane_occupancy.avg(), (1.0 + 6.0 / 7.0) / 3.0);
+    }
 }
@@ -3,9 +3,30 @@
 use super::super::stats_collector::its_stats::ItsStats;
 use super::trigger_stats::TriggerStats;
 use crate::util::*;
+use std::collections::BTreeMap;
+
+/// The memory position and orbit of an RDH, e.g. the first RDH seen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RdhPosition {
+    /// Memory position of the RDH in the input data
+    pub mem_pos: u64,
+    /// Orbit of the RDH
+    pub orbit: u32,
+}
+
+/// The number of RDHs and the total payload size of a link.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinkPayloadStats {
+    /// Link ID
+    pub link_id: u8,
+    /// Number of RDHs from the link
+    pub rdh_count: u64,
+    /// Total payload size of the link in bytes
+    pub payload_size: u64,
+}
 
 /// Stores stats extracted from the RDHs of the raw data.
-#[derive(Default, PartialEq, Debug, Serialize, Deserialize)]
+#[derive(Default, Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct RdhStats {
     /// Total RDHs seen.
     rdhs_seen: u64,
@@ -22,6 +43,12 @@ pub struct RdhStats {
     links: Vec<u8>,
     /// FEE IDs seen
     fee_id: Vec<u16>,
+    /// CRU IDs seen
+    #[serde(default)]
+    cru_ids_observed: Vec<u16>,
+    /// DWs (data wrapper IDs) seen
+    #[serde(default)]
+    dws_observed: Vec<u8>,
     /// System ID observed in the data
     system_id: Option<SystemId>,
     /// First Trigger Type observed in the data.
@@ -32,6 +59,16 @@ pub struct RdhStats {
     its_stats: ItsStats,
     /// Stats for the trigger types observed in the data
     trigger_stats: TriggerStats,
+    /// First RDH seen (lowest memory position)
+    first_rdh: Option<RdhPosition>,
+    /// Last RDH seen (highest memory position)
+    last_rdh: Option<RdhPosition>,
+    /// RDHs seen and payload size per link, sorted by link ID
+    #[serde(default)]
+    link_payload_stats: Vec<LinkPayloadStats>,
+    /// RDHs seen per trigger type, e.g. `HB` -> 812, described by [trigger_type_string_from_int](crate::analyze::view::lib::trigger_type_string_from_int)
+    #[serde(default)]
+    trigger_type_counts: BTreeMap<String, u64>,
 }
 
 impl RdhStats {
@@ -102,6 +139,30 @@ impl RdhStats {
         self.fee_id.as_slice()
     }
 
+    /// Stores an observed CRU ID if not already seen.
+    pub fn record_cru_id_observed(&mut self, cru_id: u16) {
+        if !self.cru_ids_observed.contains(&cru_id) {
+            self.cru_ids_observed.push(cru_id);
+        }
+    }
+
+    /// Returns a borrowed slice of the vector with the observed CRU IDs
+    pub fn cru_ids_as_slice(&self) -> &[u16] {
+        self.cru_ids_observed.as_slice()
+    }
+
+    /// Stores an observed DW if not already seen.
+    pub fn record_dw_observed(&mut self, dw: u8) {
+        if !self.dws_observed.contains(&dw) {
+            self.dws_observed.push(dw);
+        }
+    }
+
+    /// Returns a borrowed slice of the vector with the observed DWs
+    pub fn dws_as_slice(&self) -> &[u8] {
+        self.dws_observed.as_slice()
+    }
+
     /// Stores a System ID as observed.
     ///
     /// Attempting to set it more than once will panic.
@@ -141,6 +202,17 @@ impl RdhStats {
     /// Records trigger type stats
     pub fn record_trigger_type(&mut self, trigger_type: u32) {
         self.trigger_stats.collect_stats(trigger_type);
+        let trigger_type_str =
+            crate::analyze::view::lib::trigger_type_string_from_int(trigger_type);
+        *self
+            .trigger_type_counts
+            .entry(trigger_type_str.trim_end().to_string())
+            .or_default() += 1;
+    }
+
+    /// Returns the number of RDHs seen per trigger type, sorted by the trigger type description
+    pub fn trigger_type_counts(&self) -> &BTreeMap<String, u64> {
+        &self.trigger_type_counts
     }
 
     /// Returns a borrowed reference to [TriggerStats]
@@ -160,6 +232,50 @@ impl RdhStats {
         self.its_stats.layer_staves_as_slice()
     }
 
+    /// Records the first and last RDH of a batch of RDHs, keeping the RDHs with the lowest and highest memory position seen.
+    pub fn record_rdh_positions(&mut self, first: RdhPosition, last: RdhPosition) {
+        if self
+            .first_rdh
+            .map_or(true, |rdh| first.mem_pos < rdh.mem_pos)
+        {
+            self.first_rdh = Some(first);
+        }
+        if self.last_rdh.map_or(true, |rdh| last.mem_pos > rdh.mem_pos) {
+            self.last_rdh = Some(last);
+        }
+    }
+
+    /// Returns the first RDH seen, if any RDHs were seen.
+    pub fn first_rdh(&self) -> Option<RdhPosition> {
+        self.first_rdh
+    }
+
+    /// Returns the last RDH seen, if any RDHs were seen.
+    pub fn last_rdh(&self) -> Option<RdhPosition> {
+        self.last_rdh
+    }
+
+    /// Adds the RDHs seen and payload size of each link, e.g. from a batch of RDHs.
+    pub fn record_link_payload_stats(&mut self, link_payload_stats: &[LinkPayloadStats]) {
+        for stats in link_payload_stats {
+            match self
+                .link_payload_stats
+                .binary_search_by_key(&stats.link_id, |link| link.link_id)
+            {
+                Ok(idx) => {
+                    self.link_payload_stats[idx].rdh_count += stats.rdh_count;
+                    self.link_payload_stats[idx].payload_size += stats.payload_size;
+                }
+                Err(idx) => self.link_payload_stats.insert(idx, *stats),
+            }
+        }
+    }
+
+    /// Returns the RDHs seen and payload size of each link, sorted by link ID.
+    pub fn link_payload_stats(&self) -> &[LinkPayloadStats] {
+        &self.link_payload_stats
+    }
+
     pub(super) fn add_payload_size(&mut self, payload_size: u64) {
         self.payload_size += payload_size;
     }
@@ -202,6 +318,76 @@ impl RdhStats {
         self.rdhs_filtered
     }
 
+    /// Returns a description of each value that is only recorded once (e.g. RDH version) and differs between this and another [RdhStats].
+    pub(super) fn merge_conflicts(&self, other: &Self) -> Vec<String> {
+        let mut conflicts = Vec::new();
+        if let Some((this, other)) = self.rdh_version.zip(other.rdh_version) {
+            if this != other {
+                conflicts.push(format!("RDH version mismatch: {this} and {other}"));
+            }
+        }
+        if let Some((this, other)) = self.data_format.zip(other.data_format) {
+            if this != other {
+                conflicts.push(format!("Data format mismatch: {this} and {other}"));
+            }
+        }
+        if let Some((this, other)) = self.system_id.zip(other.system_id) {
+            if this != other {
+                conflicts.push(format!("System ID mismatch: {this} and {other}"));
+            }
+        }
+        conflicts
+    }
+
+    /// Adds the stats of another [RdhStats] to this one, they must not have any [merge conflicts](Self::merge_conflicts).
+    ///
+    /// Counts are added and the observed links, FEE IDs, CRU IDs, DWs and layer/staves are combined.
+    /// Values that are only recorded once (e.g. RDH version) are kept if already recorded, otherwise taken from the other.
+    pub(super) fn merge(&mut self, other: &Self) {
+        self.rdhs_seen += other.rdhs_seen;
+        self.rdhs_filtered += other.rdhs_filtered;
+        self.hbfs_seen += other.hbfs_seen;
+        self.payload_size += other.payload_size;
+        self.rdh_version = self.rdh_version.or(other.rdh_version);
+        self.data_format = self.data_format.or(other.data_format);
+        self.system_id = self.system_id.or(other.system_id);
+        if self.run_trigger_type.is_none() {
+            self.run_trigger_type = other.run_trigger_type.clone();
+        }
+        other.links.iter().for_each(|link_id| {
+            if !self.links.contains(link_id) {
+                self.links.push(*link_id);
+            }
+        });
+        other
+            .fee_id
+            .iter()
+            .for_each(|&fee_id| self.record_fee_observed(fee_id));
+        other
+            .cru_ids_observed
+            .iter()
+            .for_each(|&cru_id| self.record_cru_id_observed(cru_id));
+        other
+            .dws_observed
+            .iter()
+            .for_each(|&dw| self.record_dw_observed(dw));
+        if let Some((first, last)) = other.first_rdh.zip(other.last_rdh) {
+            self.record_rdh_positions(first, last);
+        }
+        self.record_link_payload_stats(&other.link_payload_stats);
+        self.its_stats.merge(&other.its_stats);
+        self.trigger_stats.merge(&other.trigger_stats);
+        other
+            .trigger_type_counts
+            .iter()
+            .for_each(|(trigger_type, count)| {
+                *self
+                    .trigger_type_counts
+                    .entry(trigger_type.clone())
+                    .or_default() += count
+            });
+    }
+
     pub(crate) fn finalize(&mut self) {
         self.sort_links_observed();
     }
@@ -229,10 +415,16 @@ impl RdhStats {
             data_format: other.data_format,
             links: other.links.clone(),
             fee_id: other.fee_id.clone(),
+            cru_ids_observed: other.cru_ids_observed.clone(),
+            dws_observed: other.dws_observed.clone(),
             system_id: other.system_id,
             run_trigger_type: other.run_trigger_type.clone(),
             its_stats: ItsStats::default(), // Validated in previous seperate function
             trigger_stats: TriggerStats::default(), // Validated in seperate function
+            first_rdh: other.first_rdh,
+            last_rdh: other.last_rdh,
+            link_payload_stats: other.link_payload_stats.clone(),
+            trigger_type_counts: other.trigger_type_counts.clone(),
         };
 
         if let Err(mut local_top_field_errs) = self.validate_fields(&other_top_fields_only) {
@@ -258,8 +450,14 @@ impl RdhStats {
         data_format,
         links,
         fee_id,
+        cru_ids_observed,
+        dws_observed,
         system_id,
-        run_trigger_type
+        run_trigger_type,
+        first_rdh,
+        last_rdh,
+        link_payload_stats,
+        trigger_type_counts
     );
 }
 
@@ -279,10 +477,23 @@ mod tests {
             data_format: Some(0),
             links: vec![0, 1, 2, 3, 4, 5, 6],
             fee_id: vec![8, 9, 10, 11, 12, 13, 14],
+            cru_ids_observed: vec![152],
+            dws_observed: vec![0, 1],
             system_id: Some(SystemId::MFT),
             run_trigger_type: Some((1, "Test".into())),
             its_stats: ItsStats::default(),
             trigger_stats: TriggerStats::default(),
+            first_rdh: Some(RdhPosition {
+                mem_pos: 0,
+                orbit: 0x0B7D_BB1C,
+            }),
+            last_rdh: None,
+            link_payload_stats: vec![LinkPayloadStats {
+                link_id: 3,
+                rdh_count: 10,
+                payload_size: 1856,
+            }],
+            trigger_type_counts: BTreeMap::from([("HB".to_string(), 10)]),
         };
 
         rdh_stats.add_hbfs_seen(1);
@@ -298,4 +509,67 @@ mod tests {
         let rdh_stats_de_toml: RdhStats = toml::from_str(&rdh_stats_ser_toml).unwrap();
         assert_eq!(rdh_stats, rdh_stats_de_toml);
     }
+
+    #[test]
+    fn test_record_rdh_positions() {
+        let rdh = |mem_pos, orbit| RdhPosition { mem_pos, orbit };
+        let mut rdh_stats = RdhStats::default();
+        assert_eq!(rdh_stats.first_rdh(), None);
+        rdh_stats.record_rdh_positions(rdh(0x200, 3), rdh(0x300, 4));
+        rdh_stats.record_rdh_positions(rdh(0x400, 5), rdh(0x500, 5));
+        assert_eq!(rdh_stats.first_rdh(), Some(rdh(0x200, 3)));
+        assert_eq!(rdh_stats.last_rdh(), Some(rdh(0x500, 5)));
+
+        // E.g. stats of an earlier input file processed independently
+        let mut other = RdhStats::default();
+        other.record_rdh_positions(rdh(0, 1), rdh(0x100, 2));
+        rdh_stats.merge(&other);
+        assert_eq!(rdh_stats.first_rdh(), Some(rdh(0, 1)));
+        assert_eq!(rdh_stats.last_rdh(), Some(rdh(0x500, 5)));
+    }
+
+    #[test]
+    fn test_record_link_payload_stats() {
+        let link = |link_id, rdh_count, payload_size| LinkPayloadStats {
+            link_id,
+            rdh_count,
+            payload_size,
+        };
+        let mut rdh_stats = RdhStats::default();
+        rdh_stats.record_link_payload_stats(&[link(5, 2, 100), link(1, 1, 50)]);
+        rdh_stats.record_link_payload_stats(&[link(5, 1, 10)]);
+
+        let mut other = RdhStats::default();
+        other.record_link_payload_stats(&[link(3, 4, 400), link(1, 1, 20)]);
+        rdh_stats.merge(&other);
+
+        assert_eq!(
+            rdh_stats.link_payload_stats(),
+            &[link(1, 2, 70), link(3, 4, 400), link(5, 3, 110)]
+        );
+    }
+
+    #[test]
+    fn test_record_trigger_type_counts() {
+        let mut rdh_stats = RdhStats::default();
+        // SOC + HB, HB, PhT, HB
+        [0x202, 0x2, 0x10, 0x2]
+            .into_iter()
+            .for_each(|trigger_type| rdh_stats.record_trigger_type(trigger_type));
+
+        let mut other = RdhStats::default();
+        other.record_trigger_type(0x2);
+        other.record_trigger_type(0x0);
+        rdh_stats.merge(&other);
+
+        assert_eq!(
+            rdh_stats.trigger_type_counts(),
+            &BTreeMap::from([
+                ("HB".to_string(), 3),
+                ("Other".to_string(), 1),
+                ("PhT".to_string(), 1),
+                ("SOC".to_string(), 1),
+            ])
+        );
+    }
 }
@@ -175,6 +175,30 @@ impl TriggerStats {
         self.tof
     }
 
+    /// Adds the trigger counts of another [TriggerStats] to this one
+    pub(super) fn merge(&mut self, other: &Self) {
+        self.orbit += other.orbit;
+        self.hb += other.hb;
+        self.hbr += other.hbr;
+        self.hc += other.hc;
+        self.pht += other.pht;
+        self.pp += other.pp;
+        self.cal += other.cal;
+        self.sot += other.sot;
+        self.eot += other.eot;
+        self.soc += other.soc;
+        self.eoc += other.eoc;
+        self.tf += other.tf;
+        self.fe_rst += other.fe_rst;
+        self.rt += other.rt;
+        self.rs += other.rs;
+        self.lhc_gap1 += other.lhc_gap1;
+        self.lhc_gap2 += other.lhc_gap2;
+        self.tpc_sync += other.tpc_sync;
+        self.tpc_rst += other.tpc_rst;
+        self.tof += other.tof;
+    }
+
     pub(super) fn validate_other(&self, other: &Self) -> Result<(), Vec<String>> {
         // Do this to ensure that adding a new field to the struct doesn't break the validation
         // If a new field is added, this will fail to compile, before explicitly adding the new field to this instantiation
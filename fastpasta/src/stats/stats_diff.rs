@@ -0,0 +1,178 @@
+//! Contains [diff_stats] that compares two [StatsCollector]s field by field, used by the `diff` subcommand.
+//!
+//! The stats are compared in their serialized form, so the fields are named as in the [STATS_SCHEMA](super::stats_schema::STATS_SCHEMA), e.g. `rdh_stats.rdhs_seen`.
+//! Objects and maps are compared field by field, arrays and other values are compared as a whole.
+//! The fields in [ALWAYS_IGNORED_FIELDS] vary between runs on the same data, and are never compared.
+
+use crate::util::*;
+
+/// Fields that vary between runs on the same data, they are ignored like in the equality of [StatsCollector]
+pub const ALWAYS_IGNORED_FIELDS: [&str; 3] =
+    ["schema_version", "processing_time", "throughput_mb_s"];
+
+/// A field that differs between two stats, a field missing from one of them is [null](serde_json::Value::Null)
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDiff {
+    /// Name of the field, nested fields are separated by `.`
+    pub field: String,
+    /// Value in the old stats
+    pub old: serde_json::Value,
+    /// Value in the new stats
+    pub new: serde_json::Value,
+}
+
+impl fmt::Display for FieldDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.old, &self.new) {
+            // List the elements that are only in one of the arrays, e.g. the links or error messages
+            (serde_json::Value::Array(old), serde_json::Value::Array(new)) => {
+                write!(
+                    f,
+                    "{field}: {old_len} -> {new_len} elements",
+                    field = self.field,
+                    old_len = old.len(),
+                    new_len = new.len()
+                )?;
+                for removed in old.iter().filter(|elem| !new.contains(elem)) {
+                    write!(f, "\n  - {removed}")?;
+                }
+                for added in new.iter().filter(|elem| !old.contains(elem)) {
+                    write!(f, "\n  + {added}")?;
+                }
+                Ok(())
+            }
+            (old, new) => write!(f, "{field}: {old} -> {new}", field = self.field),
+        }
+    }
+}
+
+/// Compares two [StatsCollector]s and returns the fields that differ, excluding the `ignore_fields` and their nested fields.
+pub fn diff_stats(
+    old: &StatsCollector,
+    new: &StatsCollector,
+    ignore_fields: &[String],
+) -> Result<Vec<FieldDiff>, serde_json::Error> {
+    let mut diffs = Vec::new();
+    diff_values(
+        "",
+        &serde_json::to_value(old)?,
+        &serde_json::to_value(new)?,
+        ignore_fields,
+        &mut diffs,
+    );
+    Ok(diffs)
+}
+
+fn is_ignored(field: &str, ignore_fields: &[String]) -> bool {
+    let is_field_or_nested = |ignored: &str| {
+        field == ignored
+            || field
+                .strip_prefix(ignored)
+                .is_some_and(|nested| nested.starts_with('.'))
+    };
+    ALWAYS_IGNORED_FIELDS
+        .iter()
+        .any(|&ignored| is_field_or_nested(ignored))
+        || ignore_fields
+            .iter()
+            .any(|ignored| is_field_or_nested(ignored))
+}
+
+fn diff_values(
+    field: &str,
+    old: &serde_json::Value,
+    new: &serde_json::Value,
+    ignore_fields: &[String],
+    diffs: &mut Vec<FieldDiff>,
+) {
+    if is_ignored(field, ignore_fields) {
+        return;
+    }
+    match (old, new) {
+        (serde_json::Value::Object(old_fields), serde_json::Value::Object(new_fields)) => {
+            // The fields of both, the fields of the old stats followed by the fields only in the new stats
+            let names = old_fields.keys().chain(
+                new_fields
+                    .keys()
+                    .filter(|name| !old_fields.contains_key(*name)),
+            );
+            for name in names {
+                let nested_field = if field.is_empty() {
+                    name.to_string()
+                } else {
+                    format!("{field}.{name}")
+                };
+                diff_values(
+                    &nested_field,
+                    old_fields.get(name).unwrap_or(&serde_json::Value::Null),
+                    new_fields.get(name).unwrap_or(&serde_json::Value::Null),
+                    ignore_fields,
+                    diffs,
+                );
+            }
+        }
+        (old, new) if old != new => diffs.push(FieldDiff {
+            field: field.to_string(),
+            old: old.clone(),
+            new: new.clone(),
+        }),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn stats_with_errors(errors: &[&str]) -> StatsCollector {
+        let mut stats = StatsCollector::default();
+        stats.collect(StatType::RdhVersion(7));
+        stats.collect(StatType::LinksObserved(8));
+        for &error in errors {
+            stats.collect(StatType::Error(error.into()));
+        }
+        stats.finalize(false);
+        stats
+    }
+
+    #[test]
+    fn test_diff_identical_stats() {
+        let stats = stats_with_errors(&["0x0: [E10] error"]);
+        assert_eq!(diff_stats(&stats, &stats.clone(), &[]).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_diff_stats_fields() {
+        let old = stats_with_errors(&["0x0: [E10] error"]);
+        let mut new = stats_with_errors(&["0x0: [E10] error", "0x40: [E11] error"]);
+        new.collect(StatType::LinksObserved(3));
+
+        let diffs = diff_stats(&old, &new, &[]).unwrap();
+        let fields: Vec<&str> = diffs.iter().map(|diff| diff.field.as_str()).collect();
+        assert_eq!(
+            fields,
+            [
+                "error_stats.reported_errors",
+                "error_stats.total_errors",
+                "error_stats.unique_error_codes",
+                "rdh_stats.links",
+            ]
+        );
+        assert_eq!(
+            diffs[0].to_string(),
+            "error_stats.reported_errors: 1 -> 2 elements\n  + \"0x40: [E11] error\""
+        );
+        assert_eq!(diffs[1].to_string(), "error_stats.total_errors: 1 -> 2");
+        assert_eq!(
+            diffs[3].to_string(),
+            "rdh_stats.links: 1 -> 2 elements\n  + 3"
+        );
+
+        // Ignoring a field also ignores its nested fields
+        let ignore_fields = ["error_stats".to_string(), "rdh_stats.link".to_string()];
+        let not_ignored_diffs = diff_stats(&old, &new, &ignore_fields).unwrap();
+        assert_eq!(not_ignored_diffs.len(), 1);
+        assert_eq!(not_ignored_diffs[0].field, "rdh_stats.links");
+    }
+}
@@ -4,7 +4,10 @@ pub(super) mod stat_summerize_utils;
 mod table_formatter_utils;
 
 use self::{
-    stat_format_utils::{format_error_codes, format_fee_ids, format_links_observed},
+    stat_format_utils::{
+        format_cru_ids, format_data_size, format_error_codes, format_fee_ids,
+        format_links_observed, format_trigger_type_counts,
+    },
     stat_summerize_utils::{
         summerize_data_size, summerize_filtered_fee_ids, summerize_filtered_its_layer_staves,
         summerize_filtered_links, summerize_layers_staves_seen,
@@ -17,6 +20,8 @@ pub fn make_report(
     processing_time: Duration,
     stats: &mut StatsCollector,
     filter_target: Option<FilterTarget>,
+    filter_trigger_type: Option<u32>,
+    filter_orbit_range: Option<(u32, u32)>,
 ) -> Report {
     debug_assert!(stats.is_finalized);
 
@@ -29,8 +34,13 @@ pub fn make_report(
     // Add global stats
     add_global_stats_to_report(&mut report, stats);
 
-    if filter_target.is_some() {
-        let filtered_stats: Vec<StatSummary> = add_filtered_stats(stats, filter_target);
+    if filter_target.is_some() || filter_trigger_type.is_some() || filter_orbit_range.is_some() {
+        let filtered_stats: Vec<StatSummary> = add_filtered_stats(
+            stats,
+            filter_target,
+            filter_trigger_type,
+            filter_orbit_range,
+        );
         report.add_filter_stats(tabled::Table::new(filtered_stats));
     } else {
         // Check if the observed system ID is ITS
@@ -63,6 +73,26 @@ pub fn make_report(
     report
 }
 
+/// Formats the RDHs seen and payload size of each link as a small table.
+pub fn format_link_payload_stats(link_payload_stats: &[LinkPayloadStats]) -> String {
+    let mut table = format!(
+        "Payload per link ({} links)\n  {:>4}  {:>12}  {:>12}",
+        link_payload_stats.len(),
+        "Link",
+        "RDHs",
+        "Payload"
+    );
+    for link in link_payload_stats {
+        table.push_str(&format!(
+            "\n  {:>4}  {:>12}  {:>12}",
+            link.link_id,
+            link.rdh_count,
+            format_data_size(link.payload_size)
+        ));
+    }
+    table
+}
+
 /// Helper function that adds the global stats to the report
 fn add_global_stats_to_report(report: &mut Report, stats: &mut StatsCollector) {
     if stats.err_count() == 0 {
@@ -85,6 +115,14 @@ fn add_global_stats_to_report(report: &mut Report, stats: &mut StatsCollector) {
         value: format!("{trigger_type_raw:#02X}"),
         notes: trigger_type_str.into_string(),
     });
+    // Empty if the stats are from before trigger types were counted
+    if !stats.rdh_stats().trigger_type_counts().is_empty() {
+        report.add_stat(StatSummary::new(
+            "Triggers per type".to_string(),
+            format_trigger_type_counts(stats.rdh_stats().trigger_type_counts()),
+            None,
+        ));
+    }
     report.add_stat(StatSummary::new(
         "Total RDHs".to_string(),
         stats.rdh_stats().rdhs_seen().to_string(),
@@ -100,12 +138,32 @@ fn add_global_stats_to_report(report: &mut Report, stats: &mut StatsCollector) {
         format_fee_ids(stats.rdh_stats().fee_ids_as_slice()),
         None,
     ));
+    // Empty if the stats are from before CRU IDs were recorded
+    if !stats.rdh_stats().cru_ids_as_slice().is_empty() {
+        report.add_stat(StatSummary::new(
+            "CRU IDs seen".to_string(),
+            format_cru_ids(stats.rdh_stats().cru_ids_as_slice()),
+            Some(format!(
+                "DW: {}",
+                format_cru_ids(stats.rdh_stats().dws_as_slice())
+            )),
+        ));
+    }
+    if stats.payload_words_seen() > 0 {
+        report.add_stat(StatSummary::new(
+            "Payload words".to_string(),
+            stats.payload_words_seen().to_string(),
+            None,
+        ));
+    }
 }
 
 /// Helper function that builds a vector of the stats associated with the filtered data
 fn add_filtered_stats(
     stats: &StatsCollector,
     filter_target: Option<FilterTarget>,
+    filter_trigger_type: Option<u32>,
+    filter_orbit_range: Option<(u32, u32)>,
 ) -> Vec<StatSummary> {
     let mut filtered_stats: Vec<StatSummary> = Vec::new();
     filtered_stats.push(StatSummary::new(
@@ -125,22 +183,40 @@ fn add_filtered_stats(
         stats.rdh_stats().payload_size(),
     ));
 
-    if let Some(filter_target) = filter_target {
+    if let Some(filter_target) = &filter_target {
         let filtered_target = match filter_target {
-            FilterTarget::Link(link_id) => {
-                summerize_filtered_links(link_id, stats.rdh_stats().links_as_slice())
+            FilterTarget::Links(links_mask) => {
+                summerize_filtered_links(*links_mask, stats.rdh_stats().links_as_slice())
             }
-            FilterTarget::Fee(fee_id) => {
-                summerize_filtered_fee_ids(fee_id, stats.rdh_stats().fee_ids_as_slice())
+            FilterTarget::Fee(fee_id_ranges) => {
+                summerize_filtered_fee_ids(fee_id_ranges, stats.rdh_stats().fee_ids_as_slice())
             }
             FilterTarget::ItsLayerStave(fee_id_no_link) => summerize_filtered_its_layer_staves(
-                fee_id_no_link,
+                *fee_id_no_link,
                 stats.rdh_stats().layer_staves_as_slice(),
             ),
         };
         filtered_stats.push(filtered_target);
     }
 
+    if let Some(trigger_type_mask) = filter_trigger_type {
+        filtered_stats.push(StatSummary::new(
+            "Trigger type".to_string(),
+            crate::analyze::view::lib::trigger_type_string_from_int(trigger_type_mask)
+                .trim_end()
+                .to_string(),
+            None,
+        ));
+    }
+
+    if let Some((start, end)) = filter_orbit_range {
+        filtered_stats.push(StatSummary::new(
+            "Orbits".to_string(),
+            format!("{start}-{end}"),
+            None,
+        ));
+    }
+
     if filter_target.is_some_and(|target| !matches!(target, FilterTarget::ItsLayerStave(_))) {
         // Check if the observed system ID is ITS
         if matches!(stats.rdh_stats().system_id(), Some(SystemId::ITS)) {
@@ -207,6 +283,20 @@ fn add_alpide_stats_to_report(report: &mut Report, alpide_stats: &AlpideStats) {
         None,
     ));
 
+    let lane_occupancy = alpide_stats.lane_occupancy();
+    if lane_occupancy.frames_seen() > 0 {
+        alpide_stat.push(StatSummary::new(
+            "Lane Occupancy".to_string(),
+            format!(
+                "min {:.2} avg {:.2} max {:.2}",
+                lane_occupancy.min(),
+                lane_occupancy.avg(),
+                lane_occupancy.max()
+            ),
+            Some(format!("{} frames", lane_occupancy.frames_seen())),
+        ));
+    }
+
     report.add_alpide_stats(tabled::Table::new(alpide_stat));
 }
 
@@ -73,6 +73,17 @@ pub(crate) fn format_fee_ids(fee_ids_seen: &[u16]) -> String {
     format_nums_max_lines_width(MAX_LINE_WIDTH as u16, Some(5), &fee_ids_seen)
 }
 
+/// Sort and format the CRU IDs or DWs seen
+pub(crate) fn format_cru_ids<T: Ord + Copy + ToString>(cru_ids_seen: &[T]) -> String {
+    let mut cru_ids_seen = cru_ids_seen.to_owned();
+    cru_ids_seen.sort_unstable();
+    cru_ids_seen
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
 pub(crate) fn format_error_codes(error_codes: &[String]) -> String {
     error_codes
         .iter()
@@ -87,6 +98,17 @@ pub(crate) fn format_error_codes(error_codes: &[String]) -> String {
         .collect()
 }
 
+/// Format the number of RDHs seen per trigger type, e.g. `HB: 10, PhT: 42`
+pub(crate) fn format_trigger_type_counts(
+    trigger_type_counts: &std::collections::BTreeMap<String, u64>,
+) -> String {
+    trigger_type_counts
+        .iter()
+        .map(|(trigger_type, count)| format!("{trigger_type}: {count}"))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
 /// Generic function to format a list of numbers into a string with a max width and optional max lines.
 pub fn format_nums_max_lines_width(max_width: u16, max_lines: Option<u16>, nums: &[u16]) -> String {
     let mut result = String::new();
@@ -3,27 +3,58 @@ use super::stat_format_utils::format_layers_and_staves;
 use crate::util::*;
 
 /// Helper functions to format the summary of filtered link ID
-pub(crate) fn summerize_filtered_links(link_to_filter: u8, links_observed: &[u8]) -> StatSummary {
+pub(crate) fn summerize_filtered_links(links_mask: u16, links_observed: &[u8]) -> StatSummary {
     let mut filtered_links_stat = StatSummary::new("Link ID".to_string(), "".to_string(), None);
+    let (found, not_found): (Vec<u8>, Vec<u8>) = FilterTarget::link_ids_from_mask(links_mask)
+        .partition(|link_id| links_observed.contains(link_id));
     // Format links that were filtered, separated by commas
-    if links_observed.contains(&link_to_filter) {
-        filtered_links_stat.value = link_to_filter.to_string();
-    } else {
+    if found.is_empty() {
         filtered_links_stat.value = "none".red().to_string();
-        filtered_links_stat.notes = format!("not found: {link_to_filter}").red().to_string();
+    } else {
+        filtered_links_stat.value = found.iter().join(", ");
+    }
+    if !not_found.is_empty() {
+        filtered_links_stat.notes = format!("not found: {}", not_found.iter().join(", "))
+            .red()
+            .to_string();
     }
     filtered_links_stat
 }
 
-/// Helper functions to format the summary of filtered FEE ID
-pub(crate) fn summerize_filtered_fee_ids(fee_id: u16, fee_ids_seen: &[u16]) -> StatSummary {
+/// Helper functions to format the summary of filtered FEE IDs
+pub(crate) fn summerize_filtered_fee_ids(
+    fee_id_ranges: &[(u16, u16)],
+    fee_ids_seen: &[u16],
+) -> StatSummary {
     let mut filtered_feeid_stat = StatSummary::new("FEE ID".to_string(), "".to_string(), None);
+    let found: Vec<u16> = fee_ids_seen
+        .iter()
+        .copied()
+        .filter(|&fee_id| FilterTarget::fee_id_in_ranges(fee_id, fee_id_ranges))
+        .sorted_unstable()
+        .collect();
+    // FEE IDs and ranges that none of the FEE IDs seen are in
+    let not_found: Vec<String> = fee_id_ranges
+        .iter()
+        .filter(|(start, end)| !fee_ids_seen.iter().any(|id| (start..=end).contains(&id)))
+        .map(|&(start, end)| {
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{start}-{end}")
+            }
+        })
+        .collect();
 
-    if fee_ids_seen.contains(&fee_id) {
-        filtered_feeid_stat.value = fee_id.to_string();
-    } else {
+    if found.is_empty() {
         filtered_feeid_stat.value = "none".red().to_string();
-        filtered_feeid_stat.notes = format!("not found: {fee_id}").red().to_string();
+    } else {
+        filtered_feeid_stat.value = found.iter().join(", ");
+    }
+    if !not_found.is_empty() {
+        filtered_feeid_stat.notes = format!("not found: {}", not_found.join(", "))
+            .red()
+            .to_string();
     }
     filtered_feeid_stat
 }
@@ -0,0 +1,205 @@
+//! Contains the [STATS_SCHEMA] that documents the fields and types of the serialized stats, printed with the `print-stats-schema` subcommand.
+//!
+//! The stats written with `--output-stats` as JSON or TOML follow this layout, and include the [STATS_SCHEMA_VERSION] as `schema_version`.
+//! Nested fields are separated by `.`, and `[]` denotes the elements of an array.
+//! Fields of type `T | null` are optional, in TOML they are left out instead of being `null`.
+
+use super::stats_collector::STATS_SCHEMA_VERSION;
+
+/// The fields of the serialized stats and their types.
+pub const STATS_SCHEMA: &[(&str, &str)] = &[
+    ("schema_version", "u32"),
+    ("is_finalized", "bool"),
+    ("rdh_stats", "object"),
+    ("rdh_stats.rdhs_seen", "u64"),
+    ("rdh_stats.rdhs_filtered", "u64"),
+    ("rdh_stats.rdh_version", "u8 | null"),
+    ("rdh_stats.hbfs_seen", "u32"),
+    ("rdh_stats.payload_size", "u64"),
+    ("rdh_stats.data_format", "u8 | null"),
+    ("rdh_stats.links", "[u8]"),
+    ("rdh_stats.fee_id", "[u16]"),
+    ("rdh_stats.cru_ids_observed", "[u16]"),
+    ("rdh_stats.dws_observed", "[u8]"),
+    ("rdh_stats.system_id", "string | null"),
+    ("rdh_stats.run_trigger_type", "[u32, string] | null"),
+    ("rdh_stats.its_stats", "object"),
+    ("rdh_stats.its_stats.layer_staves_seen", "[[u8, u8]]"),
+    ("rdh_stats.trigger_stats", "object"),
+    ("rdh_stats.trigger_stats.orbit", "u32"),
+    ("rdh_stats.trigger_stats.hb", "u32"),
+    ("rdh_stats.trigger_stats.hbr", "u32"),
+    ("rdh_stats.trigger_stats.hc", "u32"),
+    ("rdh_stats.trigger_stats.pht", "u32"),
+    ("rdh_stats.trigger_stats.pp", "u32"),
+    ("rdh_stats.trigger_stats.cal", "u32"),
+    ("rdh_stats.trigger_stats.sot", "u32"),
+    ("rdh_stats.trigger_stats.eot", "u32"),
+    ("rdh_stats.trigger_stats.soc", "u32"),
+    ("rdh_stats.trigger_stats.eoc", "u32"),
+    ("rdh_stats.trigger_stats.tf", "u32"),
+    ("rdh_stats.trigger_stats.fe_rst", "u32"),
+    ("rdh_stats.trigger_stats.rt", "u32"),
+    ("rdh_stats.trigger_stats.rs", "u32"),
+    ("rdh_stats.trigger_stats.lhc_gap1", "u32"),
+    ("rdh_stats.trigger_stats.lhc_gap2", "u32"),
+    ("rdh_stats.trigger_stats.tpc_sync", "u32"),
+    ("rdh_stats.trigger_stats.tpc_rst", "u32"),
+    ("rdh_stats.trigger_stats.tof", "u32"),
+    ("rdh_stats.first_rdh", "object | null"),
+    ("rdh_stats.first_rdh.mem_pos", "u64"),
+    ("rdh_stats.first_rdh.orbit", "u32"),
+    ("rdh_stats.last_rdh", "object | null"),
+    ("rdh_stats.last_rdh.mem_pos", "u64"),
+    ("rdh_stats.last_rdh.orbit", "u32"),
+    ("rdh_stats.link_payload_stats", "[object]"),
+    ("rdh_stats.link_payload_stats[].link_id", "u8"),
+    ("rdh_stats.link_payload_stats[].rdh_count", "u64"),
+    ("rdh_stats.link_payload_stats[].payload_size", "u64"),
+    ("rdh_stats.trigger_type_counts", "{string: u64}"),
+    ("error_stats", "object"),
+    ("error_stats.fatal_error", "string | null"),
+    ("error_stats.reported_errors", "[string]"),
+    ("error_stats.custom_checks_stats_errors", "[string]"),
+    ("error_stats.total_errors", "u64"),
+    ("error_stats.unique_error_codes", "[string]"),
+    ("error_stats.error_code_counts", "{string: u64}"),
+    ("error_stats.staves_with_errors", "[[u8, u8]] | null"),
+    ("alpide_stats", "object | null"),
+    ("alpide_stats.readout_flags", "object"),
+    ("alpide_stats.readout_flags.chip_trailers_seen", "u32"),
+    ("alpide_stats.readout_flags.busy_violations", "u32"),
+    ("alpide_stats.readout_flags.data_overrun", "u32"),
+    ("alpide_stats.readout_flags.transmission_in_fatal", "u32"),
+    ("alpide_stats.readout_flags.flushed_incomplete", "u32"),
+    ("alpide_stats.readout_flags.strobe_extended", "u32"),
+    ("alpide_stats.readout_flags.busy_transitions", "u32"),
+    ("alpide_stats.lane_occupancy", "object"),
+    ("alpide_stats.lane_occupancy.frames_seen", "u32"),
+    ("alpide_stats.lane_occupancy.occupancy_sum", "f64"),
+    ("alpide_stats.lane_occupancy.min_occupancy", "f64"),
+    ("alpide_stats.lane_occupancy.max_occupancy", "f64"),
+    ("alpide_lane_errors", "[object] | null"),
+    ("alpide_lane_errors[].fee_id", "u16"),
+    ("alpide_lane_errors[].lane", "u8"),
+    ("alpide_lane_errors[].error_counts", "{string: u64}"),
+    ("payload_words_seen", "u64"),
+    ("processing_time", "object"),
+    ("processing_time.secs", "u64"),
+    ("processing_time.nanos", "u32"),
+    ("throughput_mb_s", "f64"),
+];
+
+/// Returns the schema version and the fields of the serialized stats with their types, one field per line.
+pub fn stats_schema_text() -> String {
+    let name_width = STATS_SCHEMA
+        .iter()
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or_default();
+    let mut schema = format!("schema_version {STATS_SCHEMA_VERSION}");
+    for (name, field_type) in STATS_SCHEMA {
+        schema.push_str(&format!("\n{name:<name_width$}  {field_type}"));
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::stats_collector::rdh_stats::{LinkPayloadStats, RdhPosition};
+    use crate::util::*;
+
+    /// Collects the names of all fields in a serialized value, in the notation of the [STATS_SCHEMA]
+    fn collect_field_names(prefix: &str, value: &serde_json::Value, names: &mut Vec<String>) {
+        match value {
+            serde_json::Value::Object(fields) => {
+                for (name, field) in fields {
+                    let name = if prefix.is_empty() {
+                        name.to_string()
+                    } else {
+                        format!("{prefix}.{name}")
+                    };
+                    names.push(name.clone());
+                    // The keys of maps are not fields
+                    if name != "error_stats.error_code_counts"
+                        && name != "rdh_stats.trigger_type_counts"
+                        && name != "alpide_lane_errors[].error_counts"
+                    {
+                        collect_field_names(&name, field, names);
+                    }
+                }
+            }
+            serde_json::Value::Array(elements) => {
+                if let Some(element @ serde_json::Value::Object(_)) = elements.first() {
+                    collect_field_names(&format!("{prefix}[]"), element, names);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    #[test]
+    fn test_schema_matches_serialized_stats() {
+        let mut stats_collector = StatsCollector::with_alpide_stats();
+        stats_collector.collect(StatType::Error("0x0: [E10] error".into()));
+        stats_collector.collect(StatType::RunTriggerType((0, "trigger type".into())));
+        stats_collector.collect(StatType::SystemId(SystemId::ITS));
+        stats_collector.collect(StatType::RdhVersion(7));
+        stats_collector.collect(StatType::TriggerType(0x2));
+        stats_collector.collect(StatType::DataFormat(2));
+        stats_collector.collect(StatType::LayerStaveSeen {
+            layer: 0,
+            stave: 12,
+        });
+        stats_collector.collect(StatType::RdhPositions {
+            first: RdhPosition {
+                mem_pos: 0,
+                orbit: 1,
+            },
+            last: RdhPosition {
+                mem_pos: 0x40,
+                orbit: 2,
+            },
+        });
+        stats_collector.collect(StatType::LinkPayloadStats(Box::new([LinkPayloadStats {
+            link_id: 8,
+            rdh_count: 2,
+            payload_size: 0,
+        }])));
+        stats_collector.collect(StatType::AlpideLaneErrors {
+            fee_id: 524,
+            lane_errors: Box::new([(
+                8,
+                AlpideLaneError::ChipIdMismatch {
+                    expected: 8,
+                    found: 9,
+                },
+            )]),
+        });
+        stats_collector.finalize(false);
+
+        let mut serialized_names = Vec::new();
+        collect_field_names(
+            "",
+            &serde_json::to_value(stats_collector).unwrap(),
+            &mut serialized_names,
+        );
+        serialized_names.sort();
+        let schema_names: Vec<String> = STATS_SCHEMA
+            .iter()
+            .map(|(name, _)| name.to_string())
+            .sorted()
+            .collect();
+
+        // Remember to bump the STATS_SCHEMA_VERSION when the schema changes!
+        assert_eq!(serialized_names, schema_names);
+    }
+
+    #[test]
+    fn test_stats_schema_text() {
+        let schema = stats_schema_text();
+        assert!(schema.starts_with(&format!("schema_version {STATS_SCHEMA_VERSION}\n")));
+        assert_eq!(schema.lines().count(), STATS_SCHEMA.len() + 1);
+    }
+}
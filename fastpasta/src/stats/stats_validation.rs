@@ -30,6 +30,67 @@ pub fn validate_custom_stats(
         }
     }
 
+    if let Some(expect_hbfs) = custom_checks.hbfs() {
+        if rdh_stats.hbfs_seen() != expect_hbfs {
+            errors.push(
+                format!(
+                    "[E9006] Expected {expect_hbfs} HBFs, but found {observed_hbfs}",
+                    observed_hbfs = rdh_stats.hbfs_seen()
+                )
+                .into(),
+            );
+        }
+    }
+
+    if let Some(expect_links) = custom_checks.expected_links() {
+        let observed_links = rdh_stats.links_as_slice();
+        let missing_links = expect_links
+            .iter()
+            .filter(|link| !observed_links.contains(link))
+            .sorted()
+            .collect_vec();
+        let unexpected_links = observed_links
+            .iter()
+            .filter(|link| !expect_links.contains(link))
+            .sorted()
+            .collect_vec();
+        if !missing_links.is_empty() || !unexpected_links.is_empty() {
+            errors.push(
+                format!(
+                    "[E9007] Observed links do not match the expected links {expect_links:?}, missing: {missing_links:?}, unexpected: {unexpected_links:?}"
+                )
+                .into(),
+            );
+        }
+    }
+
+    if let Some(expect_layer_stave_counts) = custom_checks.layer_stave_counts() {
+        let observed_layer_staves = rdh_stats.layer_staves_as_slice();
+        let short_layers = expect_layer_stave_counts
+            .iter()
+            .sorted()
+            .filter_map(|&(layer, expect_staves)| {
+                let observed_staves = observed_layer_staves
+                    .iter()
+                    .filter(|(observed_layer, _)| *observed_layer == layer)
+                    .map(|(_, stave)| stave)
+                    .unique()
+                    .count();
+                (observed_staves < usize::from(expect_staves))
+                    .then(|| format!("L{layer}: {observed_staves}/{expect_staves}"))
+            })
+            .collect_vec();
+        if !short_layers.is_empty() {
+            errors.push(
+                format!(
+                    "[E9008] Fewer staves observed than expected in layers (observed/expected): {}",
+                    short_layers.join(", ")
+                )
+                .into(),
+            );
+        }
+    }
+
     if errors.is_empty() {
         Ok(())
     } else {
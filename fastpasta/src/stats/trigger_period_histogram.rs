@@ -0,0 +1,82 @@
+//! Contains the [TriggerPeriodHistogram] that tallies the trigger periods detected between consecutive TDHs with internal trigger set.
+//!
+//! The periods are calculated in the same way as the ITS trigger period check, accounting for the trigger_bc wrapping around.
+//! Useful for discovering the trigger period of the data before checking it with `--its-trigger-period`.
+
+use crate::util::*;
+use std::collections::BTreeMap;
+
+/// Tallies the number of times each trigger period (in bunch crossings) is detected.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct TriggerPeriodHistogram {
+    counts: BTreeMap<u16, u64>,
+}
+
+impl TriggerPeriodHistogram {
+    /// Count a detected trigger period.
+    pub fn add(&mut self, period: u16) {
+        *self.counts.entry(period).or_default() += 1;
+    }
+
+    /// Add the counts of another histogram to this one, e.g. to combine the histograms of several links.
+    pub fn merge(&mut self, other: &Self) {
+        other
+            .counts
+            .iter()
+            .for_each(|(&period, &count)| *self.counts.entry(period).or_default() += count);
+    }
+
+    /// Total number of trigger periods counted.
+    pub fn total(&self) -> u64 {
+        self.counts.values().sum()
+    }
+
+    /// Returns all detected trigger periods and their count, sorted by period in ascending order.
+    pub fn counts(&self) -> Vec<(u16, u64)> {
+        self.counts
+            .iter()
+            .map(|(&period, &count)| (period, count))
+            .collect()
+    }
+}
+
+impl fmt::Display for TriggerPeriodHistogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.total();
+        writeln!(f, "TDH trigger period histogram ({total} periods)")?;
+        for (period, count) in self.counts() {
+            writeln!(
+                f,
+                "  {period:>5} BC: {count:>12} ({percent:>6.2}%)",
+                percent = count as f64 / total as f64 * 100.0
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_add_and_merge() {
+        let mut histogram = TriggerPeriodHistogram::default();
+        histogram.add(198);
+        histogram.add(198);
+        histogram.add(3);
+
+        let mut other = TriggerPeriodHistogram::default();
+        other.add(198);
+        other.add(3564);
+        histogram.merge(&other);
+
+        assert_eq!(histogram.total(), 5);
+        assert_eq!(histogram.counts(), vec![(3, 1), (198, 3), (3564, 1)]);
+        let histogram_str = histogram.to_string();
+        println!("{histogram_str}");
+        assert!(histogram_str.starts_with("TDH trigger period histogram (5 periods)"));
+        assert!(histogram_str.contains("    198 BC:            3 ( 60.00%)"));
+    }
+}
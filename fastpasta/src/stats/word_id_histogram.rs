@@ -0,0 +1,114 @@
+//! Contains the [WordIdHistogram] that tallies the frequency of each GBT word ID observed in the payloads.
+//!
+//! The word ID is the 10th byte of a GBT word. Counting them does not require any knowledge of the payload structure,
+//! which makes the histogram useful for discovering unexpected or undocumented word types, e.g. in malformed or non-ITS data.
+
+use crate::util::*;
+
+/// Tallies the number of times each word ID (10th byte of a GBT word) is observed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordIdHistogram {
+    counts: [u64; 256],
+}
+
+impl Default for WordIdHistogram {
+    fn default() -> Self {
+        Self { counts: [0; 256] }
+    }
+}
+
+impl WordIdHistogram {
+    /// Splits a payload into GBT words and counts the ID of each word.
+    ///
+    /// Payloads that fail preprocessing (e.g. excessive padding) are not counted.
+    pub fn count_payload(&mut self, payload: &[u8]) {
+        if let Ok(gbt_words) = preprocess_payload(payload) {
+            gbt_words.for_each(|gbt_word| self.counts[gbt_word[9] as usize] += 1);
+        }
+    }
+
+    /// Add the counts of another histogram to this one, e.g. to combine the histograms of input files processed independently.
+    pub fn merge(&mut self, other: &Self) {
+        self.counts
+            .iter_mut()
+            .zip(other.counts.iter())
+            .for_each(|(count, other_count)| *count += other_count);
+    }
+
+    /// Total number of GBT words counted.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// Returns all observed word IDs and their count, sorted by count in descending order.
+    ///
+    /// Word IDs with the same count are sorted by ID in ascending order.
+    pub fn sorted_counts(&self) -> Vec<(u8, u64)> {
+        let mut id_counts: Vec<(u8, u64)> = self
+            .counts
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count > 0)
+            .map(|(id, &count)| (id as u8, count))
+            .collect();
+        id_counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        id_counts
+    }
+}
+
+impl fmt::Display for WordIdHistogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let total = self.total();
+        writeln!(f, "Word ID histogram ({total} GBT words)")?;
+        for (id, count) in self.sorted_counts() {
+            writeln!(
+                f,
+                "  {id:#04X}: {count:>12} ({percent:>6.2}%)",
+                percent = count as f64 / total as f64 * 100.0
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_count_payload_flavor_0() {
+        let mut histogram = WordIdHistogram::default();
+        // IHW followed by a TDH
+        histogram.count_payload(&START_PAYLOAD_FLAVOR_0);
+        histogram.count_payload(&START_PAYLOAD_FLAVOR_0);
+
+        assert_eq!(histogram.total(), 4);
+        assert_eq!(histogram.sorted_counts(), vec![(Ihw::ID, 2), (Tdh::ID, 2)]);
+    }
+
+    #[test]
+    fn test_count_payload_flavor_2_sorted_by_frequency() {
+        let mut histogram = WordIdHistogram::default();
+        histogram.count_payload(&START_PAYLOAD_FLAVOR_2);
+        // Payload with 3 words of the same unknown ID
+        histogram.count_payload(&[0x3D; 30]);
+
+        assert_eq!(histogram.total(), 5);
+        assert_eq!(
+            histogram.sorted_counts(),
+            vec![(0x3D, 3), (Ihw::ID, 1), (Tdh::ID, 1)]
+        );
+        let histogram_str = histogram.to_string();
+        println!("{histogram_str}");
+        assert!(histogram_str.contains("0x3D:            3 ( 60.00%)"));
+    }
+
+    #[test]
+    fn test_count_payload_bad_padding_is_skipped() {
+        let mut histogram = WordIdHistogram::default();
+        histogram.count_payload(&[0xFF; 32]);
+        assert_eq!(histogram.total(), 0);
+        assert!(histogram.sorted_counts().is_empty());
+    }
+}
@@ -5,10 +5,12 @@ pub(crate) use {
     crate::{
         analyze::{
             validators::{
+                ft0::ft0_running::Ft0RunningValidator,
                 its::{
                     self,
                     alpide::{
-                        self, alpide_readout_frame::AlpideReadoutFrame,
+                        self, alpide_lane_error::AlpideLaneError,
+                        alpide_readout_frame::AlpideReadoutFrame,
                         lane_alpide_frame_analyzer::LaneAlpideFrameAnalyzer,
                     },
                     cdp_running::CdpRunningValidator,
@@ -19,7 +21,7 @@ pub(crate) use {
                     lib::ItsPayloadWord,
                     status_word::{util::StatusWordContainer, StatusWordSanityChecker},
                 },
-                lib::preprocess_payload,
+                lib::{check_payload_padding, data_format_0_ff_padding_len, preprocess_payload},
                 link_validator::LinkValidator,
                 rdh::RdhCruSanityValidator,
                 rdh_running::RdhCruRunningChecker,
@@ -34,14 +36,21 @@ pub(crate) use {
             custom_checks::{custom_checks_cfg::CustomChecks, CustomChecksOpt},
             inputoutput::{DataOutputFormat, DataOutputMode},
             prelude::*,
+            trigger_type_masks::{trigger_type_masks, TriggerTypeMasks, TriggerTypeMnemonic},
             Cfg,
         },
         stats::{
             self,
+            check_error::CheckError,
+            live_status::LiveStatus,
             stats_collector::{
-                its_stats::alpide_stats::AlpideStats, rdh_stats::RdhStats, StatsCollector,
+                its_stats::{alpide_lane_errors::AlpideLaneErrors, alpide_stats::AlpideStats},
+                rdh_stats::{LinkPayloadStats, RdhPosition, RdhStats},
+                StatsCollector,
             },
             stats_report::report::{Report, StatSummary},
+            trigger_period_histogram::TriggerPeriodHistogram,
+            word_id_histogram::WordIdHistogram,
             StatType, SystemId,
         },
         words::{
@@ -67,7 +76,7 @@ pub(crate) use {
         Args, Subcommand,
     },
     crossbeam_channel, flume,
-    indicatif::{ProgressBar, ProgressStyle},
+    indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle},
     itertools::Itertools,
     owo_colors::OwoColorize,
     regex::Regex,
@@ -76,11 +85,11 @@ pub(crate) use {
     sm::sm,
     std::{
         error, fmt, fs, hint,
-        io::{self, StdoutLock},
+        io::{self, IsTerminal, StdoutLock},
         marker::PhantomData,
         mem,
         ops::RangeInclusive,
-        path::{self, Path, PathBuf},
+        path::{Path, PathBuf},
         process::ExitCode,
         slice::ChunksExact,
         str::{Chars, FromStr},
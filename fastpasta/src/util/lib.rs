@@ -1,16 +1,60 @@
 //! Miscellaneous utility functions
 use crate::util::*;
+use std::sync::Mutex;
+
+/// Format of the timestamps of log messages, the same as [stderrlog] uses with millisecond granularity
+const LOG_TIMESTAMP_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.3f%:z";
 
 /// Start the [stderrlog] instance, and immediately use it to log the configured [DataOutputMode].
+///
+/// If a log file is set, the log messages are also written to it by a [TeeLogger].
 pub fn init_error_logger(cfg: &(impl UtilOpt + InputOutputOpt)) {
-    stderrlog::new()
+    // Colors are disabled if stderr is not a terminal
+    let color_choice = if cfg.no_color() || !io::stderr().is_terminal() {
+        stderrlog::ColorChoice::Never
+    } else {
+        stderrlog::ColorChoice::Auto
+    };
+    crate::set_colored_errors(!cfg.no_color() && io::stderr().is_terminal());
+    let mut stderr_log = stderrlog::new();
+    _ = stderr_log
         .module("fastpasta")
         .verbosity(cfg.verbosity() as usize)
-        .init()
-        .expect("Failed to initialize logger");
+        .color(color_choice);
+    if cfg.log_timestamps() {
+        _ = stderr_log.timestamp(stderrlog::Timestamp::Millisecond);
+    }
+    let log_file_result = cfg.log_file().map(|path| {
+        fs::File::options()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Failed to open log file {}: {e}", path.display()))
+    });
+    match log_file_result {
+        Some(Ok(log_file)) => {
+            log::set_max_level(verbosity_level_filter(cfg.verbosity()));
+            log::set_boxed_logger(Box::new(TeeLogger {
+                stderr_log,
+                log_file: Mutex::new(io::LineWriter::new(log_file)),
+            }))
+            .expect("Failed to initialize logger");
+        }
+        Some(Err(e)) => {
+            stderr_log.init().expect("Failed to initialize logger");
+            log::error!("{e}, logging to stderr only");
+        }
+        None => stderr_log.init().expect("Failed to initialize logger"),
+    }
     match cfg.output_mode() {
         DataOutputMode::Stdout => log::trace!("Data ouput set to stdout"),
         DataOutputMode::File(p) => log::trace!("Data ouput set to file: {:?}", p),
+        DataOutputMode::SplitByLink(p) => {
+            log::trace!("Data output set to one file per link: {:?}", p)
+        }
+        DataOutputMode::SplitByHbf(p) => {
+            log::trace!("Data output set to one file per HBF: {:?}", p)
+        }
         DataOutputMode::None => {
             log::trace!("Data output set to suppressed")
         }
@@ -20,6 +64,59 @@ pub fn init_error_logger(cfg: &(impl UtilOpt + InputOutputOpt)) {
     log::trace!("Views enabled: {:#?}", Cfg::global().view());
 }
 
+/// Returns the level filter of a verbosity level, in the same way as [stderrlog]
+fn verbosity_level_filter(verbosity: u8) -> log::LevelFilter {
+    match verbosity {
+        0 => log::LevelFilter::Error,
+        1 => log::LevelFilter::Warn,
+        2 => log::LevelFilter::Info,
+        3 => log::LevelFilter::Debug,
+        _ => log::LevelFilter::Trace,
+    }
+}
+
+/// Logs to stderr with [stderrlog], and writes the same log messages to a log file with a timestamp on each line.
+struct TeeLogger {
+    stderr_log: stderrlog::StdErrLog,
+    log_file: Mutex<io::LineWriter<fs::File>>,
+}
+
+impl log::Log for TeeLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        self.stderr_log.enabled(metadata)
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        self.stderr_log.log(record);
+        let line = format_log_file_line(
+            &chrono::Local::now()
+                .format(LOG_TIMESTAMP_FORMAT)
+                .to_string(),
+            record.level(),
+            record.args(),
+        );
+        // A failure to write the log file should not stop processing
+        if let Ok(mut log_file) = self.log_file.lock() {
+            _ = io::Write::write_all(&mut *log_file, line.as_bytes());
+        }
+    }
+
+    fn flush(&self) {
+        self.stderr_log.flush();
+        if let Ok(mut log_file) = self.log_file.lock() {
+            _ = io::Write::flush(&mut *log_file);
+        }
+    }
+}
+
+/// Formats a line of the log file like [stderrlog] formats a log message with a timestamp, e.g. `2024-05-23T10:42:01.123+02:00 - WARN message`
+fn format_log_file_line(timestamp: &str, level: log::Level, msg: &fmt::Arguments) -> String {
+    format!("{timestamp} - {level} {msg}\n")
+}
+
 /// Initializes the Ctrl+C handler to facilitate graceful shutdown on Ctrl+C
 ///
 /// Also handles SIGTERM and SIGHUP if the `termination` feature is enabled
@@ -43,12 +140,22 @@ pub fn init_ctrlc_handler(stop_flag: Arc<AtomicBool>) {
 }
 
 /// Exits the program with the appropriate exit code
-pub fn exit(exit_code: u8, any_errors_flag: &AtomicBool) -> ExitCode {
+///
+/// A failure to process the data takes precedence over the exit code of a detected error code, which takes precedence over the any errors exit code.
+pub fn exit(
+    exit_code: u8,
+    any_errors_flag: &AtomicBool,
+    error_code_exit_code: Option<u8>,
+) -> ExitCode {
     if exit_code == 0 {
         log::debug!("Exit successful from data processing");
-        if Cfg::global().any_errors_exit_code().is_some() && any_errors_flag.load(Ordering::Relaxed)
+        if let Some(error_code_exit_code) = error_code_exit_code {
+            ExitCode::from(error_code_exit_code)
+        } else if (Cfg::global().any_errors_exit_code().is_some() || Cfg::global().quiet_stats())
+            && any_errors_flag.load(Ordering::Relaxed)
         {
-            ExitCode::from(Cfg::global().any_errors_exit_code().unwrap())
+            // The verdict of `--quiet-stats` is also reflected in the exit code
+            ExitCode::from(Cfg::global().any_errors_exit_code().unwrap_or(1))
         } else {
             ExitCode::SUCCESS
         }
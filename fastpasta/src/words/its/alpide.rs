@@ -11,6 +11,10 @@ pub struct AlpideFrameChipData {
     pub(crate) chip_id: u8,
     /// Bunch counter for the frame \[10:3\]
     pub(crate) bunch_counter: Option<u8>,
+    /// Readout flags \[3:0\] from the Chip Trailer, not set for a Chip Empty Frame
+    pub(crate) readout_flags: Option<u8>,
+    /// Set if the chip was seen in a Chip Empty Frame instead of a Chip Header
+    pub(crate) empty_frame: bool,
     // Other data from the chip
     //pub(crate) data: Vec<u8>,
 }
@@ -21,6 +25,8 @@ impl AlpideFrameChipData {
         Self {
             chip_id,
             bunch_counter: None,
+            readout_flags: None,
+            empty_frame: false,
             //data: Vec::new(),
         }
     }
@@ -31,6 +37,8 @@ impl AlpideFrameChipData {
         Self {
             chip_id,
             bunch_counter: None,
+            readout_flags: None,
+            empty_frame: false,
             //data: Vec::with_capacity(0),
         }
     }
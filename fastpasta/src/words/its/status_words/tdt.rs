@@ -73,6 +73,12 @@ impl Tdt {
     pub fn lane_status_15_0(&self) -> u32 {
         self.lane_status_15_0
     }
+    /// Returns the integer value of the full 56 bit lane_status field, in the same layout as the lane_status of the [DDW0][super::ddw::Ddw0].
+    pub fn lane_status(&self) -> u64 {
+        (self.lane_status_27_24 as u64) << 48
+            | (self.lane_status_23_16 as u64) << 32
+            | self.lane_status_15_0 as u64
+    }
 }
 
 impl fmt::Display for Tdt {
@@ -180,6 +186,10 @@ mod tests {
             | ((LANE_4_TO_7_IN_FATAL as u32) << 8)
             | (LANE_0_AND_3_IN_WARNING as u32);
         assert_eq!(tdt.lane_status_15_0(), combined_lane_status_15_to_0);
+        assert_eq!(
+            tdt.lane_status(),
+            u64::from_le_bytes(raw_data_tdt[..8].try_into().unwrap()) & 0x00FF_FFFF_FFFF_FFFF
+        );
 
         let loaded_tdt = Tdt::load(&mut tdt.to_byte_slice()).unwrap();
         assert_eq!(tdt, loaded_tdt);
@@ -60,6 +60,39 @@ pub fn ddw0_tdt_lane_status_as_string(ddw0_tdt_slice: &[u8]) -> String {
     }
 }
 
+/// Takes a DDW0 or TDT slice and returns the 2-bit status of each of the 28 lanes, starting from lane 0
+///
+/// The status is 0 for OK, 1 for warning, 2 for error, and 3 for fatal.
+///
+/// # Examples
+///
+/// ```
+/// # use fastpasta::words::its::status_words::util::ddw0_tdt_lane_statuses;
+/// /// Example of a DDW0 with lane 0 in a Warning state and lane 5 in a Fatal state
+/// let ddw0_slice = [0x01, 0b1100, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xE4];
+/// let statuses: Vec<u8> = ddw0_tdt_lane_statuses(&ddw0_slice).collect();
+/// assert_eq!(statuses.len(), 28);
+/// assert_eq!(statuses[0], 1);
+/// assert_eq!(statuses[5], 3);
+/// assert!(statuses.iter().enumerate().all(|(lane, status)| lane == 0 || lane == 5 || *status == 0));
+/// ```
+pub fn ddw0_tdt_lane_statuses(ddw0_tdt_slice: &[u8]) -> impl Iterator<Item = u8> + '_ {
+    debug_assert!(ddw0_tdt_slice.len() == 10);
+    ddw0_tdt_slice[..7]
+        .iter()
+        .flat_map(|byte| (0..4).map(move |lane_in_byte| (byte >> (lane_in_byte * 2)) & 0b11))
+}
+
+/// Takes a 2-bit lane status and returns a single char description, `-` for OK, `W` for warning, `E` for error, and `F` for fatal
+pub fn lane_status_as_char(lane_status: u8) -> char {
+    match lane_status & 0b11 {
+        0 => '-',
+        1 => 'W',
+        2 => 'E',
+        _ => 'F',
+    }
+}
+
 /// Takes a full TDH slice and returns a string description of whether the no_data field is 1 or 0
 pub fn tdh_no_data_as_string(tdh_slice: &[u8]) -> String {
     if tdh_no_data(tdh_slice) {
@@ -1,4 +1,6 @@
 //! All functionality related to writing data to a file or stdout
 
+pub mod ascii_dump;
 pub mod lib;
+pub mod verify_output;
 pub mod writer;
@@ -0,0 +1,52 @@
+//! Contains the [ascii_dump] function that formats CDPs as a human-readable dump, used by the writer with `--dump-format ascii`.
+//!
+//! Each CDP is dumped as the memory position and decoded [RDH], like in `view rdh`, followed by one line per payload word in hex.
+//! The payload words are 16 bytes in data format 0 (10 bytes of data and 6 bytes of padding), and 10 bytes in the other data formats.
+
+use crate::util::*;
+use std::fmt::Write;
+
+/// Returns the header text of the [RDH] columns, written once before the first CDP of the dump.
+pub fn ascii_dump_header() -> String {
+    RdhCru::rdh_header_text_with_indent_to_string(11)
+}
+
+/// Formats the CDPs as the memory position and decoded [RDH] of each CDP, followed by its payload words in hex.
+pub fn ascii_dump<T: RDH, const CAP: usize>(cdp_array: &CdpArray<T, CAP>) -> String {
+    let mut dump = String::new();
+    for (rdh, payload, mem_pos) in cdp_array {
+        // Writing to a String cannot fail
+        _ = writeln!(dump, "{mem_pos:>8X}:  {rdh}");
+        let word_size = if rdh.data_format() == 0 { 16 } else { 10 };
+        for word in payload.chunks(word_size) {
+            _ = writeln!(
+                dump,
+                "{:>11}[{}]",
+                "",
+                word.iter().map(|byte| format!("{byte:02X}")).join(" ")
+            );
+        }
+    }
+    dump
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alice_protocol_reader::prelude::test_data::CORRECT_RDH_CRU_V7;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_ascii_dump() {
+        let mut cdp_array: CdpArray<RdhCru, 2> = CdpArray::new();
+        // Data format 2, 10 byte words
+        cdp_array.push(CORRECT_RDH_CRU_V7, (0..15).collect(), 0x40);
+
+        let dump = ascii_dump(&cdp_array);
+        let lines: Vec<&str> = dump.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], format!("      40:  {CORRECT_RDH_CRU_V7}"));
+        assert_eq!(lines[1], "           [00 01 02 03 04 05 06 07 08 09]");
+        assert_eq!(lines[2], "           [0A 0B 0C 0D 0E]");
+    }
+}
@@ -1,5 +1,6 @@
 //! Contains functionality for writing filtered data to disk or stdout.
 
+use super::ascii_dump::{ascii_dump, ascii_dump_header};
 use super::writer::BufferedWriter;
 use super::writer::Writer;
 use crate::util::*;
@@ -11,6 +12,8 @@ use crossbeam_channel::Receiver;
 const BUFFER_SIZE: usize = 1024 * 1024; // 1MB buffer
 
 /// Spawns a thread with the Writer running, and returns the thread handle.
+///
+/// With `--dump-format ascii` the CDPs are written as a human-readable [ascii_dump] instead of raw bytes.
 pub fn spawn_writer<T: RDH + 'static, const CAP: usize>(
     config: &'static impl InputOutputOpt,
     stop_flag: Arc<AtomicBool>,
@@ -20,22 +23,114 @@ pub fn spawn_writer<T: RDH + 'static, const CAP: usize>(
     writer_thread
         .spawn({
             let mut writer = BufferedWriter::<T>::new(config, BUFFER_SIZE);
-            move || loop {
-                // Receive batch from checker
-                let cdps = match data_recv.recv() {
-                    Ok(cdps) => cdps,
-                    Err(e) => {
-                        debug_assert_eq!(e, crossbeam_channel::RecvError);
+            let mut extract_rdhs = config.extract_rdhs().map(RdhIndexSelector::new);
+            let dump_format = config.dump_format();
+            move || {
+                if dump_format == DumpFormat::Ascii {
+                    writer
+                        .write(format!("{}\n", ascii_dump_header()).as_bytes())
+                        .expect("Failed to write ASCII dump");
+                }
+                loop {
+                    // Receive batch from checker
+                    let cdps = match data_recv.recv() {
+                        Ok(cdps) => cdps,
+                        Err(e) => {
+                            debug_assert_eq!(e, crossbeam_channel::RecvError);
+                            break;
+                        }
+                    };
+                    if stop_flag.load(Ordering::SeqCst) {
+                        log::trace!("Stopping writer thread");
                         break;
                     }
-                };
-                if stop_flag.load(Ordering::SeqCst) {
-                    log::trace!("Stopping writer thread");
-                    break;
+                    // Push data onto the writer's buffer, which will flush it when the buffer is full or when the writer is dropped
+                    let cdps = match extract_rdhs.as_mut() {
+                        Some(selector) => selector.select(cdps),
+                        None => cdps,
+                    };
+                    match dump_format {
+                        DumpFormat::Raw => writer.push_cdp_arr(cdps),
+                        // The dump is written directly, as the buffer of the writer holds raw CDPs
+                        DumpFormat::Ascii => writer
+                            .write(ascii_dump(&cdps).as_bytes())
+                            .expect("Failed to write ASCII dump"),
+                    }
+                }
+                if let Some(selector) = extract_rdhs {
+                    selector.warn_indices_out_of_range();
                 }
-                // Push data onto the writer's buffer, which will flush it when the buffer is full or when the writer is dropped
-                writer.push_cdp_arr(cdps);
             }
         })
         .expect("Failed to spawn writer thread")
 }
+
+/// Selects CDPs by the index of their RDH in the input stream.
+struct RdhIndexSelector {
+    /// Sorted and deduplicated indices to select
+    indices: Vec<u64>,
+    /// Index of the next RDH in the stream
+    next_rdh_index: u64,
+}
+
+impl RdhIndexSelector {
+    fn new(indices: &[u64]) -> Self {
+        let mut indices = indices.to_vec();
+        indices.sort_unstable();
+        indices.dedup();
+        Self {
+            indices,
+            next_rdh_index: 0,
+        }
+    }
+
+    /// Returns a [CdpArray] with only the CDPs at the selected indices, in the order they were received.
+    fn select<T: RDH, const CAP: usize>(&mut self, cdps: CdpArray<T, CAP>) -> CdpArray<T, CAP> {
+        let mut selected = CdpArray::new();
+        for (rdh, payload, mem_pos) in cdps {
+            if self.indices.binary_search(&self.next_rdh_index).is_ok() {
+                selected.push(rdh, payload, mem_pos);
+            }
+            self.next_rdh_index += 1;
+        }
+        selected
+    }
+
+    /// Warns about selected indices that are beyond the number of RDHs received.
+    fn warn_indices_out_of_range(&self) {
+        self.indices
+            .iter()
+            .filter(|&&idx| idx >= self.next_rdh_index)
+            .for_each(|idx| {
+                log::warn!(
+                    "RDH index {idx} is beyond the end of the input ({rdhs} RDHs)",
+                    rdhs = self.next_rdh_index
+                )
+            });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alice_protocol_reader::prelude::test_data::CORRECT_RDH_CRU_V7;
+
+    #[test]
+    fn test_rdh_index_selector_across_batches() {
+        let mut selector = RdhIndexSelector::new(&[4, 0, 2, 2]);
+        let mut batch = CdpArray::<RdhCru, 3>::new();
+        for mem_pos in [0, 0x40, 0x80] {
+            batch.push(CORRECT_RDH_CRU_V7, vec![], mem_pos);
+        }
+        let selected = selector.select(batch);
+        assert_eq!(selected.rdh_mem_pos_slice(), &[0, 0x80]);
+
+        let mut next_batch = CdpArray::<RdhCru, 3>::new();
+        for mem_pos in [0xC0, 0x100, 0x140] {
+            next_batch.push(CORRECT_RDH_CRU_V7, vec![], mem_pos);
+        }
+        let next_selected = selector.select(next_batch);
+        assert_eq!(next_selected.rdh_mem_pos_slice(), &[0x100]);
+        assert_eq!(selector.next_rdh_index, 6);
+    }
+}
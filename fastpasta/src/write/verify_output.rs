@@ -0,0 +1,80 @@
+//! Contains the [verify_output] function that reads back a written output file and checks that it contains all the filtered RDHs.
+//!
+//! A truncated or otherwise incomplete write shows up as fewer RDHs read back than were filtered.
+
+use crate::util::*;
+use alice_protocol_reader::init_reader;
+
+/// Reads back the output file with a fresh [InputScanner] and checks that it contains `rdhs_filtered` RDHs.
+///
+/// Returns an error message describing the mismatch, or the error from reading the file.
+pub fn verify_output<T: RDH>(path: &Path, rdhs_filtered: u64) -> Result<(), String> {
+    let rdhs_read_back = count_rdhs::<T>(path).map_err(|e| {
+        format!(
+            "Output verification failed: Reading back {} failed: {e}",
+            path.display()
+        )
+    })?;
+    if rdhs_read_back != rdhs_filtered {
+        return Err(format!(
+            "Output verification failed: {rdhs_read_back} RDHs read back from {}, but {rdhs_filtered} RDHs were filtered",
+            path.display()
+        ));
+    }
+    Ok(())
+}
+
+/// Counts the CDPs in a file until EOF, a CDP cut short by EOF is not counted.
+fn count_rdhs<T: RDH>(path: &Path) -> io::Result<u64> {
+    let mut scanner = InputScanner::minimal(init_reader(&[path.to_path_buf()])?);
+    let mut rdhs: u64 = 0;
+    loop {
+        match scanner.load_cdp::<T>() {
+            Ok((rdh, payload, _)) if payload.len() == usize::from(rdh.payload_size()) => rdhs += 1,
+            // The payload is cut short by EOF
+            Ok(_) => return Ok(rdhs),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(rdhs),
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alice_protocol_reader::prelude::test_data::CORRECT_RDH_CRU_V7;
+    use temp_dir::TempDir;
+
+    fn cdps_data(cdps: usize) -> Vec<u8> {
+        let mut data = Vec::new();
+        for _ in 0..cdps {
+            data.extend(CORRECT_RDH_CRU_V7.to_byte_slice());
+            data.resize(data.len() + CORRECT_RDH_CRU_V7.payload_size() as usize, 0);
+        }
+        data
+    }
+
+    #[test]
+    fn test_verify_output_complete() {
+        let tmp_d = TempDir::new().unwrap();
+        let path = tmp_d.child("out.raw");
+        fs::write(&path, cdps_data(3)).unwrap();
+        assert_eq!(verify_output::<RdhCru>(&path, 3), Ok(()));
+    }
+
+    #[test]
+    fn test_verify_output_truncated() {
+        let tmp_d = TempDir::new().unwrap();
+        let path = tmp_d.child("out.raw");
+        let data = cdps_data(3);
+        // Cut the last payload short
+        fs::write(&path, &data[..data.len() - 10]).unwrap();
+        assert_eq!(
+            verify_output::<RdhCru>(&path, 3).unwrap_err(),
+            format!(
+                "Output verification failed: 2 RDHs read back from {}, but 3 RDHs were filtered",
+                path.display()
+            )
+        );
+    }
+}
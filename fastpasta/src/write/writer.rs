@@ -5,6 +5,8 @@
 //! Implements drop to flush the remaining data to the file once processing is done.
 
 use crate::util::*;
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
 
 /// Trait for a writer that can write ALICE readout data to file/stdout.
 pub trait Writer<T: RDH> {
@@ -27,6 +29,8 @@ pub struct BufferedWriter<T: RDH> {
     filtered_rdhs_buffer: Vec<T>,
     filtered_payload_buffers: Vec<Vec<u8>>, // 1 Linked list per payload
     buf_writer: Option<io::BufWriter<fs::File>>, // If no file is specified -> write to stdout
+    link_writers: Option<LinkWriters>, // If the output is split by link -> write to a file per link
+    hbf_writer: Option<HbfWriter>,     // If the output is split by HBF -> write to a file per HBF
     max_buffer_size: usize,
 }
 
@@ -34,25 +38,28 @@ impl<T: RDH> BufferedWriter<T> {
     /// Create a new BufferedWriter from a config and a max buffer size.
     pub fn new(config: &impl InputOutputOpt, max_buffer_size: usize) -> Self {
         // Create output file, and buf writer if specified
-        let buf_writer = match config.output() {
-            Some(path) if "stdout".eq(path.to_str().unwrap()) => None,
-            Some(path) => {
-                let path: path::PathBuf = path.to_owned();
+        let (buf_writer, link_writers, hbf_writer) = match config.output_mode() {
+            DataOutputMode::File(path) => {
                 // Likely better to use File::create_new() but it's not stable yet
                 let mut _f = fs::File::create(&path).expect("Failed to create output file");
                 let file = fs::File::options()
                     .append(true)
                     .open(path)
                     .expect("Failed to open/create output file");
-                let buf_writer = io::BufWriter::new(file);
-                Some(buf_writer)
+                (Some(io::BufWriter::new(file)), None, None)
             }
-            None => None,
+            // The files are created as data from each link is written
+            DataOutputMode::SplitByLink(path) => (None, Some(LinkWriters::new(path.into())), None),
+            // The files are created as data from each HBF is written
+            DataOutputMode::SplitByHbf(path) => (None, None, Some(HbfWriter::new(path.into()))),
+            DataOutputMode::Stdout | DataOutputMode::None => (None, None, None),
         };
         BufferedWriter {
             filtered_rdhs_buffer: Vec::with_capacity(max_buffer_size), // Will most likely not be filled as payloads are usually larger, but hard to say
             filtered_payload_buffers: Vec::with_capacity(max_buffer_size),
             buf_writer,
+            link_writers,
+            hbf_writer,
             max_buffer_size,
         }
     }
@@ -61,6 +68,16 @@ impl<T: RDH> BufferedWriter<T> {
 impl<T: RDH> Writer<T> for BufferedWriter<T> {
     #[inline]
     fn write(&mut self, data: &[u8]) -> io::Result<()> {
+        if self.link_writers.is_some() {
+            return Err(io::Error::other(
+                "Cannot write data without a link ID when the output is split by link",
+            ));
+        }
+        if self.hbf_writer.is_some() {
+            return Err(io::Error::other(
+                "Cannot write data without an RDH when the output is split by HBF",
+            ));
+        }
         match &mut self.buf_writer {
             Some(buf_writer) => io::Write::write_all(buf_writer, data),
             None => io::Write::write_all(&mut io::stdout(), data),
@@ -116,17 +133,37 @@ impl<T: RDH> Writer<T> for BufferedWriter<T> {
             self.filtered_payload_buffers.len()
         );
 
-        let mut data = vec![];
-        for (rdh, payload) in self
-            .filtered_rdhs_buffer
-            .iter()
-            .zip(self.filtered_payload_buffers.iter())
-        {
-            data.extend(rdh.to_byte_slice());
-            data.extend(payload);
-        }
+        if let Some(link_writers) = &mut self.link_writers {
+            for (rdh, payload) in self
+                .filtered_rdhs_buffer
+                .iter()
+                .zip(self.filtered_payload_buffers.iter())
+            {
+                let link_writer = link_writers.get_or_create(rdh.link_id())?;
+                io::Write::write_all(link_writer, rdh.to_byte_slice())?;
+                io::Write::write_all(link_writer, payload)?;
+            }
+        } else if let Some(hbf_writer) = &mut self.hbf_writer {
+            for (rdh, payload) in self
+                .filtered_rdhs_buffer
+                .iter()
+                .zip(self.filtered_payload_buffers.iter())
+            {
+                hbf_writer.write_cdp(rdh, payload)?;
+            }
+        } else {
+            let mut data = vec![];
+            for (rdh, payload) in self
+                .filtered_rdhs_buffer
+                .iter()
+                .zip(self.filtered_payload_buffers.iter())
+            {
+                data.extend(rdh.to_byte_slice());
+                data.extend(payload);
+            }
 
-        self.write(&data)?;
+            self.write(&data)?;
+        }
         self.filtered_rdhs_buffer.clear();
         self.filtered_payload_buffers.clear();
         Ok(())
@@ -137,8 +174,113 @@ impl<T: RDH> Drop for BufferedWriter<T> {
     fn drop(&mut self) {
         if mem::needs_drop::<Self>() {
             self.flush().expect("Failed to flush buffer");
+            if let Some(link_writers) = &mut self.link_writers {
+                link_writers
+                    .flush_all()
+                    .expect("Failed to flush link output files");
+            }
+            if let Some(hbf_writer) = &mut self.hbf_writer {
+                hbf_writer
+                    .finish_hbf()
+                    .expect("Failed to flush HBF output file");
+            }
+        }
+    }
+}
+
+/// Buffered writers to one output file per link, created when data from a link is first written.
+struct LinkWriters {
+    /// The output path that the path of each link's output file is derived from
+    path: PathBuf,
+    writers: BTreeMap<u8, io::BufWriter<fs::File>>,
+}
+
+impl LinkWriters {
+    fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            writers: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the writer for the given link, creating its output file if it doesn't exist yet.
+    fn get_or_create(&mut self, link_id: u8) -> io::Result<&mut io::BufWriter<fs::File>> {
+        match self.writers.entry(link_id) {
+            Entry::Occupied(entry) => Ok(entry.into_mut()),
+            Entry::Vacant(entry) => {
+                let file = fs::File::create(link_output_path(&self.path, link_id))?;
+                Ok(entry.insert(io::BufWriter::new(file)))
+            }
         }
     }
+
+    fn flush_all(&mut self) -> io::Result<()> {
+        self.writers.values_mut().try_for_each(io::Write::flush)
+    }
+}
+
+/// Buffered writer to one output file per HBF, the file of an HBF is created when its first CDP is written.
+///
+/// An HBF ends at an RDH with the stop bit set, the CDPs following it are written to the file of the next HBF.
+struct HbfWriter {
+    /// The output path that the path of each HBF's output file is derived from
+    path: PathBuf,
+    /// Index of the HBF currently being written
+    hbf_index: u64,
+    writer: Option<io::BufWriter<fs::File>>,
+}
+
+impl HbfWriter {
+    fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            hbf_index: 0,
+            writer: None,
+        }
+    }
+
+    /// Writes a CDP to the file of the current HBF, and moves on to the next HBF if the stop bit of the [RDH] is set.
+    fn write_cdp(&mut self, rdh: &impl RDH, payload: &[u8]) -> io::Result<()> {
+        if self.writer.is_none() {
+            let file = fs::File::create(hbf_output_path(&self.path, self.hbf_index))?;
+            self.writer = Some(io::BufWriter::new(file));
+        }
+        let writer = self.writer.as_mut().unwrap();
+        io::Write::write_all(writer, rdh.to_byte_slice())?;
+        io::Write::write_all(writer, payload)?;
+        if rdh.stop_bit() == 1 {
+            self.finish_hbf()?;
+            self.hbf_index += 1;
+        }
+        Ok(())
+    }
+
+    /// Flushes and closes the file of the current HBF, if it was created.
+    fn finish_hbf(&mut self) -> io::Result<()> {
+        if let Some(mut writer) = self.writer.take() {
+            io::Write::flush(&mut writer)?;
+        }
+        Ok(())
+    }
+}
+
+/// Derives the path of the output file of a link by appending the link ID to the file stem, e.g. `out.raw` -> `out_link3.raw`.
+pub fn link_output_path(path: &Path, link_id: u8) -> PathBuf {
+    output_path_with_suffix(path, &format!("link{link_id}"))
+}
+
+/// Derives the path of the output file of an HBF by appending the HBF index to the file stem, e.g. `out.raw` -> `out_hbf0.raw`.
+pub fn hbf_output_path(path: &Path, hbf_index: u64) -> PathBuf {
+    output_path_with_suffix(path, &format!("hbf{hbf_index}"))
+}
+
+fn output_path_with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let file_name = match path.extension() {
+        Some(ext) => format!("{stem}_{suffix}.{}", ext.to_string_lossy()),
+        None => format!("{stem}_{suffix}"),
+    };
+    path.with_file_name(file_name)
 }
 
 #[cfg(test)]
@@ -152,13 +294,13 @@ mod tests {
     const CONFIG_STR_NEEDS_OUTPUT: [&str; 4] =
         ["fastpasta", "tests/test-data/10_rdh.raw", "-f", "2"];
 
-    fn build_test_config(output_path: &path::Path) -> MockConfig {
+    fn build_test_config(output_path: &Path) -> MockConfig {
         let mut cfg = MockConfig::new();
         cfg.check = Some(CheckCommands::Sanity(CheckModeArgs::default()));
         cfg.output = Some(output_path.to_owned());
         cfg.output_mode = DataOutputMode::File(output_path.into());
-        cfg.input_file = Some(path::PathBuf::from(INPUT_FILE_STR));
-        cfg.filter_link = Some(2);
+        cfg.input_files = vec![PathBuf::from(INPUT_FILE_STR)];
+        cfg.filter_links = vec![2];
         cfg
     }
 
@@ -222,4 +364,103 @@ mod tests {
             assert_eq!(buf_size, length);
         }
     }
+
+    #[test]
+    fn test_link_output_path() {
+        assert_eq!(
+            link_output_path(Path::new("dir/out.raw"), 3),
+            PathBuf::from("dir/out_link3.raw")
+        );
+        assert_eq!(
+            link_output_path(Path::new("out"), 11),
+            PathBuf::from("out_link11")
+        );
+    }
+
+    #[test]
+    fn test_hbf_output_path() {
+        assert_eq!(
+            hbf_output_path(Path::new("dir/out.raw"), 0),
+            PathBuf::from("dir/out_hbf0.raw")
+        );
+        assert_eq!(
+            hbf_output_path(Path::new("out"), 12),
+            PathBuf::from("out_hbf12")
+        );
+    }
+
+    #[test]
+    fn test_split_by_hbf_writes_file_per_hbf() {
+        let tmp_d = TempDir::new().unwrap();
+        let test_file_path = tmp_d.child("test.raw");
+        let mut cfg = build_test_config(&test_file_path);
+        cfg.output_mode = DataOutputMode::SplitByHbf(test_file_path.clone().into());
+        cfg.split_by_hbf = true;
+
+        let mut rdh_bytes = CORRECT_RDH_CRU_V7.to_byte_slice().to_vec();
+        rdh_bytes[38] = 1; // Stop bit
+        let rdh_stop = RdhCru::load(&mut rdh_bytes.as_slice()).unwrap();
+        assert_eq!(rdh_stop.stop_bit(), 1);
+        assert_eq!(CORRECT_RDH_CRU_V7.stop_bit(), 0);
+
+        let mut cdp_vec = CdpVec::new();
+        cdp_vec.push(CORRECT_RDH_CRU_V7, vec![0xA; 10], 0);
+        cdp_vec.push(rdh_stop, vec![0xB; 10], 0x40);
+        cdp_vec.push(CORRECT_RDH_CRU_V7, vec![0xC; 10], 0x80);
+        {
+            let mut writer = BufferedWriter::<RdhCru>::new(&cfg, 10);
+            writer.push_cdp_vec(cdp_vec);
+        }
+
+        assert!(!test_file_path.exists());
+        let hbf0_data = fs::read(tmp_d.child("test_hbf0.raw")).unwrap();
+        let mut expect_hbf0 = CORRECT_RDH_CRU_V7.to_byte_slice().to_vec();
+        expect_hbf0.extend([0xA; 10]);
+        expect_hbf0.extend(&rdh_bytes);
+        expect_hbf0.extend([0xB; 10]);
+        assert_eq!(hbf0_data, expect_hbf0);
+
+        // The last HBF is written even without a closing stop bit
+        let hbf1_data = fs::read(tmp_d.child("test_hbf1.raw")).unwrap();
+        let mut expect_hbf1 = CORRECT_RDH_CRU_V7.to_byte_slice().to_vec();
+        expect_hbf1.extend([0xC; 10]);
+        assert_eq!(hbf1_data, expect_hbf1);
+        assert!(!tmp_d.child("test_hbf2.raw").exists());
+    }
+
+    #[test]
+    fn test_split_by_link_writes_file_per_link() {
+        let tmp_d = TempDir::new().unwrap();
+        let test_file_path = tmp_d.child("test.raw");
+        let mut cfg = build_test_config(&test_file_path);
+        cfg.output_mode = DataOutputMode::SplitByLink(test_file_path.clone().into());
+        cfg.split_by_link = true;
+
+        let mut rdh_bytes = CORRECT_RDH_CRU_V7.to_byte_slice().to_vec();
+        rdh_bytes[12] = 3; // Link ID
+        let rdh_link3 = RdhCru::load(&mut rdh_bytes.as_slice()).unwrap();
+        assert_eq!(rdh_link3.link_id(), 3);
+
+        let mut cdp_vec = CdpVec::new();
+        cdp_vec.push(CORRECT_RDH_CRU_V7, vec![0xA; 10], 0);
+        cdp_vec.push(rdh_link3, vec![0xB; 10], 0x40);
+        cdp_vec.push(CORRECT_RDH_CRU_V7, vec![0xC; 10], 0x80);
+        {
+            let mut writer = BufferedWriter::<RdhCru>::new(&cfg, 10);
+            writer.push_cdp_vec(cdp_vec);
+        }
+
+        assert!(!test_file_path.exists());
+        let link0_data = fs::read(tmp_d.child("test_link0.raw")).unwrap();
+        let mut expect_link0 = CORRECT_RDH_CRU_V7.to_byte_slice().to_vec();
+        expect_link0.extend([0xA; 10]);
+        expect_link0.extend(CORRECT_RDH_CRU_V7.to_byte_slice());
+        expect_link0.extend([0xC; 10]);
+        assert_eq!(link0_data, expect_link0);
+
+        let link3_data = fs::read(tmp_d.child("test_link3.raw")).unwrap();
+        let mut expect_link3 = rdh_bytes;
+        expect_link3.extend([0xB; 10]);
+        assert_eq!(link3_data, expect_link3);
+    }
 }
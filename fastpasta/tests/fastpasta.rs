@@ -43,6 +43,44 @@ fn fastpasta_help() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn fastpasta_print_stats_schema() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg("print-stats-schema");
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    match_on_out(
+        true,
+        &cmd.output()?.stdout,
+        format!(
+            "(?m)^schema_version {}$",
+            fastpasta::stats::stats_collector::STATS_SCHEMA_VERSION
+        ),
+        1,
+    )?;
+    match_on_out(true, &cmd.output()?.stdout, r"rdh_stats\.rdhs_seen +u64", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn fastpasta_list_systems() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg("list-systems");
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    let stdout = cmd.output()?.stdout;
+    match_on_out(true, &stdout, r"(?m)^ +3  TPC +-$", 1)?;
+    match_on_out(true, &stdout, r"(?m)^ +32  ITS +its, its-stave$", 1)?;
+    match_on_out(true, &stdout, r"(?m)^ +34  FT0 +ft0$", 1)?;
+
+    Ok(())
+}
+
 /// Test that all test data files can be parsed successfully
 #[test]
 fn file_exists_exit_successful_10_rdh() -> Result<(), Box<dyn std::error::Error>> {
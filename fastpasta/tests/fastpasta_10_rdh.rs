@@ -154,6 +154,29 @@ fn check_all_its_trigger_period_mismatch() -> Result<(), Box<dyn std::error::Err
     Ok(())
 }
 
+#[test]
+fn check_all_its_stave_lane_occupancy() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_10_RDH)
+        .arg("check")
+        .arg("all")
+        .arg("its-stave")
+        .arg("--filter-its-stave")
+        .arg("L0_12");
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    match_on_out(
+        false,
+        &cmd.output()?.stdout,
+        r"lane occupancy.*min 1\.00 avg 1\.00 max 1\.00.*5 frames",
+        1,
+    )?;
+
+    Ok(())
+}
+
 #[test]
 fn check_all_its_trigger_period() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::cargo_bin("fastpasta")?;
@@ -198,6 +221,45 @@ fn check_all_its_stave_filter() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn check_all_its_alpide_stats_only() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_10_RDH)
+        .arg("check")
+        .arg("all")
+        .arg("its")
+        .arg("--alpide-stats-only");
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    // Same ALPIDE stats as `check all its-stave`
+    assert_alpide_stats_report(&cmd.output()?.stdout, 15, 0, 0, 0, 0, 0, 0)?;
+
+    Ok(())
+}
+
+#[test]
+fn alpide_stats_only_requires_check_all_its() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_10_RDH)
+        .arg("check")
+        .arg("sanity")
+        .arg("its")
+        .arg("--alpide-stats-only");
+    cmd.assert().failure();
+
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "Invalid config:.*--alpide-stats-only.*requires the `check all its` command",
+        1,
+    )?;
+
+    Ok(())
+}
+
 #[test]
 fn check_all_its_stave() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::cargo_bin("fastpasta")?;
@@ -334,6 +396,54 @@ fn filter_fee_not_found() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn filter_fee_multiple_and_ranges() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--filter-fee", "1337,500-600,20500-20600"])
+        .arg("-o")
+        .arg(tmp_fpath.as_os_str());
+
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    match_on_out(false, &cmd.output()?.stdout, "Total.*RDHs.*10", 1)?;
+    // FEE ID 524 is in the range 500-600, the other FEE ID and range are not found
+    match_on_out(
+        false,
+        &cmd.output()?.stdout,
+        r"FEE ID.*524.*not found: 1337, 20500-20600",
+        1,
+    )?;
+    match_on_out(false, &cmd.output()?.stdout, r"\|.* RDHs.*10", 1)?;
+    assert_eq!(
+        std::fs::read(tmp_fpath.path())?,
+        std::fs::read(FILE_10_RDH)?
+    );
+
+    Ok(())
+}
+
+#[test]
+fn filter_fee_range_start_after_end() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--filter-fee", "600-500"])
+        .args(["check", "sanity"]);
+    cmd.assert().failure();
+
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "Start of FEE ID range to filter by is after the end: 600-500",
+        1,
+    )?;
+
+    Ok(())
+}
+
 #[test]
 fn view_its_readout_frame() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::cargo_bin("fastpasta")?;
@@ -352,6 +462,66 @@ fn view_its_readout_frame() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn view_ddw0() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_10_RDH)
+        .arg("view")
+        .arg("ddw0")
+        .arg("--disable-styled-views");
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    // 5 DDW0s, all lanes OK
+    match_on_out(false, &cmd.output()?.stdout, r"524\s+8\s+-{28}\s+-", 5)?;
+    match_on_out(false, &cmd.output()?.stdout, r"\s+E0:\s+524", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn view_alpide() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_10_RDH)
+        .arg("view")
+        .arg("alpide")
+        .arg("--filter-its-stave")
+        .arg("L0_12")
+        .arg("--disable-styled-views");
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    // 5 readout frames with data from lane 6, 7, and 8 each with the chip ID matching the lane
+    match_on_out(
+        false,
+        &cmd.output()?.stdout,
+        r"524\s+([678])\s+\1\s+0\s+-",
+        15,
+    )?;
+    match_on_out(false, &cmd.output()?.stdout, r"\s+50:\s+524", 3)?;
+
+    Ok(())
+}
+
+#[test]
+fn view_alpide_requires_stave_filter() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_10_RDH).arg("view").arg("alpide");
+    cmd.assert().failure();
+
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "Invalid config:.*view alpide.*--filter-its-stave",
+        1,
+    )?;
+
+    Ok(())
+}
+
 #[test]
 fn view_its_readout_frame_data() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::cargo_bin("fastpasta")?;
@@ -378,6 +548,92 @@ fn view_its_readout_frame_data() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn check_all_its_gzip_file() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_10_RDH_GZ).arg("check").arg("all").arg("its");
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    validate_report_summary(&cmd.output()?.stdout)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_all_its_gzip_stdin() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.pipe_stdin(FILE_10_RDH_GZ)?
+        .arg("--gzip")
+        .arg("check")
+        .arg("all")
+        .arg("its");
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    validate_report_summary(&cmd.output()?.stdout)?;
+
+    Ok(())
+}
+
+#[test]
+fn filter_link_gzip_file_output_matches_uncompressed() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd_gz = Command::cargo_bin("fastpasta")?;
+    cmd_gz.arg(FILE_10_RDH_GZ).arg("--filter-link").arg("8");
+    let mut cmd_raw = Command::cargo_bin("fastpasta")?;
+    cmd_raw.arg(FILE_10_RDH).arg("--filter-link").arg("8");
+
+    let out_gz = cmd_gz.output()?;
+    assert!(out_gz.status.success());
+    assert_eq!(out_gz.stdout, cmd_raw.output()?.stdout);
+    assert_eq!(out_gz.stdout, std::fs::read(FILE_10_RDH)?);
+
+    Ok(())
+}
+
+#[test]
+fn check_all_its_zstd_file() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_10_RDH_ZST).arg("check").arg("all").arg("its");
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    validate_report_summary(&cmd.output()?.stdout)?;
+
+    Ok(())
+}
+
+#[test]
+fn filter_link_zstd_stdin_output_matches_uncompressed() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.pipe_stdin(FILE_10_RDH_ZST)?
+        .arg("--zstd")
+        .arg("--filter-link")
+        .arg("8");
+
+    let out = cmd.output()?;
+    assert!(out.status.success());
+    assert_eq!(out.stdout, std::fs::read(FILE_10_RDH)?);
+
+    Ok(())
+}
+
+#[test]
+fn zstd_and_gzip_conflict() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.pipe_stdin(FILE_10_RDH_ZST)?
+        .arg("--zstd")
+        .arg("--gzip")
+        .arg("check")
+        .arg("sanity");
+    cmd.assert().failure();
+
+    Ok(())
+}
+
 #[test]
 fn check_sanity_stdin() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::cargo_bin("fastpasta")?;
@@ -414,6 +670,94 @@ fn filter_link_check_sanity_pipe_between_fastpasta() -> Result<(), Box<dyn std::
     Ok(())
 }
 
+#[test]
+fn filter_config_link() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_named_file("filters.toml");
+    std::fs::write(&tmp_fpath, "links = [8]\ntrigger_type = \"HB\"")?;
+
+    // Same as filtering with `--filter-link 8`, all RDHs are from link 8 with HB set
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .arg("--filter-config")
+        .arg(tmp_fpath.as_os_str())
+        .args(["-o", "stdout"]);
+    let out = cmd.assert().success().get_output().stdout.clone();
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    assert_eq!(out, std::fs::read(FILE_10_RDH)?);
+
+    // Filters set on the command line take precedence
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .arg("--filter-config")
+        .arg(tmp_fpath.as_os_str())
+        .args(["--filter-link", "3", "-o", "stdout"]);
+    cmd.assert().success();
+    assert!(cmd.output()?.stdout.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn filter_link_cru_internal() -> Result<(), Box<dyn std::error::Error>> {
+    // Link 15 is the CRU internal link, none of the RDHs are from it
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--filter-link", "15", "-o", "stdout"]);
+    cmd.assert().success();
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    assert!(cmd.output()?.stdout.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn filter_link_out_of_range() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--filter-link", "8,12", "check", "sanity"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        r"Link ID to filter by is out of range: 12 \(expected 0-11 or 15\)",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn filter_config_invalid() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--filter-config", "does-not-exist.toml"])
+        .args(["check", "sanity"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "Filter config file does not exist",
+        1,
+    )?;
+
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_named_file("filters.toml");
+    std::fs::write(&tmp_fpath, "links = [8]\nfee = 524")?;
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .arg("--filter-config")
+        .arg(tmp_fpath.as_os_str())
+        .args(["check", "sanity"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "Only one of `links`, `fee`, and `its_stave` can be set",
+        1,
+    )?;
+
+    Ok(())
+}
+
 #[test]
 fn check_sanity_custom_exit_code() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::cargo_bin("fastpasta")?;
@@ -609,6 +953,10 @@ fn check_sanity_output_stats_json_toml() -> Result<(), Box<dyn std::error::Error
         serde_json::from_str(&stats_str)?;
     assert_eq!(stats_from_json.rdh_stats().rdh_version(), 7);
     assert_eq!(stats_from_json.rdhs_seen(), 10);
+    assert_eq!(
+        stats_from_json.schema_version(),
+        fastpasta::stats::stats_collector::STATS_SCHEMA_VERSION
+    );
 
     // Serialize it to TOML and back to a StatsCollector from TOML to compare
     let stats_from_toml: fastpasta::stats::stats_collector::StatsCollector =
@@ -708,7 +1056,8 @@ fn test_check_all_its_with_stats_validation() -> Result<(), Box<dyn std::error::
         .arg("--output-stats")
         .arg(tmp_fpath.as_os_str())
         .arg("--stats-format")
-        .arg("json");
+        .arg("json")
+        .arg("--json-pretty");
 
     cmd.assert().success();
 
@@ -751,19 +1100,76 @@ fn test_check_all_its_with_stats_validation() -> Result<(), Box<dyn std::error::
     Ok(())
 }
 
-// https://gitlab.cern.ch/mkonig/fastpasta/-/issues/45
-// Test that everything up until a faulty payload reading (caused by a faulty RDH offset_to_next field) is processed correctly
 #[test]
-fn view_its_readout_frames_cutoff_last_byte_padding_issue45(
-) -> Result<(), Box<dyn std::error::Error>> {
-    // Read the file into a buffer and discard the last byte
-    let mut file = std::fs::File::open(FILE_10_RDH)?;
-    let mut buffer = Vec::new();
-    std::io::Read::read_to_end(&mut file, &mut buffer)?;
-    _ = buffer.pop();
+fn test_check_all_its_with_csv_stats_validation() -> Result<(), Box<dyn std::error::Error>> {
+    let check_arg = ["check", "all", "its"];
 
-    // Make a tmp file and write the buffer to it
-    let (_tmp_dir, tmp_file) = make_tmp_dir_w_fpath();
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_named_file("out-stats.csv");
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(check_arg)
+        .arg("--output-stats")
+        .arg(tmp_fpath.as_os_str())
+        .arg("--stats-format")
+        .arg("csv");
+
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+
+    let stats_str = std::fs::read_to_string(&tmp_fpath)?;
+    assert!(
+        stats_str.starts_with("stat,value\nrdhs_seen,10\n"),
+        "{stats_str}"
+    );
+    assert!(stats_str.contains("links,8"), "{stats_str}");
+
+    // Now run again with the created stats as input
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(check_arg)
+        .arg("--input-stats")
+        .arg(tmp_fpath.as_os_str());
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+
+    // Now alter the stats and run again, expect an error
+    let (_tmp_dir2, tmp_fpath2) = make_tmp_dir_w_named_file("out-stats-wrong.csv");
+    std::fs::write(
+        tmp_fpath2.as_os_str(),
+        stats_str.replace("rdhs_seen,10", "rdhs_seen,11"),
+    )?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(check_arg)
+        .arg("--input-stats")
+        .arg(tmp_fpath2.as_os_str())
+        .arg("--any-errors-exit-code")
+        .arg("123");
+
+    cmd.assert().failure().code(123);
+
+    match_on_out(false, &cmd.output()?.stderr, "ERROR .* mismatch.*11", 1)?;
+
+    Ok(())
+}
+
+// https://gitlab.cern.ch/mkonig/fastpasta/-/issues/45
+// Test that everything up until a faulty payload reading (caused by a faulty RDH offset_to_next field) is processed correctly
+#[test]
+fn view_its_readout_frames_cutoff_last_byte_padding_issue45(
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Read the file into a buffer and discard the last byte
+    let mut file = std::fs::File::open(FILE_10_RDH)?;
+    let mut buffer = Vec::new();
+    std::io::Read::read_to_end(&mut file, &mut buffer)?;
+    _ = buffer.pop();
+
+    // Make a tmp file and write the buffer to it
+    let (_tmp_dir, tmp_file) = make_tmp_dir_w_fpath();
     tmp_file.write_binary(&buffer)?;
 
     let mut cmd = Command::cargo_bin("fastpasta")?;
@@ -838,3 +1244,1671 @@ fn check_all_path_instead_of_subcmd() -> Result<(), Box<dyn std::error::Error>>
 
     Ok(())
 }
+
+#[test]
+fn check_sanity_word_id_histogram() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .arg("check")
+        .arg("sanity")
+        .arg("--word-id-histogram");
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    validate_report_summary(&cmd.output()?.stdout)?;
+    match_on_out(
+        false,
+        &cmd.output()?.stdout,
+        "Word ID histogram.*35 GBT words",
+        1,
+    )?;
+    // 7 distinct word IDs each seen 5 times (IHW, TDH, TDT, DDW0 and 3 IB lanes)
+    match_on_out(false, &cmd.output()?.stdout, r"0x..:\s+5 \( 14.29%\)", 7)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_link_payload_stats() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH).arg("check").arg("sanity");
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    match_on_out(
+        false,
+        &cmd.output()?.stdout,
+        r"Payload per link \(1 links\)",
+        1,
+    )?;
+    // All 10 RDHs are from link 8 with 560 B payload in total
+    match_on_out(false, &cmd.output()?.stdout, r"\s+8\s+10\s+560 B", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn dry_run_check_all_its() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .arg("--dry-run")
+        .args(["check", "all", "its"])
+        .args(["--filter-link", "8"]);
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    match_on_out(false, &cmd.output()?.stdout, "Dry run, no data is read", 1)?;
+    match_on_out(
+        false,
+        &cmd.output()?.stdout,
+        "Mode:.*Check RDH and ITS sanity and running checks",
+        1,
+    )?;
+    match_on_out(false, &cmd.output()?.stdout, "Filters:.*links 8", 1)?;
+    // No data is read, so there's no report
+    match_on_out(false, &cmd.output()?.stdout, "Total RDHs", 0)?;
+
+    Ok(())
+}
+
+#[test]
+fn compare_raw_identical() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg("compare-raw").arg(FILE_10_RDH).arg(FILE_10_RDH);
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    match_on_out(false, &cmd.output()?.stdout, "identical.*10 CDPs", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn compare_raw_flipped_byte() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    let mut candidate = std::fs::read(FILE_10_RDH)?;
+    // Flip a byte in the 3rd GBT word of the first payload (data format 0 -> 16 byte words)
+    candidate[0x63] ^= 0xFF;
+    tmp_fpath.write_binary(&candidate)?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg("compare-raw")
+        .arg(FILE_10_RDH)
+        .arg(tmp_fpath.path());
+    cmd.assert().failure();
+
+    let stdout = cmd.output()?.stdout;
+    match_on_out(false, &stdout, "Files diverge", 1)?;
+    match_on_out(false, &stdout, "0x60: Payload differs in GBT word 2", 1)?;
+    match_on_out(false, &stdout, r"golden:\s+\[A7 00 C0 41 FF B0", 1)?;
+    match_on_out(false, &stdout, r"candidate:\s+\[A7 00 C0 BE FF B0", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_all_its_offset_to_next_into_payload() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    let mut data = std::fs::read(FILE_10_RDH)?;
+    // Set the offset_new_packet of the first RDH to 0x90, pointing 16 bytes before the end of its payload
+    data[8] = 0x90;
+    tmp_fpath.write_binary(&data)?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path()).args(["check", "all", "its"]);
+    cmd.assert().success();
+
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        r"0x90: \[E14\] RDH is not at the offset to next of the previous RDH at 0x0: offset to next is 144 but the RDH was read 160 bytes after it",
+        1,
+    )?;
+    match_on_out(false, &cmd.output()?.stdout, "total.*errors.*1", 1)?;
+
+    // The offset is not checked if RDHs are filtered out
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path())
+        .args(["check", "all", "its"])
+        .args(["--filter-link", "8"]);
+    cmd.assert().success();
+    match_on_out(false, &cmd.output()?.stderr, r"\[E14\]", 0)?;
+
+    Ok(())
+}
+
+#[test]
+fn view_rdh_html() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, html_fpath) = make_tmp_dir_w_named_file("report.html");
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .arg("view")
+        .arg("rdh")
+        .arg("--html")
+        .arg(html_fpath.path());
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    let html = std::fs::read_to_string(html_fpath.path())?;
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.trim_end().ends_with("</html>"));
+    assert_eq!(html.matches("<table>").count(), 1);
+    assert_eq!(html.matches("</table>").count(), 1);
+    // Header row + 10 RDHs, none of which have errors
+    assert_eq!(html.matches("<tr>").count(), 11);
+    assert_eq!(html.matches("</tr>").count(), 11);
+    assert!(!html.contains("class=\"error\""));
+    // First RDH is at memory position 0 and the second at 0xA0
+    assert!(html.contains("<td>0</td>"));
+    assert!(html.contains("<td>A0</td>"));
+
+    Ok(())
+}
+
+#[test]
+fn view_html_requires_view_rdh() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, html_fpath) = make_tmp_dir_w_named_file("report.html");
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .arg("check")
+        .arg("sanity")
+        .arg("--html")
+        .arg(html_fpath.path());
+    cmd.assert().failure();
+
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "HTML output is only supported with `view rdh`",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn view_rdh_compact() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["view", "rdh", "--compact", "--disable-styled-views"]);
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    let stdout = &cmd.output()?.stdout;
+    match_on_out(
+        false,
+        stdout,
+        "Memory.*Ver.*FEE ID.*Link.*Trigger.*Stop.*Page.*Payload size",
+        1,
+    )?;
+    // One line per RDH with memory position, version, FEE ID, link ID, trigger type, stop bit, page counter and payload size
+    match_on_out(
+        false,
+        stdout,
+        r"[0-9A-F]+:\s+7\s+524\s+8\s+(SOC|HB)\s+[01]\s+[01]\s+(96|16)",
+        10,
+    )?;
+    match_on_out(false, stdout, r"A0:\s+7\s+524\s+8\s+SOC\s+1\s+1\s+16", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn view_compact_requires_view_rdh() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH).args(["view", "ddw0", "--compact"]);
+    cmd.assert().failure();
+
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "Compact output is only supported with `view rdh`",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_trailing_bytes_warning() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    let mut truncated = std::fs::read(FILE_10_RDH)?;
+    let file_size = truncated.len();
+    // Garbage at the end of the file that is too short to be an RDH
+    truncated.extend_from_slice(&[0xFF; 17]);
+    tmp_fpath.write_binary(&truncated)?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path()).arg("check").arg("sanity");
+    cmd.assert().success();
+
+    match_on_out(
+        true,
+        &cmd.output()?.stderr,
+        format!("WARN {file_size:#X}: 17 trailing bytes could not be parsed as an RDH"),
+        1,
+    )?;
+    validate_report_summary(&cmd.output()?.stdout)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_output_stats_json_compact_and_pretty() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, compact_fpath) = make_tmp_dir_w_named_file("compact.json");
+    let (_tmp_dir2, pretty_fpath) = make_tmp_dir_w_named_file("pretty.json");
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .arg("check")
+        .arg("sanity")
+        .arg("--output-stats")
+        .arg(compact_fpath.as_os_str())
+        .arg("--stats-format")
+        .arg("json");
+    cmd.assert().success();
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .arg("check")
+        .arg("sanity")
+        .arg("--output-stats")
+        .arg(pretty_fpath.as_os_str())
+        .arg("--stats-format")
+        .arg("json")
+        .arg("--json-pretty");
+    cmd.assert().success();
+
+    let compact_str = std::fs::read_to_string(compact_fpath)?;
+    let pretty_str = std::fs::read_to_string(pretty_fpath)?;
+    assert!(!compact_str.trim_end().contains('\n'));
+    assert!(pretty_str.contains("\n  \""));
+    assert!(compact_str.contains("\"processing_time\""));
+    assert!(compact_str.contains("\"throughput_mb_s\""));
+
+    // Same stats regardless of formatting
+    let stats_from_compact: fastpasta::stats::stats_collector::StatsCollector =
+        serde_json::from_str(&compact_str)?;
+    let stats_from_pretty: fastpasta::stats::stats_collector::StatsCollector =
+        serde_json::from_str(&pretty_str)?;
+    assert_eq!(stats_from_compact, stats_from_pretty);
+
+    Ok(())
+}
+
+#[test]
+fn extract_rdhs_by_index() -> Result<(), Box<dyn std::error::Error>> {
+    // Make a file with the first 3 CDPs
+    let data = std::fs::read(FILE_10_RDH)?;
+    let mut cdps: Vec<&[u8]> = Vec::new();
+    let mut pos = 0;
+    for _ in 0..3 {
+        let offset_to_next = u16::from_le_bytes([data[pos + 8], data[pos + 9]]) as usize;
+        cdps.push(&data[pos..pos + offset_to_next]);
+        pos += offset_to_next;
+    }
+    let (_tmp_dir, input_fpath) = make_tmp_dir_w_named_file("3_rdh.raw");
+    input_fpath.write_binary(&cdps.concat())?;
+    let (_tmp_dir2, output_fpath) = make_tmp_dir_w_named_file("subset.raw");
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(input_fpath.path())
+        .arg("--extract-rdhs")
+        .arg("2,0,7")
+        .arg("-o")
+        .arg(output_fpath.path());
+    cmd.assert().success();
+
+    match_on_out(
+        true,
+        &cmd.output()?.stderr,
+        r"WARN RDH index 7 is beyond the end of the input \(3 RDHs\)",
+        1,
+    )?;
+    let subset = std::fs::read(output_fpath.path())?;
+    assert_eq!(subset, [cdps[0], cdps[2]].concat());
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_its_warn_ff_padding_data_format_0() -> Result<(), Box<dyn std::error::Error>> {
+    // Append 0xFF padding to the payload of the first CDP and update the offset and memory size in the RDH
+    let data = std::fs::read(FILE_10_RDH)?;
+    let offset_to_next = u16::from_le_bytes([data[8], data[9]]);
+    let mut first_cdp = data[..offset_to_next as usize].to_vec();
+    first_cdp.extend_from_slice(&[0xFF; 6]);
+    first_cdp[8..10].copy_from_slice(&(offset_to_next + 6).to_le_bytes());
+    first_cdp[10..12].copy_from_slice(&(offset_to_next + 6).to_le_bytes());
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    tmp_fpath.write_binary(&first_cdp)?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path())
+        .arg("check")
+        .arg("sanity")
+        .arg("its");
+    cmd.assert().success();
+
+    match_on_out(
+        true,
+        &cmd.output()?.stderr,
+        format!(
+            "WARN {:#X}: \\[W02\\] unexpected 0xFF padding in data format 0 payload \\(6 bytes\\)",
+            offset_to_next
+        ),
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_its_warnings_as_errors() -> Result<(), Box<dyn std::error::Error>> {
+    // Same 0xFF padding in a data format 0 payload as above, which is reported as a warning
+    let data = std::fs::read(FILE_10_RDH)?;
+    let offset_to_next = u16::from_le_bytes([data[8], data[9]]);
+    let mut first_cdp = data[..offset_to_next as usize].to_vec();
+    first_cdp.extend_from_slice(&[0xFF; 6]);
+    first_cdp[8..10].copy_from_slice(&(offset_to_next + 6).to_le_bytes());
+    first_cdp[10..12].copy_from_slice(&(offset_to_next + 6).to_le_bytes());
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    tmp_fpath.write_binary(&first_cdp)?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path())
+        .args(["check", "sanity", "its"])
+        .arg("--warnings-as-errors")
+        .args(["--any-errors-exit-code", "7"]);
+    cmd.assert().failure().code(7);
+
+    let stderr = cmd.output()?.stderr;
+    match_on_out(
+        true,
+        &stderr,
+        format!(
+            "ERROR {:#X}: \\[W02\\] unexpected 0xFF padding in data format 0 payload \\(6 bytes\\)",
+            offset_to_next
+        ),
+        1,
+    )?;
+    match_on_out(true, &stderr, "WARN .*W02", 0)?;
+
+    Ok(())
+}
+
+#[test]
+fn live_status_does_not_alter_stdout() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH).arg("view").arg("rdh");
+    cmd.assert().success();
+    let view_stdout = cmd.output()?.stdout;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .arg("view")
+        .arg("rdh")
+        .arg("--live-status");
+    cmd.assert().success();
+
+    assert_eq!(cmd.output()?.stdout, view_stdout);
+    // The final status line is terminated by a newline
+    match_on_out(true, &cmd.output()?.stderr, "L8: 10 RDHs 0 err\n", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_live_status() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .arg("check")
+        .arg("sanity")
+        .arg("--live-status");
+    cmd.assert().success();
+
+    validate_report_summary(&cmd.output()?.stdout)?;
+    // The final status line is terminated by a newline
+    match_on_out(true, &cmd.output()?.stderr, "L8: 10 RDHs 0 err\n", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn live_status_requires_check_or_view() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--filter-link", "8", "-o", "stdout", "--live-status"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "`--live-status` requires the `check` or `view` subcommand",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn progress_does_not_alter_stdout() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH).arg("view").arg("rdh");
+    cmd.assert().success();
+    let view_stdout = cmd.output()?.stdout;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .arg("view")
+        .arg("rdh")
+        .arg("--progress");
+    cmd.assert().success();
+    assert_eq!(cmd.output()?.stdout, view_stdout);
+
+    // Input size is unknown when reading from stdin
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.pipe_stdin(FILE_10_RDH)?
+        .arg("view")
+        .arg("rdh")
+        .arg("--progress");
+    cmd.assert().success();
+    assert_eq!(cmd.output()?.stdout, view_stdout);
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_progress() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .arg("check")
+        .arg("sanity")
+        .arg("--progress");
+    cmd.assert().success();
+
+    validate_report_summary(&cmd.output()?.stdout)?;
+
+    Ok(())
+}
+
+#[test]
+fn progress_conflicts_with_live_status() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .arg("check")
+        .arg("sanity")
+        .arg("--progress")
+        .arg("--live-status");
+    cmd.assert().failure();
+    match_on_out(false, &cmd.output()?.stderr, "cannot be used with", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_trigger_type_masks_override() -> Result<(), Box<dyn std::error::Error>> {
+    // The run trigger type 0x6A03 is decoded as SOC with the default masks
+    let (_tmp_dir, masks_fpath) = make_tmp_dir_w_named_file("trigger_type_masks.toml");
+    std::fs::write(&masks_fpath, "soc = 0")?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .arg("check")
+        .arg("sanity")
+        .arg("--trigger-type-masks")
+        .arg(masks_fpath.path());
+    cmd.assert().success();
+
+    // Without the SOC mask, the heartbeat bit is the most significant trigger
+    match_on_out(
+        false,
+        &cmd.output()?.stdout,
+        "Run Trigger Type.*0x6A03.*HB",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn trigger_type_masks_invalid_toml() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, masks_fpath) = make_tmp_dir_w_named_file("trigger_type_masks.toml");
+    std::fs::write(&masks_fpath, "physics = 0b1000")?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .arg("check")
+        .arg("sanity")
+        .arg("--trigger-type-masks")
+        .arg(masks_fpath.path());
+    cmd.assert().failure();
+
+    match_on_out(false, &cmd.output()?.stderr, "unknown field `physics`", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn config_from_env_vars() -> Result<(), Box<dyn std::error::Error>> {
+    // FILE_10_RDH only contains link 8
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .env("FASTPASTA_CHECK", "sanity its")
+        .env("FASTPASTA_FILTER_LINK", "3");
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    match_on_out(false, &cmd.output()?.stdout, "Total.*RDHs.*10", 1)?;
+    // Checking the filtered stats
+    match_on_out(false, &cmd.output()?.stdout, r"\|.*RDHs\s+0\s", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn config_cli_args_override_env_vars() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--filter-link", "8", "view", "rdh", "-d"])
+        .env("FASTPASTA_CHECK", "sanity")
+        .env("FASTPASTA_FILTER_LINK", "3");
+    cmd.assert().success();
+
+    // The view subcommand and link filter from the command line are used
+    match_on_out(false, &cmd.output()?.stdout, "RDH", 1)?;
+    match_on_out(false, &cmd.output()?.stdout, r"7\s+64\s+524\s+32", 10)?;
+
+    Ok(())
+}
+
+#[test]
+fn filter_trigger_type_view_rdh() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--filter-trigger-type", "SOC", "view", "rdh", "-d"]);
+    cmd.assert().success();
+
+    // Only the 2 RDHs of the first HBF have the SOC bit set
+    match_on_out(false, &cmd.output()?.stdout, r"0x6a03", 2)?;
+    match_on_out(false, &cmd.output()?.stdout, r"0x6003", 0)?;
+
+    Ok(())
+}
+
+#[test]
+fn filter_trigger_type_output() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--filter-trigger-type", "SOC", "-o"])
+        .arg(tmp_fpath.path());
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    // The 2 RDHs of the first HBF are written
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path()).args(["view", "rdh", "-d"]);
+    cmd.assert().success();
+    match_on_out(false, &cmd.output()?.stdout, r"0x6a03", 2)?;
+    match_on_out(false, &cmd.output()?.stdout, r"0x6003", 0)?;
+
+    Ok(())
+}
+
+#[test]
+fn filter_trigger_type_check_sanity_no_match() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--filter-trigger-type", "PhT", "check", "sanity"]);
+    cmd.assert().success();
+
+    let stdout = cmd.output()?.stdout;
+    match_on_out(false, &stdout, r"RDHs\s+0", 1)?;
+    match_on_out(false, &stdout, r"Trigger type\s+PhT", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn filter_trigger_type_unrecognized() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--filter-trigger-type", "Physics", "check", "sanity"]);
+    cmd.assert().failure();
+
+    match_on_out(false, &cmd.output()?.stderr, "unrecognized trigger type", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn filter_orbit_range_view_rdh() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--filter-orbit-range", "513-514", "view", "rdh", "-d"]);
+    cmd.assert().success();
+
+    // Orbits 0x201 and 0x202 have 2 RDHs each
+    match_on_out(false, &cmd.output()?.stdout, r"0x20[12]\s+0", 4)?;
+    match_on_out(false, &cmd.output()?.stdout, r"0x20[034]\s+0", 0)?;
+
+    Ok(())
+}
+
+#[test]
+fn filter_orbit_range_output() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--filter-orbit-range", "513-514", "-o"])
+        .arg(tmp_fpath.path());
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    // Orbits 0x201 and 0x202 have 2 RDHs each
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path()).args(["view", "rdh", "-d"]);
+    cmd.assert().success();
+    match_on_out(false, &cmd.output()?.stdout, r"0x20[12]\s+0", 4)?;
+    match_on_out(false, &cmd.output()?.stdout, r"0x20[034]\s+0", 0)?;
+
+    Ok(())
+}
+
+#[test]
+fn filter_orbit_range_check_sanity() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--filter-orbit-range", "512-512", "check", "sanity"]);
+    cmd.assert().success();
+
+    let stdout = cmd.output()?.stdout;
+    match_on_out(false, &stdout, r"RDHs\s+2", 1)?;
+    match_on_out(false, &stdout, r"Orbits\s+512-512", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn filter_orbit_range_stats_first_last_rdh() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--filter-orbit-range", "513-514", "check", "sanity"])
+        .args(["-D", "json", "-S"])
+        .arg(tmp_fpath.path());
+    cmd.assert().success();
+
+    let stats = std::fs::read_to_string(tmp_fpath.path())?;
+    match_on_out(
+        false,
+        stats.as_bytes(),
+        r#""first_rdh":\{"mem_pos":240,"orbit":513\},"last_rdh":\{"mem_pos":640,"orbit":514\}"#,
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn filter_orbit_range_invalid() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--filter-orbit-range", "514-513", "check", "sanity"]);
+    cmd.assert().failure();
+    match_on_out(false, &cmd.output()?.stderr, "start of orbit range", 1)?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--filter-orbit-range", "513", "check", "sanity"]);
+    cmd.assert().failure();
+    match_on_out(false, &cmd.output()?.stderr, "invalid orbit range", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn max_payload_size_exceeded() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--max-payload-size", "40", "check", "all", "its"]);
+    cmd.assert().success();
+
+    // The 5 RDHs with a payload size of 96 bytes are above the limit
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        r"\[E102\] Payload size 96 exceeds the maximum payload size 40",
+        5,
+    )?;
+    match_on_out(false, &cmd.output()?.stderr, r"0x0: \[E102\]", 1)?;
+
+    Ok(())
+}
+
+/// Writes a copy of the 10 RDH file where the header size of the first RDH is corrupted
+fn create_bad_first_rdh_header_size(
+    tmp_dir: &TempDir,
+) -> Result<ChildPath, Box<dyn std::error::Error>> {
+    let mut data = std::fs::read(FILE_10_RDH)?;
+    data[1] = 0x20; // Header size is 0x40
+    let bad_file = tmp_dir.child("bad_first_rdh.raw");
+    bad_file.write_binary(&data)?;
+    Ok(bad_file)
+}
+
+#[test]
+fn bad_first_rdh_fails_without_force_rdh_version() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = TempDir::new()?;
+    let bad_file = create_bad_first_rdh_header_size(&tmp_dir)?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(bad_file.path()).args(["check", "sanity"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "Initial RDH0 deserialization failed sanity check",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn force_rdh_version_bad_first_rdh() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = TempDir::new()?;
+    let bad_file = create_bad_first_rdh_header_size(&tmp_dir)?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(bad_file.path())
+        .args(["--force-rdh-version", "7", "check", "sanity", "-v1"]);
+    cmd.assert().success();
+
+    let (stdout, stderr) = (cmd.output()?.stdout, cmd.output()?.stderr);
+    // The failed initial sanity check is only a warning, the bad RDH is still reported by the RDH sanity check
+    match_on_out(
+        false,
+        &stderr,
+        "WARN.*Initial RDH0 deserialization failed sanity check",
+        1,
+    )?;
+    match_on_out(false, &stderr, r"0x0: \[E10\].*header size", 1)?;
+    match_on_out(false, &stdout, "Total.*RDHs.*10", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn force_rdh_version_out_of_range() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--force-rdh-version", "255", "check", "sanity"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "RDH version to force is out of range",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn reader_stall_threshold_no_stalls() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH).args([
+        "--reader-stall-threshold",
+        "10000",
+        "check",
+        "all",
+        "its",
+        "-v2",
+    ]);
+    cmd.assert().success();
+
+    // The 10 RDHs fit in a single batch, so sending it cannot block for 10 seconds
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "Reader never stalled waiting for the analysis",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn check_all_ft0_on_its_data() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH).args(["check", "all", "ft0"]);
+    cmd.assert().success();
+
+    let (stdout, stderr) = (cmd.output()?.stdout, cmd.output()?.stderr);
+    // ITS system ID and FEE ID are not valid for FT0
+    match_on_out(false, &stderr, r"\[E10\].*system_id = 0x20", 10)?;
+    // 560 bytes of payload in data format 0
+    match_on_out(false, &stdout, "Payload words.*35", 1)?;
+    match_on_out(false, &stdout, "Error codes: E10: 10", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_custom_checks_hbf_count() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = TempDir::new()?;
+    let tmp_custom_checks_path = tmp_dir.path().join("tmp_custom_checks.toml");
+
+    // There's 5 HBFs in the file
+    create_custom_checks_toml("hbfs = 5", &tmp_custom_checks_path)?;
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["check", "sanity", "--checks-toml"])
+        .arg(&tmp_custom_checks_path);
+    cmd.assert().success();
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+
+    create_custom_checks_toml("hbfs = 4", &tmp_custom_checks_path)?;
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["check", "sanity", "--checks-toml"])
+        .arg(&tmp_custom_checks_path);
+    cmd.assert().success();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        r"ERROR.*\[E9006\] Expected 4 HBFs, but found 5",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_custom_checks_expected_links() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = TempDir::new()?;
+    let tmp_custom_checks_path = tmp_dir.path().join("tmp_custom_checks.toml");
+
+    // All RDHs in the file are from link 8
+    create_custom_checks_toml("expected_links = [8]", &tmp_custom_checks_path)?;
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["check", "sanity", "--checks-toml"])
+        .arg(&tmp_custom_checks_path);
+    cmd.assert().success();
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+
+    create_custom_checks_toml("expected_links = [0, 3]", &tmp_custom_checks_path)?;
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["check", "sanity", "--checks-toml"])
+        .arg(&tmp_custom_checks_path);
+    cmd.assert().success();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        r"ERROR.*\[E9007\].*missing: \[0, 3\], unexpected: \[8\]",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_custom_checks_layer_stave_counts() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = TempDir::new()?;
+    let tmp_custom_checks_path = tmp_dir.path().join("tmp_custom_checks.toml");
+
+    // All RDHs in the file are from stave L0_12
+    create_custom_checks_toml("layer_stave_counts = [[0, 1]]", &tmp_custom_checks_path)?;
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["check", "sanity", "--checks-toml"])
+        .arg(&tmp_custom_checks_path);
+    cmd.assert().success();
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+
+    create_custom_checks_toml(
+        "layer_stave_counts = [[1, 16], [0, 12]]",
+        &tmp_custom_checks_path,
+    )?;
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["check", "sanity", "--checks-toml"])
+        .arg(&tmp_custom_checks_path);
+    cmd.assert().success();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        r"ERROR.*\[E9008\].*L0: 1/12, L1: 0/16",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn check_all_its_quiet_stats_pass() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_10_RDH)
+        .args(["check", "all", "its", "--quiet-stats"]);
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    match_on_out(true, &cmd.output()?.stdout, "^PASS\n$", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn quiet_stats_requires_check() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_10_RDH).args(["view", "rdh", "--quiet-stats"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "Invalid config: `--quiet-stats` requires the `check` subcommand",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn stats_flush_interval_requires_stats_file() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--stats-flush-interval", "5s"])
+        .args(["check", "sanity"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "Invalid config:.*--stats-flush-interval.*requires writing the stats to a file",
+        1,
+    )?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--stats-flush-interval", "0s"])
+        .args(["check", "sanity"]);
+    cmd.assert().failure();
+    match_on_out(false, &cmd.output()?.stderr, "Invalid number of seconds", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn scan_for_rdh_skips_junk_at_start() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    let mut data = vec![0xAB; 5];
+    data.extend(std::fs::read(FILE_10_RDH)?);
+    tmp_fpath.write_binary(&data)?;
+
+    // Without scanning, the junk at the start fails the initial sanity check
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path()).args(["check", "sanity"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "Initial RDH0.*failed sanity check",
+        1,
+    )?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path())
+        .args(["--scan-for-rdh", "check", "all", "its"]);
+    cmd.assert().success();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "0x5: Found a valid RDH after skipping 5 bytes",
+        1,
+    )?;
+    match_on_out(false, &cmd.output()?.stderr, "ERROR", 0)?;
+    match_on_out(false, &cmd.output()?.stdout, "Total.*RDHs.*10", 1)?;
+
+    // Memory positions are relative to the start of the input, including the skipped bytes
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path())
+        .args(["--scan-for-rdh", "view", "rdh", "--compact", "-d"]);
+    cmd.assert().success();
+    match_on_out(false, &cmd.output()?.stdout, r"\s5:\s+7\s+524", 1)?;
+    match_on_out(false, &cmd.output()?.stdout, r"A5:\s+7\s+524", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn view_det_field_status() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    let mut data = std::fs::read(FILE_10_RDH)?;
+    // Set the error bit in the detector field (RDH3) of the 5th RDH at 0x1E0
+    data[0x1E0 + 48] = 0b0100;
+    tmp_fpath.write_binary(&data)?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path())
+        .args(["view", "det-field-status", "-d"]);
+    cmd.assert().success();
+
+    let stdout = cmd.output()?.stdout;
+    // Only the first RDH and the transitions to and from the error are printed
+    match_on_out(false, &stdout, r"0x\w+\s+\w+:\s+524\s", 3)?;
+    match_on_out(false, &stdout, r"0x200\s+0:\s+524\s+-", 1)?;
+    match_on_out(false, &stdout, r"1E0:\s+524\s+Error", 1)?;
+    match_on_out(false, &stdout, r"280:\s+524\s+-", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_all_its_big_endian() -> Result<(), Box<dyn std::error::Error>> {
+    let data = std::fs::read(FILE_10_RDH)?;
+    // Reverse the byte order of each RDH subword and each GBT word (padded to 128 bits in data format 0)
+    let mut be_data = data.clone();
+    let mut rdh_pos = 0;
+    while rdh_pos < be_data.len() {
+        let offset_to_next = u16::from_le_bytes([data[rdh_pos + 8], data[rdh_pos + 9]]) as usize;
+        let (rdh, payload) = be_data[rdh_pos..rdh_pos + offset_to_next].split_at_mut(64);
+        rdh.chunks_exact_mut(8).for_each(<[u8]>::reverse);
+        payload.chunks_exact_mut(16).for_each(<[u8]>::reverse);
+        rdh_pos += offset_to_next;
+    }
+    let (tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    tmp_fpath.write_binary(&be_data)?;
+
+    // Without the endianness set, the first RDH fails the sanity check
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path()).args(["check", "all", "its"]);
+    cmd.assert().failure();
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path())
+        .args(["--endianness", "big", "check", "all", "its"]);
+    cmd.assert().success();
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    validate_report_summary(&cmd.output()?.stdout)?;
+
+    // The data is written as little-endian
+    let output_fpath = tmp_dir.child("out.raw");
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path())
+        .args(["--endianness", "big", "--filter-link", "8", "-o"])
+        .arg(output_fpath.path());
+    cmd.assert().success();
+    assert_eq!(std::fs::read(output_fpath.path())?, data);
+
+    Ok(())
+}
+
+#[test]
+fn check_all_packet_counter_not_increasing() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    let mut data = std::fs::read(FILE_10_RDH)?;
+    // Set the packet counter of the 2nd RDH (the last page of the first HBF) to the same value as the first RDH
+    data[0xA0 + 13] = 0;
+    tmp_fpath.write_binary(&data)?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path()).args(["check", "all"]);
+    cmd.assert().success();
+
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        r"0xA0: \[E15\] packet_counter did not increase within the HBF: packet_counter = 0 expected: 1 or higher",
+        1,
+    )?;
+    match_on_out(false, &cmd.output()?.stderr, r"\[E\d+\]", 1)?;
+
+    // Running checks are not enabled with `check sanity`
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path()).args(["check", "sanity"]);
+    cmd.assert().success();
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_all_its_input_offset() -> Result<(), Box<dyn std::error::Error>> {
+    // Start reading at the 5th RDH
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--input-offset", "0x1E0", "check", "all", "its"]);
+    cmd.assert().success();
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    match_on_out(false, &cmd.output()?.stdout, r"Total.*RDHs.*6", 1)?;
+
+    // Memory positions are relative to the start of the input
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--input-offset", "480", "view", "rdh", "--compact", "-d"]);
+    cmd.assert().success();
+    match_on_out(false, &cmd.output()?.stdout, r"1E0:\s+7\s+524", 1)?;
+    match_on_out(false, &cmd.output()?.stdout, r"\s0:\s+7\s+524", 0)?;
+
+    // The offset is beyond the end of the file
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--input-offset", "0x5000", "check", "sanity"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        r"Input offset 0x5000 is beyond the end of the input \(1200 bytes\)",
+        1,
+    )?;
+
+    // The offset leaves less than a full RDH before the end of the file
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--input-offset", "1199", "check", "sanity"]);
+    cmd.assert().failure().code(1);
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        r"Input offset 0x4AF leaves less than a full RDH \(64 bytes\) before the end of the input \(1200 bytes\)",
+        1,
+    )?;
+
+    // The offset is not at an RDH
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--input-offset", "0x10", "check", "sanity"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "Initial RDH0.*failed sanity check",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn input_offset_requires_input_file() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.pipe_stdin(FILE_10_RDH)?
+        .args(["--input-offset", "0x1E0", "check", "sanity"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "`--input-offset` requires input files, it cannot be used with stdin",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn check_all_its_max_bytes() -> Result<(), Box<dyn std::error::Error>> {
+    // The limit is reached within the 2nd RDH at 0xA0 (160), it is still read in full
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--max-bytes", "200", "check", "all", "its"]);
+    cmd.assert().success();
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    match_on_out(false, &cmd.output()?.stdout, r"Total.*RDHs.*2", 1)?;
+
+    // Bytes are counted from the input offset
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH).args([
+        "--input-offset",
+        "0x1E0",
+        "--max-bytes",
+        "1B",
+        "view",
+        "rdh",
+        "--compact",
+        "-d",
+    ]);
+    cmd.assert().success();
+    match_on_out(false, &cmd.output()?.stdout, r"[0-9A-F]+:\s+7\s+524", 1)?;
+    match_on_out(false, &cmd.output()?.stdout, r"1E0:\s+7\s+524", 1)?;
+
+    // A limit beyond the end of the input reads everything
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--max-bytes", "1MB", "check", "sanity"]);
+    cmd.assert().success();
+    match_on_out(false, &cmd.output()?.stdout, r"Total.*RDHs.*10", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn view_rdh_sample_every() -> Result<(), Box<dyn std::error::Error>> {
+    // Every 2nd CDP is viewed, starting with the first
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--sample-every", "2", "view", "rdh", "-d"]);
+    cmd.assert().success();
+    match_on_out(
+        false,
+        &cmd.output()?.stdout,
+        r"[0-9A-F]+:\s+7\s+64\s+524",
+        5,
+    )?;
+    match_on_out(false, &cmd.output()?.stdout, r"F0:\s+7\s+64\s+524", 1)?;
+    match_on_out(false, &cmd.output()?.stdout, r"A0:\s+7\s+64\s+524", 0)?;
+
+    // All RDHs are still counted
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--sample-every", "3", "check", "sanity"]);
+    cmd.assert().success();
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    match_on_out(false, &cmd.output()?.stdout, r"Total.*RDHs.*10", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_sample_every_counts_all_hbfs() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = TempDir::new()?;
+    let tmp_custom_checks_path = tmp_dir.path().join("tmp_custom_checks.toml");
+
+    // Only every 3rd CDP is checked, but all 5 HBFs and the RDHs and payload of link 8 are counted
+    create_custom_checks_toml("hbfs = 5", &tmp_custom_checks_path)?;
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--sample-every", "3", "check", "sanity", "--checks-toml"])
+        .arg(&tmp_custom_checks_path);
+    cmd.assert().success();
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    match_on_out(false, &cmd.output()?.stdout, r"Total.*HBFs.*5", 1)?;
+    match_on_out(false, &cmd.output()?.stdout, r"8\s+10\s+560 B", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn sample_every_check_all_invalid() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--sample-every", "2", "check", "all", "its"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "Invalid config: `--sample-every` cannot be used with `check all`",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_trigger_type_counts() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH).arg("check").arg("sanity");
+    cmd.assert().success();
+
+    // The run trigger type is reported once, and the trigger types of all RDHs are counted
+    match_on_out(
+        false,
+        &cmd.output()?.stdout,
+        "Run Trigger Type.*0x6A03.*SOC",
+        1,
+    )?;
+    match_on_out(
+        false,
+        &cmd.output()?.stdout,
+        "Triggers per type.*HB: 8, SOC: 2",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn view_hbf() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH).args(["view", "hbf", "-d"]);
+    cmd.assert().success();
+
+    // 10 RDHs with alternating stop bits make up 5 HBFs of 2 RDHs each
+    let stdout = cmd.output()?.stdout;
+    match_on_out(
+        false,
+        &stdout,
+        r"HBF\s+Orbit 0x20[0-4]\s+FEE ID 524\s+Link 8\n",
+        5,
+    )?;
+    match_on_out(false, &stdout, r"RDHs\s+2\n", 5)?;
+    match_on_out(false, &stdout, r"Memory position\s+0 - A0\n", 1)?;
+    match_on_out(false, &stdout, r"Trigger type\s+0x6A03 SOC", 1)?;
+    match_on_out(false, &stdout, r"Trigger type\s+0x6003 HB", 4)?;
+    match_on_out(false, &stdout, "incomplete", 0)?;
+
+    Ok(())
+}
+
+#[test]
+fn view_hbf_incomplete() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    let mut data = std::fs::read(FILE_10_RDH)?;
+    // Clear the stop bit of the last RDH at 0x460, so the last HBF never ends
+    data[0x460 + 38] = 0;
+    tmp_fpath.write_binary(&data)?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path()).args(["view", "hbf", "-d"]);
+    cmd.assert().success();
+
+    let stdout = cmd.output()?.stdout;
+    match_on_out(false, &stdout, r"HBF\s+Orbit", 5)?;
+    match_on_out(
+        false,
+        &stdout,
+        r"Orbit 0x204\s+FEE ID 524\s+Link 8\s+\(incomplete",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn rdh_sanity_lenient_unexpected_header_size() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    let mut data = std::fs::read(FILE_10_RDH)?;
+    // Set an unexpected header size in the first RDH
+    data[1] = 0x50;
+    tmp_fpath.write_binary(&data)?;
+
+    // The strict (default) profile aborts processing
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path()).args(["view", "rdh"]);
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "Initial RDH0 deserialization failed sanity check: RDH0: Header size = 0x50",
+        1,
+    )?;
+    match_on_out(false, &cmd.output()?.stdout, VIEW_RDH_REGEX_SANITY, 0)?;
+
+    // The lenient profile only warns about it
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path())
+        .args(["--rdh-sanity", "lenient", "view", "rdh"]);
+    cmd.assert().success();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "WARN.*Initial RDH0 has unusual values.*Header size = 0x50",
+        1,
+    )?;
+    // All RDHs are viewed, the first with its header size of 80
+    match_on_out(false, &cmd.output()?.stdout, VIEW_RDH_REGEX_SANITY, 9)?;
+    match_on_out(false, &cmd.output()?.stdout, r" 0: .*7.*80 .*524", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_all_duplicate_rdh() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    let data = std::fs::read(FILE_10_RDH)?;
+    // Repeat the first CDP
+    let mut duplicated_data = data[..0xA0].to_vec();
+    duplicated_data.extend_from_slice(&data);
+    tmp_fpath.write_binary(&duplicated_data)?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path()).args(["check", "all"]);
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        r"0xA0: \[E16\] duplicate RDH: identical to the previous RDH of the link at 0x0",
+        1,
+    )?;
+    match_on_out(false, &cmd.output()?.stderr, r"\[E16\]", 1)?;
+
+    // Not a running check
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path()).args(["check", "sanity"]);
+    match_on_out(false, &cmd.output()?.stderr, r"\[E16\]", 0)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_mixed_data_format() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    let mut data = std::fs::read(FILE_10_RDH)?;
+    // Change the data format of the 3rd RDH from 0 to 2
+    data[0xF0 + 24] = 2;
+    tmp_fpath.write_binary(&data)?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path()).args(["check", "sanity"]);
+    let stderr = cmd.output()?.stderr;
+    match_on_out(
+        false,
+        &stderr,
+        r"0xF0: \[E17\] Data format changed from 0 to 2",
+        1,
+    )?;
+    match_on_out(
+        false,
+        &stderr,
+        r"0x190: \[E17\] Data format changed from 2 to 0",
+        1,
+    )?;
+
+    // The change can be allowed
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path())
+        .args(["check", "sanity", "--allow-mixed-data-format"]);
+    cmd.assert().success();
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_all_max_errors_per_link() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    let data = std::fs::read(FILE_10_RDH)?;
+    // Repeat each CDP, causing errors in every other CDP
+    let rdh_mem_positions = [
+        0, 0xA0, 0xF0, 0x190, 0x1E0, 0x280, 0x2D0, 0x370, 0x3C0, 0x460,
+    ];
+    let mut repeated_data = Vec::new();
+    for (idx, &start) in rdh_mem_positions.iter().enumerate() {
+        let end = rdh_mem_positions
+            .get(idx + 1)
+            .copied()
+            .unwrap_or(data.len());
+        repeated_data.extend_from_slice(&data[start..end]);
+        repeated_data.extend_from_slice(&data[start..end]);
+    }
+    tmp_fpath.write_binary(&repeated_data)?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path()).args(["check", "all"]);
+    match_on_out(false, &cmd.output()?.stderr, r"ERROR 0x910: \[E16\]", 1)?;
+
+    // The errors of the first CDP with errors are reported, then the link is no longer checked
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path())
+        .args(["check", "all", "--max-errors-per-link", "1"]);
+    let stderr = cmd.output()?.stderr;
+    match_on_out(false, &stderr, r"ERROR 0xA0: \[E1[156]\]", 3)?;
+    match_on_out(false, &stderr, r"ERROR 0x", 3)?;
+    match_on_out(
+        false,
+        &stderr,
+        r"0xA0: Link 8 reached the maximum of 1 errors per link",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn filter_link_dump_format_ascii() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["--filter-link", "8", "--dump-format", "ascii"]);
+    cmd.assert().success();
+
+    let stdout = cmd.output()?.stdout;
+    // The header and a row for each RDH
+    match_on_out(false, &stdout, "RDH.*Header.*FEE.*Sys", 1)?;
+    match_on_out(false, &stdout, VIEW_RDH_REGEX_SANITY, 10)?;
+    // The payload words of data format 0 are 16 bytes, e.g. the IHW of the first CDP
+    match_on_out(
+        false,
+        &stdout,
+        r"(?m)^\s+\[03 1A 00 00 00 02 00 00 00 E8 00 00 00 00 00 00\]$",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn dump_format_ascii_split_by_link_invalid() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args([
+            "--filter-link",
+            "8",
+            "--dump-format",
+            "ascii",
+            "--split-by-link",
+        ])
+        .arg("-o")
+        .arg(tmp_fpath.path());
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "Invalid config: An ASCII dump cannot be split by link",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn check_all_its_ihw_no_active_lanes() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    let mut data = std::fs::read(FILE_10_RDH)?;
+    // Clear the active lanes of the IHW in the first CDP, it is followed by 3 data words
+    data[0x40] = 0;
+    data[0x41] = 0;
+    tmp_fpath.write_binary(&data)?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path()).args(["check", "all", "its"]);
+    let stderr = cmd.output()?.stderr;
+    // Reported once for the readout frame, along with each data word from an inactive lane
+    match_on_out(
+        false,
+        &stderr,
+        r"0x60: \[E76\] Data word in a readout frame where the IHW has no active lanes",
+        1,
+    )?;
+    match_on_out(false, &stderr, r"\[E76\]", 1)?;
+    match_on_out(false, &stderr, r"\[E72\]", 3)?;
+
+    Ok(())
+}
+
+#[test]
+fn log_file_with_timestamps() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = TempDir::new()?;
+    let log_file = tmp_dir.child("fastpasta.log");
+    const TIMESTAMPED_INFO: &str =
+        r"(?m)^\d{4}-\d{2}-\d{2}T\d{2}:\d{2}:\d{2}\.\d{3}[+-]\d{2}:\d{2} - INFO ITS detected$";
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["-v", "2", "--log-file"])
+        .arg(log_file.path())
+        .args(["check", "sanity"]);
+    cmd.assert().success();
+    // The log file is appended to
+    cmd.assert().success();
+    match_on_out(true, &std::fs::read(log_file.path())?, TIMESTAMPED_INFO, 2)?;
+    // Timestamps on stderr are opt-in
+    match_on_out(true, &cmd.output()?.stderr, "(?m)^INFO ITS detected$", 1)?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["-v", "2", "--log-timestamps", "check", "sanity"]);
+    match_on_out(true, &cmd.output()?.stderr, TIMESTAMPED_INFO, 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_expect_single_cru() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    let mut data = std::fs::read(FILE_10_RDH)?;
+    // Change the CRU ID of the 3rd RDH from 23 to 24
+    data[0xF0 + 14] = 24;
+    tmp_fpath.write_binary(&data)?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path())
+        .args(["check", "sanity", "--expect-single-cru"]);
+    cmd.assert().success();
+    let output = cmd.output()?;
+    match_on_out(
+        false,
+        &output.stderr,
+        r"\[W03\] CRU ID 24 observed, but the data is expected to come from a single CRU \(CRU IDs seen: 23, 24\)",
+        1,
+    )?;
+    match_on_out(false, &output.stdout, r"CRU IDs seen.*23, 24.*DW: 0", 1)?;
+
+    // Multiple CRU IDs are only recorded without the flag
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path()).args(["check", "sanity"]);
+    cmd.assert().success();
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_recv_timeout_stalled_stdin() -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::Write;
+    use std::process::Stdio;
+
+    let mut child = std::process::Command::new(assert_cmd::cargo::cargo_bin("fastpasta"))
+        .args(["--recv-timeout", "1", "check", "sanity"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let mut stdin = child.stdin.take().unwrap();
+    stdin.write_all(&std::fs::read(FILE_10_RDH)?)?;
+
+    // Stdin is kept open without sending more data, like a stalled upstream
+    let output = child.wait_with_output()?;
+    drop(stdin);
+
+    assert!(output.status.success());
+    match_on_out(
+        false,
+        &output.stderr,
+        "No data received for 1s, stopping gracefully",
+        1,
+    )?;
+
+    Ok(())
+}
@@ -46,3 +46,242 @@ fn view_its_readout_frames() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn check_ordering_link_orbit() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_12_LINKS_2HBF).args(["check", "ordering"]);
+    cmd.assert().success();
+
+    let (stdout, stderr) = (cmd.output()?.stdout, cmd.output()?.stderr);
+
+    match_on_out(false, &stdout, "errors.*0", 1)?;
+    match_on_out(false, &stderr, "error", 0)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_ordering_orbit_link_reports_first_violation() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    // The data is sorted by link, so the first CDP of link 1 is out of order by orbit
+    cmd.arg(FILE_12_LINKS_2HBF)
+        .args(["check", "ordering", "--key", "orbit-link"]);
+    cmd.assert().success();
+
+    let stderr = cmd.output()?.stderr;
+
+    match_on_out(false, &stderr, "error", 1)?;
+    match_on_out(
+        true,
+        &stderr,
+        r"0x3380: \[E13\] CDP is out of order by \(orbit, link\): \(1043926912, 1\) follows \(1043926913, 0\) at 0x3330",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn check_all_its_stave_filter_multiple_links() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_12_LINKS_2HBF)
+        .args(["--filter-link", "0,3,7", "check", "all", "its-stave"]);
+    cmd.assert().success();
+
+    let (stdout, stderr) = (cmd.output()?.stdout, cmd.output()?.stderr);
+
+    match_on_out(false, &stdout, "errors.*0", 1)?;
+    match_on_out(false, &stdout, r"RDHs.*18\b", 1)?;
+    match_on_out(false, &stdout, "link id.*0, 3, 7", 1)?;
+    match_on_out(false, &stderr, "error", 0)?;
+
+    Ok(())
+}
+
+#[test]
+fn filter_multiple_links_rejects_duplicates_and_out_of_range(
+) -> Result<(), Box<dyn std::error::Error>> {
+    for (links, expect_err) in [
+        ("0,3,3", "duplicate link id.*3"),
+        ("0,12", "out of range.*12"),
+    ] {
+        let mut cmd = Command::cargo_bin("fastpasta")?;
+        cmd.arg(FILE_12_LINKS_2HBF)
+            .args(["--filter-link", links, "check", "sanity"]);
+        cmd.assert().failure();
+
+        match_on_out(false, &cmd.output()?.stderr, expect_err, 1)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn filter_links_split_by_link_output() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = TempDir::new()?;
+    let out_path = tmp_dir.path().join("out.raw");
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_12_LINKS_2HBF)
+        .args(["--filter-link", "0,3", "--split-by-link", "-o"])
+        .arg(&out_path);
+    cmd.assert().success();
+
+    assert!(!out_path.exists());
+    let mut out_files = std::fs::read_dir(tmp_dir.path())?
+        .map(|entry| entry.map(|e| e.file_name()))
+        .collect::<Result<Vec<_>, _>>()?;
+    out_files.sort();
+    assert_eq!(out_files, ["out_link0.raw", "out_link3.raw"]);
+
+    // Each file should contain the same data as filtering only that link
+    for link in ["0", "3"] {
+        let mut cmd_link = Command::cargo_bin("fastpasta")?;
+        cmd_link
+            .arg(FILE_12_LINKS_2HBF)
+            .args(["--filter-link", link]);
+        let link_data = cmd_link.output()?.stdout;
+        assert!(!link_data.is_empty());
+        assert_eq!(
+            std::fs::read(tmp_dir.path().join(format!("out_link{link}.raw")))?,
+            link_data
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn split_by_link_requires_output_file() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_12_LINKS_2HBF)
+        .args(["--filter-link", "0", "--split-by-link"]);
+    cmd.assert().failure();
+
+    let mut cmd_stdout = Command::cargo_bin("fastpasta")?;
+    cmd_stdout.arg(FILE_12_LINKS_2HBF).args([
+        "--filter-link",
+        "0",
+        "--split-by-link",
+        "-o",
+        "stdout",
+    ]);
+    cmd_stdout
+        .assert()
+        .failure()
+        .stderr(contains("Splitting output by link requires an output file"));
+
+    Ok(())
+}
+
+#[test]
+fn filter_link_split_by_hbf_output() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = TempDir::new()?;
+    let out_path = tmp_dir.path().join("out.raw");
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_12_LINKS_2HBF)
+        .args(["--filter-link", "0", "--split-by-hbf", "-o"])
+        .arg(&out_path);
+    cmd.assert().success();
+
+    assert!(!out_path.exists());
+    let mut out_files = std::fs::read_dir(tmp_dir.path())?
+        .map(|entry| entry.map(|e| e.file_name()))
+        .collect::<Result<Vec<_>, _>>()?;
+    out_files.sort();
+    assert_eq!(out_files, ["out_hbf0.raw", "out_hbf1.raw"]);
+
+    // Concatenating the files should give the same data as filtering the link
+    let mut cmd_link = Command::cargo_bin("fastpasta")?;
+    cmd_link
+        .arg(FILE_12_LINKS_2HBF)
+        .args(["--filter-link", "0"]);
+    let link_data = cmd_link.output()?.stdout;
+    let hbf0_data = std::fs::read(tmp_dir.path().join("out_hbf0.raw"))?;
+    let hbf1_data = std::fs::read(tmp_dir.path().join("out_hbf1.raw"))?;
+    assert!(!hbf0_data.is_empty() && !hbf1_data.is_empty());
+    assert_eq!([hbf0_data, hbf1_data].concat(), link_data);
+
+    Ok(())
+}
+
+#[test]
+fn split_by_hbf_invalid_args() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd_stdout = Command::cargo_bin("fastpasta")?;
+    cmd_stdout.arg(FILE_12_LINKS_2HBF).args([
+        "--filter-link",
+        "0",
+        "--split-by-hbf",
+        "-o",
+        "stdout",
+    ]);
+    cmd_stdout
+        .assert()
+        .failure()
+        .stderr(contains("Splitting output by HBF requires an output file"));
+
+    let mut cmd_split_by_link = Command::cargo_bin("fastpasta")?;
+    cmd_split_by_link.arg(FILE_12_LINKS_2HBF).args([
+        "--split-by-hbf",
+        "--split-by-link",
+        "-o",
+        "out.raw",
+    ]);
+    cmd_split_by_link
+        .assert()
+        .failure()
+        .stderr(contains("cannot be used with"));
+
+    Ok(())
+}
+
+#[test]
+fn filter_link_verify_output() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_12_LINKS_2HBF)
+        .args(["--filter-link", "0", "--verify-output", "-v", "2", "-o"])
+        .arg(tmp_fpath.as_os_str());
+    cmd.assert().success();
+
+    let stderr = cmd.output()?.stderr;
+    match_on_out(false, &stderr, "Output verified: 6 RDHs read back", 1)?;
+    match_on_out(false, &stderr, "error", 0)?;
+
+    Ok(())
+}
+
+#[test]
+fn verify_output_invalid_args() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd_stdout = Command::cargo_bin("fastpasta")?;
+    cmd_stdout.arg(FILE_12_LINKS_2HBF).args([
+        "--filter-link",
+        "0",
+        "--verify-output",
+        "-o",
+        "stdout",
+    ]);
+    cmd_stdout.assert().failure().stderr(contains(
+        "`--verify-output` requires writing the output data to a single file",
+    ));
+
+    let mut cmd_extract = Command::cargo_bin("fastpasta")?;
+    cmd_extract.arg(FILE_12_LINKS_2HBF).args([
+        "--extract-rdhs",
+        "0,1",
+        "--verify-output",
+        "-o",
+        "out.raw",
+    ]);
+    cmd_extract.assert().failure().stderr(contains(
+        "`--verify-output` cannot be used with `--extract-rdhs`",
+    ));
+
+    Ok(())
+}
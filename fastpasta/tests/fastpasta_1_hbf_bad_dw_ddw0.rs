@@ -109,6 +109,62 @@ fn check_all_its_max_error_5() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn check_all_its_first_n_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_1_HBF_BAD_DW_DDW0)
+        .arg("check")
+        .arg("all")
+        .arg("its")
+        .args(["--first-n-errors", "1"]);
+
+    // All 4 errors are counted, only the first is shown
+    cmd.assert()
+        .success()
+        .stderr(contains("ERROR ").count(1))
+        .stderr(contains("... and 3 more errors suppressed"));
+
+    Ok(())
+}
+
+#[test]
+fn check_all_its_first_n_errors_with_max_error() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_1_HBF_BAD_DW_DDW0)
+        .arg("check")
+        .arg("all")
+        .arg("its")
+        .args(["--first-n-errors", "3"])
+        .args(["-e", "2"]);
+
+    // Processing stops at 2 errors, so there's nothing to suppress
+    cmd.assert()
+        .success()
+        .stderr(contains("ERROR ").count(2))
+        .stderr(contains("more errors suppressed").not());
+
+    Ok(())
+}
+
+#[test]
+fn check_all_its_first_n_errors_fewer_than_max_error() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_1_HBF_BAD_DW_DDW0)
+        .arg("check")
+        .arg("all")
+        .arg("its")
+        .args(["--first-n-errors", "1"])
+        .args(["-e", "3"]);
+
+    // Only the errors up to the max tolerated errors are counted as suppressed
+    cmd.assert()
+        .success()
+        .stderr(contains("ERROR ").count(1))
+        .stderr(contains("... and 2 more errors suppressed"));
+
+    Ok(())
+}
+
 #[test]
 fn view_its_readout_frame() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::cargo_bin("fastpasta")?;
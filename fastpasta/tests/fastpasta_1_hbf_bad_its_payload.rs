@@ -182,3 +182,45 @@ fn view_its_readout_frames() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn check_all_its_error_context() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_1_HBF_BAD_ITS_PAYLOAD)
+        .args(["check", "all", "its", "--error-context", "1"]);
+    cmd.assert().success();
+
+    let stderr = cmd.output()?.stderr;
+    // The error is followed by the word before it, the offending word, and the word after it
+    match_on_out(false, &stderr, r"error.*0x50:.*\[E40\] ID is not 0xE8", 1)?;
+    match_on_out(true, &stderr, r"\t0x40: C0 00 00 00 00 00 00 00 00 E0", 1)?;
+    match_on_out(
+        true,
+        &stderr,
+        r"\t0x50: 03 1A 00 00 00 02 00 00 00 E0 <--",
+        1,
+    )?;
+    // The word after E40 is also the word before E72
+    match_on_out(true, &stderr, r"\t0x60: A7 00 C0 41 FF B0 00 00 00 27", 2)?;
+    match_on_out(true, &stderr, " <--", 2)?;
+
+    Ok(())
+}
+
+#[test]
+fn error_context_requires_its_check() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_1_HBF_BAD_ITS_PAYLOAD)
+        .args(["check", "all", "--error-context", "1"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "Invalid config: Error context requires checking ITS",
+        1,
+    )?;
+
+    Ok(())
+}
@@ -34,6 +34,165 @@ fn check_sanity_its() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn check_sanity_its_error_format_json() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_1_HBF_BAD_TDT)
+        .arg("check")
+        .arg("sanity")
+        .arg("its")
+        .arg("--error-format")
+        .arg("json");
+    cmd.assert().success();
+
+    let stderr = cmd.output()?.stderr;
+    match_on_out(
+        true,
+        &stderr,
+        r#"\{"code":70,"mem_pos":144,"fee_id":524,"link_id":8,"message":"ID is invalid: 0xF1 \[00 00 00 00 00 00 00 00 01 F1\]"\}"#,
+        1,
+    )?;
+    match_on_out(true, &stderr, r#"\{"code":70,"mem_pos":224,"#, 1)?;
+    // Each error is only written once, as JSON
+    match_on_out(true, &stderr, r#"\{"code":"#, 4)?;
+    match_on_out(false, &stderr, "error.*0x90:", 0)?;
+    match_on_out(false, &cmd.output()?.stdout, "total.*errors.*4", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_its_error_format_json_filter_error_code() -> Result<(), Box<dyn std::error::Error>>
+{
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_1_HBF_BAD_TDT)
+        .arg("check")
+        .arg("sanity")
+        .arg("its")
+        .arg("--error-format")
+        .arg("json")
+        .arg("--show-only-errors-with-codes")
+        .arg("991");
+    cmd.assert().success();
+
+    let stderr = cmd.output()?.stderr;
+    match_on_out(true, &stderr, r#"\{"code":991,"#, 2)?;
+    match_on_out(true, &stderr, r#"\{"code":70,"#, 0)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_its_mute_error_codes() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_1_HBF_BAD_TDT)
+        .arg("check")
+        .arg("sanity")
+        .arg("its")
+        .arg("--mute-error-codes")
+        .arg("E991");
+    cmd.assert().success();
+
+    match_on_out(false, &cmd.output()?.stderr, "error.*0x90:.*E70", 1)?;
+    match_on_out(false, &cmd.output()?.stderr, "E991", 0)?;
+    match_on_out(false, &cmd.output()?.stdout, "total.*errors.*2", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_its_mute_all_error_codes_no_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_1_HBF_BAD_TDT)
+        .arg("check")
+        .arg("sanity")
+        .arg("its")
+        .arg("--mute-error-codes")
+        .arg("E991,70")
+        .arg("--any-errors-exit-code")
+        .arg("3");
+    // Muted errors do not count as errors in the data
+    cmd.assert().success();
+
+    match_on_out(false, &cmd.output()?.stderr, "error.*0x", 0)?;
+    match_on_out(false, &cmd.output()?.stdout, "total.*errors.*0", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_its_exit_code_on_error_code() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_1_HBF_BAD_TDT)
+        .arg("check")
+        .arg("sanity")
+        .arg("its")
+        .arg("--exit-code-on-error-code")
+        .arg("E11=4,E991=6,E70=5")
+        .arg("--any-errors-exit-code")
+        .arg("3");
+    // No E11 errors, the E991 mapping is the first with errors detected so it takes precedence over E70
+    cmd.assert().failure().code(6);
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_1_HBF_BAD_TDT)
+        .arg("check")
+        .arg("sanity")
+        .arg("its")
+        .arg("--exit-code-on-error-code")
+        .arg("E11=4")
+        .arg("--any-errors-exit-code")
+        .arg("3");
+    // No mapped error code detected, falls back to the any errors exit code
+    cmd.assert().failure().code(3);
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_its_error_code_histogram() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_1_HBF_BAD_TDT)
+        .arg("check")
+        .arg("sanity")
+        .arg("its");
+    cmd.assert().success();
+
+    match_on_out(
+        true,
+        &cmd.output()?.stdout,
+        "Error codes: E70: 2, E991: 2",
+        1,
+    )?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_1_HBF_BAD_TDT)
+        .arg("check")
+        .arg("sanity")
+        .arg("its")
+        .arg("--output-stats")
+        .arg("stdout")
+        .arg("--stats-format")
+        .arg("json");
+    cmd.assert().success();
+
+    match_on_out(
+        true,
+        &cmd.output()?.stdout,
+        r#""error_code_counts":\{"E70":2,"E991":2\}"#,
+        1,
+    )?;
+
+    Ok(())
+}
+
 #[test]
 fn check_all_its() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::cargo_bin("fastpasta")?;
@@ -135,3 +294,332 @@ fn view_its_readout_frames() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn check_sanity_its_multiple_input_files() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    // The 10 RDH file has no errors and is 0x4B0 bytes, so the errors are offset by 0x4B0
+    cmd.arg(FILE_10_RDH)
+        .arg(FILE_1_HBF_BAD_TDT)
+        .arg("check")
+        .arg("sanity")
+        .arg("its")
+        .arg("-v2");
+    cmd.assert().success();
+
+    match_on_out(false, &cmd.output()?.stderr, "error.*0x540:.*id.*0xF1", 1)?;
+    match_on_out(false, &cmd.output()?.stderr, "error.*0x590:.*id.*0xE4", 1)?;
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "0x4B0: start of input file.*1_hbf_bad_tdt.raw",
+        1,
+    )?;
+    match_on_out(false, &cmd.output()?.stdout, "Total.*RDHs.*12", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_its_multiple_input_files_jobs() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    // Each file is processed independently, so no errors are caused by the transition from one file to the next,
+    // but the memory positions are still offset as if the files were concatenated
+    cmd.arg(FILE_10_RDH)
+        .arg(FILE_1_HBF_BAD_TDT)
+        .arg(FILE_1_HBF_BAD_ITS_PAYLOAD)
+        .args(["check", "sanity", "its", "--jobs", "2", "-v2"]);
+    cmd.assert().success();
+
+    let stderr = cmd.output()?.stderr;
+    // E991 and E70
+    match_on_out(false, &stderr, "error.*0x540:", 2)?;
+    match_on_out(false, &stderr, "error.*0x590:", 2)?;
+    match_on_out(false, &stderr, r"error.*0x5F0:.*\[E40\]", 1)?;
+    match_on_out(false, &stderr, "error.*0x5E0:", 0)?;
+    match_on_out(
+        false,
+        &stderr,
+        "0x5A0: start of input file.*1_hbf_bad_its_payload.raw",
+        1,
+    )?;
+    let stdout = cmd.output()?.stdout;
+    match_on_out(false, &stdout, "Total.*RDHs.*14", 1)?;
+    match_on_out(false, &stdout, "Total.*errors.*5", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_multiple_input_files_jobs_empty_file() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    std::fs::write(tmp_fpath.path(), [])?;
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    // The empty file fails on its own, the other file is still processed
+    cmd.arg(FILE_10_RDH)
+        .arg(tmp_fpath.path())
+        .args(["check", "sanity", "--jobs", "2"]);
+    cmd.assert().failure().code(1);
+
+    let stderr = cmd.output()?.stderr;
+    match_on_out(false, &stderr, "Processing.*tmp.raw failed", 1)?;
+    match_on_out(false, &stderr, r"1 input file\(s\) failed to process", 1)?;
+    match_on_out(false, &stderr, "panicked", 0)?;
+    match_on_out(false, &cmd.output()?.stdout, "Total.*RDHs.*10", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn check_sanity_its_dump_ambiguous() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_1_HBF_BAD_TDT)
+        .args(["check", "sanity", "its", "--dump-ambiguous"])
+        .arg(tmp_fpath.path());
+    cmd.assert().success();
+
+    // The two words reported with E991, 10 bytes each
+    let dumped_words = std::fs::read(tmp_fpath.path())?;
+    assert_eq!(
+        dumped_words,
+        [
+            [0, 0, 0, 0, 0, 0, 0, 0, 0x01, 0xF1],
+            [0, 0, 0, 0, 0, 0, 0, 0, 0x00, 0xE4]
+        ]
+        .concat()
+    );
+    let index = std::fs::read_to_string(
+        fastpasta::analyze::validators::its::cdp_running::ambiguous_word_dump::index_path(
+            tmp_fpath.path(),
+        ),
+    )?;
+    assert_eq!(
+        index,
+        "mem_pos,fee_id,link_id,error_code\n0x90,524,8,E991\n0xE0,524,8,E991\n"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn jobs_requires_check() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_1_HBF_BAD_TDT)
+        .args(["view", "rdh", "--jobs", "2"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "Invalid config:.*--jobs.*requires the `check` subcommand",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn merge_stats_json() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = TempDir::new()?;
+    let stats_files = [
+        (FILE_10_RDH, tmp_dir.path().join("10_rdh.json")),
+        (
+            FILE_1_HBF_BAD_TDT,
+            tmp_dir.path().join("1_hbf_bad_tdt.json"),
+        ),
+    ];
+    for (raw_file, stats_file) in &stats_files {
+        let mut cmd = Command::cargo_bin("fastpasta")?;
+        cmd.arg(raw_file)
+            .args(["check", "all", "its", "-D", "json", "-S"])
+            .arg(stats_file);
+        cmd.assert().success();
+    }
+    let combined_stats_file = tmp_dir.path().join("combined.json");
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg("merge-stats")
+        .args(stats_files.iter().map(|(_, stats_file)| stats_file))
+        .arg("-o")
+        .arg(&combined_stats_file);
+    cmd.assert().success();
+
+    let combined_stats = std::fs::read_to_string(&combined_stats_file)?;
+    match_on_out(false, combined_stats.as_bytes(), r#""rdhs_seen":12,"#, 1)?;
+    match_on_out(false, combined_stats.as_bytes(), r#""hbfs_seen":6,"#, 1)?;
+    match_on_out(false, combined_stats.as_bytes(), r#""total_errors":4,"#, 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn merge_stats_conflicting_data_format() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = TempDir::new()?;
+    let stats_files = [
+        (
+            FILE_1_HBF_BAD_TDT,
+            tmp_dir.path().join("1_hbf_bad_tdt.json"),
+        ),
+        // Data format 2
+        (
+            FILE_2_RDH_DET_FIELD_V1_21_0,
+            tmp_dir.path().join("2_rdh_det_field.json"),
+        ),
+    ];
+    for (raw_file, stats_file) in &stats_files {
+        let mut cmd = Command::cargo_bin("fastpasta")?;
+        cmd.arg(raw_file)
+            .args(["check", "sanity", "-D", "json", "-S"])
+            .arg(stats_file);
+        cmd.assert().success();
+    }
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg("merge-stats")
+        .args(stats_files.iter().map(|(_, stats_file)| stats_file));
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "ERROR.*Data format mismatch: 0 and 2",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn merge_stats_requires_two_files() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.args(["merge-stats", "stats.json"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "Invalid config: Merging stats requires at least two stats files",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn follow_requires_one_input_file() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.args([FILE_1_HBF_BAD_TDT, FILE_10_RDH])
+        .args(["check", "sanity", "--follow"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "Invalid config:.*--follow.*requires exactly one input file",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn check_all_its_quiet_stats_fail() -> Result<(), Box<dyn std::error::Error>> {
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_1_HBF_BAD_TDT)
+        .args(["check", "all", "its", "--quiet-stats"])
+        .arg("--output-stats")
+        .arg(tmp_fpath.as_os_str())
+        .args(["--stats-format", "json"]);
+    cmd.assert().code(1);
+
+    // Only the verdict is printed, no error messages or report
+    match_on_out(true, &cmd.output()?.stdout, "^FAIL \\(4 errors\\)\n$", 1)?;
+    match_on_out(true, &cmd.output()?.stderr, "ERROR ", 0)?;
+
+    // The stats are still written
+    let stats_str = std::fs::read_to_string(tmp_fpath)?;
+    let stats: fastpasta::stats::stats_collector::StatsCollector =
+        serde_json::from_str(&stats_str)?;
+    assert_eq!(stats.err_count(), 4);
+
+    Ok(())
+}
+
+#[test]
+fn diff_stats_json() -> Result<(), Box<dyn std::error::Error>> {
+    let tmp_dir = TempDir::new()?;
+    let stats_files = [
+        (FILE_10_RDH, tmp_dir.path().join("10_rdh.json")),
+        (
+            FILE_1_HBF_BAD_TDT,
+            tmp_dir.path().join("1_hbf_bad_tdt.json"),
+        ),
+    ];
+    for (raw_file, stats_file) in &stats_files {
+        let mut cmd = Command::cargo_bin("fastpasta")?;
+        cmd.arg(raw_file)
+            .args(["check", "all", "its", "-D", "json", "-S"])
+            .arg(stats_file);
+        cmd.assert().success();
+    }
+    let (old_stats, new_stats) = (&stats_files[0].1, &stats_files[1].1);
+
+    // The processing time differs between runs, but is never compared
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg("diff").arg(old_stats).arg(old_stats);
+    cmd.assert().success();
+    match_on_out(false, &cmd.output()?.stdout, "Stats are identical", 1)?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg("diff").arg(old_stats).arg(new_stats);
+    cmd.assert().failure().code(1);
+    let stdout = cmd.output()?.stdout;
+    match_on_out(false, &stdout, r"(?m)^rdh_stats\.rdhs_seen: 10 -> 2$", 1)?;
+    match_on_out(false, &stdout, r"(?m)^rdh_stats\.hbfs_seen: 5 -> 1$", 1)?;
+    match_on_out(
+        false,
+        &stdout,
+        r"(?m)^error_stats\.total_errors: 0 -> 4$",
+        1,
+    )?;
+    match_on_out(false, &stdout, "processing_time", 0)?;
+
+    // Only the ignored fields differ
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg("diff").arg(old_stats).arg(new_stats).args([
+        "--ignore",
+        "rdh_stats,error_stats,alpide_stats,payload_words_seen",
+    ]);
+    cmd.assert().success();
+    match_on_out(false, &cmd.output()?.stdout, "Stats are identical", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn diff_stats_unknown_ignore_field() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.args([
+        "diff",
+        "old.json",
+        "new.json",
+        "--ignore",
+        "rdh_stats.rdhs_seen,rdh_stats.rdh_seen",
+    ]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "Invalid config: Unknown stats field to ignore: rdh_stats.rdh_seen",
+        1,
+    )?;
+
+    Ok(())
+}
@@ -149,3 +149,31 @@ fn check_all_its_stave_100_error_message_matching_err_code_filter_and_maximum_al
 
     Ok(())
 }
+
+#[test]
+fn view_ddw0_lane_errors() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_2_HBF_2ND_BAD_FRAME)
+        .arg("view")
+        .arg("ddw0")
+        .arg("--disable-styled-views");
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    // The 1st HBF has no lane errors, the 2nd HBF closes with lanes 6-8 in error
+    match_on_out(
+        false,
+        &cmd.output()?.stdout,
+        r"27E0:\s+4616\s+4\s+-{28}\s+-",
+        1,
+    )?;
+    match_on_out(
+        true,
+        &cmd.output()?.stdout,
+        r"3800:\s+4616\s+4\s+-{6}EEE-{19}\s+Error",
+        1,
+    )?;
+
+    Ok(())
+}
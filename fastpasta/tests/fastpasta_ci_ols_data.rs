@@ -104,6 +104,12 @@ chip_orders_ob = [[0, 1, 2, 3, 4, 5, 6], [8, 9, 10, 11, 12, 13, 14]]
     assert_eq!(stats_toml.rdh_stats().trigger_stats().pht(), 2);
     assert_eq!(stats_toml.rdh_stats().trigger_stats().hb(), 2);
     assert_eq!(stats_toml.rdh_stats().trigger_stats().orbit(), 2);
+    // The chip ID order error is counted for the lane it was found in
+    let lane_errors = stats_toml.alpide_lane_errors().unwrap().lanes();
+    assert_eq!(lane_errors.len(), 1);
+    assert_eq!(lane_errors[0].fee_id(), 20522);
+    assert_eq!(lane_errors[0].lane(), 12);
+    assert_eq!(lane_errors[0].error_counts()["chip_id_order_mismatch"], 1);
 
     // Feed the stats back and check that it matches the collected stats
     let mut cmd = Command::cargo_bin("fastpasta")?;
@@ -229,3 +235,31 @@ chip_orders_ob = [[0, 1, 2, 3, 4, 5, 6], [8, 9, 10, 11, 12, 13, 14], [0, 2, 3, 4
 
     Ok(())
 }
+
+#[test]
+fn check_sanity_its_strict_padding() -> Result<(), Box<dyn std::error::Error>> {
+    // The padding of the data format 2 payloads is consistent with the data format
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_CI_OLS_DATA_1HBF)
+        .args(["check", "sanity", "its", "--strict-padding"]);
+    cmd.assert().success();
+    match_on_out(false, &cmd.output()?.stderr, r"\[E90\]", 0)?;
+
+    let (_tmp_dir, tmp_fpath) = make_tmp_dir_w_fpath();
+    let mut data = std::fs::read(FILE_CI_OLS_DATA_1HBF)?;
+    // The first payload is 450 bytes of GBT words and 14 bytes of padding, overwrite the first padding byte
+    data[0x40 + 450] = 0x00;
+    tmp_fpath.write_binary(&data)?;
+
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(tmp_fpath.path())
+        .args(["check", "sanity", "its", "--strict-padding"]);
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        r"0x203: \[E90\] Payload before the end of payload 0xFF padding is 451 bytes, not a multiple of the 10 byte GBT words of data format 2",
+        1,
+    )?;
+
+    Ok(())
+}
@@ -116,3 +116,57 @@ fn check_all_its_stave_debug_verbosity() -> Result<(), Box<dyn std::error::Error
 
     Ok(())
 }
+
+#[test]
+fn errors_not_colored_if_stderr_not_terminal() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_ERR_NOT_HBF).arg("check").arg("all");
+    cmd.assert().success();
+
+    // stderr is piped in tests, so the error messages should be written without ANSI escape codes
+    let stderr = cmd.output()?.stderr;
+    match_on_out(
+        false,
+        &stderr,
+        prefix_and_then(ERROR_PREFIX, "0xa0.*pages"),
+        1,
+    )?;
+    assert!(
+        !stderr.contains(&0x1B),
+        "{}",
+        String::from_utf8_lossy(&stderr)
+    );
+
+    Ok(())
+}
+
+#[test]
+fn view_rdh_no_color() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_ERR_NOT_HBF).arg("view").arg("rdh");
+    cmd.assert().success();
+    assert!(cmd.output()?.stdout.contains(&0x1B));
+
+    // `--no-color` disables the styled views like `-d`
+    let mut cmd_no_color = Command::cargo_bin("fastpasta")?;
+    cmd_no_color
+        .arg(FILE_ERR_NOT_HBF)
+        .arg("view")
+        .arg("rdh")
+        .arg("--no-color");
+    cmd_no_color.assert().success();
+
+    let mut cmd_disable_styled = Command::cargo_bin("fastpasta")?;
+    cmd_disable_styled
+        .arg(FILE_ERR_NOT_HBF)
+        .arg("view")
+        .arg("rdh")
+        .arg("-d");
+
+    let no_color_stdout = cmd_no_color.output()?.stdout;
+    assert!(!no_color_stdout.contains(&0x1B));
+    assert_eq!(no_color_stdout, cmd_disable_styled.output()?.stdout);
+
+    Ok(())
+}
@@ -395,3 +395,41 @@ chip_count_ob = 6
 
     Ok(())
 }
+
+#[test]
+fn check_all_its_trigger_period_histogram() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_RAWTF_EPN180_L6_1)
+        .args(["check", "all", "its", "--trigger-period-histogram"]);
+    cmd.assert().success();
+
+    let stdout = cmd.output()?.stdout;
+    // There's 18 TDTs, but a period is from one TDT to the next so there's N-1 periods.
+    match_on_out(
+        false,
+        &stdout,
+        r"TDH trigger period histogram \(17 periods\)",
+        1,
+    )?;
+    match_on_out(false, &stdout, r"198 BC:\s+17 \(100.00%\)", 1)?;
+
+    Ok(())
+}
+
+#[test]
+fn trigger_period_histogram_requires_check_all_its() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_RAWTF_EPN180_L6_1)
+        .args(["check", "sanity", "its", "--trigger-period-histogram"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "Trigger period histogram requires",
+        1,
+    )?;
+
+    Ok(())
+}
@@ -25,6 +25,24 @@ fn check_sanity_its() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn check_sanity_its_check_reserved() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+
+    cmd.arg(FILE_THRS_CDW_LINKS)
+        .arg("check")
+        .arg("sanity")
+        .arg("its")
+        .arg("--check-reserved")
+        .arg("-v4");
+    cmd.assert().success();
+
+    assert_no_errors_or_warn(&cmd.output()?.stderr)?;
+    match_on_out(false, &cmd.output()?.stdout, "total errors.*0", 1)?;
+
+    Ok(())
+}
+
 #[test]
 fn check_all_its() -> Result<(), Box<dyn std::error::Error>> {
     let mut cmd = Command::cargo_bin("fastpasta")?;
@@ -131,3 +131,146 @@ fn file_doesnt_exist_v4() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn strict_padding_requires_checking_its() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["check", "sanity", "--strict-padding"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "`--strict-padding` requires checking ITS",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn check_reserved_requires_checking_its() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["check", "sanity", "--check-reserved"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "`--check-reserved` requires checking ITS",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn warnings_as_errors_requires_check() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["view", "rdh", "--warnings-as-errors"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "`--warnings-as-errors` requires the `check` subcommand",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn dump_ambiguous_requires_checking_its() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["check", "sanity", "--dump-ambiguous", "ambiguous.raw"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "`--dump-ambiguous` requires checking ITS",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn recv_timeout_requires_check_or_view() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH).args(["--recv-timeout", "30s"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "`--recv-timeout` requires the `check` or `view` subcommand",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn its_barrel_requires_check_all_its_stave() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["check", "all", "its", "--its-barrel", "inner"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "`--its-barrel` requires the `check all its-stave` command",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn its_barrel_invalid_value() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["check", "all", "its-stave", "--its-barrel", "center"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "Invalid ITS barrel, expected one of: inner, middle, outer, all",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn first_n_errors_zero() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["check", "sanity", "--first-n-errors", "0"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "`--first-n-errors` must be at least 1",
+        1,
+    )?;
+
+    Ok(())
+}
+
+#[test]
+fn first_n_errors_requires_check() -> Result<(), Box<dyn std::error::Error>> {
+    let mut cmd = Command::cargo_bin("fastpasta")?;
+    cmd.arg(FILE_10_RDH)
+        .args(["view", "rdh", "--first-n-errors", "5"]);
+    cmd.assert().failure();
+    match_on_out(
+        false,
+        &cmd.output()?.stderr,
+        "`--first-n-errors` requires the `check` subcommand",
+        1,
+    )?;
+
+    Ok(())
+}
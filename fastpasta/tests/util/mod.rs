@@ -34,12 +34,15 @@ pub const FILE_CI_OLS_DATA_1HBF: &str = "../tests/test-data/ci_ols_data_1hbf.raw
 pub const FILE_2_RDH_DET_FIELD_V1_21_0: &str = "../tests/test-data/2_rdh_det_field_v1.21.0.raw"; // has Detector field v1.21.0
 pub const FILE_2_HBF_2ND_BAD_FRAME: &str = "../tests/test-data/2_hbf_2nd_bad_frame.raw"; // First HBF is valid but second lacks data words even though no error has been indicated with APE/TDT/DDW
 pub const FILE_12_LINKS_2HBF: &str = "../tests/test-data/12_links_2hbf.raw"; // 12 links with 1 HBF each
+pub const FILE_10_RDH_GZ: &str = "../tests/test-data/10_rdh.raw.gz"; // 10_rdh.raw compressed with gzip
+pub const FILE_10_RDH_ZST: &str = "../tests/test-data/10_rdh.raw.zst"; // 10_rdh.raw compressed with zstd
 
 /// matches a single ANSI escape code
 pub const ANSI_ESCAPE_REGEX: &str = r"(\x9B|\x1B\[)[0-?]*[ -\/]*[@-~]";
 /// WARN prefix with an ANSI escape code
 pub const WARN_PREFIX: &str = concat!("WARN ", r"(\x9B|\x1B\[)[0-?]*[ -\/]*[@-~]");
-pub const ERROR_PREFIX: &str = concat!("ERROR ", r"(\x9B|\x1B\[)[0-?]*[ -\/]*[@-~]");
+/// ERROR prefix, error messages are only colored with an ANSI escape code if stderr is a terminal
+pub const ERROR_PREFIX: &str = concat!("ERROR ", r"((\x9B|\x1B\[)[0-?]*[ -\/]*[@-~])?");
 
 /// Helper function to create a regex pattern with a prefix and a suffix
 pub fn prefix_and_then<S>(prefix: &'static str, suffix: S) -> String